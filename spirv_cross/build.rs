@@ -11,7 +11,8 @@ fn main() {
     let is_apple = target_vendor.is_ok() && target_vendor.unwrap() == "apple";
 
     let target_os = std::env::var("CARGO_CFG_TARGET_OS");
-    let is_ios = target_os.is_ok() && target_os.unwrap() == "ios";
+    let is_ios = target_os.as_ref().map_or(false, |os| os == "ios");
+    let is_android = target_os.as_ref().map_or(false, |os| os == "android");
 
     let target_env: Result<String, std::env::VarError> = std::env::var("CARGO_CFG_TARGET_ENV");
     let is_ohos = target_env.is_ok() && target_env.unwrap() == "ohos";
@@ -26,37 +27,196 @@ fn main() {
         build.flag("-std=c++14").cpp_set_stdlib("c++");
     } else if is_ohos {
         build.flag("-std=c++14").cpp_link_stdlib("c++_static");
+    } else if is_android {
+        // The NDK and Termux only ship LLVM's libc++. The shared runtime is linked by default, as
+        // apps loading several native libraries must share one copy of it, while `CXXSTDLIB`
+        // selects another one, such as `c++_static` for standalone executables.
+        build.flag("-std=c++14");
+        if target_env_var("CXXSTDLIB").is_none() {
+            build.cpp_link_stdlib("c++_shared");
+        }
     } else {
         build.flag_if_supported("-std=c++14");
     }
 
-    build
-        .flag("-DSPIRV_CROSS_EXCEPTIONS_TO_ASSERTIONS")
-        .flag("-DSPIRV_CROSS_WRAPPER_NO_EXCEPTIONS");
+    // Extra flags for toolchains the defaults above don't suit, added last to take precedence
+    if let Some(flags) = target_env_var("SPIRV_CROSS_CXXFLAGS") {
+        for flag in flags.split_whitespace() {
+            build.flag(flag);
+        }
+    }
+
+    #[cfg(feature = "system")]
+    let include_paths = configure_system_spirv_cross(&mut build);
+    #[cfg(not(feature = "system"))]
+    let include_paths = configure_vendored_spirv_cross(&mut build);
+
+    #[cfg(feature = "buildtime-bindgen")]
+    generate_bindings(&include_paths);
+    #[cfg(not(feature = "buildtime-bindgen"))]
+    let _ = include_paths;
 
+    build.file("src/wrapper.cpp");
+
+    // The HLSL and MSL compilers inherit from the GLSL compiler, so it is only left out when no
+    // backend is enabled, such as for the `reflect` target alone.
+    #[cfg(any(feature = "glsl", feature = "hlsl", feature = "msl"))]
+    build.flag("-DSPIRV_CROSS_WRAPPER_GLSL");
+
+    #[cfg(feature = "hlsl")]
+    build.flag("-DSPIRV_CROSS_WRAPPER_HLSL");
+
+    #[cfg(feature = "msl")]
+    build.flag("-DSPIRV_CROSS_WRAPPER_MSL");
+
+    #[cfg(feature = "reflect")]
+    build.flag("-DSPIRV_CROSS_WRAPPER_REFLECT");
+
+    build.compile("spirv-cross-rust-wrapper");
+
+    // Emitted after the wrapper so that static linkers resolve its references.
+    #[cfg(feature = "system")]
+    link_system_spirv_cross();
+}
+
+/// Reads an environment variable for the target being built, preferring a variant suffixed with
+/// the target triple, such as `SPIRV_CROSS_CXXFLAGS_aarch64_linux_android`, like `cc` does.
+fn target_env_var(name: &str) -> Option<String> {
+    let target = std::env::var("TARGET")
+        .unwrap_or_default()
+        .replace('-', "_");
+    let target_name = format!("{}_{}", name, target);
+    println!("cargo:rerun-if-env-changed={}", target_name);
+    println!("cargo:rerun-if-env-changed={}", name);
+    std::env::var(&target_name)
+        .or_else(|_| std::env::var(name))
+        .ok()
+}
+
+#[cfg(not(feature = "system"))]
+fn configure_vendored_spirv_cross(build: &mut cc::Build) -> Vec<std::path::PathBuf> {
+    // SPIRV-Cross is built with exceptions so that malformed modules and unsupported features
+    // are reported as errors instead of aborting the process.
     build
-        .file("src/wrapper.cpp")
         .file("src/vendor/SPIRV-Cross/spirv_cfg.cpp")
         .file("src/vendor/SPIRV-Cross/spirv_cross.cpp")
         .file("src/vendor/SPIRV-Cross/spirv_cross_parsed_ir.cpp")
         .file("src/vendor/SPIRV-Cross/spirv_parser.cpp")
         .file("src/vendor/SPIRV-Cross/spirv_cross_util.cpp");
 
-    // Ideally the GLSL compiler would be omitted here, but the HLSL and MSL compiler
-    // currently inherit from it. So it's necessary to unconditionally include it here.
-    build
-        .file("src/vendor/SPIRV-Cross/spirv_glsl.cpp")
-        .flag("-DSPIRV_CROSS_WRAPPER_GLSL");
+    #[cfg(any(feature = "glsl", feature = "hlsl", feature = "msl"))]
+    build.file("src/vendor/SPIRV-Cross/spirv_glsl.cpp");
 
     #[cfg(feature = "hlsl")]
-    build
-        .file("src/vendor/SPIRV-Cross/spirv_hlsl.cpp")
-        .flag("-DSPIRV_CROSS_WRAPPER_HLSL");
+    build.file("src/vendor/SPIRV-Cross/spirv_hlsl.cpp");
 
     #[cfg(feature = "msl")]
-    build
-        .file("src/vendor/SPIRV-Cross/spirv_msl.cpp")
-        .flag("-DSPIRV_CROSS_WRAPPER_MSL");
+    build.file("src/vendor/SPIRV-Cross/spirv_msl.cpp");
 
-    build.compile("spirv-cross-rust-wrapper");
+    // The wrapper includes the vendored headers relative to itself.
+    Vec::new()
+}
+
+// The oldest SPIRV-Cross release the wrapper builds against, as the version of its C API, which
+// is what the pkg-config files of an installation report.
+#[cfg(feature = "system")]
+const SYSTEM_SPIRV_CROSS_MIN_VERSION: &str = "0.44.0";
+
+// The C API of SPIRV-Cross can't express everything the wrapper exposes (sharing parsed IR,
+// MSL sampler mappings, ...), so the wrapper is still compiled, but against the headers and
+// static C++ libraries of the installation instead of the vendored sources. Those libraries have
+// no pkg-config file of their own, so they are found through the one of the static C library
+// installed next to them, which is not linked itself. Linking a C library as well would bring in
+// a second copy of SPIRV-Cross.
+#[cfg(feature = "system")]
+fn configure_system_spirv_cross(build: &mut cc::Build) -> Vec<std::path::PathBuf> {
+    let library = pkg_config::Config::new()
+        .atleast_version(SYSTEM_SPIRV_CROSS_MIN_VERSION)
+        .cargo_metadata(false)
+        .probe("spirv-cross-c")
+        .unwrap_or_else(|error| {
+            panic!(
+                "the `system` feature requires SPIRV-Cross {} or newer with its static libraries \
+                 to be discoverable with pkg-config: {}",
+                SYSTEM_SPIRV_CROSS_MIN_VERSION, error
+            )
+        });
+    for link_path in &library.link_paths {
+        println!("cargo:rustc-link-search=native={}", link_path.display());
+    }
+
+    build.flag("-DSPIRV_CROSS_WRAPPER_SYSTEM");
+    // The pkg-config file may point either at the install prefix or at its `spirv_cross`
+    // subdirectory, the wrapper includes headers as `<spirv_cross/...>`.
+    let mut include_paths = Vec::new();
+    for include_path in &library.include_paths {
+        include_paths.push(include_path.clone());
+        if let Some(parent) = include_path.parent() {
+            include_paths.push(parent.to_path_buf());
+        }
+    }
+    for include_path in &include_paths {
+        build.include(include_path);
+    }
+    include_paths
+}
+
+#[cfg(feature = "system")]
+fn link_system_spirv_cross() {
+    #[cfg(feature = "msl")]
+    println!("cargo:rustc-link-lib=static=spirv-cross-msl");
+    #[cfg(feature = "hlsl")]
+    println!("cargo:rustc-link-lib=static=spirv-cross-hlsl");
+    #[cfg(any(feature = "glsl", feature = "hlsl", feature = "msl"))]
+    println!("cargo:rustc-link-lib=static=spirv-cross-glsl");
+    println!("cargo:rustc-link-lib=static=spirv-cross-util");
+    println!("cargo:rustc-link-lib=static=spirv-cross-core");
+}
+
+// Mirrors the native configuration of `bindings_generator`, so that the generated bindings only
+// differ from the checked-in ones where the target's type layout does.
+#[cfg(feature = "buildtime-bindgen")]
+fn generate_bindings(include_paths: &[std::path::PathBuf]) {
+    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    let target = std::env::var("TARGET").unwrap();
+
+    let mut builder = bindgen::Builder::default()
+        .header("src/wrapper.hpp")
+        .clang_args(["-x", "c++", "-std=c++14"].iter())
+        .clang_arg(format!("--target={}", target))
+        .enable_cxx_namespaces()
+        .whitelist_function("sc_internal.*")
+        .whitelist_type("spv::.*")
+        .whitelist_type("Sc.*")
+        .bitfield_enum(".*(Mask|Flags)")
+        .rustified_enum("spv::BuiltIn")
+        .rustified_enum("spv::Decoration")
+        .rustified_enum("spv::ExecutionModel")
+        .rustified_enum("spv::ImageFormat")
+        .rustified_enum("spv::Dim")
+        .rustified_enum("spv::StorageClass")
+        .rustified_enum("ScInternalResult")
+        .rustified_enum("spirv_cross::SPIRType_BaseType")
+        .rustified_enum("spirv_cross::MSLVertexFormat")
+        .opaque_type("std::.*")
+        .clang_args(vec![
+            "-DSPIRV_CROSS_WRAPPER_GLSL",
+            "-DSPIRV_CROSS_WRAPPER_MSL",
+            "-DSPIRV_CROSS_WRAPPER_HLSL",
+        ])
+        .layout_tests(false);
+
+    #[cfg(feature = "system")]
+    {
+        builder = builder.clang_arg("-DSPIRV_CROSS_WRAPPER_SYSTEM");
+    }
+    for include_path in include_paths {
+        builder = builder.clang_arg(format!("-I{}", include_path.display()));
+    }
+
+    builder
+        .generate()
+        .expect("unable to generate bindings for the wrapper")
+        .write_to_file(out_dir.join("bindings_native.rs"))
+        .expect("unable to write the generated bindings");
 }
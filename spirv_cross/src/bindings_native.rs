@@ -2607,6 +2607,7 @@ pub mod root {
             pub ycbcr_conversion_enable: bool,
         }
     }
+    pub type ScInternalParsedIr = ::std::os::raw::c_void;
     pub type ScInternalCompilerBase = ::std::os::raw::c_void;
     pub type ScInternalCompilerHlsl = ::std::os::raw::c_void;
     pub type ScInternalCompilerMsl = ::std::os::raw::c_void;
@@ -2626,6 +2627,9 @@ pub mod root {
         pub work_group_size_x: u32,
         pub work_group_size_y: u32,
         pub work_group_size_z: u32,
+        pub work_group_size_id_x: u32,
+        pub work_group_size_id_y: u32,
+        pub work_group_size_id_z: u32,
     }
     #[repr(C)]
     #[derive(Debug, Copy, Clone)]
@@ -2651,6 +2655,23 @@ pub mod root {
     }
     #[repr(C)]
     #[derive(Debug, Copy, Clone)]
+    pub struct ScHlslRegister {
+        pub register_space: u32,
+        pub register_binding: u32,
+    }
+    #[repr(C)]
+    #[derive(Debug, Copy, Clone)]
+    pub struct ScHlslResourceBinding {
+        pub stage: root::spv::ExecutionModel,
+        pub desc_set: u32,
+        pub binding: u32,
+        pub cbv: root::ScHlslRegister,
+        pub uav: root::ScHlslRegister,
+        pub srv: root::ScHlslRegister,
+        pub sampler: root::ScHlslRegister,
+    }
+    #[repr(C)]
+    #[derive(Debug, Copy, Clone)]
     pub struct ScHlslCompilerOptions {
         pub shader_model: i32,
         pub point_size_compat: bool,
@@ -2660,6 +2681,10 @@ pub mod root {
         pub force_storage_buffer_as_uav: bool,
         pub nonwritable_uav_texture_as_srv: bool,
         pub force_zero_initialized_variables: bool,
+        pub emit_line_directives: bool,
+        pub force_temporary: bool,
+        pub flatten_multidimensional_arrays: bool,
+        pub preserve_structured_buffers: bool,
     }
     #[repr(C)]
     #[derive(Debug, Copy, Clone)]
@@ -2684,6 +2709,17 @@ pub mod root {
         pub force_native_arrays: bool,
         pub force_zero_initialized_variables: bool,
         pub force_active_argument_buffer_resources: bool,
+        pub emit_line_directives: bool,
+        pub force_temporary: bool,
+        pub flatten_multidimensional_arrays: bool,
+        pub texture_buffer_native: bool,
+        pub texel_buffer_texture_width: u32,
+        pub emulate_cube_array: bool,
+        pub device_index: u32,
+        pub dispatch_base: bool,
+        pub emulate_subgroups: bool,
+        pub fixed_subgroup_size: u32,
+        pub ios_use_simdgroup_functions: bool,
     }
     #[repr(C)]
     #[derive(Debug, Copy, Clone)]
@@ -2705,6 +2741,7 @@ pub mod root {
         pub emit_line_directives: bool,
         pub enable_storage_image_qualifier_deduction: bool,
         pub force_zero_initialized_variables: bool,
+        pub omit_write_only_image_formats: bool,
     }
     #[repr(C)]
     #[derive(Debug, Copy, Clone)]
@@ -2722,6 +2759,19 @@ pub mod root {
     }
     #[repr(C)]
     #[derive(Debug, Copy, Clone)]
+    pub struct ScBuiltInResource {
+        pub builtin: root::spv::BuiltIn,
+        pub value_type_id: u32,
+        pub resource: root::ScResource,
+    }
+    #[repr(C)]
+    #[derive(Debug, Copy, Clone)]
+    pub struct ScBuiltInResourceArray {
+        pub data: *mut root::ScBuiltInResource,
+        pub num: usize,
+    }
+    #[repr(C)]
+    #[derive(Debug, Copy, Clone)]
     pub struct ScShaderResources {
         pub uniform_buffers: root::ScResourceArray,
         pub storage_buffers: root::ScResourceArray,
@@ -2734,7 +2784,18 @@ pub mod root {
         pub push_constant_buffers: root::ScResourceArray,
         pub separate_images: root::ScResourceArray,
         pub separate_samplers: root::ScResourceArray,
+        pub builtin_inputs: root::ScBuiltInResourceArray,
+        pub builtin_outputs: root::ScBuiltInResourceArray,
     }
+    pub type ScVariableTypeRemapCallback = ::std::option::Option<
+        unsafe extern "C" fn(
+            user_data: *mut ::std::os::raw::c_void,
+            type_id: u32,
+            variable_name: *const ::std::os::raw::c_char,
+            type_name: *const ::std::os::raw::c_char,
+            remapped_type_name: *mut *const ::std::os::raw::c_char,
+        ),
+    >;
     #[repr(C)]
     #[derive(Debug, Copy, Clone)]
     pub struct ScSpecializationConstant {
@@ -2761,6 +2822,17 @@ pub mod root {
             message: *mut *const ::std::os::raw::c_char,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_parsed_ir_new(
+            ir: *mut *mut root::ScInternalParsedIr,
+            words: *const u32,
+            size: usize,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_parsed_ir_delete(ir: *mut root::ScInternalParsedIr)
+            -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_hlsl_new(
             compiler: *mut *mut root::ScInternalCompilerHlsl,
@@ -2768,6 +2840,12 @@ pub mod root {
             size: usize,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_hlsl_new_from_ir(
+            compiler: *mut *mut root::ScInternalCompilerHlsl,
+            ir: *const root::ScInternalParsedIr,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_hlsl_set_options(
             compiler: *const root::ScInternalCompilerHlsl,
@@ -2781,9 +2859,17 @@ pub mod root {
             count: usize,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_hlsl_add_resource_bindings(
+            compiler: *const root::ScInternalCompilerHlsl,
+            bindings: *const root::ScHlslResourceBinding,
+            count: usize,
+        ) -> root::ScInternalResult;
+    }
     #[repr(C)]
     #[derive(Debug, Copy, Clone)]
     pub struct ScMslConstSamplerMapping {
+        pub id: u32,
         pub desc_set: u32,
         pub binding: u32,
         pub sampler: root::spirv_cross::MSLConstexprSampler,
@@ -2795,6 +2881,18 @@ pub mod root {
             size: usize,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_msl_new_from_ir(
+            compiler: *mut *mut root::ScInternalCompilerMsl,
+            ir: *const root::ScInternalParsedIr,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_msl_new_from_compiler(
+            compiler: *mut *mut root::ScInternalCompilerMsl,
+            source: *const root::ScInternalCompilerBase,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_msl_set_options(
             compiler: *const root::ScInternalCompilerMsl,
@@ -2807,6 +2905,36 @@ pub mod root {
             is_rasterization_disabled: *mut bool,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_msl_is_resource_binding_used(
+            compiler: *const root::ScInternalCompilerMsl,
+            model: root::spv::ExecutionModel,
+            desc_set: u32,
+            binding: u32,
+            is_used: *mut bool,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_msl_is_shader_input_used(
+            compiler: *const root::ScInternalCompilerMsl,
+            location: u32,
+            is_used: *mut bool,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_msl_needs_buffer_size_buffer(
+            compiler: *const root::ScInternalCompilerMsl,
+            needs_buffer_size_buffer: *mut bool,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_msl_get_automatic_resource_binding(
+            compiler: *const root::ScInternalCompilerMsl,
+            id: u32,
+            binding: *mut u32,
+            secondary_binding: *mut u32,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_msl_compile(
             compiler: *const root::ScInternalCompilerBase,
@@ -2819,6 +2947,19 @@ pub mod root {
             const_sampler_count: usize,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_reflect_new(
+            compiler: *mut *mut root::ScInternalCompilerBase,
+            ir: *const u32,
+            size: usize,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_reflect_new_from_ir(
+            compiler: *mut *mut root::ScInternalCompilerBase,
+            ir: *const root::ScInternalParsedIr,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_glsl_new(
             compiler: *mut *mut root::ScInternalCompilerGlsl,
@@ -2826,6 +2967,12 @@ pub mod root {
             size: usize,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_glsl_new_from_ir(
+            compiler: *mut *mut root::ScInternalCompilerGlsl,
+            ir: *const root::ScInternalParsedIr,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_glsl_set_options(
             compiler: *const root::ScInternalCompilerGlsl,
@@ -2856,6 +3003,33 @@ pub mod root {
             id: u32,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_glsl_remap_subpass_input_to_sampler(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_glsl_set_image_format(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+            format: root::spv::ImageFormat,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_glsl_get_image_format(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+            format: *mut root::spv::ImageFormat,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_glsl_get_required_extensions(
+            compiler: *const root::ScInternalCompilerBase,
+            extensions: *mut *mut *const ::std::os::raw::c_char,
+            size: *mut usize,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_get_decoration(
             compiler: *const root::ScInternalCompilerBase,
@@ -2864,6 +3038,65 @@ pub mod root {
             decoration: root::spv::Decoration,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_has_decoration(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+            decoration: root::spv::Decoration,
+            result: *mut bool,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_has_member_decoration(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+            index: u32,
+            decoration: root::spv::Decoration,
+            result: *mut bool,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_buffer_is_hlsl_counter_buffer(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+            is_counter_buffer: *mut bool,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_buffer_get_hlsl_counter_buffer(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+            has_counter_buffer: *mut bool,
+            counter_id: *mut u32,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_needs_dummy_sampler_for_combined_images(
+            compiler: *const root::ScInternalCompilerBase,
+            needs_dummy_sampler: *mut bool,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_variable_is_depth_or_compare(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+            is_depth_or_compare: *mut bool,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_build_dummy_sampler_for_combined_images(
+            compiler: *const root::ScInternalCompilerBase,
+            sampler_id: *mut u32,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_get_decoration_string(
+            compiler: *const root::ScInternalCompilerBase,
+            result: *mut *const ::std::os::raw::c_char,
+            id: u32,
+            decoration: root::spv::Decoration,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_set_decoration(
             compiler: *const root::ScInternalCompilerBase,
@@ -2872,6 +3105,24 @@ pub mod root {
             argument: u32,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_set_decorations(
+            compiler: *const root::ScInternalCompilerBase,
+            ids: *const u32,
+            decorations: *const root::spv::Decoration,
+            arguments: *const u32,
+            count: usize,
+            invalid_entry: *mut usize,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_set_decoration_string(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+            decoration: root::spv::Decoration,
+            argument: *const ::std::os::raw::c_char,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_unset_decoration(
             compiler: *const root::ScInternalCompilerBase,
@@ -2886,6 +3137,13 @@ pub mod root {
             name: *mut *const ::std::os::raw::c_char,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_get_remapped_declared_block_name(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+            name: *mut *const ::std::os::raw::c_char,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_set_name(
             compiler: *const root::ScInternalCompilerBase,
@@ -2901,6 +3159,18 @@ pub mod root {
             name: *const ::std::os::raw::c_char,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_strip_names(
+            compiler: *const root::ScInternalCompilerBase,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_set_variable_type_remap_callback(
+            compiler: *const root::ScInternalCompilerBase,
+            callback: root::ScVariableTypeRemapCallback,
+            user_data: *mut ::std::os::raw::c_void,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_get_entry_points(
             compiler: *const root::ScInternalCompilerBase,
@@ -2908,6 +3178,12 @@ pub mod root {
             size: *mut usize,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_get_current_entry_point(
+            compiler: *const root::ScInternalCompilerBase,
+            entry_point: *mut *mut root::ScEntryPoint,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_get_active_buffer_ranges(
             compiler: *const root::ScInternalCompilerBase,
@@ -2930,6 +3206,132 @@ pub mod root {
             shader_resources: *mut root::ScShaderResources,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_get_shader_resources_of_kinds(
+            compiler: *const root::ScInternalCompilerBase,
+            kinds: u32,
+            shader_resources: *mut root::ScShaderResources,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_get_shader_resource_list(
+            compiler: *const root::ScInternalCompilerBase,
+            kind: u32,
+            resources: *mut root::ScResourceArray,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_get_declared_capabilities(
+            compiler: *const root::ScInternalCompilerBase,
+            capabilities: *mut *mut u32,
+            size: *mut usize,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_get_used_opcodes(
+            compiler: *const root::ScInternalCompilerBase,
+            opcodes: *mut *mut u32,
+            size: *mut usize,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_get_spirv(
+            compiler: *const root::ScInternalCompilerBase,
+            words: *mut *mut u32,
+            size: *mut usize,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_get_meta_ids(
+            compiler: *const root::ScInternalCompilerBase,
+            ids: *mut *mut u32,
+            size: *mut usize,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_get_descriptor_count(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+            count: *mut u32,
+            is_runtime_sized: *mut bool,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_get_outer_array(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+            element_type_id: *mut u32,
+            length: *mut u32,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_get_workgroup_shared_memory_size(
+            compiler: *const root::ScInternalCompilerBase,
+            name: *const ::std::os::raw::c_char,
+            execution_model: root::spv::ExecutionModel,
+            size: *mut u32,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_get_entry_point_interface_variables(
+            compiler: *const root::ScInternalCompilerBase,
+            name: *const ::std::os::raw::c_char,
+            execution_model: root::spv::ExecutionModel,
+            ids: *mut *mut u32,
+            size: *mut usize,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_get_entry_point_subgroup_size(
+            compiler: *const root::ScInternalCompilerBase,
+            name: *const ::std::os::raw::c_char,
+            execution_model: root::spv::ExecutionModel,
+            has_subgroup_size: *mut bool,
+            subgroup_size: *mut u32,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_get_decorations(
+            compiler: *const root::ScInternalCompilerBase,
+            ids: *const u32,
+            id_count: usize,
+            decorations: *const root::spv::Decoration,
+            decoration_count: usize,
+            has_decorations: *mut bool,
+            values: *mut u32,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_get_execution_mode_argument(
+            compiler: *const root::ScInternalCompilerBase,
+            mode: root::spv::ExecutionMode,
+            index: u32,
+            has_argument: *mut bool,
+            argument: *mut u32,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_has_execution_mode(
+            compiler: *const root::ScInternalCompilerBase,
+            mode: root::spv::ExecutionMode,
+            has_mode: *mut bool,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_set_execution_mode(
+            compiler: *const root::ScInternalCompilerBase,
+            mode: root::spv::ExecutionMode,
+            enable: bool,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_get_shader_resources_for_entry_point(
+            compiler: *const root::ScInternalCompilerBase,
+            name: *const ::std::os::raw::c_char,
+            execution_model: root::spv::ExecutionModel,
+            shader_resources: *mut root::ScShaderResources,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_get_specialization_constants(
             compiler: *const root::ScInternalCompilerBase,
@@ -2945,6 +3347,14 @@ pub mod root {
             constant_low_bits: u32,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_get_type_parent(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+            is_pointer: *mut bool,
+            parent_type: *mut u32,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_get_type(
             compiler: *const root::ScInternalCompilerBase,
@@ -3015,6 +3425,14 @@ pub mod root {
             execution_model: root::spv::ExecutionModel,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_rename_entry_point(
+            compiler: *const root::ScInternalCompilerBase,
+            old_name: *const ::std::os::raw::c_char,
+            new_name: *const ::std::os::raw::c_char,
+            execution_model: root::spv::ExecutionModel,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_compile(
             compiler: *const root::ScInternalCompilerBase,
@@ -3033,9 +3451,42 @@ pub mod root {
             size: *mut usize,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_get_active_builtins(
+            compiler: *const root::ScInternalCompilerBase,
+            inputs: *mut *mut root::spv::BuiltIn,
+            input_count: *mut usize,
+            outputs: *mut *mut root::spv::BuiltIn,
+            output_count: *mut usize,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_set_remove_unused_variables(
+            compiler: *const root::ScInternalCompilerBase,
+            remove: bool,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_keep_unused_resources(
+            compiler: *const root::ScInternalCompilerBase,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_alias_variable(
+            compiler: *const root::ScInternalCompilerBase,
+            alias_id: u32,
+            target_id: u32,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_free_pointer(
             pointer: *mut ::std::os::raw::c_void,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_arena_begin() -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_arena_end() -> root::ScInternalResult;
+    }
 }
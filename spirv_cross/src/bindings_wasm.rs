@@ -2545,6 +2545,7 @@ pub mod root {
             pub ycbcr_conversion_enable: bool,
         }
     }
+    pub type ScInternalParsedIr = ::std::os::raw::c_void;
     pub type ScInternalCompilerBase = ::std::os::raw::c_void;
     pub type ScInternalCompilerHlsl = ::std::os::raw::c_void;
     pub type ScInternalCompilerMsl = ::std::os::raw::c_void;
@@ -2564,6 +2565,9 @@ pub mod root {
         pub work_group_size_x: u32,
         pub work_group_size_y: u32,
         pub work_group_size_z: u32,
+        pub work_group_size_id_x: u32,
+        pub work_group_size_id_y: u32,
+        pub work_group_size_id_z: u32,
     }
     #[repr(C)]
     #[derive(Debug, Copy, Clone)]
@@ -2589,6 +2593,23 @@ pub mod root {
     }
     #[repr(C)]
     #[derive(Debug, Copy, Clone)]
+    pub struct ScHlslRegister {
+        pub register_space: u32,
+        pub register_binding: u32,
+    }
+    #[repr(C)]
+    #[derive(Debug, Copy, Clone)]
+    pub struct ScHlslResourceBinding {
+        pub stage: root::spv::ExecutionModel,
+        pub desc_set: u32,
+        pub binding: u32,
+        pub cbv: root::ScHlslRegister,
+        pub uav: root::ScHlslRegister,
+        pub srv: root::ScHlslRegister,
+        pub sampler: root::ScHlslRegister,
+    }
+    #[repr(C)]
+    #[derive(Debug, Copy, Clone)]
     pub struct ScHlslCompilerOptions {
         pub shader_model: i32,
         pub point_size_compat: bool,
@@ -2598,6 +2619,10 @@ pub mod root {
         pub force_storage_buffer_as_uav: bool,
         pub nonwritable_uav_texture_as_srv: bool,
         pub force_zero_initialized_variables: bool,
+        pub emit_line_directives: bool,
+        pub force_temporary: bool,
+        pub flatten_multidimensional_arrays: bool,
+        pub preserve_structured_buffers: bool,
     }
     #[repr(C)]
     #[derive(Debug, Copy, Clone)]
@@ -2622,6 +2647,17 @@ pub mod root {
         pub force_native_arrays: bool,
         pub force_zero_initialized_variables: bool,
         pub force_active_argument_buffer_resources: bool,
+        pub emit_line_directives: bool,
+        pub force_temporary: bool,
+        pub flatten_multidimensional_arrays: bool,
+        pub texture_buffer_native: bool,
+        pub texel_buffer_texture_width: u32,
+        pub emulate_cube_array: bool,
+        pub device_index: u32,
+        pub dispatch_base: bool,
+        pub emulate_subgroups: bool,
+        pub fixed_subgroup_size: u32,
+        pub ios_use_simdgroup_functions: bool,
     }
     #[repr(C)]
     #[derive(Debug, Copy, Clone)]
@@ -2643,6 +2679,7 @@ pub mod root {
         pub emit_line_directives: bool,
         pub enable_storage_image_qualifier_deduction: bool,
         pub force_zero_initialized_variables: bool,
+        pub omit_write_only_image_formats: bool,
     }
     #[repr(C)]
     #[derive(Debug, Copy, Clone)]
@@ -2660,6 +2697,19 @@ pub mod root {
     }
     #[repr(C)]
     #[derive(Debug, Copy, Clone)]
+    pub struct ScBuiltInResource {
+        pub builtin: root::spv::BuiltIn,
+        pub value_type_id: u32,
+        pub resource: root::ScResource,
+    }
+    #[repr(C)]
+    #[derive(Debug, Copy, Clone)]
+    pub struct ScBuiltInResourceArray {
+        pub data: *mut root::ScBuiltInResource,
+        pub num: usize,
+    }
+    #[repr(C)]
+    #[derive(Debug, Copy, Clone)]
     pub struct ScShaderResources {
         pub uniform_buffers: root::ScResourceArray,
         pub storage_buffers: root::ScResourceArray,
@@ -2672,7 +2722,18 @@ pub mod root {
         pub push_constant_buffers: root::ScResourceArray,
         pub separate_images: root::ScResourceArray,
         pub separate_samplers: root::ScResourceArray,
+        pub builtin_inputs: root::ScBuiltInResourceArray,
+        pub builtin_outputs: root::ScBuiltInResourceArray,
     }
+    pub type ScVariableTypeRemapCallback = ::std::option::Option<
+        unsafe extern "C" fn(
+            user_data: *mut ::std::os::raw::c_void,
+            type_id: u32,
+            variable_name: *const ::std::os::raw::c_char,
+            type_name: *const ::std::os::raw::c_char,
+            remapped_type_name: *mut *const ::std::os::raw::c_char,
+        ),
+    >;
     #[repr(C)]
     #[derive(Debug, Copy, Clone)]
     pub struct ScSpecializationConstant {
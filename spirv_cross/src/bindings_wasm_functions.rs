@@ -17,9 +17,24 @@ const U32_SIZE: u32 = std::mem::size_of::<u32>() as u32;
 extern "C" {
     // Raw SPIRV-Cross bindings
     // Pointers and the result type are replaced with `u32`
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_parsed_ir_new(ir: u32, words: u32, size: u32) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_parsed_ir_delete(ir: u32) -> u32;
+
     #[wasm_bindgen(js_namespace = sc_internal)]
     fn _sc_internal_compiler_glsl_new(compiler: u32, ir: u32, size: u32) -> u32;
 
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_reflect_new(compiler: u32, ir: u32, size: u32) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_reflect_new_from_ir(compiler: u32, ir: u32) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_glsl_new_from_ir(compiler: u32, ir: u32) -> u32;
+
     #[wasm_bindgen(js_namespace = sc_internal)]
     fn _sc_internal_compiler_glsl_set_options(compiler: u32, options: u32) -> u32;
 
@@ -39,6 +54,22 @@ extern "C" {
     #[wasm_bindgen(js_namespace = sc_internal)]
     fn _sc_internal_compiler_glsl_flatten_buffer_block(compiler: u32, id: u32) -> u32;
 
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_glsl_remap_subpass_input_to_sampler(compiler: u32, id: u32) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_glsl_set_image_format(compiler: u32, id: u32, format: u32) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_glsl_get_image_format(compiler: u32, id: u32, format: u32) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_glsl_get_required_extensions(
+        compiler: u32,
+        extensions: u32,
+        size: u32,
+    ) -> u32;
+
     #[wasm_bindgen(js_namespace = sc_internal)]
     fn _sc_internal_compiler_get_decoration(
         compiler: u32,
@@ -47,6 +78,65 @@ extern "C" {
         decoration: u32,
     ) -> u32;
 
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_has_decoration(
+        compiler: u32,
+        id: u32,
+        decoration: u32,
+        result: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_has_member_decoration(
+        compiler: u32,
+        id: u32,
+        index: u32,
+        decoration: u32,
+        result: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_decoration_string(
+        compiler: u32,
+        result: u32,
+        id: u32,
+        decoration: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_buffer_is_hlsl_counter_buffer(
+        compiler: u32,
+        id: u32,
+        is_counter_buffer: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_buffer_get_hlsl_counter_buffer(
+        compiler: u32,
+        id: u32,
+        has_counter_buffer: u32,
+        counter_id: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_needs_dummy_sampler_for_combined_images(
+        compiler: u32,
+        needs_dummy_sampler: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_variable_is_depth_or_compare(
+        compiler: u32,
+        id: u32,
+        is_depth_or_compare: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_build_dummy_sampler_for_combined_images(
+        compiler: u32,
+        sampler_id: u32,
+    ) -> u32;
+
     #[wasm_bindgen(js_namespace = sc_internal)]
     fn _sc_internal_compiler_set_decoration(
         compiler: u32,
@@ -55,21 +145,52 @@ extern "C" {
         argument: u32,
     ) -> u32;
 
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_set_decorations(
+        compiler: u32,
+        ids: u32,
+        decorations: u32,
+        arguments: u32,
+        count: u32,
+        invalid_entry: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_set_decoration_string(
+        compiler: u32,
+        id: u32,
+        decoration: u32,
+        argument: u32,
+    ) -> u32;
+
     #[wasm_bindgen(js_namespace = sc_internal)]
     fn _sc_internal_compiler_unset_decoration(compiler: u32, id: u32, decoration: u32) -> u32;
 
     #[wasm_bindgen(js_namespace = sc_internal)]
     fn _sc_internal_compiler_get_name(compiler: u32, id: u32, name: u32) -> u32;
 
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_remapped_declared_block_name(
+        compiler: u32,
+        id: u32,
+        name: u32,
+    ) -> u32;
+
     #[wasm_bindgen(js_namespace = sc_internal)]
     fn _sc_internal_compiler_set_name(compiler: u32, id: u32, name: u32) -> u32;
 
     #[wasm_bindgen(js_namespace = sc_internal)]
     fn _sc_internal_compiler_set_member_name(compiler: u32, id: u32, index: u32, name: u32) -> u32;
 
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_strip_names(compiler: u32) -> u32;
+
     #[wasm_bindgen(js_namespace = sc_internal)]
     fn _sc_internal_compiler_get_entry_points(compiler: u32, entry_points: u32, size: u32) -> u32;
 
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_current_entry_point(compiler: u32, entry_point: u32) -> u32;
+
     #[wasm_bindgen(js_namespace = sc_internal)]
     fn _sc_internal_compiler_get_active_buffer_ranges(
         compiler: u32,
@@ -89,6 +210,28 @@ extern "C" {
     #[wasm_bindgen(js_namespace = sc_internal)]
     fn _sc_internal_compiler_get_shader_resources(compiler: u32, shader_resources: u32) -> u32;
 
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_shader_resources_of_kinds(
+        compiler: u32,
+        kinds: u32,
+        shader_resources: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_shader_resource_list(
+        compiler: u32,
+        kind: u32,
+        resources: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_shader_resources_for_entry_point(
+        compiler: u32,
+        name: u32,
+        execution_model: u32,
+        shader_resources: u32,
+    ) -> u32;
+
     #[wasm_bindgen(js_namespace = sc_internal)]
     fn _sc_internal_compiler_get_specialization_constants(
         compiler: u32,
@@ -107,6 +250,14 @@ extern "C" {
     #[wasm_bindgen(js_namespace = sc_internal)]
     fn _sc_internal_compiler_get_type(compiler: u32, id: u32, spirv_type: u32) -> u32;
 
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_type_parent(
+        compiler: u32,
+        id: u32,
+        is_pointer: u32,
+        parent_type: u32,
+    ) -> u32;
+
     #[wasm_bindgen(js_namespace = sc_internal)]
     fn _sc_internal_compiler_get_member_name(compiler: u32, id: u32, index: u32, name: u32) -> u32;
 
@@ -158,6 +309,123 @@ extern "C" {
     fn _sc_internal_compiler_set_entry_point(compiler: u32, name: u32, execution_model: u32)
         -> u32;
 
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_rename_entry_point(
+        compiler: u32,
+        old_name: u32,
+        new_name: u32,
+        execution_model: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_active_interface_variables(
+        compiler: u32,
+        ids: u32,
+        size: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_active_builtins(
+        compiler: u32,
+        inputs: u32,
+        input_count: u32,
+        outputs: u32,
+        output_count: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_set_remove_unused_variables(compiler: u32, remove: u32) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_keep_unused_resources(compiler: u32) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_alias_variable(compiler: u32, alias_id: u32, target_id: u32) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_declared_capabilities(
+        compiler: u32,
+        capabilities: u32,
+        size: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_used_opcodes(compiler: u32, opcodes: u32, size: u32) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_spirv(compiler: u32, words: u32, size: u32) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_meta_ids(compiler: u32, ids: u32, size: u32) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_descriptor_count(
+        compiler: u32,
+        id: u32,
+        count: u32,
+        is_runtime_sized: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_outer_array(
+        compiler: u32,
+        id: u32,
+        element_type_id: u32,
+        length: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_workgroup_shared_memory_size(
+        compiler: u32,
+        name: u32,
+        execution_model: u32,
+        size: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_entry_point_interface_variables(
+        compiler: u32,
+        name: u32,
+        execution_model: u32,
+        ids: u32,
+        size: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_entry_point_subgroup_size(
+        compiler: u32,
+        name: u32,
+        execution_model: u32,
+        has_subgroup_size: u32,
+        subgroup_size: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_decorations(
+        compiler: u32,
+        ids: u32,
+        id_count: u32,
+        decorations: u32,
+        decoration_count: u32,
+        has_decorations: u32,
+        values: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_get_execution_mode_argument(
+        compiler: u32,
+        mode: u32,
+        index: u32,
+        has_argument: u32,
+        argument: u32,
+    ) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_has_execution_mode(compiler: u32, mode: u32, has_mode: u32) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_compiler_set_execution_mode(compiler: u32, mode: u32, enable: u32) -> u32;
+
     #[wasm_bindgen(js_namespace = sc_internal)]
     fn _sc_internal_compiler_compile(compiler: u32, shader: u32) -> u32;
 
@@ -166,6 +434,21 @@ extern "C" {
 
     #[wasm_bindgen(js_namespace = sc_internal)]
     fn _sc_internal_free_pointer(pointer: u32) -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_arena_begin() -> u32;
+
+    #[wasm_bindgen(js_namespace = sc_internal)]
+    fn _sc_internal_arena_end() -> u32;
+}
+
+// The function added to the wrapper last. A module without it was built from an older wrapper,
+// whose structs have other layouts and which lacks functions the crate calls, so no parsed IR or
+// compiler is created from it. Update this with each function added to the wrapper.
+const NEWEST_WRAPPER_FUNCTION: &str = "_sc_internal_compiler_get_shader_resources_of_kinds";
+
+fn is_module_outdated() -> bool {
+    !emscripten::get_module().has_function(NEWEST_WRAPPER_FUNCTION)
 }
 
 fn map_internal_result(result: u32) -> bindings::ScInternalResult {
@@ -185,11 +468,47 @@ pub fn sc_internal_get_latest_exception_message(
     bindings::ScInternalResult::Success
 }
 
+pub fn sc_internal_parsed_ir_new(
+    ir: *mut *mut bindings::ScInternalParsedIr,
+    words: *const u32,
+    size: usize,
+) -> bindings::ScInternalResult {
+    if is_module_outdated() {
+        return bindings::ScInternalResult::Unhandled;
+    }
+    let spirv_bytes = size * (U32_SIZE as usize);
+    unsafe {
+        let spirv = std::slice::from_raw_parts(words as *const u8, spirv_bytes);
+        let module = emscripten::get_module();
+        let spirv_ptr = module.allocate(spirv_bytes as u32);
+        module.set_from_u8_slice(spirv_ptr, spirv);
+        let ir_ptr_to_ptr = module.allocate(U32_SIZE);
+        let result = map_internal_result(_sc_internal_parsed_ir_new(
+            ir_ptr_to_ptr.as_offset(),
+            spirv_ptr.as_offset(),
+            size as u32,
+        ));
+        *ir = module.get_u32(ir_ptr_to_ptr) as *mut bindings::ScInternalParsedIr;
+        module.free(ir_ptr_to_ptr);
+        module.free(spirv_ptr);
+        result
+    }
+}
+
+pub fn sc_internal_parsed_ir_delete(
+    ir: *mut bindings::ScInternalParsedIr,
+) -> bindings::ScInternalResult {
+    map_internal_result(_sc_internal_parsed_ir_delete(ir as u32))
+}
+
 pub fn sc_internal_compiler_glsl_new(
     compiler: *mut *mut bindings::ScInternalCompilerGlsl,
     ir: *const u32,
     size: usize,
 ) -> bindings::ScInternalResult {
+    if is_module_outdated() {
+        return bindings::ScInternalResult::Unhandled;
+    }
     let spirv_bytes = size * (U32_SIZE as usize);
     unsafe {
         let spirv = std::slice::from_raw_parts(ir as *const u8, spirv_bytes);
@@ -209,6 +528,73 @@ pub fn sc_internal_compiler_glsl_new(
     }
 }
 
+pub fn sc_internal_compiler_glsl_new_from_ir(
+    compiler: *mut *mut bindings::ScInternalCompilerGlsl,
+    ir: *const bindings::ScInternalParsedIr,
+) -> bindings::ScInternalResult {
+    if is_module_outdated() {
+        return bindings::ScInternalResult::Unhandled;
+    }
+    let module = emscripten::get_module();
+    unsafe {
+        let compiler_ptr_to_ptr = module.allocate(U32_SIZE);
+        let result = map_internal_result(_sc_internal_compiler_glsl_new_from_ir(
+            compiler_ptr_to_ptr.as_offset(),
+            ir as u32,
+        ));
+        *compiler = module.get_u32(compiler_ptr_to_ptr) as *mut bindings::ScInternalCompilerGlsl;
+        module.free(compiler_ptr_to_ptr);
+        result
+    }
+}
+
+pub fn sc_internal_compiler_reflect_new(
+    compiler: *mut *mut bindings::ScInternalCompilerBase,
+    ir: *const u32,
+    size: usize,
+) -> bindings::ScInternalResult {
+    if is_module_outdated() {
+        return bindings::ScInternalResult::Unhandled;
+    }
+    let spirv_bytes = size * (U32_SIZE as usize);
+    unsafe {
+        let spirv = std::slice::from_raw_parts(ir as *const u8, spirv_bytes);
+        let module = emscripten::get_module();
+        let spirv_ptr = module.allocate(spirv_bytes as u32);
+        module.set_from_u8_slice(spirv_ptr, spirv);
+        let compiler_ptr_to_ptr = module.allocate(U32_SIZE);
+        let result = map_internal_result(_sc_internal_compiler_reflect_new(
+            compiler_ptr_to_ptr.as_offset(),
+            spirv_ptr.as_offset(),
+            size as u32,
+        ));
+        *compiler = module.get_u32(compiler_ptr_to_ptr) as *mut bindings::ScInternalCompilerBase;
+        module.free(compiler_ptr_to_ptr);
+        module.free(spirv_ptr);
+        result
+    }
+}
+
+pub fn sc_internal_compiler_reflect_new_from_ir(
+    compiler: *mut *mut bindings::ScInternalCompilerBase,
+    ir: *const bindings::ScInternalParsedIr,
+) -> bindings::ScInternalResult {
+    if is_module_outdated() {
+        return bindings::ScInternalResult::Unhandled;
+    }
+    let module = emscripten::get_module();
+    unsafe {
+        let compiler_ptr_to_ptr = module.allocate(U32_SIZE);
+        let result = map_internal_result(_sc_internal_compiler_reflect_new_from_ir(
+            compiler_ptr_to_ptr.as_offset(),
+            ir as u32,
+        ));
+        *compiler = module.get_u32(compiler_ptr_to_ptr) as *mut bindings::ScInternalCompilerBase;
+        module.free(compiler_ptr_to_ptr);
+        result
+    }
+}
+
 pub fn sc_internal_compiler_glsl_set_options(
     compiler: *const bindings::ScInternalCompilerGlsl,
     options: *const bindings::ScGlslCompilerOptions,
@@ -298,6 +684,75 @@ pub fn sc_internal_compiler_glsl_flatten_buffer_block(
     }
 }
 
+pub fn sc_internal_compiler_glsl_remap_subpass_input_to_sampler(
+    compiler: *const bindings::ScInternalCompilerBase,
+    id: u32,
+) -> bindings::ScInternalResult {
+    map_internal_result(_sc_internal_compiler_glsl_remap_subpass_input_to_sampler(
+        compiler as u32,
+        id,
+    ))
+}
+
+pub fn sc_internal_compiler_glsl_set_image_format(
+    compiler: *const bindings::ScInternalCompilerBase,
+    id: u32,
+    format: bindings::spv::ImageFormat,
+) -> bindings::ScInternalResult {
+    map_internal_result(_sc_internal_compiler_glsl_set_image_format(
+        compiler as u32,
+        id,
+        format as u32,
+    ))
+}
+
+pub fn sc_internal_compiler_glsl_get_image_format(
+    compiler: *const bindings::ScInternalCompilerBase,
+    id: u32,
+    format: *mut bindings::spv::ImageFormat,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let format_ptr = module.allocate(U32_SIZE);
+        let result = map_internal_result(_sc_internal_compiler_glsl_get_image_format(
+            compiler as u32,
+            id,
+            format_ptr.as_offset(),
+        ));
+        // The format is written as a 32-bit enum, matching the representation of the binding
+        *format =
+            std::mem::transmute::<u32, bindings::spv::ImageFormat>(module.get_u32(format_ptr));
+        module.free(format_ptr);
+        result
+    }
+}
+
+pub fn sc_internal_compiler_glsl_get_required_extensions(
+    compiler: *const bindings::ScInternalCompilerBase,
+    extensions: *mut *mut *const ::std::os::raw::c_char,
+    size: *mut usize,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let extensions_ptr_to_ptr = module.allocate(U32_SIZE);
+        let size_ptr = module.allocate(U32_SIZE);
+
+        let result = map_internal_result(_sc_internal_compiler_glsl_get_required_extensions(
+            compiler as u32,
+            extensions_ptr_to_ptr.as_offset(),
+            size_ptr.as_offset(),
+        ));
+
+        *extensions = module.get_u32(extensions_ptr_to_ptr) as *mut *const ::std::os::raw::c_char;
+        *size = module.get_u32(size_ptr) as usize;
+
+        module.free(size_ptr);
+        module.free(extensions_ptr_to_ptr);
+
+        result
+    }
+}
+
 pub fn sc_internal_compiler_get_decoration(
     compiler: *const bindings::ScInternalCompilerBase,
     result: *mut u32,
@@ -319,6 +774,171 @@ pub fn sc_internal_compiler_get_decoration(
     }
 }
 
+pub fn sc_internal_compiler_has_decoration(
+    compiler: *const bindings::ScInternalCompilerBase,
+    id: u32,
+    decoration: bindings::spv::Decoration,
+    result: *mut bool,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let result_ptr = module.allocate(U32_SIZE);
+        let ret = map_internal_result(_sc_internal_compiler_has_decoration(
+            compiler as u32,
+            id,
+            decoration as u32,
+            result_ptr.as_offset(),
+        ));
+        *result = module.get_u8(result_ptr) != 0;
+        module.free(result_ptr);
+        ret
+    }
+}
+
+pub fn sc_internal_compiler_has_member_decoration(
+    compiler: *const bindings::ScInternalCompilerBase,
+    id: u32,
+    index: u32,
+    decoration: bindings::spv::Decoration,
+    result: *mut bool,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let result_ptr = module.allocate(U32_SIZE);
+        let ret = map_internal_result(_sc_internal_compiler_has_member_decoration(
+            compiler as u32,
+            id,
+            index,
+            decoration as u32,
+            result_ptr.as_offset(),
+        ));
+        *result = module.get_u8(result_ptr) != 0;
+        module.free(result_ptr);
+        ret
+    }
+}
+
+pub fn sc_internal_compiler_get_decoration_string(
+    compiler: *const bindings::ScInternalCompilerBase,
+    result: *mut *const ::std::os::raw::c_char,
+    id: u32,
+    decoration: bindings::spv::Decoration,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let result_ptr_to_ptr = module.allocate(U32_SIZE);
+        let ret = map_internal_result(_sc_internal_compiler_get_decoration_string(
+            compiler as u32,
+            result_ptr_to_ptr.as_offset(),
+            id,
+            decoration as u32,
+        ));
+        *result = module.get_u32(result_ptr_to_ptr) as *const ::std::os::raw::c_char;
+        module.free(result_ptr_to_ptr);
+        ret
+    }
+}
+
+pub fn sc_internal_compiler_buffer_is_hlsl_counter_buffer(
+    compiler: *const bindings::ScInternalCompilerBase,
+    id: u32,
+    is_counter_buffer: *mut bool,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let is_counter_buffer_ptr = module.allocate(U32_SIZE);
+        let ret = map_internal_result(_sc_internal_compiler_buffer_is_hlsl_counter_buffer(
+            compiler as u32,
+            id,
+            is_counter_buffer_ptr.as_offset(),
+        ));
+        *is_counter_buffer = module.get_u8(is_counter_buffer_ptr) != 0;
+        module.free(is_counter_buffer_ptr);
+        ret
+    }
+}
+
+pub fn sc_internal_compiler_buffer_get_hlsl_counter_buffer(
+    compiler: *const bindings::ScInternalCompilerBase,
+    id: u32,
+    has_counter_buffer: *mut bool,
+    counter_id: *mut u32,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let has_counter_buffer_ptr = module.allocate(U32_SIZE);
+        let counter_id_ptr = module.allocate(U32_SIZE);
+        let ret = map_internal_result(_sc_internal_compiler_buffer_get_hlsl_counter_buffer(
+            compiler as u32,
+            id,
+            has_counter_buffer_ptr.as_offset(),
+            counter_id_ptr.as_offset(),
+        ));
+        *has_counter_buffer = module.get_u8(has_counter_buffer_ptr) != 0;
+        *counter_id = module.get_u32(counter_id_ptr);
+        module.free(has_counter_buffer_ptr);
+        module.free(counter_id_ptr);
+        ret
+    }
+}
+
+pub fn sc_internal_compiler_needs_dummy_sampler_for_combined_images(
+    compiler: *const bindings::ScInternalCompilerBase,
+    needs_dummy_sampler: *mut bool,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let needs_dummy_sampler_ptr = module.allocate(U32_SIZE);
+        let ret = map_internal_result(
+            _sc_internal_compiler_needs_dummy_sampler_for_combined_images(
+                compiler as u32,
+                needs_dummy_sampler_ptr.as_offset(),
+            ),
+        );
+        *needs_dummy_sampler = module.get_u8(needs_dummy_sampler_ptr) != 0;
+        module.free(needs_dummy_sampler_ptr);
+        ret
+    }
+}
+
+pub fn sc_internal_compiler_variable_is_depth_or_compare(
+    compiler: *const bindings::ScInternalCompilerBase,
+    id: u32,
+    is_depth_or_compare: *mut bool,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let is_depth_or_compare_ptr = module.allocate(U32_SIZE);
+        let ret = map_internal_result(_sc_internal_compiler_variable_is_depth_or_compare(
+            compiler as u32,
+            id,
+            is_depth_or_compare_ptr.as_offset(),
+        ));
+        *is_depth_or_compare = module.get_u8(is_depth_or_compare_ptr) != 0;
+        module.free(is_depth_or_compare_ptr);
+        ret
+    }
+}
+
+pub fn sc_internal_compiler_build_dummy_sampler_for_combined_images(
+    compiler: *const bindings::ScInternalCompilerBase,
+    sampler_id: *mut u32,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let sampler_id_ptr = module.allocate(U32_SIZE);
+        let ret = map_internal_result(
+            _sc_internal_compiler_build_dummy_sampler_for_combined_images(
+                compiler as u32,
+                sampler_id_ptr.as_offset(),
+            ),
+        );
+        *sampler_id = module.get_u32(sampler_id_ptr);
+        module.free(sampler_id_ptr);
+        ret
+    }
+}
+
 pub fn sc_internal_compiler_set_decoration(
     compiler: *const bindings::ScInternalCompilerBase,
     id: u32,
@@ -333,6 +953,69 @@ pub fn sc_internal_compiler_set_decoration(
     ))
 }
 
+pub fn sc_internal_compiler_set_decorations(
+    compiler: *const bindings::ScInternalCompilerBase,
+    ids: *const u32,
+    decorations: *const bindings::spv::Decoration,
+    arguments: *const u32,
+    count: usize,
+    invalid_entry: *mut usize,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let copy_to_module = |words: *const u32| {
+            let bytes = std::slice::from_raw_parts(words as *const u8, count * U32_SIZE as usize);
+            let ptr = module.allocate(bytes.len() as u32);
+            module.set_from_u8_slice(ptr, bytes);
+            ptr
+        };
+        let ids_ptr = copy_to_module(ids);
+        let decorations_ptr = copy_to_module(decorations as *const u32);
+        let arguments_ptr = copy_to_module(arguments);
+        let invalid_entry_ptr = module.allocate(U32_SIZE);
+
+        let result = map_internal_result(_sc_internal_compiler_set_decorations(
+            compiler as u32,
+            ids_ptr.as_offset(),
+            decorations_ptr.as_offset(),
+            arguments_ptr.as_offset(),
+            count as u32,
+            invalid_entry_ptr.as_offset(),
+        ));
+
+        *invalid_entry = module.get_u32(invalid_entry_ptr) as usize;
+
+        module.free(invalid_entry_ptr);
+        module.free(arguments_ptr);
+        module.free(decorations_ptr);
+        module.free(ids_ptr);
+
+        result
+    }
+}
+
+pub fn sc_internal_compiler_set_decoration_string(
+    compiler: *const bindings::ScInternalCompilerBase,
+    id: u32,
+    decoration: bindings::spv::Decoration,
+    argument: *const ::std::os::raw::c_char,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let argument_bytes = CStr::from_ptr(argument).to_bytes_with_nul();
+        let argument_ptr = module.allocate(argument_bytes.len() as u32);
+        module.set_from_u8_slice(argument_ptr, argument_bytes);
+        let result = map_internal_result(_sc_internal_compiler_set_decoration_string(
+            compiler as u32,
+            id,
+            decoration as u32,
+            argument_ptr.as_offset(),
+        ));
+        module.free(argument_ptr);
+        result
+    }
+}
+
 pub fn sc_internal_compiler_unset_decoration(
     compiler: *const bindings::ScInternalCompilerBase,
     id: u32,
@@ -365,6 +1048,26 @@ pub fn sc_internal_compiler_get_name(
     }
 }
 
+pub fn sc_internal_compiler_get_remapped_declared_block_name(
+    compiler: *const bindings::ScInternalCompilerBase,
+    id: u32,
+    name: *mut *const ::std::os::raw::c_char,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let name_ptr_to_ptr = module.allocate(U32_SIZE);
+        let result = map_internal_result(_sc_internal_compiler_get_remapped_declared_block_name(
+            compiler as u32,
+            id,
+            name_ptr_to_ptr.as_offset(),
+        ));
+        let name_ptr = module.get_u32(name_ptr_to_ptr);
+        *name = name_ptr as *const ::std::os::raw::c_char;
+        module.free(name_ptr_to_ptr);
+        result
+    }
+}
+
 pub fn sc_internal_compiler_set_name(
     compiler: *const bindings::ScInternalCompilerBase,
     id: u32,
@@ -407,6 +1110,12 @@ pub fn sc_internal_compiler_set_member_name(
     }
 }
 
+pub fn sc_internal_compiler_strip_names(
+    compiler: *const bindings::ScInternalCompilerBase,
+) -> bindings::ScInternalResult {
+    map_internal_result(_sc_internal_compiler_strip_names(compiler as u32))
+}
+
 pub fn sc_internal_compiler_get_entry_points(
     compiler: *const bindings::ScInternalCompilerBase,
     entry_points: *mut *mut bindings::ScEntryPoint,
@@ -433,6 +1142,27 @@ pub fn sc_internal_compiler_get_entry_points(
     }
 }
 
+pub fn sc_internal_compiler_get_current_entry_point(
+    compiler: *const bindings::ScInternalCompilerBase,
+    entry_point: *mut *mut bindings::ScEntryPoint,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let entry_point_ptr_to_ptr = module.allocate(U32_SIZE);
+
+        let result = map_internal_result(_sc_internal_compiler_get_current_entry_point(
+            compiler as u32,
+            entry_point_ptr_to_ptr.as_offset(),
+        ));
+
+        *entry_point = module.get_u32(entry_point_ptr_to_ptr) as *mut bindings::ScEntryPoint;
+
+        module.free(entry_point_ptr_to_ptr);
+
+        result
+    }
+}
+
 pub fn sc_internal_compiler_get_active_buffer_ranges(
     compiler: *const bindings::ScInternalCompilerBase,
     id: u32,
@@ -514,45 +1244,127 @@ pub fn sc_internal_compiler_get_shader_resources(
     }
 }
 
-pub fn sc_internal_compiler_get_specialization_constants(
+pub fn sc_internal_compiler_get_shader_resources_of_kinds(
     compiler: *const bindings::ScInternalCompilerBase,
-    constants: *mut *mut bindings::ScSpecializationConstant,
-    size: *mut usize,
+    kinds: u32,
+    shader_resources: *mut bindings::ScShaderResources,
 ) -> bindings::ScInternalResult {
     let module = emscripten::get_module();
     unsafe {
-        let constants_ptr_to_ptr = module.allocate(U32_SIZE);
-        let constants_size_ptr = module.allocate(U32_SIZE);
-        let result = map_internal_result(_sc_internal_compiler_get_specialization_constants(
+        let num_bytes = std::mem::size_of::<bindings::ScShaderResources>();
+        let shader_resources_ptr = module.allocate(num_bytes as u32);
+        let result = map_internal_result(_sc_internal_compiler_get_shader_resources_of_kinds(
             compiler as u32,
-            constants_ptr_to_ptr.as_offset(),
-            constants_size_ptr.as_offset() as u32,
+            kinds,
+            shader_resources_ptr.as_offset(),
         ));
-        *constants =
-            module.get_u32(constants_ptr_to_ptr) as *mut bindings::ScSpecializationConstant;
-        *size = module.get_u32(constants_size_ptr) as usize;
-        module.free(constants_size_ptr);
-        module.free(constants_ptr_to_ptr);
+        module.read_bytes_into_pointer_while(
+            shader_resources_ptr,
+            |byte, bytes_read| bytes_read < num_bytes,
+            false,
+            shader_resources as *mut u8,
+        );
+        module.free(shader_resources_ptr);
         result
     }
 }
 
-pub fn sc_internal_compiler_set_scalar_constant(
+pub fn sc_internal_compiler_get_shader_resource_list(
     compiler: *const bindings::ScInternalCompilerBase,
-    id: u32,
-    constant_high_bits: u32,
-    constant_low_bits: u32,
+    kind: u32,
+    resources: *mut bindings::ScResourceArray,
 ) -> bindings::ScInternalResult {
-    map_internal_result(_sc_internal_compiler_set_scalar_constant(
-        compiler as u32,
-        id,
-        constant_high_bits,
-        constant_low_bits,
-    ))
-}
-
-pub fn sc_internal_compiler_get_type(
-    compiler: *const bindings::ScInternalCompilerBase,
+    let module = emscripten::get_module();
+    unsafe {
+        let num_bytes = std::mem::size_of::<bindings::ScResourceArray>();
+        let resources_ptr = module.allocate(num_bytes as u32);
+        let result = map_internal_result(_sc_internal_compiler_get_shader_resource_list(
+            compiler as u32,
+            kind,
+            resources_ptr.as_offset(),
+        ));
+        module.read_bytes_into_pointer_while(
+            resources_ptr,
+            |byte, bytes_read| bytes_read < num_bytes,
+            false,
+            resources as *mut u8,
+        );
+        module.free(resources_ptr);
+        result
+    }
+}
+
+pub fn sc_internal_compiler_get_shader_resources_for_entry_point(
+    compiler: *const bindings::ScInternalCompilerBase,
+    name: *const ::std::os::raw::c_char,
+    execution_model: bindings::spv::ExecutionModel,
+    shader_resources: *mut bindings::ScShaderResources,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let name_bytes = CStr::from_ptr(name).to_bytes_with_nul();
+        let name_ptr = module.allocate(name_bytes.len() as u32);
+        module.set_from_u8_slice(name_ptr, name_bytes);
+        let num_bytes = std::mem::size_of::<bindings::ScShaderResources>();
+        let shader_resources_ptr = module.allocate(num_bytes as u32);
+        let result =
+            map_internal_result(_sc_internal_compiler_get_shader_resources_for_entry_point(
+                compiler as u32,
+                name_ptr.as_offset(),
+                execution_model as u32,
+                shader_resources_ptr.as_offset(),
+            ));
+        module.read_bytes_into_pointer_while(
+            shader_resources_ptr,
+            |byte, bytes_read| bytes_read < num_bytes,
+            false,
+            shader_resources as *mut u8,
+        );
+        module.free(name_ptr);
+        module.free(shader_resources_ptr);
+        result
+    }
+}
+
+pub fn sc_internal_compiler_get_specialization_constants(
+    compiler: *const bindings::ScInternalCompilerBase,
+    constants: *mut *mut bindings::ScSpecializationConstant,
+    size: *mut usize,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let constants_ptr_to_ptr = module.allocate(U32_SIZE);
+        let constants_size_ptr = module.allocate(U32_SIZE);
+        let result = map_internal_result(_sc_internal_compiler_get_specialization_constants(
+            compiler as u32,
+            constants_ptr_to_ptr.as_offset(),
+            constants_size_ptr.as_offset() as u32,
+        ));
+        *constants =
+            module.get_u32(constants_ptr_to_ptr) as *mut bindings::ScSpecializationConstant;
+        *size = module.get_u32(constants_size_ptr) as usize;
+        module.free(constants_size_ptr);
+        module.free(constants_ptr_to_ptr);
+        result
+    }
+}
+
+pub fn sc_internal_compiler_set_scalar_constant(
+    compiler: *const bindings::ScInternalCompilerBase,
+    id: u32,
+    constant_high_bits: u32,
+    constant_low_bits: u32,
+) -> bindings::ScInternalResult {
+    map_internal_result(_sc_internal_compiler_set_scalar_constant(
+        compiler as u32,
+        id,
+        constant_high_bits,
+        constant_low_bits,
+    ))
+}
+
+pub fn sc_internal_compiler_get_type(
+    compiler: *const bindings::ScInternalCompilerBase,
     id: u32,
     spirv_type: *mut *const bindings::ScType,
 ) -> bindings::ScInternalResult {
@@ -571,6 +1383,34 @@ pub fn sc_internal_compiler_get_type(
     }
 }
 
+pub fn sc_internal_compiler_get_type_parent(
+    compiler: *const bindings::ScInternalCompilerBase,
+    id: u32,
+    is_pointer: *mut bool,
+    parent_type: *mut u32,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let is_pointer_ptr = module.allocate(U32_SIZE);
+        let parent_type_ptr = module.allocate(U32_SIZE);
+
+        let result = map_internal_result(_sc_internal_compiler_get_type_parent(
+            compiler as u32,
+            id,
+            is_pointer_ptr.as_offset(),
+            parent_type_ptr.as_offset(),
+        ));
+
+        *is_pointer = module.get_u8(is_pointer_ptr) != 0;
+        *parent_type = module.get_u32(parent_type_ptr);
+
+        module.free(parent_type_ptr);
+        module.free(is_pointer_ptr);
+
+        result
+    }
+}
+
 pub fn sc_internal_compiler_get_member_name(
     compiler: *const bindings::ScInternalCompilerBase,
     id: u32,
@@ -765,6 +1605,500 @@ pub fn sc_internal_compiler_set_entry_point(
     }
 }
 
+pub fn sc_internal_compiler_rename_entry_point(
+    compiler: *const bindings::ScInternalCompilerBase,
+    old_name: *const ::std::os::raw::c_char,
+    new_name: *const ::std::os::raw::c_char,
+    execution_model: bindings::spv::ExecutionModel,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let old_name_bytes = CStr::from_ptr(old_name).to_bytes_with_nul();
+        let old_name_ptr = module.allocate(old_name_bytes.len() as u32);
+        module.set_from_u8_slice(old_name_ptr, old_name_bytes);
+        let new_name_bytes = CStr::from_ptr(new_name).to_bytes_with_nul();
+        let new_name_ptr = module.allocate(new_name_bytes.len() as u32);
+        module.set_from_u8_slice(new_name_ptr, new_name_bytes);
+
+        let result = map_internal_result(_sc_internal_compiler_rename_entry_point(
+            compiler as u32,
+            old_name_ptr.as_offset(),
+            new_name_ptr.as_offset(),
+            execution_model as u32,
+        ));
+
+        module.free(old_name_ptr);
+        module.free(new_name_ptr);
+
+        result
+    }
+}
+
+pub fn sc_internal_compiler_get_active_interface_variables(
+    compiler: *const bindings::ScInternalCompilerBase,
+    ids: *mut *mut u32,
+    size: *mut usize,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let ids_ptr_to_ptr = module.allocate(U32_SIZE);
+        let size_ptr = module.allocate(U32_SIZE);
+
+        let result = map_internal_result(_sc_internal_compiler_get_active_interface_variables(
+            compiler as u32,
+            ids_ptr_to_ptr.as_offset(),
+            size_ptr.as_offset(),
+        ));
+
+        *ids = module.get_u32(ids_ptr_to_ptr) as *mut u32;
+        *size = module.get_u32(size_ptr) as usize;
+
+        module.free(size_ptr);
+        module.free(ids_ptr_to_ptr);
+
+        result
+    }
+}
+
+pub fn sc_internal_compiler_get_active_builtins(
+    compiler: *const bindings::ScInternalCompilerBase,
+    inputs: *mut *mut bindings::spv::BuiltIn,
+    input_count: *mut usize,
+    outputs: *mut *mut bindings::spv::BuiltIn,
+    output_count: *mut usize,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let inputs_ptr_to_ptr = module.allocate(U32_SIZE);
+        let input_count_ptr = module.allocate(U32_SIZE);
+        let outputs_ptr_to_ptr = module.allocate(U32_SIZE);
+        let output_count_ptr = module.allocate(U32_SIZE);
+
+        let result = map_internal_result(_sc_internal_compiler_get_active_builtins(
+            compiler as u32,
+            inputs_ptr_to_ptr.as_offset(),
+            input_count_ptr.as_offset(),
+            outputs_ptr_to_ptr.as_offset(),
+            output_count_ptr.as_offset(),
+        ));
+
+        *inputs = module.get_u32(inputs_ptr_to_ptr) as *mut bindings::spv::BuiltIn;
+        *input_count = module.get_u32(input_count_ptr) as usize;
+        *outputs = module.get_u32(outputs_ptr_to_ptr) as *mut bindings::spv::BuiltIn;
+        *output_count = module.get_u32(output_count_ptr) as usize;
+
+        module.free(output_count_ptr);
+        module.free(outputs_ptr_to_ptr);
+        module.free(input_count_ptr);
+        module.free(inputs_ptr_to_ptr);
+
+        result
+    }
+}
+
+pub fn sc_internal_compiler_set_remove_unused_variables(
+    compiler: *const bindings::ScInternalCompilerBase,
+    remove: bool,
+) -> bindings::ScInternalResult {
+    map_internal_result(_sc_internal_compiler_set_remove_unused_variables(
+        compiler as u32,
+        remove as u32,
+    ))
+}
+
+pub fn sc_internal_compiler_keep_unused_resources(
+    compiler: *const bindings::ScInternalCompilerBase,
+) -> bindings::ScInternalResult {
+    map_internal_result(_sc_internal_compiler_keep_unused_resources(compiler as u32))
+}
+
+pub fn sc_internal_compiler_alias_variable(
+    compiler: *const bindings::ScInternalCompilerBase,
+    alias_id: u32,
+    target_id: u32,
+) -> bindings::ScInternalResult {
+    map_internal_result(_sc_internal_compiler_alias_variable(
+        compiler as u32,
+        alias_id,
+        target_id,
+    ))
+}
+
+pub fn sc_internal_compiler_get_declared_capabilities(
+    compiler: *const bindings::ScInternalCompilerBase,
+    capabilities: *mut *mut u32,
+    size: *mut usize,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let capabilities_ptr_to_ptr = module.allocate(U32_SIZE);
+        let size_ptr = module.allocate(U32_SIZE);
+
+        let result = map_internal_result(_sc_internal_compiler_get_declared_capabilities(
+            compiler as u32,
+            capabilities_ptr_to_ptr.as_offset(),
+            size_ptr.as_offset(),
+        ));
+
+        *capabilities = module.get_u32(capabilities_ptr_to_ptr) as *mut u32;
+        *size = module.get_u32(size_ptr) as usize;
+
+        module.free(size_ptr);
+        module.free(capabilities_ptr_to_ptr);
+
+        result
+    }
+}
+
+pub fn sc_internal_compiler_get_used_opcodes(
+    compiler: *const bindings::ScInternalCompilerBase,
+    opcodes: *mut *mut u32,
+    size: *mut usize,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let opcodes_ptr_to_ptr = module.allocate(U32_SIZE);
+        let size_ptr = module.allocate(U32_SIZE);
+
+        let result = map_internal_result(_sc_internal_compiler_get_used_opcodes(
+            compiler as u32,
+            opcodes_ptr_to_ptr.as_offset(),
+            size_ptr.as_offset(),
+        ));
+
+        *opcodes = module.get_u32(opcodes_ptr_to_ptr) as *mut u32;
+        *size = module.get_u32(size_ptr) as usize;
+
+        module.free(size_ptr);
+        module.free(opcodes_ptr_to_ptr);
+
+        result
+    }
+}
+
+pub fn sc_internal_compiler_get_spirv(
+    compiler: *const bindings::ScInternalCompilerBase,
+    words: *mut *mut u32,
+    size: *mut usize,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let words_ptr_to_ptr = module.allocate(U32_SIZE);
+        let size_ptr = module.allocate(U32_SIZE);
+
+        let result = map_internal_result(_sc_internal_compiler_get_spirv(
+            compiler as u32,
+            words_ptr_to_ptr.as_offset(),
+            size_ptr.as_offset(),
+        ));
+
+        *words = module.get_u32(words_ptr_to_ptr) as *mut u32;
+        *size = module.get_u32(size_ptr) as usize;
+
+        module.free(size_ptr);
+        module.free(words_ptr_to_ptr);
+
+        result
+    }
+}
+
+pub fn sc_internal_compiler_get_meta_ids(
+    compiler: *const bindings::ScInternalCompilerBase,
+    ids: *mut *mut u32,
+    size: *mut usize,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let ids_ptr_to_ptr = module.allocate(U32_SIZE);
+        let size_ptr = module.allocate(U32_SIZE);
+
+        let result = map_internal_result(_sc_internal_compiler_get_meta_ids(
+            compiler as u32,
+            ids_ptr_to_ptr.as_offset(),
+            size_ptr.as_offset(),
+        ));
+
+        *ids = module.get_u32(ids_ptr_to_ptr) as *mut u32;
+        *size = module.get_u32(size_ptr) as usize;
+
+        module.free(size_ptr);
+        module.free(ids_ptr_to_ptr);
+
+        result
+    }
+}
+
+pub fn sc_internal_compiler_get_descriptor_count(
+    compiler: *const bindings::ScInternalCompilerBase,
+    id: u32,
+    count: *mut u32,
+    is_runtime_sized: *mut bool,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let count_ptr = module.allocate(U32_SIZE);
+        let is_runtime_sized_ptr = module.allocate(U32_SIZE);
+
+        let result = map_internal_result(_sc_internal_compiler_get_descriptor_count(
+            compiler as u32,
+            id,
+            count_ptr.as_offset(),
+            is_runtime_sized_ptr.as_offset(),
+        ));
+
+        *count = module.get_u32(count_ptr);
+        *is_runtime_sized = module.get_u8(is_runtime_sized_ptr) != 0;
+
+        module.free(is_runtime_sized_ptr);
+        module.free(count_ptr);
+
+        result
+    }
+}
+
+pub fn sc_internal_compiler_get_outer_array(
+    compiler: *const bindings::ScInternalCompilerBase,
+    id: u32,
+    element_type_id: *mut u32,
+    length: *mut u32,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let element_type_id_ptr = module.allocate(U32_SIZE);
+        let length_ptr = module.allocate(U32_SIZE);
+
+        let result = map_internal_result(_sc_internal_compiler_get_outer_array(
+            compiler as u32,
+            id,
+            element_type_id_ptr.as_offset(),
+            length_ptr.as_offset(),
+        ));
+
+        *element_type_id = module.get_u32(element_type_id_ptr);
+        *length = module.get_u32(length_ptr);
+
+        module.free(length_ptr);
+        module.free(element_type_id_ptr);
+
+        result
+    }
+}
+
+pub fn sc_internal_compiler_get_workgroup_shared_memory_size(
+    compiler: *const bindings::ScInternalCompilerBase,
+    name: *const ::std::os::raw::c_char,
+    execution_model: bindings::spv::ExecutionModel,
+    size: *mut u32,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let name_bytes = CStr::from_ptr(name).to_bytes_with_nul();
+        let name_ptr = module.allocate(name_bytes.len() as u32);
+        module.set_from_u8_slice(name_ptr, name_bytes);
+        let size_ptr = module.allocate(U32_SIZE);
+
+        let result = map_internal_result(_sc_internal_compiler_get_workgroup_shared_memory_size(
+            compiler as u32,
+            name_ptr.as_offset(),
+            execution_model as u32,
+            size_ptr.as_offset(),
+        ));
+
+        *size = module.get_u32(size_ptr);
+
+        module.free(size_ptr);
+        module.free(name_ptr);
+
+        result
+    }
+}
+
+pub fn sc_internal_compiler_get_entry_point_interface_variables(
+    compiler: *const bindings::ScInternalCompilerBase,
+    name: *const ::std::os::raw::c_char,
+    execution_model: bindings::spv::ExecutionModel,
+    ids: *mut *mut u32,
+    size: *mut usize,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let name_bytes = CStr::from_ptr(name).to_bytes_with_nul();
+        let name_ptr = module.allocate(name_bytes.len() as u32);
+        module.set_from_u8_slice(name_ptr, name_bytes);
+        let ids_ptr_to_ptr = module.allocate(U32_SIZE);
+        let size_ptr = module.allocate(U32_SIZE);
+
+        let result =
+            map_internal_result(_sc_internal_compiler_get_entry_point_interface_variables(
+                compiler as u32,
+                name_ptr.as_offset(),
+                execution_model as u32,
+                ids_ptr_to_ptr.as_offset(),
+                size_ptr.as_offset(),
+            ));
+
+        *ids = module.get_u32(ids_ptr_to_ptr) as *mut u32;
+        *size = module.get_u32(size_ptr) as usize;
+
+        module.free(size_ptr);
+        module.free(ids_ptr_to_ptr);
+        module.free(name_ptr);
+
+        result
+    }
+}
+
+pub fn sc_internal_compiler_get_entry_point_subgroup_size(
+    compiler: *const bindings::ScInternalCompilerBase,
+    name: *const ::std::os::raw::c_char,
+    execution_model: bindings::spv::ExecutionModel,
+    has_subgroup_size: *mut bool,
+    subgroup_size: *mut u32,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let name_bytes = CStr::from_ptr(name).to_bytes_with_nul();
+        let name_ptr = module.allocate(name_bytes.len() as u32);
+        module.set_from_u8_slice(name_ptr, name_bytes);
+        let has_subgroup_size_ptr = module.allocate(U32_SIZE);
+        let subgroup_size_ptr = module.allocate(U32_SIZE);
+
+        let result = map_internal_result(_sc_internal_compiler_get_entry_point_subgroup_size(
+            compiler as u32,
+            name_ptr.as_offset(),
+            execution_model as u32,
+            has_subgroup_size_ptr.as_offset(),
+            subgroup_size_ptr.as_offset(),
+        ));
+
+        *has_subgroup_size = module.get_u8(has_subgroup_size_ptr) != 0;
+        *subgroup_size = module.get_u32(subgroup_size_ptr);
+
+        module.free(subgroup_size_ptr);
+        module.free(has_subgroup_size_ptr);
+        module.free(name_ptr);
+
+        result
+    }
+}
+
+pub fn sc_internal_compiler_get_execution_mode_argument(
+    compiler: *const bindings::ScInternalCompilerBase,
+    mode: bindings::spv::ExecutionMode,
+    index: u32,
+    has_argument: *mut bool,
+    argument: *mut u32,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let has_argument_ptr = module.allocate(U32_SIZE);
+        let argument_ptr = module.allocate(U32_SIZE);
+
+        let result = map_internal_result(_sc_internal_compiler_get_execution_mode_argument(
+            compiler as u32,
+            mode,
+            index,
+            has_argument_ptr.as_offset(),
+            argument_ptr.as_offset(),
+        ));
+
+        *has_argument = module.get_u8(has_argument_ptr) != 0;
+        *argument = module.get_u32(argument_ptr);
+
+        module.free(argument_ptr);
+        module.free(has_argument_ptr);
+
+        result
+    }
+}
+
+pub fn sc_internal_compiler_has_execution_mode(
+    compiler: *const bindings::ScInternalCompilerBase,
+    mode: bindings::spv::ExecutionMode,
+    has_mode: *mut bool,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    unsafe {
+        let has_mode_ptr = module.allocate(U32_SIZE);
+
+        let result = map_internal_result(_sc_internal_compiler_has_execution_mode(
+            compiler as u32,
+            mode,
+            has_mode_ptr.as_offset(),
+        ));
+
+        *has_mode = module.get_u8(has_mode_ptr) != 0;
+
+        module.free(has_mode_ptr);
+
+        result
+    }
+}
+
+pub fn sc_internal_compiler_set_execution_mode(
+    compiler: *const bindings::ScInternalCompilerBase,
+    mode: bindings::spv::ExecutionMode,
+    enable: bool,
+) -> bindings::ScInternalResult {
+    map_internal_result(_sc_internal_compiler_set_execution_mode(
+        compiler as u32,
+        mode,
+        enable as u32,
+    ))
+}
+
+pub fn sc_internal_compiler_get_decorations(
+    compiler: *const bindings::ScInternalCompilerBase,
+    ids: *const u32,
+    id_count: usize,
+    decorations: *const bindings::spv::Decoration,
+    decoration_count: usize,
+    has_decorations: *mut bool,
+    values: *mut u32,
+) -> bindings::ScInternalResult {
+    let module = emscripten::get_module();
+    let count = id_count * decoration_count;
+    unsafe {
+        let ids_bytes = std::slice::from_raw_parts(ids as *const u8, id_count * U32_SIZE as usize);
+        let ids_ptr = module.allocate(ids_bytes.len() as u32);
+        module.set_from_u8_slice(ids_ptr, ids_bytes);
+        let decorations_bytes = std::slice::from_raw_parts(
+            decorations as *const u8,
+            decoration_count * U32_SIZE as usize,
+        );
+        let decorations_ptr = module.allocate(decorations_bytes.len() as u32);
+        module.set_from_u8_slice(decorations_ptr, decorations_bytes);
+        let has_decorations_ptr = module.allocate(count as u32);
+        let values_ptr = module.allocate(count as u32 * U32_SIZE);
+
+        let result = map_internal_result(_sc_internal_compiler_get_decorations(
+            compiler as u32,
+            ids_ptr.as_offset(),
+            id_count as u32,
+            decorations_ptr.as_offset(),
+            decoration_count as u32,
+            has_decorations_ptr.as_offset(),
+            values_ptr.as_offset(),
+        ));
+
+        for index in 0..count {
+            let has_decoration_ptr =
+                emscripten::Pointer::from_offset(has_decorations_ptr.as_offset() + index as u32);
+            *has_decorations.add(index) = module.get_u8(has_decoration_ptr) != 0;
+            let value_ptr =
+                emscripten::Pointer::from_offset(values_ptr.as_offset() + index as u32 * U32_SIZE);
+            *values.add(index) = module.get_u32(value_ptr);
+        }
+
+        module.free(values_ptr);
+        module.free(has_decorations_ptr);
+        module.free(decorations_ptr);
+        module.free(ids_ptr);
+
+        result
+    }
+}
+
 pub fn sc_internal_compiler_compile(
     compiler: *const bindings::ScInternalCompilerBase,
     shader: *mut *const ::std::os::raw::c_char,
@@ -794,3 +2128,11 @@ pub fn sc_internal_free_pointer(
 ) -> bindings::ScInternalResult {
     map_internal_result(_sc_internal_free_pointer(pointer as u32))
 }
+
+pub fn sc_internal_arena_begin() -> bindings::ScInternalResult {
+    map_internal_result(_sc_internal_arena_begin())
+}
+
+pub fn sc_internal_arena_end() -> bindings::ScInternalResult {
+    map_internal_result(_sc_internal_arena_end())
+}
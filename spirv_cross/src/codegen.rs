@@ -0,0 +1,305 @@
+//! Generation of Rust struct definitions matching the explicit layout of buffer blocks.
+use crate::spirv::{self, Decoration, Type};
+use crate::ErrorCode;
+use std::collections::{HashMap, HashSet};
+
+/// Keywords which cannot be used as identifiers, some of which cannot even be raw identifiers.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "do", "dyn", "else",
+    "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop", "macro",
+    "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "self", "Self",
+    "static", "struct", "super", "trait", "true", "try", "type", "typeof", "unsafe", "unsized",
+    "use", "virtual", "where", "while", "yield",
+];
+
+/// The name, size and alignment of a generated Rust type.
+#[derive(Clone)]
+struct RustType {
+    name: String,
+    size: u32,
+    alignment: u32,
+}
+
+/// The buffer block member whose type is being generated.
+struct Member<'a> {
+    struct_name: &'a str,
+    name: &'a str,
+    matrix_stride: u32,
+    row_major: bool,
+}
+
+#[derive(Default)]
+struct Generator {
+    structs: HashMap<u32, RustType>,
+    names: HashSet<String>,
+    definitions: Vec<String>,
+}
+
+impl Generator {
+    /// Reserves a struct name, adding a number if another struct already uses it.
+    fn reserve_name(&mut self, name: String) -> String {
+        let mut unique = name.clone();
+        let mut suffix = 1;
+        while !self.names.insert(unique.clone()) {
+            suffix += 1;
+            unique = format!("{}{}", name, suffix);
+        }
+        unique
+    }
+
+    fn add_definition(&mut self, name: &str, fields: &[(String, String)]) {
+        let mut definition = format!(
+            "#[repr(C)]\n#[derive(Copy, Clone)]\npub struct {} {{\n",
+            name
+        );
+        for (field, ty) in fields {
+            definition.push_str(&format!("    pub {}: {},\n", field, ty));
+        }
+        definition.push_str("}\n");
+        self.definitions.push(definition);
+    }
+}
+
+/// Turns a SPIR-V name into an identifier, replacing characters which identifiers cannot contain.
+fn to_identifier(name: &str) -> String {
+    let mut identifier = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    if identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+    if KEYWORDS.contains(&identifier.as_str()) {
+        identifier.push('_');
+    }
+    identifier
+}
+
+/// Turns a SPIR-V name, such as `type.ConstantBuffer.Globals`, into a type name like
+/// `TypeConstantBufferGlobals`.
+fn to_type_name(name: &str) -> String {
+    to_identifier(name)
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn get_scalar_type(ty: &Type) -> Option<(&'static str, u32)> {
+    Some(match ty {
+        // Booleans cannot be stored in buffers, and are loaded from 32-bit integers instead
+        Type::Boolean { .. } | Type::UInt { .. } => ("u32", 4),
+        Type::Int { .. } => ("i32", 4),
+        Type::Int64 { .. } => ("i64", 8),
+        Type::UInt64 { .. } => ("u64", 8),
+        // Rust has no stable 16-bit float, so halves are kept as their bits
+        Type::Half { .. } | Type::UShort { .. } => ("u16", 2),
+        Type::Short { .. } => ("i16", 2),
+        Type::Float { .. } => ("f32", 4),
+        Type::Double { .. } => ("f64", 8),
+        Type::SByte { .. } => ("i8", 1),
+        Type::UByte { .. } => ("u8", 1),
+        _ => return None,
+    })
+}
+
+fn get_columns(ty: &Type) -> u32 {
+    match *ty {
+        Type::Boolean { columns, .. }
+        | Type::Int { columns, .. }
+        | Type::UInt { columns, .. }
+        | Type::Half { columns, .. }
+        | Type::Float { columns, .. }
+        | Type::Double { columns, .. } => columns,
+        _ => 1,
+    }
+}
+
+fn round_up(value: u32, alignment: u32) -> u32 {
+    (value + alignment - 1) / alignment * alignment
+}
+
+impl<TTarget, TState> spirv::Ast<TTarget, TState>
+where
+    TTarget: spirv::Target,
+{
+    /// Generates `#[repr(C)]` Rust struct definitions with the same memory layout as a buffer
+    /// block struct, following its explicit offsets, array strides and matrix strides, whether
+    /// they come from the std140, std430 or scalar rules. Nested structs are defined before the
+    /// structs which contain them.
+    ///
+    /// Gaps between members are filled with `_padN` byte arrays. Matrices become arrays of their
+    /// columns, or rows if row major, which include the padding of the matrix stride. Arrays
+    /// whose stride is larger than their element become arrays of a generated element struct
+    /// with the value and its padding. A runtime array becomes a zero-length array at the end.
+    pub fn generate_rust_structs(&self, type_id: u32) -> Result<String, ErrorCode> {
+        let mut generator = Generator::default();
+        self.generate_rust_struct(&mut generator, type_id)?;
+        Ok(generator.definitions.join("\n"))
+    }
+
+    fn generate_rust_struct(
+        &self,
+        generator: &mut Generator,
+        type_id: u32,
+    ) -> Result<RustType, ErrorCode> {
+        if let Some(rust_type) = generator.structs.get(&type_id) {
+            return Ok(rust_type.clone());
+        }
+
+        let member_types = match self.get_type(type_id)? {
+            Type::Struct { member_types, .. } => member_types,
+            _ => {
+                return Err(ErrorCode::CompilationError(format!(
+                    "type {} is not a struct",
+                    type_id
+                )))
+            }
+        };
+        let name = match self.compiler.get_name(type_id)? {
+            name if name.is_empty() => format!("Struct{}", type_id),
+            name => to_type_name(&name),
+        };
+        let name = generator.reserve_name(name);
+
+        let mut fields = Vec::new();
+        let mut padding_count = 0;
+        let mut end = 0;
+        let mut alignment = 1;
+        for (index, member_type) in (0..).zip(member_types) {
+            let offset = self.get_member_decoration(type_id, index, Decoration::Offset)?;
+            if offset < end {
+                return Err(ErrorCode::CompilationError(format!(
+                    "member {} of struct {} overlaps the previous member",
+                    index, type_id
+                )));
+            }
+            if offset > end {
+                fields.push((
+                    format!("_pad{}", padding_count),
+                    format!("[u8; {}]", offset - end),
+                ));
+                padding_count += 1;
+            }
+
+            let member_name = match self.get_member_name(type_id, index)? {
+                member_name if member_name.is_empty() => format!("member{}", index),
+                member_name => to_identifier(&member_name),
+            };
+            let member = Member {
+                struct_name: &name,
+                name: &member_name,
+                matrix_stride: self.get_member_decoration(
+                    type_id,
+                    index,
+                    Decoration::MatrixStride,
+                )?,
+                row_major: self.has_member_decoration(type_id, index, Decoration::RowMajor)?,
+            };
+            let rust_type = self.generate_rust_type(generator, member_type, &member)?;
+            alignment = alignment.max(rust_type.alignment);
+            end = offset + rust_type.size;
+            fields.push((member_name, rust_type.name));
+        }
+
+        generator.add_definition(&name, &fields);
+        let rust_type = RustType {
+            name,
+            size: round_up(end, alignment),
+            alignment,
+        };
+        generator.structs.insert(type_id, rust_type.clone());
+        Ok(rust_type)
+    }
+
+    fn generate_rust_type(
+        &self,
+        generator: &mut Generator,
+        type_id: u32,
+        member: &Member,
+    ) -> Result<RustType, ErrorCode> {
+        let ty = self.get_type(type_id)?;
+        if !spirv::get_array(&ty).is_empty() {
+            let (element_type_id, length) = self.compiler.get_outer_array(type_id)?;
+            let element = self.generate_rust_type(generator, element_type_id, member)?;
+            let stride = self.get_decoration(type_id, Decoration::ArrayStride)?;
+            if stride < element.size || stride % element.alignment != 0 {
+                return Err(ErrorCode::CompilationError(format!(
+                    "array stride {} of type {} cannot be represented",
+                    stride, type_id
+                )));
+            }
+
+            let element_name = if stride == element.size {
+                element.name
+            } else {
+                let name = generator.reserve_name(format!(
+                    "{}{}Element",
+                    member.struct_name,
+                    to_type_name(member.name)
+                ));
+                let fields = [
+                    (String::from("value"), element.name),
+                    (
+                        String::from("_pad0"),
+                        format!("[u8; {}]", stride - element.size),
+                    ),
+                ];
+                generator.add_definition(&name, &fields);
+                name
+            };
+            return Ok(RustType {
+                name: format!("[{}; {}]", element_name, length),
+                size: stride * length,
+                alignment: element.alignment,
+            });
+        }
+
+        if let Type::Struct { .. } = ty {
+            return self.generate_rust_struct(generator, type_id);
+        }
+
+        let (scalar, width) = get_scalar_type(&ty).ok_or_else(|| {
+            ErrorCode::CompilationError(format!("type {} has no explicit layout", type_id))
+        })?;
+        let vecsize = spirv::get_vecsize(&ty);
+        let columns = get_columns(&ty);
+        if columns <= 1 {
+            return Ok(match vecsize {
+                1 => RustType {
+                    name: scalar.to_owned(),
+                    size: width,
+                    alignment: width,
+                },
+                _ => RustType {
+                    name: format!("[{}; {}]", scalar, vecsize),
+                    size: width * vecsize,
+                    alignment: width,
+                },
+            });
+        }
+
+        let (vector_size, count) = if member.row_major {
+            (columns, vecsize)
+        } else {
+            (vecsize, columns)
+        };
+        let stride = member.matrix_stride;
+        if stride < width * vector_size || stride % width != 0 {
+            return Err(ErrorCode::CompilationError(format!(
+                "matrix stride {} of member `{}` cannot be represented",
+                stride, member.name
+            )));
+        }
+        Ok(RustType {
+            name: format!("[[{}; {}]; {}]", scalar, stride / width, count),
+            size: stride * count,
+            alignment: width,
+        })
+    }
+}
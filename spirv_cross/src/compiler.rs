@@ -1,13 +1,86 @@
 //! Raw compiler bindings for SPIRV-Cross.
 use crate::{bindings as br, spirv::ImageType};
-use crate::ptr_util::{read_from_ptr, read_into_vec_from_ptr, read_string_from_ptr};
+use crate::ptr_util::{
+    read_from_ptr, read_into_vec_from_ptr, read_string_from_ptr, read_string_into,
+};
 use crate::spirv::{self, Decoration, Type};
 use crate::ErrorCode;
 use std::collections::HashSet;
+#[cfg(not(target_arch = "wasm32"))]
+use std::ffi::CStr;
 use std::ffi::CString;
-use std::os::raw::c_void;
+#[cfg(any(feature = "glsl", feature = "hlsl", feature = "msl"))]
+use std::io;
+use std::marker::PhantomData;
+use std::os::raw::{c_char, c_void};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::{Arc, Mutex};
 use std::{mem::MaybeUninit, ptr};
 
+/// Size of the chunks in which compiled output is handed to a writer.
+#[cfg(any(feature = "glsl", feature = "hlsl", feature = "msl"))]
+const WRITE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Writes the shader source behind `shader_ptr` to `writer` and frees it.
+#[cfg(any(feature = "glsl", feature = "hlsl", feature = "msl"))]
+pub(crate) unsafe fn write_shader<W: io::Write>(
+    shader_ptr: *const c_char,
+    mut writer: W,
+) -> Result<(), ErrorCode> {
+    #[cfg(not(target_arch = "wasm32"))]
+    let bytes = std::ffi::CStr::from_ptr(shader_ptr).to_bytes();
+    #[cfg(target_arch = "wasm32")]
+    let bytes = read_string_from_ptr(shader_ptr)?.into_bytes();
+
+    let written = bytes
+        .chunks(WRITE_CHUNK_SIZE)
+        .try_for_each(|chunk| writer.write_all(chunk))
+        .and_then(|_| writer.flush());
+    check!(br::sc_internal_free_pointer(shader_ptr as *mut c_void));
+    written.map_err(|e| ErrorCode::Io(e.to_string()))
+}
+
+/// Keeps a bridge arena active on the current thread until dropped.
+pub(crate) struct ArenaScope {
+    // The arena belongs to the thread that opened it
+    _not_send: PhantomData<*const ()>,
+}
+
+impl ArenaScope {
+    pub fn begin() -> Result<Self, ErrorCode> {
+        unsafe {
+            check!(br::sc_internal_arena_begin());
+        }
+        Ok(ArenaScope {
+            _not_send: PhantomData,
+        })
+    }
+}
+
+impl Drop for ArenaScope {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = br::sc_internal_arena_end();
+        }
+    }
+}
+
+impl spirv::WorkGroupSize {
+    fn from_raw(entry_point: &br::ScEntryPoint) -> Self {
+        let ids = [
+            entry_point.work_group_size_id_x,
+            entry_point.work_group_size_id_y,
+            entry_point.work_group_size_id_z,
+        ];
+        spirv::WorkGroupSize {
+            x: entry_point.work_group_size_x,
+            y: entry_point.work_group_size_y,
+            z: entry_point.work_group_size_z,
+            constant_ids: Some(ids).filter(|ids| ids.iter().any(|&id| id != 0)),
+        }
+    }
+}
+
 impl spirv::ExecutionModel {
     fn from_raw(raw: br::spv::ExecutionModel) -> Result<Self, ErrorCode> {
         use crate::bindings::root::spv::ExecutionModel as Em;
@@ -39,8 +112,27 @@ impl spirv::ExecutionModel {
     }
 }
 
+impl spirv::ExecutionMode {
+    fn as_raw(self) -> br::spv::ExecutionMode {
+        use crate::spirv::ExecutionMode::*;
+        match self {
+            Invocations => br::spv::ExecutionMode_ExecutionModeInvocations,
+            LocalSize => br::spv::ExecutionMode_ExecutionModeLocalSize,
+            LocalSizeHint => br::spv::ExecutionMode_ExecutionModeLocalSizeHint,
+            OutputVertices => br::spv::ExecutionMode_ExecutionModeOutputVertices,
+            VecTypeHint => br::spv::ExecutionMode_ExecutionModeVecTypeHint,
+            SubgroupSize => br::spv::ExecutionMode_ExecutionModeSubgroupSize,
+            SubgroupsPerWorkgroup => br::spv::ExecutionMode_ExecutionModeSubgroupsPerWorkgroup,
+            SubgroupsPerWorkgroupId => br::spv::ExecutionMode_ExecutionModeSubgroupsPerWorkgroupId,
+            LocalSizeId => br::spv::ExecutionMode_ExecutionModeLocalSizeId,
+            LocalSizeHintId => br::spv::ExecutionMode_ExecutionModeLocalSizeHintId,
+            OutputPrimitives => br::spv::ExecutionMode_ExecutionModeOutputPrimitivesNV,
+        }
+    }
+}
+
 impl spirv::Decoration {
-    fn as_raw(self) -> br::spv::Decoration {
+    pub(crate) fn as_raw(self) -> br::spv::Decoration {
         use crate::bindings::root::spv::Decoration as D;
         match self {
             Decoration::RelaxedPrecision => D::DecorationRelaxedPrecision,
@@ -90,10 +182,70 @@ impl spirv::Decoration {
             Decoration::PassthroughNv => D::DecorationPassthroughNV,
             Decoration::ViewportRelativeNv => D::DecorationViewportRelativeNV,
             Decoration::SecondaryViewportRelativeNv => D::DecorationSecondaryViewportRelativeNV,
+            Decoration::HlslCounterBufferGoogle => D::DecorationCounterBuffer,
+            Decoration::HlslSemanticGoogle => D::DecorationHlslSemanticGOOGLE,
+            Decoration::UserTypeGoogle => D::DecorationUserTypeGOOGLE,
         }
     }
 }
 
+impl spirv::SubgroupFeatures {
+    /// Classifies the subgroup instructions of a module. The arithmetic instructions, like
+    /// `OpGroupNonUniformIAdd`, serve both arithmetic and clustered operations depending on
+    /// their group operation operand, so they are told apart by the capabilities declared for
+    /// them.
+    #[allow(non_upper_case_globals)]
+    fn from_opcodes(opcodes: &HashSet<u32>, capabilities: &[br::spv::Capability]) -> Self {
+        use crate::bindings::spv::*;
+        let mut features = spirv::SubgroupFeatures::default();
+        let mut uses_arithmetic_instructions = false;
+        for &opcode in opcodes {
+            match opcode {
+                Op_OpGroupNonUniformElect => features.basic = true,
+                Op_OpGroupNonUniformAll
+                | Op_OpGroupNonUniformAny
+                | Op_OpGroupNonUniformAllEqual => features.vote = true,
+                Op_OpGroupNonUniformBroadcast
+                | Op_OpGroupNonUniformBroadcastFirst
+                | Op_OpGroupNonUniformBallot
+                | Op_OpGroupNonUniformInverseBallot
+                | Op_OpGroupNonUniformBallotBitExtract
+                | Op_OpGroupNonUniformBallotBitCount
+                | Op_OpGroupNonUniformBallotFindLSB
+                | Op_OpGroupNonUniformBallotFindMSB => features.ballot = true,
+                Op_OpGroupNonUniformShuffle | Op_OpGroupNonUniformShuffleXor => {
+                    features.shuffle = true
+                }
+                Op_OpGroupNonUniformShuffleUp | Op_OpGroupNonUniformShuffleDown => {
+                    features.shuffle_relative = true
+                }
+                Op_OpGroupNonUniformIAdd..=Op_OpGroupNonUniformLogicalXor => {
+                    uses_arithmetic_instructions = true
+                }
+                Op_OpGroupNonUniformQuadBroadcast | Op_OpGroupNonUniformQuadSwap => {
+                    features.quad = true
+                }
+                _ => (),
+            }
+        }
+        if uses_arithmetic_instructions {
+            features.arithmetic =
+                capabilities.contains(&Capability_CapabilityGroupNonUniformArithmetic);
+            features.clustered =
+                capabilities.contains(&Capability_CapabilityGroupNonUniformClustered);
+        }
+        // Every subgroup operation needs basic subgroup support
+        features.basic |= features.vote
+            || features.arithmetic
+            || features.ballot
+            || features.shuffle
+            || features.shuffle_relative
+            || features.clustered
+            || features.quad;
+        features
+    }
+}
+
 impl spirv::Dim {
     fn from_raw(raw: br::spv::Dim) -> Result<Self, ErrorCode> {
         use crate::bindings::root::spv::Dim as D;
@@ -112,7 +264,7 @@ impl spirv::Dim {
 }
 
 impl spirv::ImageFormat {
-    fn from_raw(raw: br::spv::ImageFormat) -> Result<Self, ErrorCode> {
+    pub(crate) fn from_raw(raw: br::spv::ImageFormat) -> Result<Self, ErrorCode> {
         use crate::bindings::root::spv::ImageFormat as IF;
         use crate::spirv::ImageFormat::*;
         match raw {
@@ -161,6 +313,56 @@ impl spirv::ImageFormat {
             _ => Err(ErrorCode::Unhandled),
         }
     }
+
+    #[cfg(feature = "glsl")]
+    pub(crate) fn as_raw(self) -> br::spv::ImageFormat {
+        use crate::bindings::root::spv::ImageFormat as IF;
+        use crate::spirv::ImageFormat::*;
+        match self {
+            Unknown => IF::ImageFormatUnknown,
+            Rgba32f => IF::ImageFormatRgba32f,
+            Rgba16f => IF::ImageFormatRgba16f,
+            R32f => IF::ImageFormatR32f,
+            Rgba8 => IF::ImageFormatRgba8,
+            Rgba8Snorm => IF::ImageFormatRgba8Snorm,
+            Rg32f => IF::ImageFormatRg32f,
+            Rg16f => IF::ImageFormatRg16f,
+            R11fG11fB10f => IF::ImageFormatR11fG11fB10f,
+            R16f => IF::ImageFormatR16f,
+            Rgba16 => IF::ImageFormatRgba16,
+            Rgb10A2 => IF::ImageFormatRgb10A2,
+            Rg16 => IF::ImageFormatRg16,
+            Rg8 => IF::ImageFormatRg8,
+            R16 => IF::ImageFormatR16,
+            R8 => IF::ImageFormatR8,
+            Rgba16Snorm => IF::ImageFormatRgba16Snorm,
+            Rg16Snorm => IF::ImageFormatRg16Snorm,
+            Rg8Snorm => IF::ImageFormatRg8Snorm,
+            R16Snorm => IF::ImageFormatR16Snorm,
+            R8Snorm => IF::ImageFormatR8Snorm,
+            Rgba32i => IF::ImageFormatRgba32i,
+            Rgba16i => IF::ImageFormatRgba16i,
+            Rgba8i => IF::ImageFormatRgba8i,
+            R32i => IF::ImageFormatR32i,
+            Rg32i => IF::ImageFormatRg32i,
+            Rg16i => IF::ImageFormatRg16i,
+            Rg8i => IF::ImageFormatRg8i,
+            R16i => IF::ImageFormatR16i,
+            R8i => IF::ImageFormatR8i,
+            Rgba32ui => IF::ImageFormatRgba32ui,
+            Rgba16ui => IF::ImageFormatRgba16ui,
+            Rgba8ui => IF::ImageFormatRgba8ui,
+            R32ui => IF::ImageFormatR32ui,
+            Rgb10a2ui => IF::ImageFormatRgb10a2ui,
+            Rg32ui => IF::ImageFormatRg32ui,
+            Rg16ui => IF::ImageFormatRg16ui,
+            Rg8ui => IF::ImageFormatRg8ui,
+            R16ui => IF::ImageFormatR16ui,
+            R8ui => IF::ImageFormatR8ui,
+            R64ui => IF::ImageFormatR64ui,
+            R64i => IF::ImageFormatR64i,
+        }
+    }
 }
 
 impl spirv::ImageType {
@@ -252,14 +454,129 @@ impl spirv::Type {
     }
 }
 
+/// A parsed SPIR-V module which can be shared between compilers.
+#[derive(Debug)]
+pub struct ParsedIr {
+    pub(crate) sc_parsed_ir: *mut br::ScInternalParsedIr,
+}
+
+impl ParsedIr {
+    pub fn new(words: &[u32]) -> Result<Self, ErrorCode> {
+        let sc_parsed_ir = spirv::parse_words(words, |words| {
+            let mut sc_parsed_ir = ptr::null_mut();
+            unsafe {
+                check!(br::sc_internal_parsed_ir_new(
+                    &mut sc_parsed_ir,
+                    words.as_ptr(),
+                    words.len(),
+                ));
+            }
+            Ok(sc_parsed_ir)
+        })?;
+
+        Ok(ParsedIr { sc_parsed_ir })
+    }
+}
+
+// The parsed IR is only read after parsing, so it can be shared between threads.
+unsafe impl Send for ParsedIr {}
+unsafe impl Sync for ParsedIr {}
+
+impl Drop for ParsedIr {
+    fn drop(&mut self) {
+        unsafe {
+            if br::ScInternalResult::Success != br::sc_internal_parsed_ir_delete(self.sc_parsed_ir)
+            {
+                log_warn!("failed to delete a parsed SPIR-V module");
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Compiler<TTargetData> {
     pub(crate) sc_compiler: *mut br::ScInternalCompilerBase,
     pub(crate) target_data: TTargetData,
-    pub(crate) has_been_compiled: bool,
+    /// Whether unused interface variables are left out of the output, as set by the options.
+    pub(crate) remove_unused_variables: bool,
+    /// Whether unused resources are emitted regardless of the interface variable filter.
+    pub(crate) keep_unused_resources: bool,
+    /// Whether names are stripped before each compile, as set by the options.
+    pub(crate) strip_names: bool,
+    /// Whether names the backend renamed are restored after each compile, as set by the options.
+    pub(crate) preserve_names: bool,
+    /// How generated identifiers are renamed in the output, as set by the options.
+    pub(crate) identifier_naming: Option<spirv::IdentifierNaming>,
+    /// Whether lints fail the compile, as set by the options.
+    pub(crate) unsupported_features: spirv::UnsupportedFeaturePolicy,
+    /// Keeps the variable type remap callback alive for as long as SPIRV-Cross may call it.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) variable_type_remap: Option<Arc<Mutex<VariableTypeRemap>>>,
+}
+
+/// A variable type remap callback, along with the type name it last returned.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) struct VariableTypeRemap {
+    callback: Box<dyn FnMut(u32, &str, &str) -> Option<String> + Send>,
+    remapped_type_name: Option<CString>,
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+impl std::fmt::Debug for VariableTypeRemap {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("VariableTypeRemap")
+            .field("remapped_type_name", &self.remapped_type_name)
+            .finish()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+unsafe extern "C" fn variable_type_remap_trampoline(
+    user_data: *mut c_void,
+    type_id: u32,
+    variable_name: *const c_char,
+    type_name: *const c_char,
+    remapped_type_name: *mut *const c_char,
+) {
+    let remap = &*(user_data as *const Mutex<VariableTypeRemap>);
+    let mut remap = match remap.lock() {
+        Ok(remap) => remap,
+        Err(_) => return,
+    };
+    let variable_name = CStr::from_ptr(variable_name).to_string_lossy();
+    let type_name = CStr::from_ptr(type_name).to_string_lossy();
+
+    // SPIRV-Cross copies the returned name before the next call, so only the latest is kept
+    remap.remapped_type_name = (remap.callback)(type_id, &variable_name, &type_name)
+        .and_then(|name| CString::new(name).ok());
+    if let Some(name) = &remap.remapped_type_name {
+        *remapped_type_name = name.as_ptr();
+    }
+}
+
+// Every compiler owns its SPIRV-Cross instance and the bridge keeps no shared state between
+// instances, so a compiler may be moved to another thread.
+unsafe impl<TTargetData: Send> Send for Compiler<TTargetData> {}
+
 impl<TTargetData> Compiler<TTargetData> {
+    /// Updates which interface variables are emitted, as the variables the entry point uses
+    /// change with the selected entry point and the module.
+    pub(crate) fn update_interface_variable_filter(&mut self) -> Result<(), ErrorCode> {
+        unsafe {
+            check!(br::sc_internal_compiler_set_remove_unused_variables(
+                self.sc_compiler,
+                self.remove_unused_variables,
+            ));
+            if self.keep_unused_resources {
+                check!(br::sc_internal_compiler_keep_unused_resources(
+                    self.sc_compiler
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     #[cfg(any(feature = "glsl", feature = "hlsl"))]
     pub fn compile(&mut self) -> Result<String, ErrorCode> {
         unsafe {
@@ -274,6 +591,18 @@ impl<TTargetData> Compiler<TTargetData> {
         }
     }
 
+    #[cfg(any(feature = "glsl", feature = "hlsl"))]
+    pub fn compile_into<W: io::Write>(&mut self, writer: W) -> Result<(), ErrorCode> {
+        unsafe {
+            let mut shader_ptr = ptr::null();
+            check!(br::sc_internal_compiler_compile(
+                self.sc_compiler,
+                &mut shader_ptr,
+            ));
+            write_shader(shader_ptr, writer)
+        }
+    }
+
     pub fn get_decoration(&self, id: u32, decoration: spirv::Decoration) -> Result<u32, ErrorCode> {
         let mut result = 0;
         unsafe {
@@ -287,7 +616,164 @@ impl<TTargetData> Compiler<TTargetData> {
         Ok(result)
     }
 
-    pub fn get_name(&mut self, id: u32) -> Result<String, ErrorCode> {
+    pub fn get_decorations(
+        &self,
+        ids: &[u32],
+        decorations: &[spirv::Decoration],
+    ) -> Result<Vec<Option<u32>>, ErrorCode> {
+        let raw_decorations = decorations
+            .iter()
+            .map(|decoration| decoration.as_raw())
+            .collect::<Vec<_>>();
+        let count = ids.len() * decorations.len();
+        let mut has_decorations = vec![false; count];
+        let mut values = vec![0; count];
+        unsafe {
+            check!(br::sc_internal_compiler_get_decorations(
+                self.sc_compiler,
+                ids.as_ptr(),
+                ids.len(),
+                raw_decorations.as_ptr(),
+                raw_decorations.len(),
+                has_decorations.as_mut_ptr(),
+                values.as_mut_ptr(),
+            ));
+        }
+        Ok(has_decorations
+            .into_iter()
+            .zip(values)
+            .map(|(has_decoration, value)| if has_decoration { Some(value) } else { None })
+            .collect())
+    }
+
+    pub fn has_decoration(
+        &self,
+        id: u32,
+        decoration: spirv::Decoration,
+    ) -> Result<bool, ErrorCode> {
+        let mut result = false;
+        unsafe {
+            check!(br::sc_internal_compiler_has_decoration(
+                self.sc_compiler,
+                id,
+                decoration.as_raw(),
+                &mut result,
+            ));
+        }
+        Ok(result)
+    }
+
+    pub fn has_member_decoration(
+        &self,
+        id: u32,
+        index: u32,
+        decoration: spirv::Decoration,
+    ) -> Result<bool, ErrorCode> {
+        let mut result = false;
+        unsafe {
+            check!(br::sc_internal_compiler_has_member_decoration(
+                self.sc_compiler,
+                id,
+                index,
+                decoration.as_raw(),
+                &mut result,
+            ));
+        }
+        Ok(result)
+    }
+
+    pub fn get_decoration_string(
+        &self,
+        id: u32,
+        decoration: spirv::Decoration,
+    ) -> Result<String, ErrorCode> {
+        unsafe {
+            let mut result_ptr = ptr::null();
+            check!(br::sc_internal_compiler_get_decoration_string(
+                self.sc_compiler,
+                &mut result_ptr,
+                id,
+                decoration.as_raw(),
+            ));
+            let result = read_string_from_ptr(result_ptr)?;
+            check!(br::sc_internal_free_pointer(result_ptr as *mut c_void));
+            Ok(result)
+        }
+    }
+
+    pub fn buffer_is_hlsl_counter_buffer(&self, id: u32) -> Result<bool, ErrorCode> {
+        let mut is_counter_buffer = false;
+        unsafe {
+            check!(br::sc_internal_compiler_buffer_is_hlsl_counter_buffer(
+                self.sc_compiler,
+                id,
+                &mut is_counter_buffer,
+            ));
+        }
+        Ok(is_counter_buffer)
+    }
+
+    pub fn buffer_get_hlsl_counter_buffer(&self, id: u32) -> Result<Option<u32>, ErrorCode> {
+        let mut has_counter_buffer = false;
+        let mut counter_id = 0;
+        unsafe {
+            check!(br::sc_internal_compiler_buffer_get_hlsl_counter_buffer(
+                self.sc_compiler,
+                id,
+                &mut has_counter_buffer,
+                &mut counter_id,
+            ));
+        }
+        Ok(if has_counter_buffer {
+            Some(counter_id)
+        } else {
+            None
+        })
+    }
+
+    pub fn needs_dummy_sampler_for_combined_images(&self) -> Result<bool, ErrorCode> {
+        let mut needs_dummy_sampler = false;
+        unsafe {
+            check!(
+                br::sc_internal_compiler_needs_dummy_sampler_for_combined_images(
+                    self.sc_compiler,
+                    &mut needs_dummy_sampler,
+                )
+            );
+        }
+        Ok(needs_dummy_sampler)
+    }
+
+    pub fn variable_is_depth_or_compare(&self, id: u32) -> Result<bool, ErrorCode> {
+        let mut is_depth_or_compare = false;
+        unsafe {
+            check!(br::sc_internal_compiler_variable_is_depth_or_compare(
+                self.sc_compiler,
+                id,
+                &mut is_depth_or_compare,
+            ));
+        }
+        Ok(is_depth_or_compare)
+    }
+
+    pub fn build_dummy_sampler_for_combined_images(&mut self) -> Result<Option<u32>, ErrorCode> {
+        let mut sampler_id = 0;
+        unsafe {
+            check!(
+                br::sc_internal_compiler_build_dummy_sampler_for_combined_images(
+                    self.sc_compiler,
+                    &mut sampler_id,
+                )
+            );
+        }
+        Ok(if sampler_id != 0 {
+            Some(sampler_id)
+        } else {
+            None
+        })
+    }
+
+    pub fn get_name(&self, id: u32) -> Result<String, ErrorCode> {
         unsafe {
             let mut name_ptr = ptr::null();
             check!(br::sc_internal_compiler_get_name(
@@ -301,6 +787,20 @@ impl<TTargetData> Compiler<TTargetData> {
         }
     }
 
+    pub fn get_remapped_declared_block_name(&self, id: u32) -> Result<String, ErrorCode> {
+        unsafe {
+            let mut name_ptr = ptr::null();
+            check!(br::sc_internal_compiler_get_remapped_declared_block_name(
+                self.sc_compiler,
+                id,
+                &mut name_ptr,
+            ));
+            let name = read_string_from_ptr(name_ptr)?;
+            check!(br::sc_internal_free_pointer(name_ptr as *mut c_void));
+            Ok(name)
+        }
+    }
+
     pub fn set_name(&mut self, id: u32, name: &str) -> Result<(), ErrorCode> {
         let name = CString::new(name);
         unsafe {
@@ -336,6 +836,53 @@ impl<TTargetData> Compiler<TTargetData> {
         Ok(())
     }
 
+    pub fn alias_variable(&mut self, alias_id: u32, target_id: u32) -> Result<(), ErrorCode> {
+        unsafe {
+            check!(br::sc_internal_compiler_alias_variable(
+                self.sc_compiler,
+                alias_id,
+                target_id,
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn strip_names(&mut self) -> Result<(), ErrorCode> {
+        unsafe {
+            check!(br::sc_internal_compiler_strip_names(self.sc_compiler));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_variable_type_remap_callback<F>(&mut self, callback: F) -> Result<(), ErrorCode>
+    where
+        F: FnMut(u32, &str, &str) -> Option<String> + Send + 'static,
+    {
+        self.set_variable_type_remap(Arc::new(Mutex::new(VariableTypeRemap {
+            callback: Box::new(callback),
+            remapped_type_name: None,
+        })))
+    }
+
+    /// Registers a remap callback, which may be shared with other compilers as they do not
+    /// compile concurrently.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn set_variable_type_remap(
+        &mut self,
+        remap: Arc<Mutex<VariableTypeRemap>>,
+    ) -> Result<(), ErrorCode> {
+        unsafe {
+            check!(br::sc_internal_compiler_set_variable_type_remap_callback(
+                self.sc_compiler,
+                Some(variable_type_remap_trampoline),
+                Arc::as_ptr(&remap) as *mut c_void,
+            ));
+        }
+        self.variable_type_remap = Some(remap);
+        Ok(())
+    }
+
     pub fn unset_decoration(
         &mut self,
         id: u32,
@@ -352,6 +899,32 @@ impl<TTargetData> Compiler<TTargetData> {
         Ok(())
     }
 
+    /// Sets the decorations of all entries in one call, or of none if an id is not in the module.
+    /// Returns the index of the first such entry.
+    pub fn set_decorations(
+        &mut self,
+        entries: &[(u32, spirv::Decoration, u32)],
+    ) -> Result<Option<usize>, ErrorCode> {
+        let ids = entries.iter().map(|entry| entry.0).collect::<Vec<_>>();
+        let decorations = entries
+            .iter()
+            .map(|entry| entry.1.as_raw())
+            .collect::<Vec<_>>();
+        let arguments = entries.iter().map(|entry| entry.2).collect::<Vec<_>>();
+        let mut invalid_entry = entries.len();
+        unsafe {
+            check!(br::sc_internal_compiler_set_decorations(
+                self.sc_compiler,
+                ids.as_ptr(),
+                decorations.as_ptr(),
+                arguments.as_ptr(),
+                entries.len(),
+                &mut invalid_entry,
+            ));
+        }
+        Ok(Some(invalid_entry).filter(|&index| index < entries.len()))
+    }
+
     pub fn set_decoration(
         &mut self,
         id: u32,
@@ -370,6 +943,25 @@ impl<TTargetData> Compiler<TTargetData> {
         Ok(())
     }
 
+    pub fn set_decoration_string(
+        &mut self,
+        id: u32,
+        decoration: spirv::Decoration,
+        argument: &str,
+    ) -> Result<(), ErrorCode> {
+        let argument = CString::new(argument).map_err(|_| ErrorCode::Unhandled)?;
+        unsafe {
+            check!(br::sc_internal_compiler_set_decoration_string(
+                self.sc_compiler,
+                id,
+                decoration.as_raw(),
+                argument.as_ptr(),
+            ));
+        }
+
+        Ok(())
+    }
+
     pub fn get_entry_points(&self) -> Result<Vec<spirv::EntryPoint>, ErrorCode> {
         let mut entry_points_raw = ptr::null_mut();
         let mut entry_points_raw_length = 0 as usize;
@@ -391,11 +983,7 @@ impl<TTargetData> Compiler<TTargetData> {
                         execution_model: spirv::ExecutionModel::from_raw(
                             entry_point_raw.execution_model,
                         )?,
-                        work_group_size: spirv::WorkGroupSize {
-                            x: entry_point_raw.work_group_size_x,
-                            y: entry_point_raw.work_group_size_y,
-                            z: entry_point_raw.work_group_size_z,
-                        },
+                        work_group_size: spirv::WorkGroupSize::from_raw(&entry_point_raw),
                     };
 
                     check!(br::sc_internal_free_pointer(
@@ -414,6 +1002,96 @@ impl<TTargetData> Compiler<TTargetData> {
         }
     }
 
+    /// Calls `visitor` with each entry point, reading every name into the same buffer.
+    pub fn visit_entry_points<F>(&self, mut visitor: F) -> Result<(), ErrorCode>
+    where
+        F: FnMut(spirv::EntryPointRef),
+    {
+        let mut entry_points_raw = ptr::null_mut();
+        let mut entry_points_raw_length = 0 as usize;
+
+        unsafe {
+            check!(br::sc_internal_compiler_get_entry_points(
+                self.sc_compiler,
+                &mut entry_points_raw,
+                &mut entry_points_raw_length,
+            ));
+
+            // Every name is freed even after an error, which is returned once all are freed
+            let mut name = String::new();
+            let mut result = Ok(());
+            for offset in 0..entry_points_raw_length {
+                let entry_point_raw =
+                    read_from_ptr::<br::ScEntryPoint>(entry_points_raw.add(offset));
+                if result.is_ok() {
+                    result = read_string_into(entry_point_raw.name, &mut name).and_then(|()| {
+                        visitor(spirv::EntryPointRef {
+                            name: &name,
+                            execution_model: spirv::ExecutionModel::from_raw(
+                                entry_point_raw.execution_model,
+                            )?,
+                            work_group_size: spirv::WorkGroupSize::from_raw(&entry_point_raw),
+                        });
+                        Ok(())
+                    });
+                }
+
+                check!(br::sc_internal_free_pointer(
+                    entry_point_raw.name as *mut c_void,
+                ));
+            }
+
+            check!(br::sc_internal_free_pointer(
+                entry_points_raw as *mut c_void,
+            ));
+
+            result
+        }
+    }
+
+    pub fn rename_entry_point(
+        &mut self,
+        old_name: &str,
+        new_name: &str,
+        execution_model: spirv::ExecutionModel,
+    ) -> Result<(), ErrorCode> {
+        let old_name = CString::new(old_name).map_err(|_| ErrorCode::Unhandled)?;
+        let new_name = CString::new(new_name).map_err(|_| ErrorCode::Unhandled)?;
+        unsafe {
+            check!(br::sc_internal_compiler_rename_entry_point(
+                self.sc_compiler,
+                old_name.as_ptr(),
+                new_name.as_ptr(),
+                execution_model.as_raw(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn get_current_entry_point(&self) -> Result<(String, spirv::ExecutionModel), ErrorCode> {
+        let mut entry_point_raw_ptr = ptr::null_mut();
+
+        unsafe {
+            check!(br::sc_internal_compiler_get_current_entry_point(
+                self.sc_compiler,
+                &mut entry_point_raw_ptr,
+            ));
+
+            let entry_point_raw = read_from_ptr::<br::ScEntryPoint>(entry_point_raw_ptr);
+            let name = read_string_from_ptr(entry_point_raw.name);
+            let execution_model = spirv::ExecutionModel::from_raw(entry_point_raw.execution_model);
+
+            check!(br::sc_internal_free_pointer(
+                entry_point_raw.name as *mut c_void,
+            ));
+            check!(br::sc_internal_free_pointer(
+                entry_point_raw_ptr as *mut c_void,
+            ));
+
+            Ok((name?, execution_model?))
+        }
+    }
+
     pub fn get_active_buffer_ranges(&self, id: u32) -> Result<Vec<spirv::BufferRange>, ErrorCode> {
         let mut active_buffer_ranges_raw = ptr::null_mut();
         let mut active_buffer_ranges_raw_length = 0 as usize;
@@ -550,6 +1228,26 @@ impl<TTargetData> Compiler<TTargetData> {
         }
     }
 
+    /// Gets the type a pointer type points to or an array type holds, with whether the type is a
+    /// pointer. Other types have no parent type.
+    pub fn get_type_parent(&self, id: u32) -> Result<Option<(bool, u32)>, ErrorCode> {
+        let mut is_pointer = false;
+        let mut parent_type = 0;
+        unsafe {
+            check!(br::sc_internal_compiler_get_type_parent(
+                self.sc_compiler,
+                id,
+                &mut is_pointer,
+                &mut parent_type,
+            ));
+        }
+        Ok(if parent_type == 0 {
+            None
+        } else {
+            Some((is_pointer, parent_type))
+        })
+    }
+
     pub fn get_member_name(&self, id: u32, index: u32) -> Result<String, ErrorCode> {
         unsafe {
             let mut name_ptr = ptr::null();
@@ -636,57 +1334,121 @@ impl<TTargetData> Compiler<TTargetData> {
                 self.sc_compiler,
                 shader_resources_raw.as_mut_ptr(),
             ));
+            read_shader_resources(&shader_resources_raw.assume_init())
+        }
+    }
+
+    pub fn get_shader_resource_list(
+        &self,
+        kind: spirv::ResourceKind,
+    ) -> Result<Vec<spirv::Resource>, ErrorCode> {
+        unsafe {
+            let mut array_raw = MaybeUninit::uninit();
+            check!(br::sc_internal_compiler_get_shader_resource_list(
+                self.sc_compiler,
+                kind as u32,
+                array_raw.as_mut_ptr(),
+            ));
+            let array_raw = array_raw.assume_init();
+            let resources = (0..array_raw.num as usize)
+                .map(|i| {
+                    let resource_raw = read_from_ptr::<br::ScResource>(array_raw.data.add(i));
+                    read_resource(&resource_raw)
+                })
+                .collect::<Result<Vec<_>, ErrorCode>>();
+
+            check!(br::sc_internal_free_pointer(array_raw.data as *mut c_void));
+            resources
+        }
+    }
+
+    /// Calls `visitor` with each resource of the given kinds, excluding built-ins, reading every
+    /// name into the same buffer.
+    pub fn visit_shader_resources<F>(
+        &self,
+        kinds: spirv::ResourceKinds,
+        mut visitor: F,
+    ) -> Result<(), ErrorCode>
+    where
+        F: FnMut(spirv::ResourceKind, spirv::ResourceRef),
+    {
+        unsafe {
+            // The lists of the kinds which are not visited, and the built-ins, are left empty
+            let mut shader_resources_raw = MaybeUninit::uninit();
+            check!(br::sc_internal_compiler_get_shader_resources_of_kinds(
+                self.sc_compiler,
+                kinds.bits(),
+                shader_resources_raw.as_mut_ptr(),
+            ));
             let shader_resources_raw = shader_resources_raw.assume_init();
 
-            let fill_resources = |array_raw: &br::ScResourceArray| {
-                let resources = (0..array_raw.num as usize)
-                    .map(|i| {
-                        let resource_raw = read_from_ptr::<br::ScResource>(array_raw.data.add(i));
-                        let name = read_string_from_ptr(resource_raw.name)?;
-                        check!(br::sc_internal_free_pointer(
-                            resource_raw.name as *mut c_void,
-                        ));
-
-                        Ok(spirv::Resource {
-                            id: resource_raw.id,
-                            type_id: resource_raw.type_id,
-                            base_type_id: resource_raw.base_type_id,
-                            name,
-                        })
-                    })
-                    .collect::<Result<Vec<_>, ErrorCode>>();
+            // Every name is freed even after an error, which is returned once all are freed
+            let mut name = String::new();
+            let mut result = Ok(());
+            for &kind in spirv::ResourceKind::ALL.iter() {
+                let array_raw = get_resource_array(&shader_resources_raw, kind);
+                for i in 0..array_raw.num as usize {
+                    let resource_raw = read_from_ptr::<br::ScResource>(array_raw.data.add(i));
+                    if result.is_ok() {
+                        result = self
+                            .read_resource_ref(&resource_raw, &mut name)
+                            .map(|resource| visitor(kind, resource));
+                    }
+
+                    check!(br::sc_internal_free_pointer(
+                        resource_raw.name as *mut c_void
+                    ));
+                }
 
                 check!(br::sc_internal_free_pointer(array_raw.data as *mut c_void));
+            }
 
-                resources
-            };
+            result
+        }
+    }
 
-            let uniform_buffers = fill_resources(&shader_resources_raw.uniform_buffers)?;
-            let storage_buffers = fill_resources(&shader_resources_raw.storage_buffers)?;
-            let stage_inputs = fill_resources(&shader_resources_raw.stage_inputs)?;
-            let stage_outputs = fill_resources(&shader_resources_raw.stage_outputs)?;
-            let subpass_inputs = fill_resources(&shader_resources_raw.subpass_inputs)?;
-            let storage_images = fill_resources(&shader_resources_raw.storage_images)?;
-            let sampled_images = fill_resources(&shader_resources_raw.sampled_images)?;
-            let atomic_counters = fill_resources(&shader_resources_raw.atomic_counters)?;
-            let push_constant_buffers =
-                fill_resources(&shader_resources_raw.push_constant_buffers)?;
-            let separate_images = fill_resources(&shader_resources_raw.separate_images)?;
-            let separate_samplers = fill_resources(&shader_resources_raw.separate_samplers)?;
-
-            Ok(spirv::ShaderResources {
-                uniform_buffers,
-                storage_buffers,
-                stage_inputs,
-                stage_outputs,
-                subpass_inputs,
-                storage_images,
-                sampled_images,
-                atomic_counters,
-                push_constant_buffers,
-                separate_images,
-                separate_samplers,
-            })
+    unsafe fn read_resource_ref<'a>(
+        &self,
+        resource_raw: &br::ScResource,
+        name: &'a mut String,
+    ) -> Result<spirv::ResourceRef<'a>, ErrorCode> {
+        read_string_into(resource_raw.name, name)?;
+        Ok(spirv::ResourceRef {
+            id: resource_raw.id,
+            type_id: resource_raw.type_id,
+            base_type_id: resource_raw.base_type_id,
+            name,
+        })
+    }
+
+    pub fn get_shader_resources_for_entry_point(
+        &self,
+        entry_point_name: &str,
+        execution_model: spirv::ExecutionModel,
+    ) -> Result<spirv::ShaderResources, ErrorCode> {
+        // SPIRV-Cross cannot select an entry point that is not in the module, so such names are
+        // rejected before crossing the FFI
+        if !self.get_entry_points()?.iter().any(|entry_point| {
+            entry_point.name == entry_point_name && entry_point.execution_model == execution_model
+        }) {
+            return Err(ErrorCode::InvalidOptions(format!(
+                "entry point `{}` ({:?}) is not in the module",
+                entry_point_name, execution_model
+            )));
+        }
+
+        let entry_point = CString::new(entry_point_name).map_err(|_| ErrorCode::Unhandled)?;
+        unsafe {
+            let mut shader_resources_raw = MaybeUninit::uninit();
+            check!(
+                br::sc_internal_compiler_get_shader_resources_for_entry_point(
+                    self.sc_compiler,
+                    entry_point.as_ptr(),
+                    execution_model.as_raw(),
+                    shader_resources_raw.as_mut_ptr(),
+                )
+            );
+            read_shader_resources(&shader_resources_raw.assume_init())
         }
     }
 
@@ -699,10 +1461,278 @@ impl<TTargetData> Compiler<TTargetData> {
                 &mut ids,
                 &mut size
             ));
-            let result: HashSet<u32> = std::slice::from_raw_parts(ids, size)
-                .iter()
-                .cloned()
-                .collect();
+            let result: HashSet<u32> = read_into_vec_from_ptr(ids, size).into_iter().collect();
+            check!(br::sc_internal_free_pointer(ids as *mut c_void));
+            Ok(result)
+        }
+    }
+
+    pub fn get_active_builtins(&self) -> Result<spirv::ActiveBuiltIns, ErrorCode> {
+        unsafe {
+            let mut inputs: *mut br::spv::BuiltIn = ptr::null_mut();
+            let mut input_count: usize = 0;
+            let mut outputs: *mut br::spv::BuiltIn = ptr::null_mut();
+            let mut output_count: usize = 0;
+            check!(br::sc_internal_compiler_get_active_builtins(
+                self.sc_compiler,
+                &mut inputs,
+                &mut input_count,
+                &mut outputs,
+                &mut output_count
+            ));
+            // Built-ins without a matching `spirv::BuiltIn` variant are left out
+            let to_set = |raw: Vec<br::spv::BuiltIn>| {
+                raw.into_iter()
+                    .filter_map(|built_in| spirv::built_in_from_raw(built_in).ok())
+                    .collect::<spirv::BuiltInSet>()
+            };
+            let active_builtins = spirv::ActiveBuiltIns {
+                inputs: to_set(read_into_vec_from_ptr(inputs, input_count)),
+                outputs: to_set(read_into_vec_from_ptr(outputs, output_count)),
+            };
+            check!(br::sc_internal_free_pointer(inputs as *mut c_void));
+            check!(br::sc_internal_free_pointer(outputs as *mut c_void));
+            Ok(active_builtins)
+        }
+    }
+
+    pub fn get_subgroup_features(&self) -> Result<spirv::SubgroupFeatures, ErrorCode> {
+        let mut features = spirv::SubgroupFeatures::from_opcodes(
+            &self.get_used_opcodes()?,
+            &self.get_declared_capabilities()?,
+        );
+        for built_in in self.get_shader_resources()?.builtin_inputs {
+            match built_in.builtin {
+                spirv::BuiltIn::SubgroupSize
+                | spirv::BuiltIn::SubgroupLocalInvocationId
+                | spirv::BuiltIn::NumSubgroups
+                | spirv::BuiltIn::SubgroupId => features.basic = true,
+                spirv::BuiltIn::SubgroupEqMask
+                | spirv::BuiltIn::SubgroupGeMask
+                | spirv::BuiltIn::SubgroupGtMask
+                | spirv::BuiltIn::SubgroupLeMask
+                | spirv::BuiltIn::SubgroupLtMask => {
+                    features.basic = true;
+                    features.ballot = true;
+                }
+                _ => (),
+            }
+        }
+        Ok(features)
+    }
+
+    pub(crate) fn get_declared_capabilities(&self) -> Result<Vec<u32>, ErrorCode> {
+        unsafe {
+            let mut capabilities: *mut u32 = ptr::null_mut();
+            let mut size: usize = 0;
+            check!(br::sc_internal_compiler_get_declared_capabilities(
+                self.sc_compiler,
+                &mut capabilities,
+                &mut size
+            ));
+            let result = read_into_vec_from_ptr(capabilities, size);
+            check!(br::sc_internal_free_pointer(capabilities as *mut c_void));
+            Ok(result)
+        }
+    }
+
+    /// Gets the words the module was parsed from, in the native endianness.
+    pub(crate) fn get_spirv(&self) -> Result<Vec<u32>, ErrorCode> {
+        unsafe {
+            let mut words: *mut u32 = ptr::null_mut();
+            let mut size: usize = 0;
+            check!(br::sc_internal_compiler_get_spirv(
+                self.sc_compiler,
+                &mut words,
+                &mut size
+            ));
+            let result = read_into_vec_from_ptr(words, size);
+            check!(br::sc_internal_free_pointer(words as *mut c_void));
+            Ok(result)
+        }
+    }
+
+    /// Gets the IDs which have names or decorations, in no particular order.
+    pub(crate) fn get_meta_ids(&self) -> Result<Vec<u32>, ErrorCode> {
+        unsafe {
+            let mut ids: *mut u32 = ptr::null_mut();
+            let mut size: usize = 0;
+            check!(br::sc_internal_compiler_get_meta_ids(
+                self.sc_compiler,
+                &mut ids,
+                &mut size
+            ));
+            let result = read_into_vec_from_ptr(ids, size);
+            check!(br::sc_internal_free_pointer(ids as *mut c_void));
+            Ok(result)
+        }
+    }
+
+    /// Gets the opcodes of the instructions in the function bodies of the module.
+    pub(crate) fn get_used_opcodes(&self) -> Result<HashSet<u32>, ErrorCode> {
+        unsafe {
+            let mut opcodes: *mut u32 = ptr::null_mut();
+            let mut size: usize = 0;
+            check!(br::sc_internal_compiler_get_used_opcodes(
+                self.sc_compiler,
+                &mut opcodes,
+                &mut size
+            ));
+            let result: HashSet<u32> = read_into_vec_from_ptr(opcodes, size).into_iter().collect();
+            check!(br::sc_internal_free_pointer(opcodes as *mut c_void));
+            Ok(result)
+        }
+    }
+
+    pub fn get_required_subgroup_size(
+        &self,
+        entry_point_name: &str,
+        execution_model: spirv::ExecutionModel,
+    ) -> Result<Option<u32>, ErrorCode> {
+        let entry_point = CString::new(entry_point_name).map_err(|_| ErrorCode::Unhandled)?;
+        let mut has_subgroup_size = false;
+        let mut subgroup_size = 0;
+        unsafe {
+            check!(br::sc_internal_compiler_get_entry_point_subgroup_size(
+                self.sc_compiler,
+                entry_point.as_ptr(),
+                execution_model.as_raw(),
+                &mut has_subgroup_size,
+                &mut subgroup_size,
+            ));
+        }
+        Ok(if has_subgroup_size {
+            Some(subgroup_size)
+        } else {
+            None
+        })
+    }
+
+    pub fn get_execution_mode_argument(
+        &self,
+        mode: spirv::ExecutionMode,
+        index: u32,
+    ) -> Result<Option<u32>, ErrorCode> {
+        let mut has_argument = false;
+        let mut argument = 0;
+        unsafe {
+            check!(br::sc_internal_compiler_get_execution_mode_argument(
+                self.sc_compiler,
+                mode.as_raw(),
+                index,
+                &mut has_argument,
+                &mut argument,
+            ));
+        }
+        Ok(if has_argument { Some(argument) } else { None })
+    }
+
+    pub fn uses_early_fragment_tests(&self) -> Result<bool, ErrorCode> {
+        let mut has_mode = false;
+        unsafe {
+            check!(br::sc_internal_compiler_has_execution_mode(
+                self.sc_compiler,
+                br::spv::ExecutionMode_ExecutionModeEarlyFragmentTests,
+                &mut has_mode,
+            ));
+        }
+        Ok(has_mode)
+    }
+
+    pub fn uses_transform_feedback(&self) -> Result<bool, ErrorCode> {
+        let mut has_mode = false;
+        unsafe {
+            check!(br::sc_internal_compiler_has_execution_mode(
+                self.sc_compiler,
+                br::spv::ExecutionMode_ExecutionModeXfb,
+                &mut has_mode,
+            ));
+        }
+        Ok(has_mode)
+    }
+
+    pub fn set_early_fragment_tests(&mut self, enable: bool) -> Result<(), ErrorCode> {
+        unsafe {
+            check!(br::sc_internal_compiler_set_execution_mode(
+                self.sc_compiler,
+                br::spv::ExecutionMode_ExecutionModeEarlyFragmentTests,
+                enable,
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn get_descriptor_count(&self, id: u32) -> Result<spirv::DescriptorCount, ErrorCode> {
+        let mut count = 0;
+        let mut is_runtime_sized = false;
+        unsafe {
+            check!(br::sc_internal_compiler_get_descriptor_count(
+                self.sc_compiler,
+                id,
+                &mut count,
+                &mut is_runtime_sized,
+            ));
+        }
+        Ok(if is_runtime_sized {
+            spirv::DescriptorCount::RuntimeSized
+        } else {
+            spirv::DescriptorCount::Fixed(count)
+        })
+    }
+
+    /// Gets the element type and length of the outermost dimension of an array type. Runtime
+    /// arrays have a length of 0.
+    pub(crate) fn get_outer_array(&self, type_id: u32) -> Result<(u32, u32), ErrorCode> {
+        let mut element_type_id = 0;
+        let mut length = 0;
+        unsafe {
+            check!(br::sc_internal_compiler_get_outer_array(
+                self.sc_compiler,
+                type_id,
+                &mut element_type_id,
+                &mut length,
+            ));
+        }
+        Ok((element_type_id, length))
+    }
+
+    pub fn get_workgroup_shared_memory_size(
+        &self,
+        entry_point_name: &str,
+        execution_model: spirv::ExecutionModel,
+    ) -> Result<u32, ErrorCode> {
+        let entry_point = CString::new(entry_point_name).map_err(|_| ErrorCode::Unhandled)?;
+        let mut size = 0;
+        unsafe {
+            check!(br::sc_internal_compiler_get_workgroup_shared_memory_size(
+                self.sc_compiler,
+                entry_point.as_ptr(),
+                execution_model.as_raw(),
+                &mut size,
+            ));
+        }
+        Ok(size)
+    }
+
+    pub fn get_entry_point_interface_variables(
+        &self,
+        entry_point_name: &str,
+        execution_model: spirv::ExecutionModel,
+    ) -> Result<Vec<u32>, ErrorCode> {
+        let entry_point = CString::new(entry_point_name).map_err(|_| ErrorCode::Unhandled)?;
+        unsafe {
+            let mut ids: *mut u32 = ptr::null_mut();
+            let mut size: usize = 0;
+            check!(
+                br::sc_internal_compiler_get_entry_point_interface_variables(
+                    self.sc_compiler,
+                    entry_point.as_ptr(),
+                    execution_model.as_raw(),
+                    &mut ids,
+                    &mut size,
+                )
+            );
+            let result = read_into_vec_from_ptr(ids, size);
             check!(br::sc_internal_free_pointer(ids as *mut c_void));
             Ok(result)
         }
@@ -791,7 +1821,112 @@ impl<TTargetData> Compiler<TTargetData> {
 impl<TTargetData> Drop for Compiler<TTargetData> {
     fn drop(&mut self) {
         unsafe {
-            br::sc_internal_compiler_delete(self.sc_compiler);
+            if br::ScInternalResult::Success != br::sc_internal_compiler_delete(self.sc_compiler) {
+                log_warn!("failed to delete a SPIRV-Cross compiler");
+            }
         }
     }
 }
+
+unsafe fn read_resource(resource_raw: &br::ScResource) -> Result<spirv::Resource, ErrorCode> {
+    let name = read_string_from_ptr(resource_raw.name)?;
+    check!(br::sc_internal_free_pointer(
+        resource_raw.name as *mut c_void
+    ));
+
+    Ok(spirv::Resource {
+        id: resource_raw.id,
+        type_id: resource_raw.type_id,
+        base_type_id: resource_raw.base_type_id,
+        name,
+    })
+}
+
+fn get_resource_array(
+    shader_resources_raw: &br::ScShaderResources,
+    kind: spirv::ResourceKind,
+) -> &br::ScResourceArray {
+    match kind {
+        spirv::ResourceKind::UniformBuffers => &shader_resources_raw.uniform_buffers,
+        spirv::ResourceKind::StorageBuffers => &shader_resources_raw.storage_buffers,
+        spirv::ResourceKind::StageInputs => &shader_resources_raw.stage_inputs,
+        spirv::ResourceKind::StageOutputs => &shader_resources_raw.stage_outputs,
+        spirv::ResourceKind::SubpassInputs => &shader_resources_raw.subpass_inputs,
+        spirv::ResourceKind::StorageImages => &shader_resources_raw.storage_images,
+        spirv::ResourceKind::SampledImages => &shader_resources_raw.sampled_images,
+        spirv::ResourceKind::AtomicCounters => &shader_resources_raw.atomic_counters,
+        spirv::ResourceKind::PushConstantBuffers => &shader_resources_raw.push_constant_buffers,
+        spirv::ResourceKind::SeparateImages => &shader_resources_raw.separate_images,
+        spirv::ResourceKind::SeparateSamplers => &shader_resources_raw.separate_samplers,
+    }
+}
+
+unsafe fn read_shader_resources(
+    shader_resources_raw: &br::ScShaderResources,
+) -> Result<spirv::ShaderResources, ErrorCode> {
+    let fill_resources = |array_raw: &br::ScResourceArray| {
+        let resources = (0..array_raw.num as usize)
+            .map(|i| {
+                let resource_raw = read_from_ptr::<br::ScResource>(array_raw.data.add(i));
+                read_resource(&resource_raw)
+            })
+            .collect::<Result<Vec<_>, ErrorCode>>();
+
+        check!(br::sc_internal_free_pointer(array_raw.data as *mut c_void));
+
+        resources
+    };
+
+    let uniform_buffers = fill_resources(&shader_resources_raw.uniform_buffers)?;
+    let storage_buffers = fill_resources(&shader_resources_raw.storage_buffers)?;
+    let stage_inputs = fill_resources(&shader_resources_raw.stage_inputs)?;
+    let stage_outputs = fill_resources(&shader_resources_raw.stage_outputs)?;
+    let subpass_inputs = fill_resources(&shader_resources_raw.subpass_inputs)?;
+    let storage_images = fill_resources(&shader_resources_raw.storage_images)?;
+    let sampled_images = fill_resources(&shader_resources_raw.sampled_images)?;
+    let atomic_counters = fill_resources(&shader_resources_raw.atomic_counters)?;
+    let push_constant_buffers = fill_resources(&shader_resources_raw.push_constant_buffers)?;
+    let separate_images = fill_resources(&shader_resources_raw.separate_images)?;
+    let separate_samplers = fill_resources(&shader_resources_raw.separate_samplers)?;
+
+    let fill_builtin_resources = |array_raw: &br::ScBuiltInResourceArray| {
+        let resources = (0..array_raw.num as usize)
+            .map(|i| {
+                let builtin_raw = read_from_ptr::<br::ScBuiltInResource>(array_raw.data.add(i));
+                let resource = read_resource(&builtin_raw.resource)?;
+
+                Ok(spirv::built_in_from_raw(builtin_raw.builtin)
+                    .ok()
+                    .map(|builtin| spirv::BuiltInResource {
+                        builtin,
+                        value_type_id: builtin_raw.value_type_id,
+                        resource,
+                    }))
+            })
+            .collect::<Result<Vec<_>, ErrorCode>>();
+
+        check!(br::sc_internal_free_pointer(array_raw.data as *mut c_void));
+
+        // Built-ins without a matching `spirv::BuiltIn` variant are left out
+        Ok(resources?.into_iter().flatten().collect::<Vec<_>>())
+    };
+
+    let builtin_inputs = fill_builtin_resources(&shader_resources_raw.builtin_inputs)?;
+    let builtin_outputs = fill_builtin_resources(&shader_resources_raw.builtin_outputs)?;
+
+    Ok(spirv::ShaderResources {
+        uniform_buffers,
+        storage_buffers,
+        stage_inputs,
+        stage_outputs,
+        subpass_inputs,
+        storage_images,
+        sampled_images,
+        atomic_counters,
+        push_constant_buffers,
+        separate_images,
+        separate_samplers,
+        builtin_inputs,
+        builtin_outputs,
+    })
+}
@@ -77,6 +77,11 @@ impl Module {
             .unwrap() as u32
     }
 
+    // Read a `u8` value from the heap.
+    pub unsafe fn get_u8(&self, pointer: Pointer) -> u8 {
+        self.heap_u8().get_index(pointer.offset)
+    }
+
     /// Set memory on the heap to `bytes`.
     pub unsafe fn set_from_u8_typed_array(&self, pointer: Pointer, bytes: Uint8Array) {
         let buffer: JsValue = self.heap_u8().buffer().into();
@@ -90,6 +95,11 @@ impl Module {
         self.set_from_u8_typed_array(pointer, Uint8Array::view(bytes));
     }
 
+    /// Whether the module exports a function, such as `_sc_internal_compiler_delete`.
+    pub fn has_function(&self, name: &str) -> bool {
+        Reflect::has(&self.module, &JsValue::from_str(name)).unwrap_or(false)
+    }
+
     fn heap_u8(&self) -> Uint8Array {
         const HEAP_U8: &'static str = "HEAPU8";
         get_value(&self.module, HEAP_U8)
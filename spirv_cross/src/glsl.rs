@@ -1,16 +1,22 @@
 use crate::bindings as br;
-use crate::ptr_util::read_into_vec_from_ptr;
+use crate::ptr_util::{read_into_vec_from_ptr, read_string_from_ptr};
 use crate::{compiler, spirv, ErrorCode};
+use std::collections::HashMap;
 use std::ffi::CString;
+use std::io;
 use std::marker::PhantomData;
+use std::os::raw::{c_char, c_void};
 use std::ptr;
 
+pub use crate::spirv::DescriptorBinding;
+
 /// A GLSL target.
 #[derive(Debug, Clone)]
 pub enum Target {}
 
 pub struct TargetData {
     combined_image_samplers_built: bool,
+    options: CompilerOptions,
 }
 
 impl spirv::Target for Target {
@@ -40,6 +46,122 @@ pub enum Version {
     V3_20Es,
 }
 
+impl Version {
+    fn as_raw(self) -> (u32, bool) {
+        use self::Version::*;
+        match self {
+            V1_10 => (1_10, false),
+            V1_20 => (1_20, false),
+            V1_30 => (1_30, false),
+            V1_40 => (1_40, false),
+            V1_50 => (1_50, false),
+            V3_30 => (3_30, false),
+            V4_00 => (4_00, false),
+            V4_10 => (4_10, false),
+            V4_20 => (4_20, false),
+            V4_30 => (4_30, false),
+            V4_40 => (4_40, false),
+            V4_50 => (4_50, false),
+            V4_60 => (4_60, false),
+            V1_00Es => (1_00, true),
+            V3_00Es => (3_00, true),
+            V3_10Es => (3_10, true),
+            V3_20Es => (3_20, true),
+        }
+    }
+
+    fn from_raw(version: u32, es: bool) -> Option<Version> {
+        use self::Version::*;
+        [
+            V1_10, V1_20, V1_30, V1_40, V1_50, V3_30, V4_00, V4_10, V4_20, V4_30, V4_40, V4_50,
+            V4_60, V1_00Es, V3_00Es, V3_10Es, V3_20Es,
+        ]
+        .iter()
+        .copied()
+        .find(|candidate| candidate.as_raw() == (version, es))
+    }
+}
+
+/// The lowest GLSL and ESSL versions which have a feature built in, or `None` for a flavour
+/// which lacks it or only has it through an extension.
+type VersionRequirement = (Option<u32>, Option<u32>);
+
+/// Gets the versions which have a capability built in, if it affects them.
+#[allow(non_upper_case_globals)]
+fn get_capability_version(capability: br::spv::Capability) -> Option<VersionRequirement> {
+    use crate::bindings::spv::*;
+    Some(match capability {
+        Capability_CapabilitySampled1D => (Some(1_10), None),
+        Capability_CapabilityClipDistance => (Some(1_30), None),
+        Capability_CapabilitySampledRect => (Some(1_40), None),
+        Capability_CapabilitySampledBuffer => (Some(1_40), Some(3_20)),
+        Capability_CapabilityGeometry | Capability_CapabilityImageMSArray => {
+            (Some(1_50), Some(3_20))
+        }
+        Capability_CapabilityFloat64 | Capability_CapabilityGeometryStreams => (Some(4_00), None),
+        Capability_CapabilityImageGatherExtended => (Some(4_00), Some(3_10)),
+        Capability_CapabilityTessellation
+        | Capability_CapabilitySampledCubeArray
+        | Capability_CapabilitySampleRateShading
+        | Capability_CapabilityInterpolationFunction => (Some(4_00), Some(3_20)),
+        Capability_CapabilityMultiViewport => (Some(4_10), None),
+        Capability_CapabilityStorageImageMultisample
+        | Capability_CapabilityImageRect
+        | Capability_CapabilityImage1D => (Some(4_20), None),
+        Capability_CapabilityImageCubeArray | Capability_CapabilityImageBuffer => {
+            (Some(4_20), Some(3_20))
+        }
+        Capability_CapabilityTransformFeedback => (Some(4_40), None),
+        Capability_CapabilityCullDistance | Capability_CapabilityDerivativeControl => {
+            (Some(4_50), None)
+        }
+        // `GL_EXT_buffer_reference`, which SPIRV-Cross only enables from these versions
+        Capability_CapabilityPhysicalStorageBufferAddresses => (Some(4_50), Some(3_20)),
+        _ => return None,
+    })
+}
+
+/// Gets the versions which have the function or operator an opcode is emitted as built in, if
+/// it affects them.
+#[allow(non_upper_case_globals)]
+fn get_opcode_version(opcode: br::spv::Op) -> Option<VersionRequirement> {
+    use crate::bindings::spv::*;
+    Some(match opcode {
+        // `texelFetch`, `textureSize`, `isnan`, `isinf` and integer bitwise operators
+        Op_OpImageFetch
+        | Op_OpImageQuerySizeLod
+        | Op_OpImageQuerySize
+        | Op_OpIsNan
+        | Op_OpIsInf
+        | Op_OpShiftRightLogical
+        | Op_OpShiftRightArithmetic
+        | Op_OpShiftLeftLogical
+        | Op_OpBitwiseOr
+        | Op_OpBitwiseXor
+        | Op_OpBitwiseAnd
+        | Op_OpNot => (Some(1_30), Some(3_00)),
+        // Bit casts such as `floatBitsToInt`
+        Op_OpBitcast => (Some(3_30), Some(3_00)),
+        // `textureQueryLod`
+        Op_OpImageQueryLod => (Some(4_00), None),
+        // `textureGather` and the bit field functions such as `bitfieldExtract`
+        Op_OpImageGather
+        | Op_OpImageDrefGather
+        | Op_OpBitFieldInsert
+        | Op_OpBitFieldSExtract
+        | Op_OpBitFieldUExtract
+        | Op_OpBitReverse
+        | Op_OpBitCount => (Some(4_00), Some(3_10)),
+        // `imageLoad` and `imageStore`
+        Op_OpImageRead | Op_OpImageWrite => (Some(4_20), Some(3_10)),
+        // `textureQueryLevels`
+        Op_OpImageQueryLevels => (Some(4_30), None),
+        // `textureSamples`
+        Op_OpImageQuerySamples => (Some(4_50), None),
+        _ => return None,
+    })
+}
+
 #[derive(Debug, Clone)]
 pub struct CompilerVertexOptions {
     pub invert_y: bool,
@@ -82,14 +204,118 @@ impl Default for CompilerFragmentOptions {
     }
 }
 
+/// The kinds of resources which keep their `layout(binding = N)` qualifier. Bindings are only
+/// emitted where the target allows them, which is GLSL 4.20 or the 420pack extension, ESSL 3.10
+/// and Vulkan semantics. Atomic counters always keep theirs.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct ExplicitBindings {
+    /// Sampled images, separate images and separate samplers.
+    pub samplers: bool,
+    pub uniform_buffers: bool,
+    pub storage_buffers: bool,
+    /// Storage images.
+    pub images: bool,
+}
+
+impl Default for ExplicitBindings {
+    fn default() -> ExplicitBindings {
+        ExplicitBindings {
+            samplers: true,
+            uniform_buffers: true,
+            storage_buffers: true,
+            images: true,
+        }
+    }
+}
+
+impl ExplicitBindings {
+    fn includes(&self, kind: spirv::ResourceKind) -> bool {
+        use spirv::ResourceKind::*;
+        match kind {
+            SampledImages | SeparateImages | SeparateSamplers => self.samplers,
+            UniformBuffers => self.uniform_buffers,
+            StorageBuffers => self.storage_buffers,
+            StorageImages => self.images,
+            _ => true,
+        }
+    }
+}
+
+/// A resource whose binding the output leaves out, as returned by `get_omitted_bindings`.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct OmittedBinding {
+    pub kind: spirv::ResourceKind,
+    pub resource: spirv::Resource,
+    /// The binding to assign through the GL API instead.
+    pub binding: u32,
+}
+
+/// The GL binding points a uniform or block is bound to. Each namespace is shared by the
+/// resources of every descriptor set.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GlBindingNamespace {
+    /// Texture units, for samplers and texel buffers.
+    TextureUnits,
+    /// Image units, for storage images.
+    ImageUnits,
+    UniformBuffers,
+    ShaderStorageBuffers,
+    AtomicCounterBuffers,
+}
+
+/// A uniform or block of the output with the descriptors it stands for and its GL binding, as
+/// returned by `get_binding_relocations`.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BindingRelocation {
+    /// The name of the uniform or block in the source.
+    pub name: String,
+    pub namespace: GlBindingNamespace,
+    /// The descriptor bindings of the module which the uniform replaces, the image and then the
+    /// sampler for a combined image sampler. Resources without a binding decoration have none.
+    pub sources: Vec<DescriptorBinding>,
+    /// The binding point or unit of the uniform, `None` if it has no binding decoration.
+    pub binding: Option<u32>,
+    /// Whether the source declares `binding` with `layout(binding = N)`, rather than leaving it
+    /// to be assigned through the GL API as `get_omitted_bindings` reports.
+    pub explicit: bool,
+}
+
+/// How specialization constants are emitted without Vulkan semantics, as GLSL then has no
+/// specialization mechanism.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpecializationConstantForm {
+    /// A `const` initialized from a `SPIRV_CROSS_CONSTANT_ID_N` macro, which `#define`s the
+    /// default value unless the application defines it before the shader source.
+    Macro,
+    /// A `const` initialized with the default value.
+    Const,
+    /// A `#define` of the name of the constant to its default value.
+    Define,
+}
+
+impl Default for SpecializationConstantForm {
+    fn default() -> Self {
+        SpecializationConstantForm::Macro
+    }
+}
+
 /// GLSL compiler options.
 #[non_exhaustive]
 #[derive(Debug, Clone)]
 pub struct CompilerOptions {
     pub version: Version,
+    /// Whether to store every expression in a temporary instead of inlining it, which works
+    /// around drivers that miscompile long expressions.
     pub force_temporary: bool,
     pub vulkan_semantics: bool,
+    /// How specialization constants are emitted without Vulkan semantics.
+    pub specialization_constant_form: SpecializationConstantForm,
     pub separate_shader_objects: bool,
+    /// Whether to flatten multidimensional arrays into one-dimensional arrays, which older GLSL
+    /// versions require.
     pub flatten_multidimensional_arrays: bool,
     pub enable_420_pack_extension: bool,
     pub emit_push_constant_as_uniform_buffer: bool,
@@ -100,6 +326,31 @@ pub struct CompilerOptions {
     pub force_zero_initialized_variables: bool,
     pub vertex: CompilerVertexOptions,
     pub fragment: CompilerFragmentOptions,
+    /// Whether to omit the format qualifier of storage images which are never read, as GLSL
+    /// does not require a format for `writeonly` images.
+    pub omit_write_only_image_formats: bool,
+    /// The kinds of resources which keep their explicit binding, so that the others can be
+    /// assigned through the GL API.
+    pub explicit_bindings: ExplicitBindings,
+    /// Whether to leave out the declarations of resources and stage inputs and outputs which the
+    /// entry point does not statically use, instead of emitting every variable of the module.
+    pub remove_unused_variables: bool,
+    /// Whether to keep the declarations of resources which the entry point does not statically use,
+    /// so shader variants share the same resource interface. This takes precedence over
+    /// `remove_unused_variables` for resources.
+    pub keep_unused_resources: bool,
+    /// Whether to remove the names of all IDs and struct members before each compile, as
+    /// `Ast::strip_names` does, so that the output only uses anonymized identifiers.
+    pub strip_names: bool,
+    /// Whether to keep the names the backend would rename for clashing with a keyword of the
+    /// target language, in which case the output may not compile. Names that clash with another
+    /// identifier are still told apart. This cannot be combined with `strip_names`.
+    pub preserve_names: bool,
+    /// Overrides for the prefixes of the identifiers SPIRV-Cross generates, or `None` to keep
+    /// them unchanged.
+    pub identifier_naming: Option<spirv::IdentifierNaming>,
+    /// Whether to fail to compile when there are lints.
+    pub unsupported_features: spirv::UnsupportedFeaturePolicy,
     /// The name and execution model of the entry point to use. If no entry
     /// point is specified, then the first entry point found will be used.
     pub entry_point: Option<(String, spirv::ExecutionModel)>,
@@ -111,6 +362,7 @@ impl Default for CompilerOptions {
             version: Version::V4_50,
             force_temporary: false,
             vulkan_semantics: false,
+            specialization_constant_form: SpecializationConstantForm::Macro,
             separate_shader_objects: false,
             flatten_multidimensional_arrays: false,
             enable_420_pack_extension: true,
@@ -121,36 +373,201 @@ impl Default for CompilerOptions {
             force_zero_initialized_variables: false,
             vertex: CompilerVertexOptions::default(),
             fragment: CompilerFragmentOptions::default(),
+            omit_write_only_image_formats: false,
+            explicit_bindings: ExplicitBindings::default(),
+            remove_unused_variables: false,
+            keep_unused_resources: false,
+            strip_names: false,
+            preserve_names: false,
+            identifier_naming: None,
+            unsupported_features: spirv::UnsupportedFeaturePolicy::Relaxed,
             entry_point: None,
         }
     }
 }
 
+impl CompilerOptions {
+    /// Creates a builder starting from the default options.
+    pub fn builder() -> CompilerOptionsBuilder {
+        CompilerOptionsBuilder {
+            options: CompilerOptions::default(),
+        }
+    }
+
+    fn validate(&self) -> Result<(), ErrorCode> {
+        spirv::validate_entry_point(&self.entry_point)?;
+        spirv::validate_names(self.strip_names, self.preserve_names)?;
+        let (version, es) = self.version.as_raw();
+        if self.vulkan_semantics && version < if es { 3_10 } else { 1_40 } {
+            return Err(ErrorCode::InvalidOptions(String::from(
+                "Vulkan semantics require GLSL 1.40 or ESSL 3.10 or later",
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Builder for `CompilerOptions`. Each setter overrides the option of the same name.
+#[derive(Debug, Clone)]
+pub struct CompilerOptionsBuilder {
+    options: CompilerOptions,
+}
+
+impl CompilerOptionsBuilder {
+    pub fn version(mut self, version: Version) -> Self {
+        self.options.version = version;
+        self
+    }
+
+    pub fn force_temporary(mut self, force_temporary: bool) -> Self {
+        self.options.force_temporary = force_temporary;
+        self
+    }
+
+    pub fn vulkan_semantics(mut self, vulkan_semantics: bool) -> Self {
+        self.options.vulkan_semantics = vulkan_semantics;
+        self
+    }
+
+    pub fn specialization_constant_form(mut self, form: SpecializationConstantForm) -> Self {
+        self.options.specialization_constant_form = form;
+        self
+    }
+
+    pub fn separate_shader_objects(mut self, separate_shader_objects: bool) -> Self {
+        self.options.separate_shader_objects = separate_shader_objects;
+        self
+    }
+
+    pub fn flatten_multidimensional_arrays(mut self, flatten_multidimensional_arrays: bool) -> Self {
+        self.options.flatten_multidimensional_arrays = flatten_multidimensional_arrays;
+        self
+    }
+
+    pub fn enable_420_pack_extension(mut self, enable_420_pack_extension: bool) -> Self {
+        self.options.enable_420_pack_extension = enable_420_pack_extension;
+        self
+    }
+
+    pub fn emit_push_constant_as_uniform_buffer(mut self, emit: bool) -> Self {
+        self.options.emit_push_constant_as_uniform_buffer = emit;
+        self
+    }
+
+    pub fn emit_uniform_buffer_as_plain_uniforms(mut self, emit: bool) -> Self {
+        self.options.emit_uniform_buffer_as_plain_uniforms = emit;
+        self
+    }
+
+    pub fn emit_line_directives(mut self, emit_line_directives: bool) -> Self {
+        self.options.emit_line_directives = emit_line_directives;
+        self
+    }
+
+    pub fn enable_storage_image_qualifier_deduction(mut self, enable: bool) -> Self {
+        self.options.enable_storage_image_qualifier_deduction = enable;
+        self
+    }
+
+    pub fn force_zero_initialized_variables(mut self, force: bool) -> Self {
+        self.options.force_zero_initialized_variables = force;
+        self
+    }
+
+    pub fn vertex(mut self, vertex: CompilerVertexOptions) -> Self {
+        self.options.vertex = vertex;
+        self
+    }
+
+    pub fn fragment(mut self, fragment: CompilerFragmentOptions) -> Self {
+        self.options.fragment = fragment;
+        self
+    }
+
+    pub fn omit_write_only_image_formats(mut self, omit_write_only_image_formats: bool) -> Self {
+        self.options.omit_write_only_image_formats = omit_write_only_image_formats;
+        self
+    }
+
+    pub fn explicit_bindings(mut self, explicit_bindings: ExplicitBindings) -> Self {
+        self.options.explicit_bindings = explicit_bindings;
+        self
+    }
+
+    pub fn remove_unused_variables(mut self, remove_unused_variables: bool) -> Self {
+        self.options.remove_unused_variables = remove_unused_variables;
+        self
+    }
+
+    pub fn keep_unused_resources(mut self, keep_unused_resources: bool) -> Self {
+        self.options.keep_unused_resources = keep_unused_resources;
+        self
+    }
+
+    pub fn strip_names(mut self, strip_names: bool) -> Self {
+        self.options.strip_names = strip_names;
+        self
+    }
+
+    pub fn preserve_names(mut self, preserve_names: bool) -> Self {
+        self.options.preserve_names = preserve_names;
+        self
+    }
+
+    pub fn identifier_naming(mut self, identifier_naming: spirv::IdentifierNaming) -> Self {
+        self.options.identifier_naming = Some(identifier_naming);
+        self
+    }
+
+    pub fn unsupported_features(mut self, policy: spirv::UnsupportedFeaturePolicy) -> Self {
+        self.options.unsupported_features = policy;
+        self
+    }
+
+    pub fn entry_point(
+        mut self,
+        name: impl Into<String>,
+        execution_model: spirv::ExecutionModel,
+    ) -> Self {
+        self.options.entry_point = Some((name.into(), execution_model));
+        self
+    }
+
+    /// Validates and returns the options.
+    pub fn build(self) -> Result<CompilerOptions, ErrorCode> {
+        self.options.validate()?;
+        Ok(self.options)
+    }
+}
+
 impl spirv::Parse<Target> for spirv::Ast<Target> {
     fn parse(module: &spirv::Module) -> Result<Self, ErrorCode> {
-        let compiler = {
+        let compiler = spirv::parse_words(&module.words, |words| {
             let mut compiler = ptr::null_mut();
             unsafe {
                 check!(br::sc_internal_compiler_glsl_new(
                     &mut compiler,
-                    module.words.as_ptr() as *const u32,
-                    module.words.len() as usize,
+                    words.as_ptr() as *const u32,
+                    words.len() as usize,
                 ));
             }
+            Ok(compiler)
+        })?;
 
-            compiler::Compiler {
-                sc_compiler: compiler,
-                target_data: TargetData {
-                    combined_image_samplers_built: false,
-                },
-                has_been_compiled: false,
-            }
-        };
+        Ok(Self::from_sc_compiler(compiler))
+    }
 
-        Ok(spirv::Ast {
-            compiler,
-            target_type: PhantomData,
-        })
+    fn from_parsed(module: &spirv::ParsedModule) -> Result<Self, ErrorCode> {
+        let mut compiler = ptr::null_mut();
+        unsafe {
+            check!(br::sc_internal_compiler_glsl_new_from_ir(
+                &mut compiler,
+                module.ir.sc_parsed_ir,
+            ));
+        }
+
+        Ok(Self::from_sc_compiler(compiler))
     }
 }
 
@@ -159,6 +576,12 @@ impl spirv::Compile<Target> for spirv::Ast<Target> {
 
     /// Set GLSL compiler specific compilation settings.
     fn set_compiler_options(&mut self, options: &CompilerOptions) -> Result<(), ErrorCode> {
+        options.validate()?;
+        spirv::validate_entry_point_in_module(
+            &options.entry_point,
+            &self.compiler.get_entry_points()?,
+        )?;
+
         if let Some((name, model)) = &options.entry_point {
             let name_raw = CString::new(name.as_str()).map_err(|_| ErrorCode::Unhandled)?;
             let model = model.as_raw();
@@ -173,26 +596,7 @@ impl spirv::Compile<Target> for spirv::Ast<Target> {
             self.compiler.target_data.combined_image_samplers_built = false;
         };
 
-        use self::Version::*;
-        let (version, es) = match options.version {
-            V1_10 => (1_10, false),
-            V1_20 => (1_20, false),
-            V1_30 => (1_30, false),
-            V1_40 => (1_40, false),
-            V1_50 => (1_50, false),
-            V3_30 => (3_30, false),
-            V4_00 => (4_00, false),
-            V4_10 => (4_10, false),
-            V4_20 => (4_20, false),
-            V4_30 => (4_30, false),
-            V4_40 => (4_40, false),
-            V4_50 => (4_50, false),
-            V4_60 => (4_60, false),
-            V1_00Es => (1_00, true),
-            V3_00Es => (3_00, true),
-            V3_10Es => (3_10, true),
-            V3_20Es => (3_20, true),
-        };
+        let (version, es) = options.version.as_raw();
         let raw_options = br::ScGlslCompilerOptions {
             vertex_invert_y: options.vertex.invert_y,
             vertex_transform_clip_space: options.vertex.transform_clip_space,
@@ -212,6 +616,7 @@ impl spirv::Compile<Target> for spirv::Ast<Target> {
             enable_storage_image_qualifier_deduction: options
                 .enable_storage_image_qualifier_deduction,
             force_zero_initialized_variables: options.force_zero_initialized_variables,
+            omit_write_only_image_formats: options.omit_write_only_image_formats,
         };
         unsafe {
             check!(br::sc_internal_compiler_glsl_set_options(
@@ -220,23 +625,356 @@ impl spirv::Compile<Target> for spirv::Ast<Target> {
             ));
         }
 
+        self.compiler.remove_unused_variables = options.remove_unused_variables;
+        self.compiler.keep_unused_resources = options.keep_unused_resources;
+        self.compiler.strip_names = options.strip_names;
+        self.compiler.preserve_names = options.preserve_names;
+        self.compiler.identifier_naming = options.identifier_naming.clone();
+        self.compiler.unsupported_features = options.unsupported_features;
+        self.compiler.target_data.options = options.clone();
         Ok(())
     }
 
+    fn get_compiler_options(&self) -> CompilerOptions {
+        self.compiler.target_data.options.clone()
+    }
+
+    fn set_entry_point(
+        &mut self,
+        name: &str,
+        execution_model: spirv::ExecutionModel,
+    ) -> Result<(), ErrorCode> {
+        let mut options = self.compiler.target_data.options.clone();
+        options.entry_point = Some((name.into(), execution_model));
+        spirv::Compile::<Target>::set_compiler_options(self, &options)
+    }
+
+    fn hash_compiler_options(&self) -> u64 {
+        let CompilerOptions {
+            version,
+            force_temporary,
+            vulkan_semantics,
+            specialization_constant_form,
+            separate_shader_objects,
+            flatten_multidimensional_arrays,
+            enable_420_pack_extension,
+            emit_push_constant_as_uniform_buffer,
+            emit_uniform_buffer_as_plain_uniforms,
+            emit_line_directives,
+            enable_storage_image_qualifier_deduction,
+            force_zero_initialized_variables,
+            vertex,
+            fragment,
+            omit_write_only_image_formats,
+            explicit_bindings,
+            remove_unused_variables,
+            keep_unused_resources,
+            strip_names,
+            preserve_names,
+            identifier_naming,
+            unsupported_features,
+            entry_point,
+        } = &self.compiler.target_data.options;
+
+        let mut hasher = spirv::StableHasher::new();
+        hasher.write_str("glsl");
+        let (version, es) = version.as_raw();
+        hasher.write_u32(version);
+        hasher.write_bool(es);
+        hasher.write_u32(*specialization_constant_form as u32);
+        for &flag in &[
+            *force_temporary,
+            *vulkan_semantics,
+            *separate_shader_objects,
+            *flatten_multidimensional_arrays,
+            *enable_420_pack_extension,
+            *emit_push_constant_as_uniform_buffer,
+            *emit_uniform_buffer_as_plain_uniforms,
+            *emit_line_directives,
+            *enable_storage_image_qualifier_deduction,
+            *force_zero_initialized_variables,
+            vertex.invert_y,
+            vertex.transform_clip_space,
+            vertex.support_nonzero_base_instance,
+            *omit_write_only_image_formats,
+            explicit_bindings.samplers,
+            explicit_bindings.uniform_buffers,
+            explicit_bindings.storage_buffers,
+            explicit_bindings.images,
+            *remove_unused_variables,
+            *keep_unused_resources,
+        ] {
+            hasher.write_bool(flag);
+        }
+        hasher.write_u32(fragment.default_float_precision as u32);
+        hasher.write_u32(fragment.default_int_precision as u32);
+        hasher.write_common_options(
+            *strip_names,
+            *preserve_names,
+            identifier_naming,
+            *unsupported_features,
+            entry_point,
+        );
+        hasher.finish()
+    }
+
+    fn get_lints(&self) -> Result<Vec<String>, ErrorCode> {
+        let options = &self.compiler.target_data.options;
+        let mut lints =
+            spirv::entry_point_lints(&options.entry_point, &self.compiler.get_entry_points()?);
+
+        let (version, es) = options.version.as_raw();
+        if !es
+            && version < 4_40
+            && self.uses_transform_feedback()?
+            && !self.get_transform_feedback_outputs()?.is_empty()
+        {
+            lints.push(String::from(
+                "transform feedback layouts require GL_ARB_enhanced_layouts before GLSL 4.40",
+            ));
+        }
+
+        Ok(lints)
+    }
+
     /// Generate GLSL shader from the AST.
     fn compile(&mut self) -> Result<String, ErrorCode> {
         self.build_combined_image_samplers()?;
-        self.compiler.compile()
+        let shader = self.without_omitted_bindings(|compiler| compiler.compile())?;
+        if !self.materializes_specialization_constants() {
+            return Ok(shader);
+        }
+
+        let constants = self.compiler.get_specialization_constants()?;
+        let options = &self.compiler.target_data.options;
+        Ok(materialize_specialization_constants(
+            &shader,
+            &constants,
+            options.specialization_constant_form,
+        ))
+    }
+
+    /// Generate GLSL shader from the AST into `writer`.
+    fn compile_into<W: io::Write>(&mut self, mut writer: W) -> Result<(), ErrorCode> {
+        if self.materializes_specialization_constants() {
+            let shader = spirv::Compile::<Target>::compile(self)?;
+            return writer
+                .write_all(shader.as_bytes())
+                .and_then(|_| writer.flush())
+                .map_err(|e| ErrorCode::Io(e.to_string()));
+        }
+
+        self.build_combined_image_samplers()?;
+        self.without_omitted_bindings(|compiler| compiler.compile_into(writer))
     }
 }
 
 impl spirv::Ast<Target> {
+    fn from_sc_compiler(sc_compiler: *mut br::ScInternalCompilerBase) -> Self {
+        spirv::Ast {
+            compiler: compiler::Compiler {
+                sc_compiler,
+                target_data: TargetData {
+                    combined_image_samplers_built: false,
+                    options: CompilerOptions::default(),
+                },
+                remove_unused_variables: false,
+                keep_unused_resources: false,
+                strip_names: false,
+                preserve_names: false,
+                identifier_naming: None,
+                unsupported_features: spirv::UnsupportedFeaturePolicy::Relaxed,
+                #[cfg(not(target_arch = "wasm32"))]
+                variable_type_remap: None,
+            },
+            target_type: PhantomData,
+            state: PhantomData,
+        }
+    }
+
+    /// Takes ownership of a SPIRV-Cross compiler previously released with `Ast::into_raw`. The
+    /// returned `Ast` uses the default compiler options until new ones are set.
+    ///
+    /// # Safety
+    ///
+    /// `sc_compiler` must come from `into_raw` on an `Ast` of this target, and must not be
+    /// used through another `Ast` afterwards.
+    pub unsafe fn from_raw(sc_compiler: *mut br::ScInternalCompilerBase) -> Self {
+        Self::from_sc_compiler(sc_compiler)
+    }
+
+    /// Gets the resources whose binding the output leaves out, because the target cannot express
+    /// bindings or `explicit_bindings` excludes their kind. Their bindings have to be assigned
+    /// through the GL API instead, such as with `glUniform1i` for samplers and images and
+    /// `glUniformBlockBinding` for uniform buffers.
+    pub fn get_omitted_bindings(&self) -> Result<Vec<OmittedBinding>, ErrorCode> {
+        let options = &self.compiler.target_data.options;
+        let supports_bindings = self.supports_bindings();
+
+        let mut omitted = Vec::new();
+        for (kind, resource) in self.compiler.get_shader_resources()?.iter() {
+            match kind {
+                spirv::ResourceKind::StageInputs
+                | spirv::ResourceKind::StageOutputs
+                | spirv::ResourceKind::SubpassInputs
+                | spirv::ResourceKind::AtomicCounters
+                | spirv::ResourceKind::PushConstantBuffers => continue,
+                _ => {}
+            }
+            if (!supports_bindings || !options.explicit_bindings.includes(kind))
+                && self
+                    .compiler
+                    .has_decoration(resource.id, spirv::Decoration::Binding)?
+            {
+                omitted.push(OmittedBinding {
+                    kind,
+                    resource: resource.clone(),
+                    binding: self
+                        .compiler
+                        .get_decoration(resource.id, spirv::Decoration::Binding)?,
+                });
+            }
+        }
+        Ok(omitted)
+    }
+
+    fn supports_bindings(&self) -> bool {
+        let options = &self.compiler.target_data.options;
+        options.vulkan_semantics
+            || match options.version.as_raw() {
+                (version, true) => version >= 3_10,
+                (version, false) => version >= 4_20 || options.enable_420_pack_extension,
+            }
+    }
+
+    /// Builds combined image samplers if needed and reports, for each uniform and block of the
+    /// output which stands for descriptors, the GL binding it ended up with. GL has no
+    /// descriptor sets, so the binding of a resource is kept and its set dropped, which can
+    /// make resources of different sets collide within a `GlBindingNamespace`. Separate images
+    /// and samplers are replaced by the combined image samplers built from them, which have no
+    /// binding unless one was set on them. Uniform buffers emitted as plain uniforms have no
+    /// binding point and are left out.
+    pub fn get_binding_relocations(&mut self) -> Result<Vec<BindingRelocation>, ErrorCode> {
+        use spirv::ResourceKind::*;
+
+        let combined_image_samplers = self.get_combined_image_samplers()?;
+        let is_combined = |id| {
+            combined_image_samplers.iter().any(|sampler| {
+                sampler.combined_id == id || sampler.image_id == id || sampler.sampler_id == id
+            })
+        };
+        let plain_uniforms = self
+            .compiler
+            .target_data
+            .options
+            .emit_uniform_buffer_as_plain_uniforms;
+
+        let mut relocations = Vec::new();
+        for (kind, resource) in self.compiler.get_shader_resources()?.iter() {
+            let namespace = match kind {
+                UniformBuffers if plain_uniforms => continue,
+                UniformBuffers => GlBindingNamespace::UniformBuffers,
+                StorageBuffers => GlBindingNamespace::ShaderStorageBuffers,
+                StorageImages => GlBindingNamespace::ImageUnits,
+                AtomicCounters => GlBindingNamespace::AtomicCounterBuffers,
+                SampledImages | SeparateImages | SeparateSamplers => {
+                    GlBindingNamespace::TextureUnits
+                }
+                StageInputs | StageOutputs | SubpassInputs | PushConstantBuffers => continue,
+            };
+            if is_combined(resource.id) {
+                continue;
+            }
+            let sources = self
+                .get_descriptor_binding(resource.id)?
+                .into_iter()
+                .collect();
+            relocations.push(self.relocate(
+                kind,
+                namespace,
+                resource.name.clone(),
+                resource.id,
+                sources,
+            )?);
+        }
+        for sampler in &combined_image_samplers {
+            let name = self.get_combined_sampler_name(sampler)?;
+            let mut sources = Vec::new();
+            for &id in &[sampler.image_id, sampler.sampler_id] {
+                sources.extend(self.get_descriptor_binding(id)?);
+            }
+            relocations.push(self.relocate(
+                SampledImages,
+                GlBindingNamespace::TextureUnits,
+                name,
+                sampler.combined_id,
+                sources,
+            )?);
+        }
+        Ok(relocations)
+    }
+
+    fn relocate(
+        &self,
+        kind: spirv::ResourceKind,
+        namespace: GlBindingNamespace,
+        name: String,
+        id: u32,
+        sources: Vec<DescriptorBinding>,
+    ) -> Result<BindingRelocation, ErrorCode> {
+        let binding = if self
+            .compiler
+            .has_decoration(id, spirv::Decoration::Binding)?
+        {
+            Some(
+                self.compiler
+                    .get_decoration(id, spirv::Decoration::Binding)?,
+            )
+        } else {
+            None
+        };
+        let explicit_bindings = self.compiler.target_data.options.explicit_bindings;
+        let explicit = binding.is_some()
+            && (kind == spirv::ResourceKind::AtomicCounters
+                || (self.supports_bindings() && explicit_bindings.includes(kind)));
+        Ok(BindingRelocation {
+            name,
+            namespace,
+            sources,
+            binding,
+            explicit,
+        })
+    }
+
+    /// Runs `compile` with the bindings `get_omitted_bindings` reports removed, restoring them
+    /// afterwards.
+    fn without_omitted_bindings<T>(
+        &mut self,
+        compile: impl FnOnce(&mut compiler::Compiler<TargetData>) -> Result<T, ErrorCode>,
+    ) -> Result<T, ErrorCode> {
+        let omitted = self.get_omitted_bindings()?;
+        for binding in &omitted {
+            self.compiler
+                .unset_decoration(binding.resource.id, spirv::Decoration::Binding)?;
+        }
+        let result = compile(&mut self.compiler);
+        for binding in &omitted {
+            self.compiler.set_decoration(
+                binding.resource.id,
+                spirv::Decoration::Binding,
+                binding.binding,
+            )?;
+        }
+        result
+    }
+
     pub fn build_combined_image_samplers(&mut self) -> Result<(), ErrorCode> {
         unsafe {
             if !self.compiler.target_data.combined_image_samplers_built {
                 check!(br::sc_internal_compiler_glsl_build_combined_image_samplers(
                     self.compiler.sc_compiler
                 ));
+                log_debug!("combined separate images and samplers for GLSL output");
                 self.compiler.target_data.combined_image_samplers_built = true
             }
         }
@@ -271,6 +1009,75 @@ impl spirv::Ast<Target> {
         }
     }
 
+    /// Builds combined image samplers if needed and returns, for each of them, the image and
+    /// sampler it replaces with their descriptor bindings. Runtime code binding textures to the
+    /// combined samplers and offline tools can share the table instead of recomputing it.
+    pub fn create_combined_sampler_mapping(
+        &mut self,
+    ) -> Result<Vec<CombinedSamplerMapping>, ErrorCode> {
+        let mut mapping = Vec::new();
+        for sampler in self.get_combined_image_samplers()? {
+            mapping.push(CombinedSamplerMapping {
+                combined_name: self.get_combined_sampler_name(&sampler)?,
+                image_name: self.compiler.get_name(sampler.image_id)?,
+                image: self.get_descriptor_binding(sampler.image_id)?,
+                sampler_name: self.compiler.get_name(sampler.sampler_id)?,
+                sampler: self.get_descriptor_binding(sampler.sampler_id)?,
+            });
+        }
+        Ok(mapping)
+    }
+
+    fn get_combined_sampler_name(
+        &self,
+        sampler: &spirv::CombinedImageSampler,
+    ) -> Result<String, ErrorCode> {
+        let name = self.compiler.get_name(sampler.combined_id)?;
+        if !name.is_empty() {
+            return Ok(name);
+        }
+        // The name the backend gives combined samplers which were not named explicitly
+        Ok(format!(
+            "SPIRV_Cross_Combined{}{}",
+            self.compiler.get_name(sampler.image_id)?,
+            self.compiler.get_name(sampler.sampler_id)?
+        ))
+    }
+
+    fn get_descriptor_binding(&self, id: u32) -> Result<Option<DescriptorBinding>, ErrorCode> {
+        if !self
+            .compiler
+            .has_decoration(id, spirv::Decoration::Binding)?
+        {
+            return Ok(None);
+        }
+        Ok(Some(DescriptorBinding {
+            desc_set: self
+                .compiler
+                .get_decoration(id, spirv::Decoration::DescriptorSet)?,
+            binding: self
+                .compiler
+                .get_decoration(id, spirv::Decoration::Binding)?,
+        }))
+    }
+
+    /// Compiles the abstract syntax tree and collects what the backend decided along the way.
+    pub fn compile_output(&mut self) -> Result<CompileOutput, ErrorCode> {
+        let shader = self.compile_with_lints()?;
+        let combined_image_samplers = if self.compiler.target_data.combined_image_samplers_built {
+            self.get_combined_image_samplers()?
+        } else {
+            Vec::new()
+        };
+        Ok(CompileOutput {
+            shader,
+            requirements: self.feature_requirements()?,
+            combined_image_samplers,
+            omitted_bindings: self.get_omitted_bindings()?,
+            binding_relocations: self.get_binding_relocations()?,
+        })
+    }
+
     pub fn add_header_line(&mut self, line: &str) -> Result<(), ErrorCode> {
         unsafe {
             let line = CString::new(line);
@@ -288,6 +1095,64 @@ impl spirv::Ast<Target> {
         }
     }
 
+    /// Assigns an explicit `layout(location = N)` to a loose uniform or sampler, so that it can
+    /// be set with `glProgramUniform` without querying its location. Requires the current
+    /// options to target GLSL 4.30 or ESSL 3.10 or later without Vulkan semantics.
+    pub fn set_uniform_location(&mut self, id: u32, location: u32) -> Result<(), ErrorCode> {
+        let options = &self.compiler.target_data.options;
+        let (version, es) = options.version.as_raw();
+        if options.vulkan_semantics || version < if es { 3_10 } else { 4_30 } {
+            return Err(ErrorCode::InvalidOptions(String::from(
+                "explicit uniform locations require GLSL 4.30 or ESSL 3.10 without Vulkan semantics",
+            )));
+        }
+
+        self.compiler
+            .set_decoration(id, spirv::Decoration::Location, location)
+    }
+
+    /// Sets the input attachment index of a subpass input, as emitted in
+    /// `layout(input_attachment_index = N)` with Vulkan semantics.
+    pub fn set_input_attachment_index(&mut self, id: u32, index: u32) -> Result<(), ErrorCode> {
+        self.compiler
+            .set_decoration(id, spirv::Decoration::InputAttachmentIndex, index)
+    }
+
+    /// Declares a subpass input as a combined image sampler which is read with `texelFetch` at
+    /// the current fragment, for render passes which were split so that the attachment is
+    /// written by an earlier pass. Other subpass inputs keep using `subpassLoad`.
+    pub fn remap_subpass_input_to_sampler(&mut self, id: u32) -> Result<(), ErrorCode> {
+        unsafe {
+            check!(
+                br::sc_internal_compiler_glsl_remap_subpass_input_to_sampler(
+                    self.compiler.sc_compiler,
+                    id,
+                )
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Overrides the format qualifier emitted for a storage image, such as `layout(rgba8)`.
+    /// `ImageFormat::Unknown` omits the qualifier, regardless of
+    /// `omit_write_only_image_formats`.
+    pub fn set_image_format(
+        &mut self,
+        id: u32,
+        format: spirv::ImageFormat,
+    ) -> Result<(), ErrorCode> {
+        unsafe {
+            check!(br::sc_internal_compiler_glsl_set_image_format(
+                self.compiler.sc_compiler,
+                id,
+                format.as_raw(),
+            ));
+        }
+
+        Ok(())
+    }
+
     pub fn flatten_buffer_block(&mut self, id: u32) -> Result<(), ErrorCode> {
         unsafe {
             check!(br::sc_internal_compiler_glsl_flatten_buffer_block(
@@ -299,3 +1164,272 @@ impl spirv::Ast<Target> {
         }
     }
 }
+
+/// What the source generated by the GLSL backend requires of the driver, as returned by
+/// `get_feature_requirements`.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct FeatureRequirements {
+    /// The version of the `#version` directive.
+    pub version: Version,
+    /// The extensions of the `#extension` directives, as returned by `get_required_extensions`.
+    pub extensions: Vec<String>,
+}
+
+/// A combined image sampler of the output with the separate image and sampler it replaces, as
+/// returned by `create_combined_sampler_mapping`.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CombinedSamplerMapping {
+    /// The name of the combined sampler uniform in the source.
+    pub combined_name: String,
+    pub image_name: String,
+    /// The binding of the image, `None` if it has no binding decoration.
+    pub image: Option<DescriptorBinding>,
+    pub sampler_name: String,
+    /// The binding of the sampler, `None` for the dummy sampler of
+    /// `build_dummy_sampler_for_combined_images`.
+    pub sampler: Option<DescriptorBinding>,
+}
+
+/// A compiled GLSL shader, as returned by `compile_output`.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct CompileOutput {
+    pub shader: spirv::CompiledShader,
+    pub requirements: FeatureRequirements,
+    /// The combined image samplers which replaced separate images and samplers, empty unless
+    /// `build_combined_image_samplers` was called.
+    pub combined_image_samplers: Vec<spirv::CombinedImageSampler>,
+    /// The resources whose binding was left out of the source, as returned by
+    /// `get_omitted_bindings`.
+    pub omitted_bindings: Vec<OmittedBinding>,
+    /// The GL bindings of the descriptors, as returned by `get_binding_relocations`.
+    pub binding_relocations: Vec<BindingRelocation>,
+}
+
+impl spirv::Ast<Target, spirv::Compiled> {
+    /// Returns the version and extensions the generated source requires, without parsing its
+    /// directives.
+    pub fn get_feature_requirements(&self) -> Result<FeatureRequirements, ErrorCode> {
+        self.feature_requirements()
+    }
+
+    /// Returns the extensions that the backend decided the shader requires, such as
+    /// `GL_EXT_texture_buffer`, in the order of their `#extension` directives.
+    pub fn get_required_extensions(&self) -> Result<Vec<String>, ErrorCode> {
+        self.required_extensions()
+    }
+}
+
+impl<TState> spirv::Ast<Target, TState> {
+    fn feature_requirements(&self) -> Result<FeatureRequirements, ErrorCode> {
+        Ok(FeatureRequirements {
+            version: self.compiler.target_data.options.version,
+            extensions: self.required_extensions()?,
+        })
+    }
+
+    fn required_extensions(&self) -> Result<Vec<String>, ErrorCode> {
+        unsafe {
+            let mut extensions_raw: *mut *const c_char = ptr::null_mut();
+            let mut extensions_raw_length: usize = 0;
+
+            check!(br::sc_internal_compiler_glsl_get_required_extensions(
+                self.compiler.sc_compiler,
+                &mut extensions_raw,
+                &mut extensions_raw_length,
+            ));
+
+            let extensions = read_into_vec_from_ptr(extensions_raw, extensions_raw_length)
+                .into_iter()
+                .map(|extension_raw| {
+                    let extension = read_string_from_ptr(extension_raw)?;
+                    check!(br::sc_internal_free_pointer(extension_raw as *mut c_void));
+                    Ok(extension)
+                })
+                .collect::<Result<Vec<_>, _>>();
+
+            check!(br::sc_internal_free_pointer(extensions_raw as *mut c_void));
+
+            extensions
+        }
+    }
+
+    /// Returns the format qualifier emitted for an image, taking `set_image_format` and the
+    /// `omit_write_only_image_formats` option into account. `ImageFormat::Unknown` means that no
+    /// qualifier is emitted.
+    pub fn get_image_format(&self, id: u32) -> Result<spirv::ImageFormat, ErrorCode> {
+        unsafe {
+            let mut format = br::spv::ImageFormat::ImageFormatUnknown;
+            check!(br::sc_internal_compiler_glsl_get_image_format(
+                self.compiler.sc_compiler,
+                id,
+                &mut format,
+            ));
+            spirv::ImageFormat::from_raw(format)
+        }
+    }
+
+    /// Returns the lowest GLSL version, or ESSL version if `es` is set, with all of the features
+    /// the module uses built in, such as storage buffers, image load and store, and texture
+    /// gathers, so the output version can be picked per shader. Returns `None` if no version of
+    /// that flavour has them all, as with 64-bit floats in ESSL. Options which change how
+    /// features are emitted, like `emit_uniform_buffer_as_plain_uniforms`, are taken into
+    /// account, while extensions which would allow a lower version are not.
+    pub fn get_minimum_version(&self, es: bool) -> Result<Option<Version>, ErrorCode> {
+        let options = &self.compiler.target_data.options;
+        let mut requirements = Vec::new();
+        for entry_point in self.get_entry_points()? {
+            requirements.push(match entry_point.execution_model {
+                spirv::ExecutionModel::Vertex | spirv::ExecutionModel::Fragment => {
+                    (Some(1_10), Some(1_00))
+                }
+                spirv::ExecutionModel::Geometry => (Some(1_50), Some(3_20)),
+                spirv::ExecutionModel::TessellationControl
+                | spirv::ExecutionModel::TessellationEvaluation => (Some(4_00), Some(3_20)),
+                spirv::ExecutionModel::GlCompute => (Some(4_30), Some(3_10)),
+                spirv::ExecutionModel::Kernel => (None, None),
+            });
+        }
+
+        let resources = self.get_shader_resources()?;
+        let uniform_buffers = !resources.uniform_buffers.is_empty()
+            || options.emit_push_constant_as_uniform_buffer
+                && !resources.push_constant_buffers.is_empty();
+        if uniform_buffers && !options.emit_uniform_buffer_as_plain_uniforms {
+            requirements.push((Some(1_40), Some(3_00)));
+        }
+        if !resources.storage_buffers.is_empty() {
+            requirements.push((Some(4_30), Some(3_10)));
+        }
+        if !resources.storage_images.is_empty() || !resources.atomic_counters.is_empty() {
+            requirements.push((Some(4_20), Some(3_10)));
+        }
+        if options.vulkan_semantics {
+            requirements.push((Some(1_40), Some(3_10)));
+        }
+
+        let capabilities = self.compiler.get_declared_capabilities()?;
+        requirements.extend(capabilities.into_iter().filter_map(get_capability_version));
+        let opcodes = self.compiler.get_used_opcodes()?;
+        requirements.extend(opcodes.into_iter().filter_map(get_opcode_version));
+
+        let minimum = requirements.into_iter().try_fold(
+            if es { 1_00 } else { 1_10 },
+            |minimum, (desktop, embedded)| {
+                let version = if es { embedded } else { desktop };
+                version.map(|version| minimum.max(version))
+            },
+        );
+        Ok(minimum.and_then(|version| Version::from_raw(version, es)))
+    }
+
+    /// Returns the name of the macro holding the default value of a specialization constant.
+    /// With `SpecializationConstantForm::Macro`, each constant is emitted as a `const`
+    /// initialized from this macro, which `#define`s the default value unless it is already
+    /// defined. Returns `None` with Vulkan semantics, where `layout(constant_id = N)` is emitted
+    /// instead, and with the other forms, which emit the default value in place of the macro.
+    pub fn get_specialization_constant_macro_name(
+        &self,
+        constant: &spirv::SpecializationConstant,
+    ) -> Option<String> {
+        let options = &self.compiler.target_data.options;
+        if options.vulkan_semantics
+            || options.specialization_constant_form != SpecializationConstantForm::Macro
+        {
+            None
+        } else {
+            Some(specialization_constant_macro_name(constant))
+        }
+    }
+
+    fn materializes_specialization_constants(&self) -> bool {
+        let options = &self.compiler.target_data.options;
+        !options.vulkan_semantics
+            && options.specialization_constant_form != SpecializationConstantForm::Macro
+    }
+}
+
+fn specialization_constant_macro_name(constant: &spirv::SpecializationConstant) -> String {
+    format!("SPIRV_CROSS_CONSTANT_ID_{}", constant.constant_id)
+}
+
+/// Rewrites the `SPIRV_CROSS_CONSTANT_ID_N` macros SPIRV-Cross emits for `constants`, which
+/// are declared as `#ifndef` / `#define` / `#endif` lines, into `form`. Any other use of a
+/// macro, such as a workgroup size, is replaced with the default value.
+fn materialize_specialization_constants(
+    source: &str,
+    constants: &[spirv::SpecializationConstant],
+    form: SpecializationConstantForm,
+) -> String {
+    let macro_names: Vec<_> = constants
+        .iter()
+        .map(specialization_constant_macro_name)
+        .collect();
+    let mut values = HashMap::new();
+    let mut lines = Vec::new();
+    let mut rest: Vec<_> = source.lines().collect();
+    rest.reverse();
+    while let Some(line) = rest.pop() {
+        let name = line
+            .strip_prefix("#ifndef ")
+            .filter(|name| macro_names.iter().any(|macro_name| macro_name == name));
+        let length = rest.len();
+        let value = name
+            .filter(|_| length >= 2 && rest[length - 2] == "#endif")
+            .and_then(|name| {
+                rest[length - 1]
+                    .strip_prefix("#define ")?
+                    .strip_prefix(name)?
+                    .strip_prefix(' ')
+            });
+        match (name, value) {
+            (Some(name), Some(value)) => {
+                values.insert(name, value);
+                rest.truncate(rest.len() - 2);
+            }
+            _ => lines.push(line),
+        }
+    }
+
+    let mut output = String::with_capacity(source.len());
+    for line in lines {
+        let definition = line
+            .strip_prefix("const ")
+            .and_then(|declaration| declaration.strip_suffix(';'))
+            .and_then(|declaration| {
+                let (declaration, initializer) = declaration.split_at(declaration.find(" = ")?);
+                let name = declaration.rsplit(' ').next()?;
+                Some((name, values.get(&initializer[" = ".len()..])?))
+            });
+        match definition {
+            Some((name, value)) if form == SpecializationConstantForm::Define => {
+                output.push_str("#define ");
+                output.push_str(name);
+                output.push(' ');
+                output.push_str(value);
+            }
+            _ => replace_macros(line, &values, &mut output),
+        }
+        output.push('\n');
+    }
+    if !source.ends_with('\n') {
+        output.pop();
+    }
+    output
+}
+
+/// Appends `line` to `output` with each identifier found in `values` replaced by its value.
+fn replace_macros(line: &str, values: &HashMap<&str, &str>, output: &mut String) {
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        let length = if c == '_' || c.is_ascii_alphanumeric() {
+            rest.find(|c: char| c != '_' && !c.is_ascii_alphanumeric())
+                .unwrap_or_else(|| rest.len())
+        } else {
+            c.len_utf8()
+        };
+        let token = &rest[..length];
+        output.push_str(values.get(token).unwrap_or(&token));
+        rest = &rest[length..];
+    }
+}
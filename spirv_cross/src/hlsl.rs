@@ -1,6 +1,7 @@
 use crate::bindings as br;
 use crate::{compiler, spirv, ErrorCode};
 use std::ffi::CString;
+use std::io;
 use std::marker::PhantomData;
 use std::ptr;
 
@@ -10,8 +11,34 @@ pub use crate::bindings::root::ScHlslRootConstant as RootConstant;
 #[derive(Debug, Clone)]
 pub enum Target {}
 
+pub struct TargetData {
+    options: CompilerOptions,
+    has_root_constants: bool,
+    has_register_shifts: bool,
+    /// The block type of each flattened uniform buffer variable and the constant register its
+    /// `float4` array is bound to.
+    flattened_buffer_blocks: Vec<FlattenedBufferBlock>,
+}
+
+struct FlattenedBufferBlock {
+    id: u32,
+    type_id: u32,
+    register: u32,
+}
+
+/// Root constants are declared in register spaces, which shader model 5.1 introduced.
+fn validate_root_constants(shader_model: ShaderModel) -> Result<(), ErrorCode> {
+    if shader_model.as_raw() < ShaderModel::V5_1.as_raw() {
+        return Err(ErrorCode::InvalidOptions(String::from(
+            "root constants require shader model 5.1 or later",
+        )));
+    }
+
+    Ok(())
+}
+
 impl spirv::Target for Target {
-    type Data = ();
+    type Data = TargetData;
 }
 
 /// A HLSL shader model version.
@@ -48,6 +75,15 @@ impl ShaderModel {
     }
 }
 
+/// How a storage buffer is declared in the generated HLSL.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum StorageBufferForm {
+    /// A `ByteAddressBuffer` or `RWByteAddressBuffer` accessed with explicit loads and stores.
+    ByteAddressBuffer,
+    /// A `StructuredBuffer<T>` or `RWStructuredBuffer<T>` of the runtime array's element type.
+    StructuredBuffer,
+}
+
 #[derive(Debug, Clone)]
 pub struct CompilerVertexOptions {
     pub invert_y: bool,
@@ -63,6 +99,44 @@ impl Default for CompilerVertexOptions {
     }
 }
 
+/// Moves the registers of one register class by a fixed offset, optionally into another register
+/// space.
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub struct RegisterShift {
+    /// Added to the binding of each resource to get its register number.
+    pub offset: u32,
+    /// The register space of every resource, or `None` to use the descriptor set as the space.
+    pub space: Option<u32>,
+}
+
+/// Register shifts for each register class, the reverse of DXC's `-fvk-b-shift`, `-fvk-t-shift`,
+/// `-fvk-u-shift` and `-fvk-s-shift`, so the generated registers fit an existing root signature
+/// without remapping each resource.
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub struct RegisterShifts {
+    /// The `b` registers of constant buffers.
+    pub constant_buffers: RegisterShift,
+    /// The `t` registers of textures and read-only buffers.
+    pub shader_resources: RegisterShift,
+    /// The `u` registers of storage images and writable buffers.
+    pub unordered_access: RegisterShift,
+    /// The `s` registers of samplers.
+    pub samplers: RegisterShift,
+}
+
+impl RegisterShifts {
+    fn uses_spaces(&self) -> bool {
+        [
+            self.constant_buffers,
+            self.shader_resources,
+            self.unordered_access,
+            self.samplers,
+        ]
+        .iter()
+        .any(|shift| shift.space.is_some())
+    }
+}
+
 /// HLSL compiler options.
 #[non_exhaustive]
 #[derive(Debug, Clone)]
@@ -77,6 +151,38 @@ pub struct CompilerOptions {
     pub nonwritable_uav_texture_as_srv: bool,
     /// Whether to force all uninitialized variables to be initialized to zero.
     pub force_zero_initialized_variables: bool,
+    /// Whether to emit `#line` directives referencing the original source, when the module
+    /// carries `OpLine` debug information.
+    pub emit_line_directives: bool,
+    /// Whether to store every expression in a temporary instead of inlining it, which works
+    /// around drivers that miscompile long expressions.
+    pub force_temporary: bool,
+    /// Whether to flatten multidimensional arrays into one-dimensional arrays.
+    pub flatten_multidimensional_arrays: bool,
+    /// Whether to emit storage buffers decorated with a DXC `UserTypeGOOGLE` structured buffer
+    /// type as `StructuredBuffer<T>` or `RWStructuredBuffer<T>` instead of byte address buffers.
+    pub preserve_structured_buffers: bool,
+    /// Offsets and spaces applied to the registers of all resources, by register class.
+    pub register_shifts: RegisterShifts,
+    /// Whether to leave out the declarations of resources and stage inputs and outputs which the
+    /// entry point does not statically use, instead of emitting every variable of the module.
+    pub remove_unused_variables: bool,
+    /// Whether to keep the declarations of resources which the entry point does not statically use,
+    /// so shader variants share the same resource interface. This takes precedence over
+    /// `remove_unused_variables` for resources.
+    pub keep_unused_resources: bool,
+    /// Whether to remove the names of all IDs and struct members before each compile, as
+    /// `Ast::strip_names` does, so that the output only uses anonymized identifiers.
+    pub strip_names: bool,
+    /// Whether to keep the names the backend would rename for clashing with a keyword of the
+    /// target language, in which case the output may not compile. Names that clash with another
+    /// identifier are still told apart. This cannot be combined with `strip_names`.
+    pub preserve_names: bool,
+    /// Overrides for the prefixes of the identifiers SPIRV-Cross generates, or `None` to keep
+    /// them unchanged.
+    pub identifier_naming: Option<spirv::IdentifierNaming>,
+    /// Whether to fail to compile when there are lints.
+    pub unsupported_features: spirv::UnsupportedFeaturePolicy,
     /// The name and execution model of the entry point to use. If no entry
     /// point is specified, then the first entry point found will be used.
     pub entry_point: Option<(String, spirv::ExecutionModel)>,
@@ -92,34 +198,185 @@ impl Default for CompilerOptions {
             force_storage_buffer_as_uav: false,
             nonwritable_uav_texture_as_srv: false,
             force_zero_initialized_variables: false,
+            emit_line_directives: false,
+            force_temporary: false,
+            flatten_multidimensional_arrays: false,
+            preserve_structured_buffers: false,
+            register_shifts: RegisterShifts::default(),
+            remove_unused_variables: false,
+            keep_unused_resources: false,
+            strip_names: false,
+            preserve_names: false,
+            identifier_naming: None,
+            unsupported_features: spirv::UnsupportedFeaturePolicy::Relaxed,
             entry_point: None,
         }
     }
 }
 
+impl CompilerOptions {
+    /// Creates a builder starting from the default options.
+    pub fn builder() -> CompilerOptionsBuilder {
+        CompilerOptionsBuilder {
+            options: CompilerOptions::default(),
+        }
+    }
+
+    fn validate(&self) -> Result<(), ErrorCode> {
+        spirv::validate_entry_point(&self.entry_point)?;
+        spirv::validate_names(self.strip_names, self.preserve_names)?;
+        if self.register_shifts.uses_spaces()
+            && self.shader_model.as_raw() < ShaderModel::V5_1.as_raw()
+        {
+            return Err(ErrorCode::InvalidOptions(String::from(
+                "register spaces require shader model 5.1 or later",
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Builder for `CompilerOptions`. Each setter overrides the option of the same name.
+#[derive(Debug, Clone)]
+pub struct CompilerOptionsBuilder {
+    options: CompilerOptions,
+}
+
+impl CompilerOptionsBuilder {
+    pub fn shader_model(mut self, shader_model: ShaderModel) -> Self {
+        self.options.shader_model = shader_model;
+        self
+    }
+
+    pub fn point_size_compat(mut self, point_size_compat: bool) -> Self {
+        self.options.point_size_compat = point_size_compat;
+        self
+    }
+
+    pub fn point_coord_compat(mut self, point_coord_compat: bool) -> Self {
+        self.options.point_coord_compat = point_coord_compat;
+        self
+    }
+
+    pub fn vertex(mut self, vertex: CompilerVertexOptions) -> Self {
+        self.options.vertex = vertex;
+        self
+    }
+
+    pub fn force_storage_buffer_as_uav(mut self, force: bool) -> Self {
+        self.options.force_storage_buffer_as_uav = force;
+        self
+    }
+
+    pub fn nonwritable_uav_texture_as_srv(mut self, enable: bool) -> Self {
+        self.options.nonwritable_uav_texture_as_srv = enable;
+        self
+    }
+
+    pub fn force_zero_initialized_variables(mut self, force: bool) -> Self {
+        self.options.force_zero_initialized_variables = force;
+        self
+    }
+
+    pub fn emit_line_directives(mut self, emit_line_directives: bool) -> Self {
+        self.options.emit_line_directives = emit_line_directives;
+        self
+    }
+
+    pub fn force_temporary(mut self, force_temporary: bool) -> Self {
+        self.options.force_temporary = force_temporary;
+        self
+    }
+
+    pub fn flatten_multidimensional_arrays(mut self, flatten_multidimensional_arrays: bool) -> Self {
+        self.options.flatten_multidimensional_arrays = flatten_multidimensional_arrays;
+        self
+    }
+
+    pub fn preserve_structured_buffers(mut self, preserve: bool) -> Self {
+        self.options.preserve_structured_buffers = preserve;
+        self
+    }
+
+    pub fn register_shifts(mut self, register_shifts: RegisterShifts) -> Self {
+        self.options.register_shifts = register_shifts;
+        self
+    }
+
+    pub fn remove_unused_variables(mut self, remove_unused_variables: bool) -> Self {
+        self.options.remove_unused_variables = remove_unused_variables;
+        self
+    }
+
+    pub fn keep_unused_resources(mut self, keep_unused_resources: bool) -> Self {
+        self.options.keep_unused_resources = keep_unused_resources;
+        self
+    }
+
+    pub fn strip_names(mut self, strip_names: bool) -> Self {
+        self.options.strip_names = strip_names;
+        self
+    }
+
+    pub fn preserve_names(mut self, preserve_names: bool) -> Self {
+        self.options.preserve_names = preserve_names;
+        self
+    }
+
+    pub fn identifier_naming(mut self, identifier_naming: spirv::IdentifierNaming) -> Self {
+        self.options.identifier_naming = Some(identifier_naming);
+        self
+    }
+
+    pub fn unsupported_features(mut self, policy: spirv::UnsupportedFeaturePolicy) -> Self {
+        self.options.unsupported_features = policy;
+        self
+    }
+
+    pub fn entry_point(
+        mut self,
+        name: impl Into<String>,
+        execution_model: spirv::ExecutionModel,
+    ) -> Self {
+        self.options.entry_point = Some((name.into(), execution_model));
+        self
+    }
+
+    /// Validates and returns the options.
+    pub fn build(self) -> Result<CompilerOptions, ErrorCode> {
+        self.options.validate()?;
+        Ok(self.options)
+    }
+}
+
 impl spirv::Parse<Target> for spirv::Ast<Target> {
     fn parse(module: &spirv::Module) -> Result<Self, ErrorCode> {
-        let compiler = {
+        let compiler = spirv::parse_words(&module.words, |words| {
             let mut compiler = ptr::null_mut();
             unsafe {
                 check!(br::sc_internal_compiler_hlsl_new(
                     &mut compiler,
-                    module.words.as_ptr() as *const u32,
-                    module.words.len() as usize,
+                    words.as_ptr() as *const u32,
+                    words.len() as usize,
                 ));
             }
+            Ok(compiler)
+        })?;
 
-            compiler::Compiler {
-                sc_compiler: compiler,
-                target_data: (),
-                has_been_compiled: false,
-            }
-        };
+        Ok(Self::from_sc_compiler(compiler))
+    }
 
-        Ok(spirv::Ast {
-            compiler,
-            target_type: PhantomData,
-        })
+    fn from_parsed(module: &spirv::ParsedModule) -> Result<Self, ErrorCode> {
+        let mut compiler = ptr::null_mut();
+        unsafe {
+            check!(br::sc_internal_compiler_hlsl_new_from_ir(
+                &mut compiler,
+                module.ir.sc_parsed_ir,
+            ));
+        }
+
+        Ok(Self::from_sc_compiler(compiler))
     }
 }
 
@@ -128,6 +385,15 @@ impl spirv::Compile<Target> for spirv::Ast<Target> {
 
     /// Set HLSL compiler specific compilation settings.
     fn set_compiler_options(&mut self, options: &CompilerOptions) -> Result<(), ErrorCode> {
+        options.validate()?;
+        if self.compiler.target_data.has_root_constants {
+            validate_root_constants(options.shader_model)?;
+        }
+        spirv::validate_entry_point_in_module(
+            &options.entry_point,
+            &self.compiler.get_entry_points()?,
+        )?;
+
         if let Some((name, model)) = &options.entry_point {
             let name_raw = CString::new(name.as_str()).map_err(|_| ErrorCode::Unhandled)?;
             let model = model.as_raw();
@@ -148,6 +414,10 @@ impl spirv::Compile<Target> for spirv::Ast<Target> {
             force_storage_buffer_as_uav: options.force_storage_buffer_as_uav,
             nonwritable_uav_texture_as_srv: options.nonwritable_uav_texture_as_srv,
             force_zero_initialized_variables: options.force_zero_initialized_variables,
+            emit_line_directives: options.emit_line_directives,
+            force_temporary: options.force_temporary,
+            flatten_multidimensional_arrays: options.flatten_multidimensional_arrays,
+            preserve_structured_buffers: options.preserve_structured_buffers,
         };
         unsafe {
             check!(br::sc_internal_compiler_hlsl_set_options(
@@ -156,18 +426,213 @@ impl spirv::Compile<Target> for spirv::Ast<Target> {
             ));
         }
 
+        self.compiler.remove_unused_variables = options.remove_unused_variables;
+        self.compiler.keep_unused_resources = options.keep_unused_resources;
+        self.compiler.strip_names = options.strip_names;
+        self.compiler.preserve_names = options.preserve_names;
+        self.compiler.identifier_naming = options.identifier_naming.clone();
+        self.compiler.unsupported_features = options.unsupported_features;
+        self.compiler.target_data.options = options.clone();
         Ok(())
     }
 
+    fn get_compiler_options(&self) -> CompilerOptions {
+        self.compiler.target_data.options.clone()
+    }
+
+    fn set_entry_point(
+        &mut self,
+        name: &str,
+        execution_model: spirv::ExecutionModel,
+    ) -> Result<(), ErrorCode> {
+        let mut options = self.compiler.target_data.options.clone();
+        options.entry_point = Some((name.into(), execution_model));
+        spirv::Compile::<Target>::set_compiler_options(self, &options)
+    }
+
+    fn hash_compiler_options(&self) -> u64 {
+        let CompilerOptions {
+            shader_model,
+            point_size_compat,
+            point_coord_compat,
+            vertex,
+            force_storage_buffer_as_uav,
+            nonwritable_uav_texture_as_srv,
+            force_zero_initialized_variables,
+            emit_line_directives,
+            force_temporary,
+            flatten_multidimensional_arrays,
+            preserve_structured_buffers,
+            register_shifts,
+            remove_unused_variables,
+            keep_unused_resources,
+            strip_names,
+            preserve_names,
+            identifier_naming,
+            unsupported_features,
+            entry_point,
+        } = &self.compiler.target_data.options;
+
+        let mut hasher = spirv::StableHasher::new();
+        hasher.write_str("hlsl");
+        hasher.write_u32(*shader_model as u32);
+        for &flag in &[
+            *point_size_compat,
+            *point_coord_compat,
+            vertex.invert_y,
+            vertex.transform_clip_space,
+            *force_storage_buffer_as_uav,
+            *nonwritable_uav_texture_as_srv,
+            *force_zero_initialized_variables,
+            *emit_line_directives,
+            *force_temporary,
+            *flatten_multidimensional_arrays,
+            *preserve_structured_buffers,
+            *remove_unused_variables,
+            *keep_unused_resources,
+        ] {
+            hasher.write_bool(flag);
+        }
+        for shift in &[
+            register_shifts.constant_buffers,
+            register_shifts.shader_resources,
+            register_shifts.unordered_access,
+            register_shifts.samplers,
+        ] {
+            hasher.write_u32(shift.offset);
+            hasher.write_option(&shift.space, |hasher, space| hasher.write_u32(*space));
+        }
+        hasher.write_common_options(
+            *strip_names,
+            *preserve_names,
+            identifier_naming,
+            *unsupported_features,
+            entry_point,
+        );
+        hasher.finish()
+    }
+
+    fn get_lints(&self) -> Result<Vec<String>, ErrorCode> {
+        Ok(spirv::entry_point_lints(
+            &self.compiler.target_data.options.entry_point,
+            &self.compiler.get_entry_points()?,
+        ))
+    }
+
     /// Generate HLSL shader from the AST.
     fn compile(&mut self) -> Result<String, ErrorCode> {
-        self.compiler.compile()
+        self.apply_register_shifts()?;
+        let shader = self.compiler.compile()?;
+        self.bind_flattened_buffer_blocks(shader)
+    }
+
+    /// Generate HLSL shader from the AST into `writer`. The output is built as a `String` first
+    /// when a flattened uniform buffer block has to be bound to its register.
+    fn compile_into<W: io::Write>(&mut self, mut writer: W) -> Result<(), ErrorCode> {
+        if self.compiler.target_data.flattened_buffer_blocks.is_empty() {
+            self.apply_register_shifts()?;
+            return self.compiler.compile_into(writer);
+        }
+
+        let shader = spirv::Compile::<Target>::compile(self)?;
+        writer
+            .write_all(shader.as_bytes())
+            .and_then(|_| writer.flush())
+            .map_err(|e| ErrorCode::Io(e.to_string()))
     }
 }
 
 impl spirv::Ast<Target> {
+    fn from_sc_compiler(sc_compiler: *mut br::ScInternalCompilerBase) -> Self {
+        spirv::Ast {
+            compiler: compiler::Compiler {
+                sc_compiler,
+                target_data: TargetData {
+                    options: CompilerOptions::default(),
+                    has_root_constants: false,
+                    has_register_shifts: false,
+                    flattened_buffer_blocks: Vec::new(),
+                },
+                remove_unused_variables: false,
+                keep_unused_resources: false,
+                strip_names: false,
+                preserve_names: false,
+                identifier_naming: None,
+                unsupported_features: spirv::UnsupportedFeaturePolicy::Relaxed,
+                variable_type_remap: None,
+            },
+            target_type: PhantomData,
+            state: PhantomData,
+        }
+    }
+
+    /// Takes ownership of a SPIRV-Cross compiler previously released with `Ast::into_raw`. The
+    /// returned `Ast` uses the default compiler options until new ones are set.
+    ///
+    /// # Safety
     ///
+    /// `sc_compiler` must come from `into_raw` on an `Ast` of this target, and must not be
+    /// used through another `Ast` afterwards.
+    pub unsafe fn from_raw(sc_compiler: *mut br::ScInternalCompilerBase) -> Self {
+        Self::from_sc_compiler(sc_compiler)
+    }
+
+    /// Remaps the registers of the resources of the current entry point following the
+    /// `register_shifts` option. Once shifts have been applied, later compiles remap every
+    /// resource again, since remappings cannot be removed.
+    fn apply_register_shifts(&mut self) -> Result<(), ErrorCode> {
+        let shifts = self.compiler.target_data.options.register_shifts;
+        if shifts == RegisterShifts::default() && !self.compiler.target_data.has_register_shifts {
+            return Ok(());
+        }
+
+        let (_, execution_model) = self.get_current_entry_point()?;
+        let resources = self.get_shader_resources()?;
+        let shift = |shift: RegisterShift, desc_set: u32, binding: u32| br::ScHlslRegister {
+            register_space: shift.space.unwrap_or(desc_set),
+            register_binding: binding + shift.offset,
+        };
+        let bindings = resources
+            .uniform_buffers
+            .iter()
+            .chain(&resources.storage_buffers)
+            .chain(&resources.storage_images)
+            .chain(&resources.sampled_images)
+            .chain(&resources.separate_images)
+            .chain(&resources.separate_samplers)
+            .map(|resource| {
+                let desc_set =
+                    self.get_decoration(resource.id, spirv::Decoration::DescriptorSet)?;
+                let binding = self.get_decoration(resource.id, spirv::Decoration::Binding)?;
+                Ok(br::ScHlslResourceBinding {
+                    stage: execution_model.as_raw(),
+                    desc_set,
+                    binding,
+                    cbv: shift(shifts.constant_buffers, desc_set, binding),
+                    uav: shift(shifts.unordered_access, desc_set, binding),
+                    srv: shift(shifts.shader_resources, desc_set, binding),
+                    sampler: shift(shifts.samplers, desc_set, binding),
+                })
+            })
+            .collect::<Result<Vec<_>, ErrorCode>>()?;
+        unsafe {
+            check!(br::sc_internal_compiler_hlsl_add_resource_bindings(
+                self.compiler.sc_compiler,
+                bindings.as_ptr(),
+                bindings.len(),
+            ));
+        }
+
+        self.compiler.target_data.has_register_shifts = true;
+        Ok(())
+    }
+
+    /// Declares push constant ranges as root constants. A non-empty layout requires the current
+    /// options, and any options applied afterwards, to target shader model 5.1 or later.
     pub fn set_root_constant_layout(&mut self, layout: Vec<RootConstant>) -> Result<(), ErrorCode> {
+        if !layout.is_empty() {
+            validate_root_constants(self.compiler.target_data.options.shader_model)?;
+        }
         unsafe {
             check!(br::sc_internal_compiler_hlsl_set_root_constant_layout(
                 self.compiler.sc_compiler,
@@ -176,6 +641,250 @@ impl spirv::Ast<Target> {
             ));
         }
 
+        self.compiler.target_data.has_root_constants = !layout.is_empty();
+        Ok(())
+    }
+
+    /// Emits a uniform buffer as a `uniform float4` array bound to `register(c<register>)`, for
+    /// shader model 3.0 where constant buffers do not exist. All members of the block must have
+    /// the same base type. Flattening the same block again moves it to the new register.
+    pub fn flatten_buffer_block(&mut self, id: u32, register: u32) -> Result<(), ErrorCode> {
+        let type_id = self
+            .get_shader_resources()?
+            .uniform_buffers
+            .iter()
+            .find(|resource| resource.id == id)
+            .map(|resource| resource.base_type_id)
+            .ok_or_else(|| ErrorCode::InvalidOptions(format!("{} is not a uniform buffer", id)))?;
+        unsafe {
+            check!(br::sc_internal_compiler_glsl_flatten_buffer_block(
+                self.compiler.sc_compiler,
+                id,
+            ));
+        }
+
+        let blocks = &mut self.compiler.target_data.flattened_buffer_blocks;
+        blocks.retain(|block| block.id != id);
+        blocks.push(FlattenedBufferBlock {
+            id,
+            type_id,
+            register,
+        });
         Ok(())
     }
+
+    /// Adds the register of each flattened uniform buffer block to the declaration of its array,
+    /// which is named after the block type.
+    fn bind_flattened_buffer_blocks(&self, shader: String) -> Result<String, ErrorCode> {
+        let mut declarations = Vec::new();
+        for block in &self.compiler.target_data.flattened_buffer_blocks {
+            let mut name = self.compiler.get_name(block.type_id)?;
+            if name.is_empty() {
+                name = format!("_{}", block.type_id);
+            }
+            let length = self.get_flattened_array_length(block.type_id)?;
+            declarations.push((format!(" {}[{}];", name, length), block.register));
+        }
+        if declarations.is_empty() {
+            return Ok(shader);
+        }
+
+        let mut output = String::with_capacity(shader.len());
+        for line in shader.split_inclusive('\n') {
+            let declaration = declarations.iter().find(|(declaration, _)| {
+                line.starts_with("uniform ") && line.trim_end().ends_with(declaration.as_str())
+            });
+            match declaration {
+                Some((_, register)) => {
+                    let end = line.trim_end().len() - 1;
+                    output.push_str(&line[..end]);
+                    output.push_str(&format!(" : register(c{});", register));
+                    output.push_str(&line[end + 1..]);
+                }
+                None => output.push_str(line),
+            }
+        }
+        Ok(output)
+    }
+
+    /// Compiles the abstract syntax tree and collects what the backend decided along the way.
+    pub fn compile_output(&mut self) -> Result<CompileOutput, ErrorCode> {
+        let shader = self.compile_with_lints()?;
+        Ok(CompileOutput {
+            shader,
+            requirements: self.feature_requirements()?,
+        })
+    }
+}
+
+/// What the source generated by the HLSL backend requires of the shader compiler, as returned by
+/// `get_feature_requirements`.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct FeatureRequirements {
+    /// The shader model the source is written for. Compilation fails rather than raising it when
+    /// the module uses features of a later shader model.
+    pub shader_model: ShaderModel,
+}
+
+/// A compiled HLSL shader, as returned by `compile_output`.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct CompileOutput {
+    pub shader: spirv::CompiledShader,
+    pub requirements: FeatureRequirements,
+}
+
+impl spirv::Ast<Target, spirv::Compiled> {
+    /// Returns the shader model the generated source requires.
+    pub fn get_feature_requirements(&self) -> Result<FeatureRequirements, ErrorCode> {
+        self.feature_requirements()
+    }
+}
+
+impl<TState> spirv::Ast<Target, TState> {
+    fn feature_requirements(&self) -> Result<FeatureRequirements, ErrorCode> {
+        Ok(FeatureRequirements {
+            shader_model: self.compiler.target_data.options.shader_model,
+        })
+    }
+
+    /// Returns the semantic, such as `TEXCOORD3`, that the HLSL backend emits for a stage input
+    /// of the current entry point, from its location. The columns of a matrix vertex input are
+    /// declared one by one, as `<name>_<column>` with the semantic `<semantic>_<column>`.
+    ///
+    /// Returns `None` for built-ins, for interface blocks, whose members are declared with
+    /// semantics of their own, and for inputs without a `Location`, which the backend assigns a
+    /// free location while emitting.
+    pub fn get_input_semantic(
+        &self,
+        resource: &spirv::Resource,
+    ) -> Result<Option<String>, ErrorCode> {
+        Ok(self
+            .get_semantic_location(resource)?
+            .map(|location| format!("TEXCOORD{}", location)))
+    }
+
+    /// Returns the semantic, such as `SV_Target1`, that the HLSL backend emits for a stage output
+    /// of the current entry point. Fragment outputs use `SV_Target`, or `COLOR` before shader
+    /// model 4.0, numbered by their location and their `Index` for dual-source blending. Other
+    /// outputs use `TEXCOORD` and their location, and `None` is returned for the same outputs as
+    /// `get_input_semantic` does.
+    pub fn get_output_semantic(
+        &self,
+        resource: &spirv::Resource,
+    ) -> Result<Option<String>, ErrorCode> {
+        let location = match self.get_semantic_location(resource)? {
+            Some(location) => location,
+            None => return Ok(None),
+        };
+        let options = &self.compiler.target_data.options;
+        let execution_model = match &options.entry_point {
+            Some((_, execution_model)) => Some(*execution_model),
+            None => self
+                .get_entry_points()?
+                .first()
+                .map(|entry_point| entry_point.execution_model),
+        };
+        if execution_model != Some(spirv::ExecutionModel::Fragment) {
+            return Ok(Some(format!("TEXCOORD{}", location)));
+        }
+
+        let index = self.get_decoration(resource.id, spirv::Decoration::Index)?;
+        let semantic = if options.shader_model.as_raw() < ShaderModel::V4_0.as_raw() {
+            "COLOR"
+        } else {
+            "SV_Target"
+        };
+        Ok(Some(format!("{}{}", semantic, location + index)))
+    }
+
+    /// Returns the location a stage variable is declared with, if it is not a built-in or an
+    /// interface block and has one.
+    fn get_semantic_location(&self, resource: &spirv::Resource) -> Result<Option<u32>, ErrorCode> {
+        if self.has_decoration(resource.id, spirv::Decoration::BuiltIn)?
+            || !self.has_decoration(resource.id, spirv::Decoration::Location)?
+        {
+            return Ok(None);
+        }
+        if let spirv::Type::Struct { .. } = self.get_type(resource.base_type_id)? {
+            return Ok(None);
+        }
+        self.get_decoration(resource.id, spirv::Decoration::Location)
+            .map(Some)
+    }
+
+    /// Returns the element of the `float4` array of a flattened uniform buffer block in which a
+    /// member starts, and the component of that element, from the `Offset` of the member. These
+    /// are positions in the array; `get_flattened_member_register` returns the register.
+    pub fn get_flattened_member_index(&self, id: u32, index: u32) -> Result<(u32, u32), ErrorCode> {
+        let offset = self.get_member_decoration(id, index, spirv::Decoration::Offset)?;
+        Ok((offset / 16, (offset & 15) / 4))
+    }
+
+    /// Returns the length of the `float4` array of a flattened uniform buffer block.
+    pub fn get_flattened_array_length(&self, id: u32) -> Result<u32, ErrorCode> {
+        Ok(self.get_declared_struct_size(id)?.div_ceil(16))
+    }
+
+    /// Returns the constant register in which a member of the flattened uniform buffer variable
+    /// `id` starts, such as `c4` or `c4.y`, from the register passed to `flatten_buffer_block`.
+    pub fn get_flattened_member_register(&self, id: u32, index: u32) -> Result<String, ErrorCode> {
+        let block = self
+            .compiler
+            .target_data
+            .flattened_buffer_blocks
+            .iter()
+            .find(|block| block.id == id)
+            .ok_or_else(|| {
+                ErrorCode::InvalidOptions(format!("uniform buffer {} is not flattened", id))
+            })?;
+        let (element, component) = self.get_flattened_member_index(block.type_id, index)?;
+        let register = format!("c{}", block.register + element);
+        Ok(match component {
+            0 => register,
+            component => format!("{}.{}", register, ["y", "z", "w"][component as usize - 1]),
+        })
+    }
+
+    /// Returns how a storage buffer is declared in the generated HLSL. Structured buffers are
+    /// only used when `preserve_structured_buffers` is enabled, the buffer carries a DXC
+    /// structured buffer `UserTypeGoogle` decoration and its block holds a single runtime array.
+    pub fn get_storage_buffer_form(
+        &self,
+        resource: &spirv::Resource,
+    ) -> Result<StorageBufferForm, ErrorCode> {
+        let options = &self.compiler.target_data.options;
+        if !options.preserve_structured_buffers {
+            return Ok(StorageBufferForm::ByteAddressBuffer);
+        }
+
+        let user_type = self
+            .get_decoration_string(resource.id, spirv::Decoration::UserTypeGoogle)?
+            .to_lowercase();
+        let is_structured_user_type = [
+            "structuredbuffer",
+            "rwstructuredbuffer",
+            "appendstructuredbuffer",
+            "consumestructuredbuffer",
+        ]
+        .iter()
+        .any(|prefix| user_type.starts_with(prefix));
+        if !is_structured_user_type {
+            return Ok(StorageBufferForm::ByteAddressBuffer);
+        }
+
+        let has_single_runtime_array = match self.get_type(resource.base_type_id)? {
+            spirv::Type::Struct { member_types, .. } if member_types.len() == 1 => {
+                match self.get_type(member_types[0])? {
+                    spirv::Type::Struct { array, .. } => array.last() == Some(&0),
+                    _ => false,
+                }
+            }
+            _ => false,
+        };
+        Ok(if has_single_runtime_array {
+            StorageBufferForm::StructuredBuffer
+        } else {
+            StorageBufferForm::ByteAddressBuffer
+        })
+    }
 }
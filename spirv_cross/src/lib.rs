@@ -1,7 +1,10 @@
+// The web build turns exceptions into assertions, so there is no message to retrieve on failure.
 #[cfg(target_arch = "wasm32")]
 macro_rules! check {
     ($check:expr) => {{
-        $check
+        if br::ScInternalResult::Success != $check {
+            return Err(ErrorCode::Unhandled);
+        }
     }};
 }
 
@@ -41,6 +44,42 @@ macro_rules! check {
     }};
 }
 
+// Diagnostics are forwarded to the `log` crate when the `log` feature is enabled, and discarded
+// without evaluating their arguments otherwise.
+#[cfg(feature = "log")]
+macro_rules! log_warn {
+    ($($arg:tt)+) => {
+        log::warn!($($arg)+)
+    };
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! log_warn {
+    ($($arg:tt)+) => {
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    };
+}
+
+#[cfg(feature = "log")]
+macro_rules! log_debug {
+    ($($arg:tt)+) => {
+        log::debug!($($arg)+)
+    };
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! log_debug {
+    ($($arg:tt)+) => {
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    };
+}
+
+#[cfg(feature = "codegen")]
+mod codegen;
 mod compiler;
 
 #[cfg(feature = "glsl")]
@@ -49,6 +88,8 @@ pub mod glsl;
 pub mod hlsl;
 #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
 pub mod msl;
+#[cfg(feature = "reflect")]
+pub mod reflect;
 
 pub mod spirv;
 
@@ -76,6 +117,9 @@ mod bindings {
     #![allow(non_upper_case_globals)]
     #![allow(non_camel_case_types)]
     #![allow(non_snake_case)]
+    #[cfg(feature = "buildtime-bindgen")]
+    include!(concat!(env!("OUT_DIR"), "/bindings_native.rs"));
+    #[cfg(not(feature = "buildtime-bindgen"))]
     include!(concat!("bindings_native.rs"));
     pub use root::*;
 }
@@ -84,6 +128,12 @@ mod bindings {
 pub enum ErrorCode {
     Unhandled,
     CompilationError(String),
+    /// Writing the compiled output failed.
+    Io(String),
+    /// The compiler options are invalid or incompatible with each other.
+    InvalidOptions(String),
+    /// The input is not a valid SPIR-V module.
+    InvalidSpirv(String),
 }
 
 impl std::fmt::Display for ErrorCode {
@@ -1,8 +1,9 @@
 use crate::bindings as br;
 use crate::{compiler, spirv, ErrorCode};
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::ffi::{CStr, CString};
+use std::io;
 use std::marker::PhantomData;
 use std::ptr;
 use std::u8;
@@ -15,6 +16,7 @@ pub struct TargetData {
     vertex_attribute_overrides: Vec<br::spirv_cross::MSLShaderInput>,
     resource_binding_overrides: Vec<br::spirv_cross::MSLResourceBinding>,
     const_samplers: Vec<br::ScMslConstSamplerMapping>,
+    options: CompilerOptions,
 }
 
 impl spirv::Target for Target {
@@ -197,19 +199,144 @@ pub struct SamplerData {
     pub lod_clamp_max: LodBase16,
     pub max_anisotropy: i32,
     // Sampler YCbCr conversion parameters
+    /// The number of planes of the sampled image, from 1 to 3.
     pub planes: u32,
+    /// The chroma subsampling of the sampled image.
     pub resolution: FormatResolution,
+    /// The filter used to reconstruct subsampled chroma.
     pub chroma_filter: SamplerFilter,
     pub x_chroma_offset: ChromaLocation,
     pub y_chroma_offset: ChromaLocation,
+    /// The component swizzle applied before the model conversion.
     pub swizzle: [ComponentSwizzle; 4],
+    /// Enables Y'CbCr conversion. The other conversion parameters are ignored otherwise.
     pub ycbcr_conversion_enable: bool,
     pub ycbcr_model: SamplerYCbCrModelConversion,
     pub ycbcr_range: SamplerYCbCrRange,
+    /// The bits per component of the sampled image.
     pub bpc: u32,
 }
 
+impl Default for SamplerData {
+    fn default() -> Self {
+        SamplerData {
+            coord: SamplerCoord::Normalized,
+            min_filter: SamplerFilter::Nearest,
+            mag_filter: SamplerFilter::Nearest,
+            mip_filter: SamplerMipFilter::None,
+            s_address: SamplerAddress::ClampToEdge,
+            t_address: SamplerAddress::ClampToEdge,
+            r_address: SamplerAddress::ClampToEdge,
+            compare_func: SamplerCompareFunc::Always,
+            border_color: SamplerBorderColor::TransparentBlack,
+            lod_clamp_min: LodBase16::ZERO,
+            lod_clamp_max: LodBase16::MAX,
+            max_anisotropy: 0,
+            planes: 1,
+            resolution: FormatResolution::_444,
+            chroma_filter: SamplerFilter::Nearest,
+            x_chroma_offset: ChromaLocation::CositedEven,
+            y_chroma_offset: ChromaLocation::CositedEven,
+            swizzle: [ComponentSwizzle::Identity; 4],
+            ycbcr_conversion_enable: false,
+            ycbcr_model: SamplerYCbCrModelConversion::RgbIdentity,
+            ycbcr_range: SamplerYCbCrRange::ItuFull,
+            bpc: 8,
+        }
+    }
+}
+
+fn const_sampler_as_raw(data: &SamplerData) -> br::spirv_cross::MSLConstexprSampler {
+    use std::mem::transmute;
+    unsafe {
+        br::spirv_cross::MSLConstexprSampler {
+            coord: transmute(data.coord),
+            min_filter: transmute(data.min_filter),
+            mag_filter: transmute(data.mag_filter),
+            mip_filter: transmute(data.mip_filter),
+            s_address: transmute(data.s_address),
+            t_address: transmute(data.t_address),
+            r_address: transmute(data.r_address),
+            compare_func: transmute(data.compare_func),
+            border_color: transmute(data.border_color),
+            lod_clamp_min: data.lod_clamp_min.into(),
+            lod_clamp_max: data.lod_clamp_max.into(),
+            max_anisotropy: data.max_anisotropy,
+            compare_enable: data.compare_func != SamplerCompareFunc::Always,
+            lod_clamp_enable: data.lod_clamp_min != LodBase16::ZERO
+                || data.lod_clamp_max != LodBase16::MAX,
+            anisotropy_enable: data.max_anisotropy != 0,
+            bpc: data.bpc,
+            chroma_filter: transmute(data.chroma_filter),
+            planes: data.planes,
+            resolution: transmute(data.resolution),
+            swizzle: transmute(data.swizzle),
+            x_chroma_offset: transmute(data.x_chroma_offset),
+            y_chroma_offset: transmute(data.y_chroma_offset),
+            ycbcr_conversion_enable: data.ycbcr_conversion_enable,
+            ycbcr_model: transmute(data.ycbcr_model),
+            ycbcr_range: transmute(data.ycbcr_range),
+        }
+    }
+}
+
 /// A MSL sampler YCbCr model conversion.
+fn write_sampler_data(hasher: &mut spirv::StableHasher, data: &SamplerData) {
+    let SamplerData {
+        coord,
+        min_filter,
+        mag_filter,
+        mip_filter,
+        s_address,
+        t_address,
+        r_address,
+        compare_func,
+        border_color,
+        lod_clamp_min,
+        lod_clamp_max,
+        max_anisotropy,
+        planes,
+        resolution,
+        chroma_filter,
+        x_chroma_offset,
+        y_chroma_offset,
+        swizzle,
+        ycbcr_conversion_enable,
+        ycbcr_model,
+        ycbcr_range,
+        bpc,
+    } = data;
+    for &value in &[
+        *coord as u32,
+        *min_filter as u32,
+        *mag_filter as u32,
+        *mip_filter as u32,
+        *s_address as u32,
+        *t_address as u32,
+        *r_address as u32,
+        *compare_func as u32,
+        *border_color as u32,
+        u32::from(lod_clamp_min.0),
+        u32::from(lod_clamp_max.0),
+        *max_anisotropy as u32,
+        *planes,
+        *resolution as u32,
+        *chroma_filter as u32,
+        *x_chroma_offset as u32,
+        *y_chroma_offset as u32,
+        swizzle[0] as u32,
+        swizzle[1] as u32,
+        swizzle[2] as u32,
+        swizzle[3] as u32,
+        *ycbcr_conversion_enable as u32,
+        *ycbcr_model as u32,
+        *ycbcr_range as u32,
+        *bpc,
+    ] {
+        hasher.write_u32(value);
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum SamplerYCbCrModelConversion {
@@ -292,7 +419,7 @@ pub struct CompilerOptions {
     pub vertex: CompilerVertexOptions,
     /// The buffer index to use for swizzle.
     pub swizzle_buffer_index: u32,
-    // The buffer index to use for indirect params.
+    /// The buffer index to use for indirect params.
     pub indirect_params_buffer_index: u32,
     /// The buffer index to use for output.
     pub output_buffer_index: u32,
@@ -300,11 +427,15 @@ pub struct CompilerOptions {
     pub patch_output_buffer_index: u32,
     /// The buffer index to use for tessellation factor.
     pub tessellation_factor_buffer_index: u32,
-    /// The buffer index to use for buffer size.
+    /// The buffer index to use for buffer size. The buffer holds one `uint` per storage buffer
+    /// whose runtime array length is read, indexed by the buffer's MSL buffer index.
     pub buffer_size_buffer_index: u32,
     /// Whether the built-in point size should be enabled.
     pub enable_point_size_builtin: bool,
-    /// Whether rasterization should be enabled.
+    /// Whether rasterization should be enabled. When `false`, vertex functions return nothing, as
+    /// for transform-only passes writing to buffers, even if they write `gl_Position`. When
+    /// `true`, rasterization is still disabled for vertex shaders which write to buffers or
+    /// images, which `is_rasterization_enabled` reports after compilation.
     pub enable_rasterization: bool,
     /// Whether to capture output to buffer.
     pub capture_output_to_buffer: bool,
@@ -320,14 +451,66 @@ pub struct CompilerOptions {
     pub resource_binding_overrides: BTreeMap<ResourceBindingLocation, ResourceBinding>,
     /// MSL vertex attribute overrides.
     pub vertex_attribute_overrides: BTreeMap<VertexAttributeLocation, VertexAttribute>,
-    /// MSL const sampler mappings.
+    /// MSL const sampler mappings by descriptor set and binding, which match the immutable
+    /// samplers of a pipeline layout without reflecting the module.
     pub const_samplers: BTreeMap<SamplerLocation, SamplerData>,
-    /// Whether to force native arrays (useful to workaround issues on some hardware).
+    /// MSL const sampler mappings by the ID of the sampler or combined image sampler variable,
+    /// for samplers without a descriptor binding. These take precedence over `const_samplers`.
+    pub const_samplers_by_id: BTreeMap<u32, SamplerData>,
+    /// Whether to emit plain C-style arrays instead of wrapping arrays in the `spvUnsafeArray`
+    /// template, which some tools cannot parse and some drivers miscompile. Native arrays
+    /// cannot be assigned by value, so SPIRV-Cross copies them with helper functions instead.
     pub force_native_arrays: bool,
     /// Whether to force all uninitialized variables to be initialized to zero.
     pub force_zero_initialized_variables: bool,
     /// Whether to force always emit resources which are part of argument buffers
     pub force_active_argument_buffer_resources: bool,
+    /// Whether to emit `#line` directives referencing the original source, when the module
+    /// carries `OpLine` debug information.
+    pub emit_line_directives: bool,
+    /// Whether to store every expression in a temporary instead of inlining it, which works
+    /// around drivers that miscompile long expressions.
+    pub force_temporary: bool,
+    /// Whether to flatten multidimensional arrays into one-dimensional arrays.
+    pub flatten_multidimensional_arrays: bool,
+    /// Whether to emit texel buffers as native `texture_buffer` objects (requires MSL 2.1).
+    /// Otherwise they are emulated with 2D textures.
+    pub texture_buffer_native: bool,
+    /// The width of the 2D textures used to emulate texel buffers.
+    pub texel_buffer_texture_width: u32,
+    /// Whether to emulate cube arrays with 2D array textures, for iOS GPU families that lack
+    /// `texturecube_array`.
+    pub emulate_cube_array: bool,
+    /// The fixed device index substituted for the `DeviceIndex` built-in.
+    pub device_index: u32,
+    /// Whether to offset the workgroup ID by the dispatch base, matching `vkCmdDispatchBase`.
+    /// Before MSL 1.2 the base is read from the indirect params buffer.
+    pub dispatch_base: bool,
+    /// Whether to emulate subgroup operations with a subgroup size of 1, for GPUs without
+    /// SIMD-group or quad-group functions.
+    pub emulate_subgroups: bool,
+    /// The fixed value of the `SubgroupSize` built-in, or 0 to read the SIMD-group size of the
+    /// device. Invocations beyond a smaller SIMD-group size appear inactive.
+    pub fixed_subgroup_size: u32,
+    /// Whether to use SIMD-group functions for subgroup operations on iOS instead of quad-group
+    /// functions, which are the only ones GPUs before the A13 have. Quad-group functions limit
+    /// the subgroup size to 4.
+    pub ios_use_simdgroup_functions: bool,
+    /// Whether to leave out the declarations of resources and stage inputs and outputs which the
+    /// entry point does not statically use, instead of emitting every variable of the module.
+    pub remove_unused_variables: bool,
+    /// Whether to remove the names of all IDs and struct members before each compile, as
+    /// `Ast::strip_names` does, so that the output only uses anonymized identifiers.
+    pub strip_names: bool,
+    /// Whether to keep the names the backend would rename for clashing with a keyword of the
+    /// target language, in which case the output may not compile. Names that clash with another
+    /// identifier are still told apart. This cannot be combined with `strip_names`.
+    pub preserve_names: bool,
+    /// Overrides for the prefixes of the identifiers SPIRV-Cross generates, or `None` to keep
+    /// them unchanged.
+    pub identifier_naming: Option<spirv::IdentifierNaming>,
+    /// Whether to fail to compile when there are lints.
+    pub unsupported_features: spirv::UnsupportedFeaturePolicy,
     /// The name and execution model of the entry point to use. If no entry
     /// point is specified, then the first entry point found will be used.
     pub entry_point: Option<(String, spirv::ExecutionModel)>,
@@ -355,37 +538,336 @@ impl Default for CompilerOptions {
             resource_binding_overrides: Default::default(),
             vertex_attribute_overrides: Default::default(),
             const_samplers: Default::default(),
+            const_samplers_by_id: Default::default(),
             force_native_arrays: false,
             force_zero_initialized_variables: false,
             force_active_argument_buffer_resources: false,
+            emit_line_directives: false,
+            force_temporary: false,
+            flatten_multidimensional_arrays: false,
+            texture_buffer_native: false,
+            texel_buffer_texture_width: 4096,
+            emulate_cube_array: false,
+            device_index: 0,
+            dispatch_base: false,
+            emulate_subgroups: false,
+            fixed_subgroup_size: 0,
+            ios_use_simdgroup_functions: false,
+            remove_unused_variables: false,
+            strip_names: false,
+            preserve_names: false,
+            identifier_naming: None,
+            unsupported_features: spirv::UnsupportedFeaturePolicy::Relaxed,
             entry_point: None,
         }
     }
 }
 
+impl CompilerOptions {
+    /// Creates a builder starting from the default options.
+    pub fn builder() -> CompilerOptionsBuilder {
+        CompilerOptionsBuilder {
+            options: CompilerOptions::default(),
+        }
+    }
+
+    fn validate(&self) -> Result<(), ErrorCode> {
+        spirv::validate_entry_point(&self.entry_point)?;
+        spirv::validate_names(self.strip_names, self.preserve_names)?;
+        if self.enable_argument_buffers && self.version.as_raw() < Version::V2_0.as_raw() {
+            return Err(ErrorCode::InvalidOptions(String::from(
+                "argument buffers require MSL 2.0 or later",
+            )));
+        }
+        if self.texture_buffer_native && self.version.as_raw() < Version::V2_1.as_raw() {
+            return Err(ErrorCode::InvalidOptions(String::from(
+                "native texture buffers require MSL 2.1 or later",
+            )));
+        }
+        if !self.texture_buffer_native && self.texel_buffer_texture_width == 0 {
+            return Err(ErrorCode::InvalidOptions(String::from(
+                "emulated texel buffers require a non-zero texel_buffer_texture_width",
+            )));
+        }
+        if self.emulate_subgroups && self.fixed_subgroup_size > 1 {
+            return Err(ErrorCode::InvalidOptions(String::from(
+                "emulated subgroups have a subgroup size of 1",
+            )));
+        }
+        for data in self
+            .const_samplers
+            .values()
+            .chain(self.const_samplers_by_id.values())
+        {
+            if data.ycbcr_conversion_enable && (data.planes < 1 || data.planes > 3) {
+                return Err(ErrorCode::InvalidOptions(format!(
+                    "Y'CbCr conversion requires 1 to 3 planes, got {}",
+                    data.planes
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Builder for `CompilerOptions`. Each setter overrides the option of the same name.
+#[derive(Debug, Clone)]
+pub struct CompilerOptionsBuilder {
+    options: CompilerOptions,
+}
+
+impl CompilerOptionsBuilder {
+    pub fn platform(mut self, platform: Platform) -> Self {
+        self.options.platform = platform;
+        self
+    }
+
+    pub fn version(mut self, version: Version) -> Self {
+        self.options.version = version;
+        self
+    }
+
+    pub fn vertex(mut self, vertex: CompilerVertexOptions) -> Self {
+        self.options.vertex = vertex;
+        self
+    }
+
+    pub fn swizzle_buffer_index(mut self, index: u32) -> Self {
+        self.options.swizzle_buffer_index = index;
+        self
+    }
+
+    pub fn indirect_params_buffer_index(mut self, index: u32) -> Self {
+        self.options.indirect_params_buffer_index = index;
+        self
+    }
+
+    pub fn output_buffer_index(mut self, index: u32) -> Self {
+        self.options.output_buffer_index = index;
+        self
+    }
+
+    pub fn patch_output_buffer_index(mut self, index: u32) -> Self {
+        self.options.patch_output_buffer_index = index;
+        self
+    }
+
+    pub fn tessellation_factor_buffer_index(mut self, index: u32) -> Self {
+        self.options.tessellation_factor_buffer_index = index;
+        self
+    }
+
+    pub fn buffer_size_buffer_index(mut self, index: u32) -> Self {
+        self.options.buffer_size_buffer_index = index;
+        self
+    }
+
+    pub fn enable_point_size_builtin(mut self, enable: bool) -> Self {
+        self.options.enable_point_size_builtin = enable;
+        self
+    }
+
+    pub fn enable_rasterization(mut self, enable: bool) -> Self {
+        self.options.enable_rasterization = enable;
+        self
+    }
+
+    pub fn capture_output_to_buffer(mut self, capture: bool) -> Self {
+        self.options.capture_output_to_buffer = capture;
+        self
+    }
+
+    pub fn swizzle_texture_samples(mut self, swizzle: bool) -> Self {
+        self.options.swizzle_texture_samples = swizzle;
+        self
+    }
+
+    pub fn tessellation_domain_origin_lower_left(mut self, lower_left: bool) -> Self {
+        self.options.tessellation_domain_origin_lower_left = lower_left;
+        self
+    }
+
+    pub fn argument_buffers(mut self, enable: bool) -> Self {
+        self.options.enable_argument_buffers = enable;
+        self
+    }
+
+    pub fn pad_fragment_output_components(mut self, pad: bool) -> Self {
+        self.options.pad_fragment_output_components = pad;
+        self
+    }
+
+    pub fn resource_binding_override(
+        mut self,
+        location: ResourceBindingLocation,
+        binding: ResourceBinding,
+    ) -> Self {
+        self.options
+            .resource_binding_overrides
+            .insert(location, binding);
+        self
+    }
+
+    pub fn vertex_attribute_override(
+        mut self,
+        location: VertexAttributeLocation,
+        attribute: VertexAttribute,
+    ) -> Self {
+        self.options
+            .vertex_attribute_overrides
+            .insert(location, attribute);
+        self
+    }
+
+    pub fn const_sampler(mut self, location: SamplerLocation, sampler: SamplerData) -> Self {
+        self.options.const_samplers.insert(location, sampler);
+        self
+    }
+
+    pub fn const_sampler_by_id(mut self, id: u32, sampler: SamplerData) -> Self {
+        self.options.const_samplers_by_id.insert(id, sampler);
+        self
+    }
+
+    pub fn force_native_arrays(mut self, force: bool) -> Self {
+        self.options.force_native_arrays = force;
+        self
+    }
+
+    pub fn force_zero_initialized_variables(mut self, force: bool) -> Self {
+        self.options.force_zero_initialized_variables = force;
+        self
+    }
+
+    pub fn force_active_argument_buffer_resources(mut self, force: bool) -> Self {
+        self.options.force_active_argument_buffer_resources = force;
+        self
+    }
+
+    pub fn emit_line_directives(mut self, emit_line_directives: bool) -> Self {
+        self.options.emit_line_directives = emit_line_directives;
+        self
+    }
+
+    pub fn force_temporary(mut self, force_temporary: bool) -> Self {
+        self.options.force_temporary = force_temporary;
+        self
+    }
+
+    pub fn flatten_multidimensional_arrays(mut self, flatten_multidimensional_arrays: bool) -> Self {
+        self.options.flatten_multidimensional_arrays = flatten_multidimensional_arrays;
+        self
+    }
+
+    pub fn texture_buffer_native(mut self, native: bool) -> Self {
+        self.options.texture_buffer_native = native;
+        self
+    }
+
+    pub fn texel_buffer_texture_width(mut self, width: u32) -> Self {
+        self.options.texel_buffer_texture_width = width;
+        self
+    }
+
+    pub fn emulate_cube_array(mut self, emulate: bool) -> Self {
+        self.options.emulate_cube_array = emulate;
+        self
+    }
+
+    pub fn device_index(mut self, index: u32) -> Self {
+        self.options.device_index = index;
+        self
+    }
+
+    pub fn dispatch_base(mut self, enable: bool) -> Self {
+        self.options.dispatch_base = enable;
+        self
+    }
+
+    pub fn emulate_subgroups(mut self, emulate: bool) -> Self {
+        self.options.emulate_subgroups = emulate;
+        self
+    }
+
+    pub fn fixed_subgroup_size(mut self, size: u32) -> Self {
+        self.options.fixed_subgroup_size = size;
+        self
+    }
+
+    pub fn ios_use_simdgroup_functions(mut self, enable: bool) -> Self {
+        self.options.ios_use_simdgroup_functions = enable;
+        self
+    }
+
+    pub fn remove_unused_variables(mut self, remove_unused_variables: bool) -> Self {
+        self.options.remove_unused_variables = remove_unused_variables;
+        self
+    }
+
+    pub fn strip_names(mut self, strip_names: bool) -> Self {
+        self.options.strip_names = strip_names;
+        self
+    }
+
+    pub fn preserve_names(mut self, preserve_names: bool) -> Self {
+        self.options.preserve_names = preserve_names;
+        self
+    }
+
+    pub fn identifier_naming(mut self, identifier_naming: spirv::IdentifierNaming) -> Self {
+        self.options.identifier_naming = Some(identifier_naming);
+        self
+    }
+
+    pub fn unsupported_features(mut self, policy: spirv::UnsupportedFeaturePolicy) -> Self {
+        self.options.unsupported_features = policy;
+        self
+    }
+
+    pub fn entry_point(
+        mut self,
+        name: impl Into<String>,
+        execution_model: spirv::ExecutionModel,
+    ) -> Self {
+        self.options.entry_point = Some((name.into(), execution_model));
+        self
+    }
+
+    /// Validates and returns the options.
+    pub fn build(self) -> Result<CompilerOptions, ErrorCode> {
+        self.options.validate()?;
+        Ok(self.options)
+    }
+}
+
 impl<'a> spirv::Parse<Target> for spirv::Ast<Target> {
     fn parse(module: &spirv::Module) -> Result<Self, ErrorCode> {
+        let sc_compiler = spirv::parse_words(&module.words, |words| {
+            let mut sc_compiler = ptr::null_mut();
+            unsafe {
+                check!(br::sc_internal_compiler_msl_new(
+                    &mut sc_compiler,
+                    words.as_ptr(),
+                    words.len(),
+                ));
+            }
+            Ok(sc_compiler)
+        })?;
+
+        Ok(Self::from_sc_compiler(sc_compiler))
+    }
+
+    fn from_parsed(module: &spirv::ParsedModule) -> Result<Self, ErrorCode> {
         let mut sc_compiler = ptr::null_mut();
         unsafe {
-            check!(br::sc_internal_compiler_msl_new(
+            check!(br::sc_internal_compiler_msl_new_from_ir(
                 &mut sc_compiler,
-                module.words.as_ptr(),
-                module.words.len(),
+                module.ir.sc_parsed_ir,
             ));
         }
 
-        Ok(spirv::Ast {
-            compiler: compiler::Compiler {
-                sc_compiler,
-                target_data: TargetData {
-                    resource_binding_overrides: Vec::new(),
-                    vertex_attribute_overrides: Vec::new(),
-                    const_samplers: Vec::new(),
-                },
-                has_been_compiled: false,
-            },
-            target_type: PhantomData,
-        })
+        Ok(Self::from_sc_compiler(sc_compiler))
     }
 }
 
@@ -394,6 +876,12 @@ impl spirv::Compile<Target> for spirv::Ast<Target> {
 
     /// Set MSL compiler specific compilation settings.
     fn set_compiler_options(&mut self, options: &CompilerOptions) -> Result<(), ErrorCode> {
+        options.validate()?;
+        spirv::validate_entry_point_in_module(
+            &options.entry_point,
+            &self.compiler.get_entry_points()?,
+        )?;
+
         if let Some((name, model)) = &options.entry_point {
             let name_raw = CString::new(name.as_str()).map_err(|_| ErrorCode::Unhandled)?;
             let model = model.as_raw();
@@ -426,6 +914,17 @@ impl spirv::Compile<Target> for spirv::Ast<Target> {
             force_native_arrays: options.force_native_arrays,
             force_zero_initialized_variables: options.force_zero_initialized_variables,
             force_active_argument_buffer_resources: options.force_active_argument_buffer_resources,
+            emit_line_directives: options.emit_line_directives,
+            force_temporary: options.force_temporary,
+            flatten_multidimensional_arrays: options.flatten_multidimensional_arrays,
+            texture_buffer_native: options.texture_buffer_native,
+            texel_buffer_texture_width: options.texel_buffer_texture_width,
+            emulate_cube_array: options.emulate_cube_array,
+            device_index: options.device_index,
+            dispatch_base: options.dispatch_base,
+            emulate_subgroups: options.emulate_subgroups,
+            fixed_subgroup_size: options.fixed_subgroup_size,
+            ios_use_simdgroup_functions: options.ios_use_simdgroup_functions,
         };
         unsafe {
             check!(br::sc_internal_compiler_msl_set_options(
@@ -462,56 +961,316 @@ impl spirv::Compile<Target> for spirv::Ast<Target> {
         );
 
         self.compiler.target_data.const_samplers.clear();
-        self.compiler
-            .target_data
-            .const_samplers
-            .extend(options.const_samplers.iter().map(|(loc, data)| unsafe {
-                use std::mem::transmute;
-                br::ScMslConstSamplerMapping {
+        self.compiler.target_data.const_samplers.extend(
+            options
+                .const_samplers
+                .iter()
+                .map(|(loc, data)| br::ScMslConstSamplerMapping {
+                    id: 0,
                     desc_set: loc.desc_set,
                     binding: loc.binding,
-                    sampler: br::spirv_cross::MSLConstexprSampler {
-                        coord: transmute(data.coord),
-                        min_filter: transmute(data.min_filter),
-                        mag_filter: transmute(data.mag_filter),
-                        mip_filter: transmute(data.mip_filter),
-                        s_address: transmute(data.s_address),
-                        t_address: transmute(data.t_address),
-                        r_address: transmute(data.r_address),
-                        compare_func: transmute(data.compare_func),
-                        border_color: transmute(data.border_color),
-                        lod_clamp_min: data.lod_clamp_min.into(),
-                        lod_clamp_max: data.lod_clamp_max.into(),
-                        max_anisotropy: data.max_anisotropy,
-                        compare_enable: data.compare_func != SamplerCompareFunc::Always,
-                        lod_clamp_enable: data.lod_clamp_min != LodBase16::ZERO
-                            || data.lod_clamp_max != LodBase16::MAX,
-                        anisotropy_enable: data.max_anisotropy != 0,
-                        bpc: data.bpc,
-                        chroma_filter: transmute(data.chroma_filter),
-                        planes: data.planes,
-                        resolution: transmute(data.resolution),
-                        swizzle: transmute(data.swizzle),
-                        x_chroma_offset: transmute(data.x_chroma_offset),
-                        y_chroma_offset: transmute(data.y_chroma_offset),
-                        ycbcr_conversion_enable: data.ycbcr_conversion_enable,
-                        ycbcr_model: transmute(data.ycbcr_model),
-                        ycbcr_range: transmute(data.ycbcr_range),
-                    },
-                }
-            }));
+                    sampler: const_sampler_as_raw(data),
+                })
+                .chain(options.const_samplers_by_id.iter().map(|(&id, data)| {
+                    br::ScMslConstSamplerMapping {
+                        id,
+                        desc_set: 0,
+                        binding: 0,
+                        sampler: const_sampler_as_raw(data),
+                    }
+                })),
+        );
 
+        self.compiler.remove_unused_variables = options.remove_unused_variables;
+        self.compiler.strip_names = options.strip_names;
+        self.compiler.preserve_names = options.preserve_names;
+        self.compiler.identifier_naming = options.identifier_naming.clone();
+        self.compiler.unsupported_features = options.unsupported_features;
+        self.compiler.target_data.options = options.clone();
         Ok(())
     }
 
+    fn get_compiler_options(&self) -> CompilerOptions {
+        self.compiler.target_data.options.clone()
+    }
+
+    fn set_entry_point(
+        &mut self,
+        name: &str,
+        execution_model: spirv::ExecutionModel,
+    ) -> Result<(), ErrorCode> {
+        let mut options = self.compiler.target_data.options.clone();
+        options.entry_point = Some((name.into(), execution_model));
+        spirv::Compile::<Target>::set_compiler_options(self, &options)
+    }
+
+    fn hash_compiler_options(&self) -> u64 {
+        let CompilerOptions {
+            platform,
+            version,
+            vertex,
+            swizzle_buffer_index,
+            indirect_params_buffer_index,
+            output_buffer_index,
+            patch_output_buffer_index,
+            tessellation_factor_buffer_index,
+            buffer_size_buffer_index,
+            enable_point_size_builtin,
+            enable_rasterization,
+            capture_output_to_buffer,
+            swizzle_texture_samples,
+            tessellation_domain_origin_lower_left,
+            enable_argument_buffers,
+            pad_fragment_output_components,
+            resource_binding_overrides,
+            vertex_attribute_overrides,
+            const_samplers,
+            const_samplers_by_id,
+            force_native_arrays,
+            force_zero_initialized_variables,
+            force_active_argument_buffer_resources,
+            emit_line_directives,
+            force_temporary,
+            flatten_multidimensional_arrays,
+            texture_buffer_native,
+            texel_buffer_texture_width,
+            emulate_cube_array,
+            device_index,
+            dispatch_base,
+            emulate_subgroups,
+            fixed_subgroup_size,
+            ios_use_simdgroup_functions,
+            remove_unused_variables,
+            strip_names,
+            preserve_names,
+            identifier_naming,
+            unsupported_features,
+            entry_point,
+        } = &self.compiler.target_data.options;
+
+        let mut hasher = spirv::StableHasher::new();
+        hasher.write_str("msl");
+        hasher.write_u32(*platform as u32);
+        hasher.write_u32(version.as_raw());
+        for &index in &[
+            *swizzle_buffer_index,
+            *indirect_params_buffer_index,
+            *output_buffer_index,
+            *patch_output_buffer_index,
+            *tessellation_factor_buffer_index,
+            *buffer_size_buffer_index,
+            *texel_buffer_texture_width,
+            *device_index,
+            *fixed_subgroup_size,
+        ] {
+            hasher.write_u32(index);
+        }
+        for &flag in &[
+            vertex.invert_y,
+            vertex.transform_clip_space,
+            *enable_point_size_builtin,
+            *enable_rasterization,
+            *capture_output_to_buffer,
+            *swizzle_texture_samples,
+            *tessellation_domain_origin_lower_left,
+            *enable_argument_buffers,
+            *pad_fragment_output_components,
+            *force_native_arrays,
+            *force_zero_initialized_variables,
+            *force_active_argument_buffer_resources,
+            *emit_line_directives,
+            *force_temporary,
+            *flatten_multidimensional_arrays,
+            *texture_buffer_native,
+            *emulate_cube_array,
+            *dispatch_base,
+            *emulate_subgroups,
+            *ios_use_simdgroup_functions,
+            *remove_unused_variables,
+        ] {
+            hasher.write_bool(flag);
+        }
+
+        hasher.write_u64(resource_binding_overrides.len() as u64);
+        for (location, binding) in resource_binding_overrides {
+            hasher.write_u32(location.stage as u32);
+            hasher.write_u32(location.desc_set);
+            hasher.write_u32(location.binding);
+            hasher.write_u32(binding.buffer_id);
+            hasher.write_u32(binding.texture_id);
+            hasher.write_u32(binding.sampler_id);
+            hasher.write_u32(binding.count);
+        }
+        hasher.write_u64(vertex_attribute_overrides.len() as u64);
+        for (location, attribute) in vertex_attribute_overrides {
+            hasher.write_u32(location.0);
+            hasher.write_u32(attribute.buffer_id);
+            hasher.write_u32(attribute.format.clone() as u32);
+            hasher.write_option(&attribute.built_in, |hasher, built_in| {
+                hasher.write_u32(spirv::built_in_as_raw(Some(*built_in)) as u32)
+            });
+            hasher.write_u32(attribute.vecsize);
+        }
+        hasher.write_u64(const_samplers.len() as u64);
+        for (location, data) in const_samplers {
+            hasher.write_u32(location.desc_set);
+            hasher.write_u32(location.binding);
+            write_sampler_data(&mut hasher, data);
+        }
+        hasher.write_u64(const_samplers_by_id.len() as u64);
+        for (id, data) in const_samplers_by_id {
+            hasher.write_u32(*id);
+            write_sampler_data(&mut hasher, data);
+        }
+
+        hasher.write_common_options(
+            *strip_names,
+            *preserve_names,
+            identifier_naming,
+            *unsupported_features,
+            entry_point,
+        );
+        hasher.finish()
+    }
+
+    fn get_lints(&self) -> Result<Vec<String>, ErrorCode> {
+        let options = &self.compiler.target_data.options;
+        let mut lints =
+            spirv::entry_point_lints(&options.entry_point, &self.compiler.get_entry_points()?);
+
+        for location in options.resource_binding_overrides.keys() {
+            let mut is_used = false;
+            unsafe {
+                check!(br::sc_internal_compiler_msl_is_resource_binding_used(
+                    self.compiler.sc_compiler,
+                    location.stage.as_raw(),
+                    location.desc_set,
+                    location.binding,
+                    &mut is_used,
+                ));
+            }
+            if !is_used {
+                lints.push(format!(
+                    "resource binding override for {:?} set {} binding {} is not used",
+                    location.stage, location.desc_set, location.binding
+                ));
+            }
+        }
+
+        for location in options.vertex_attribute_overrides.keys() {
+            let mut is_used = false;
+            unsafe {
+                check!(br::sc_internal_compiler_msl_is_shader_input_used(
+                    self.compiler.sc_compiler,
+                    location.0,
+                    &mut is_used,
+                ));
+            }
+            if !is_used {
+                lints.push(format!(
+                    "vertex attribute override for location {} is not used",
+                    location.0
+                ));
+            }
+        }
+
+        if options.texture_buffer_native
+            && options.texel_buffer_texture_width
+                != CompilerOptions::default().texel_buffer_texture_width
+        {
+            lints.push(String::from(
+                "texel_buffer_texture_width is ignored since texture_buffer_native is set",
+            ));
+        }
+
+        Ok(lints)
+    }
+
     /// Generate MSL shader from the AST.
     fn compile(&mut self) -> Result<String, ErrorCode> {
-        self.compile_internal()
+        unsafe {
+            let shader_ptr = self.compile_internal()?;
+            let shader = match CStr::from_ptr(shader_ptr).to_str() {
+                Ok(v) => v.to_owned(),
+                Err(_) => return Err(ErrorCode::Unhandled),
+            };
+            check!(br::sc_internal_free_pointer(
+                shader_ptr as *mut std::os::raw::c_void
+            ));
+            Ok(shader)
+        }
+    }
+
+    /// Generate MSL shader from the AST into `writer`.
+    fn compile_into<W: io::Write>(&mut self, writer: W) -> Result<(), ErrorCode> {
+        unsafe {
+            let shader_ptr = self.compile_internal()?;
+            compiler::write_shader(shader_ptr, writer)
+        }
     }
 }
 
 impl spirv::Ast<Target> {
-    fn compile_internal(&self) -> Result<String, ErrorCode> {
+    fn from_sc_compiler(sc_compiler: *mut br::ScInternalCompilerBase) -> Self {
+        spirv::Ast {
+            compiler: compiler::Compiler {
+                sc_compiler,
+                target_data: TargetData {
+                    resource_binding_overrides: Vec::new(),
+                    vertex_attribute_overrides: Vec::new(),
+                    const_samplers: Vec::new(),
+                    options: CompilerOptions::default(),
+                },
+                remove_unused_variables: false,
+                keep_unused_resources: false,
+                strip_names: false,
+                preserve_names: false,
+                identifier_naming: None,
+                unsupported_features: spirv::UnsupportedFeaturePolicy::Relaxed,
+                variable_type_remap: None,
+            },
+            target_type: PhantomData,
+            state: PhantomData,
+        }
+    }
+
+    /// Copies the module, with its names and decorations, the compiler options and the variable
+    /// type remap callback into a new `Ast`, which can be modified and compiled without
+    /// affecting this one.
+    fn duplicate(&self) -> Result<Self, ErrorCode> {
+        let mut sc_compiler = ptr::null_mut();
+        unsafe {
+            check!(br::sc_internal_compiler_msl_new_from_compiler(
+                &mut sc_compiler,
+                self.compiler.sc_compiler,
+            ));
+        }
+
+        let mut ast = Self::from_sc_compiler(sc_compiler);
+        spirv::Compile::<Target>::set_compiler_options(
+            &mut ast,
+            &self.compiler.target_data.options,
+        )?;
+        if let Some(remap) = &self.compiler.variable_type_remap {
+            ast.compiler.set_variable_type_remap(remap.clone())?;
+        }
+        Ok(ast)
+    }
+
+    /// Takes ownership of a SPIRV-Cross compiler previously released with `Ast::into_raw`. The
+    /// returned `Ast` uses the default compiler options until new ones are set.
+    ///
+    /// # Safety
+    ///
+    /// `sc_compiler` must come from `into_raw` on an `Ast` of this target, and must not be
+    /// used through another `Ast` afterwards.
+    pub unsafe fn from_raw(sc_compiler: *mut br::ScInternalCompilerBase) -> Self {
+        Self::from_sc_compiler(sc_compiler)
+    }
+
+    /// Compiles the AST, returning a pointer to the shader source which must be freed by the
+    /// caller.
+    fn compile_internal(&self) -> Result<*const std::os::raw::c_char, ErrorCode> {
         let vat_overrides = &self.compiler.target_data.vertex_attribute_overrides;
         let res_overrides = &self.compiler.target_data.resource_binding_overrides;
         let const_samplers = &self.compiler.target_data.const_samplers;
@@ -527,17 +1286,185 @@ impl spirv::Ast<Target> {
                 const_samplers.as_ptr(),
                 const_samplers.len(),
             ));
-            let shader = match CStr::from_ptr(shader_ptr).to_str() {
-                Ok(v) => v.to_owned(),
-                Err(_) => return Err(ErrorCode::Unhandled),
-            };
-            check!(br::sc_internal_free_pointer(
-                shader_ptr as *mut std::os::raw::c_void
-            ));
-            Ok(shader)
+            Ok(shader_ptr)
+        }
+    }
+
+    /// Compiles the abstract syntax tree and collects what the backend decided along the way.
+    pub fn compile_output(&mut self) -> Result<CompileOutput, ErrorCode> {
+        let shader = self.compile_with_lints()?;
+        let resources = self.get_shader_resources()?;
+        let mut automatic_bindings = Vec::new();
+        for resource in resources
+            .uniform_buffers
+            .iter()
+            .chain(&resources.storage_buffers)
+            .chain(&resources.storage_images)
+            .chain(&resources.sampled_images)
+            .chain(&resources.separate_images)
+            .chain(&resources.separate_samplers)
+        {
+            let (mut index, mut secondary_index) = (u32::MAX, u32::MAX);
+            unsafe {
+                check!(br::sc_internal_compiler_msl_get_automatic_resource_binding(
+                    self.compiler.sc_compiler,
+                    resource.id,
+                    &mut index,
+                    &mut secondary_index,
+                ));
+            }
+            if index != u32::MAX {
+                automatic_bindings.push(AutomaticResourceBinding {
+                    id: resource.id,
+                    index,
+                    secondary_index: Some(secondary_index).filter(|&index| index != u32::MAX),
+                });
+            }
+        }
+        let (entry_point, execution_model) = self.get_current_entry_point()?;
+        let threadgroup_memory_length = match execution_model {
+            spirv::ExecutionModel::GlCompute | spirv::ExecutionModel::Kernel => {
+                let size = self.get_workgroup_shared_memory_size(&entry_point, execution_model)?;
+                Some((size + 15) / 16 * 16)
+            }
+            _ => None,
+        };
+        Ok(CompileOutput {
+            shader,
+            requirements: self.feature_requirements()?,
+            automatic_bindings,
+            threadgroup_memory_length,
+        })
+    }
+}
+
+/// The index the MSL backend assigned to a resource, as returned in `CompileOutput`.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct AutomaticResourceBinding {
+    pub id: u32,
+    /// The `[[buffer(n)]]`, `[[texture(n)]]` or `[[sampler(n)]]` index of the resource.
+    pub index: u32,
+    /// The `[[sampler(n)]]` index of the sampler of a combined image sampler.
+    pub secondary_index: Option<u32>,
+}
+
+/// A compiled MSL shader, as returned by `compile_output`.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct CompileOutput {
+    pub shader: spirv::CompiledShader,
+    pub requirements: FeatureRequirements,
+    /// The indices the backend assigned by itself, to resources without a
+    /// `resource_binding_override`.
+    pub automatic_bindings: Vec<AutomaticResourceBinding>,
+    /// The bytes of threadgroup memory a compute kernel needs for its `Workgroup` variables,
+    /// rounded up to the 16 byte granularity of Metal, or `None` for other stages. The kernel
+    /// declares these variables itself, so this is the length to check against
+    /// `maxTotalThreadgroupMemoryLength` and to reserve alongside any memory set with
+    /// `setThreadgroupMemoryLength`.
+    pub threadgroup_memory_length: Option<u32>,
+}
+
+/// A feature of the generated source which only some Metal GPU families support, to check
+/// against the device before creating the pipeline.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[non_exhaustive]
+pub enum GpuFeature {
+    /// `texturecube_array` textures, which `emulate_cube_array` avoids.
+    CubeTextureArrays,
+    /// Native `texture_buffer` textures, from `texture_buffer_native`.
+    TextureBuffers,
+    /// Argument buffers, from `argument_buffers`.
+    ArgumentBuffers,
+    /// SIMD-group functions, used for subgroup operations other than quad operations.
+    SimdgroupFunctions,
+    /// Quad-group functions, used for quad subgroup operations.
+    QuadgroupFunctions,
+    /// Framebuffer fetch, used for subpass inputs on iOS.
+    FramebufferFetch,
+}
+
+/// What the source generated by the MSL backend requires of the device, as returned by
+/// `get_feature_requirements`.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct FeatureRequirements {
+    /// The Metal Shading Language version the source is written for.
+    pub version: Version,
+    pub platform: Platform,
+    /// The features used by the source which depend on the GPU family, in a fixed order.
+    pub gpu_features: Vec<GpuFeature>,
+}
+
+impl<TState> spirv::Ast<Target, TState> {
+    fn feature_requirements(&self) -> Result<FeatureRequirements, ErrorCode> {
+        let options = &self.compiler.target_data.options;
+        let resources = self.get_shader_resources()?;
+        let mut cube_arrays = false;
+        let mut texel_buffers = false;
+        for resource in resources
+            .sampled_images
+            .iter()
+            .chain(&resources.separate_images)
+            .chain(&resources.storage_images)
+        {
+            let ty = self.get_type(resource.base_type_id)?;
+            if let spirv::Type::Image { image, .. } | spirv::Type::SampledImage { image, .. } = ty {
+                match image.dim {
+                    spirv::Dim::DimCube => cube_arrays |= image.arrayed,
+                    spirv::Dim::DimBuffer => texel_buffers = true,
+                    _ => (),
+                }
+            }
         }
+
+        let mut gpu_features = Vec::new();
+        if cube_arrays && !options.emulate_cube_array {
+            gpu_features.push(GpuFeature::CubeTextureArrays);
+        }
+        if texel_buffers && options.texture_buffer_native {
+            gpu_features.push(GpuFeature::TextureBuffers);
+        }
+        if options.enable_argument_buffers {
+            gpu_features.push(GpuFeature::ArgumentBuffers);
+        }
+        let subgroups = self.get_subgroup_features()?;
+        let subgroup_operations = subgroups.basic
+            || subgroups.vote
+            || subgroups.arithmetic
+            || subgroups.ballot
+            || subgroups.shuffle
+            || subgroups.shuffle_relative
+            || subgroups.clustered;
+        // On iOS, subgroup operations are mapped to quad-group functions unless told otherwise
+        let quadgroup_only =
+            options.platform == Platform::iOS && !options.ios_use_simdgroup_functions;
+        if !options.emulate_subgroups {
+            if subgroup_operations && !quadgroup_only {
+                gpu_features.push(GpuFeature::SimdgroupFunctions);
+            }
+            if subgroups.quad || (subgroup_operations && quadgroup_only) {
+                gpu_features.push(GpuFeature::QuadgroupFunctions);
+            }
+        }
+        if options.platform == Platform::iOS && !resources.subpass_inputs.is_empty() {
+            gpu_features.push(GpuFeature::FramebufferFetch);
+        }
+
+        Ok(FeatureRequirements {
+            version: options.version,
+            platform: options.platform,
+            gpu_features,
+        })
+    }
+}
+
+impl spirv::Ast<Target, spirv::Compiled> {
+    /// Returns the language version, platform and GPU family dependent features the generated
+    /// source requires.
+    pub fn get_feature_requirements(&self) -> Result<FeatureRequirements, ErrorCode> {
+        self.feature_requirements()
     }
 
+    /// Returns whether rasterization is enabled.
     pub fn is_rasterization_enabled(&self) -> Result<bool, ErrorCode> {
         unsafe {
             let mut is_disabled = false;
@@ -548,6 +1475,188 @@ impl spirv::Ast<Target> {
             Ok(!is_disabled)
         }
     }
+
+    /// Returns whether the shader reads the length of a runtime array and therefore needs the
+    /// buffer size buffer bound at `buffer_size_buffer_index`.
+    pub fn needs_buffer_size_buffer(&self) -> Result<bool, ErrorCode> {
+        unsafe {
+            let mut needs_buffer_size_buffer = false;
+            check!(br::sc_internal_compiler_msl_needs_buffer_size_buffer(
+                self.compiler.sc_compiler,
+                &mut needs_buffer_size_buffer
+            ));
+            Ok(needs_buffer_size_buffer)
+        }
+    }
+}
+
+/// An entry point of a source built by `compile_combined`.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct CombinedEntryPoint {
+    /// The name of the entry point in its module, after any renaming to avoid a clash.
+    pub name: String,
+    pub execution_model: spirv::ExecutionModel,
+    /// The name of the entry point function in the MSL source.
+    pub cleansed_name: String,
+}
+
+/// A single MSL source holding the entry points of several `Ast`s.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct CombinedShader {
+    pub source: String,
+    /// The entry points in the order of the `Ast`s they were compiled from.
+    pub entry_points: Vec<CombinedEntryPoint>,
+}
+
+/// Compiles the current entry point of each `Ast` into one MSL source, such as the vertex and
+/// fragment functions of a pipeline. Declarations found in several outputs, like shared
+/// structs and helper functions, are only emitted once. An entry point whose function name is
+/// already taken is renamed with a numeric suffix, e.g. `main_1`. Each `Ast` is compiled from a
+/// copy, so the given ones are neither compiled nor renamed.
+///
+/// To combine the entry points of a single module, create an `Ast` per entry point with
+/// `Ast::from_parsed` and select each entry point through its compiler options.
+///
+/// The outputs are merged as text rather than from the modules: top-level declarations are the
+/// blocks SPIRV-Cross separates with empty lines where braces balance, and declarations are
+/// matched by their first line and deduplicated when their text is identical. Braces in
+/// comments, such as the file names of line directives, can split declarations in the wrong
+/// place, and the resource indices of different entry points are not checked against each other.
+///
+/// Fails with `ErrorCode::CompilationError` if two outputs declare the same name differently.
+pub fn compile_combined(asts: &[spirv::Ast<Target>]) -> Result<CombinedShader, ErrorCode> {
+    let mut preamble: Vec<String> = Vec::new();
+    let mut declarations: Vec<String> = Vec::new();
+    let mut declaration_indices = HashMap::new();
+    let mut entry_points: Vec<CombinedEntryPoint> = Vec::new();
+
+    for ast in asts {
+        let mut ast = ast.duplicate()?;
+        let (original_name, execution_model) = ast.get_current_entry_point()?;
+        let mut name = original_name.clone();
+        let mut source = ast.compile()?;
+        let mut cleansed_name = ast
+            .compiler
+            .get_cleansed_entry_point_name(&name, execution_model)?;
+        let mut suffix = 1;
+        while entry_points
+            .iter()
+            .any(|entry_point| entry_point.cleansed_name == cleansed_name)
+        {
+            let new_name = format!("{}_{}", original_name, suffix);
+            ast.rename_entry_point(&name, &new_name, execution_model)?;
+            name = new_name;
+            suffix += 1;
+            source = ast.compile()?;
+            cleansed_name = ast
+                .compiler
+                .get_cleansed_entry_point_name(&name, execution_model)?;
+        }
+
+        for declaration in split_declarations(&source) {
+            if declaration.lines().all(is_preamble_line) {
+                for line in declaration.lines() {
+                    if !preamble.iter().any(|existing| existing == line) {
+                        preamble.push(line.to_owned());
+                    }
+                }
+                continue;
+            }
+
+            let head = declaration_head(&declaration);
+            match declaration_indices.get(&head) {
+                Some(&index) if declarations[index] == declaration => {}
+                Some(_) => {
+                    return Err(ErrorCode::CompilationError(format!(
+                        "entry points declare `{}` differently",
+                        head
+                    )))
+                }
+                None => {
+                    declaration_indices.insert(head, declarations.len());
+                    declarations.push(declaration);
+                }
+            }
+        }
+
+        entry_points.push(CombinedEntryPoint {
+            name,
+            execution_model,
+            cleansed_name,
+        });
+    }
+
+    // Keep the layout of SPIRV-Cross output: pragmas, includes, then the namespace
+    let mut source = String::new();
+    let preamble_groups = [
+        preamble
+            .iter()
+            .filter(|line| line.starts_with("#pragma"))
+            .collect::<Vec<_>>(),
+        preamble
+            .iter()
+            .filter(|line| line.starts_with("#include"))
+            .collect::<Vec<_>>(),
+        preamble
+            .iter()
+            .filter(|line| line.starts_with("using"))
+            .collect::<Vec<_>>(),
+    ];
+    for group in preamble_groups.iter().filter(|group| !group.is_empty()) {
+        for line in group {
+            source.push_str(line);
+            source.push('\n');
+        }
+        source.push('\n');
+    }
+    for declaration in &declarations {
+        source.push_str(declaration);
+        source.push_str("\n\n");
+    }
+
+    Ok(CombinedShader {
+        source,
+        entry_points,
+    })
+}
+
+/// Splits MSL source into its top-level declarations, which SPIRV-Cross separates with empty
+/// lines outside of any braces.
+fn split_declarations(source: &str) -> Vec<String> {
+    let mut declarations = Vec::new();
+    let mut lines: Vec<&str> = Vec::new();
+    let mut depth = 0i32;
+    for line in source.lines() {
+        if line.trim().is_empty() && depth == 0 {
+            if !lines.is_empty() {
+                declarations.push(lines.join("\n"));
+                lines.clear();
+            }
+            continue;
+        }
+
+        depth += line.matches('{').count() as i32 - line.matches('}').count() as i32;
+        lines.push(line);
+    }
+    if !lines.is_empty() {
+        declarations.push(lines.join("\n"));
+    }
+
+    declarations
+}
+
+fn is_preamble_line(line: &str) -> bool {
+    line.starts_with("#pragma") || line.starts_with("#include") || line == "using namespace metal;"
+}
+
+/// The line naming a declaration, including the line after a template parameter list.
+fn declaration_head(declaration: &str) -> String {
+    let mut lines = declaration.lines();
+    let first = lines.next().unwrap_or_default();
+    match lines.next() {
+        Some(second) if first.starts_with("template") => format!("{}\n{}", first, second),
+        _ => first.to_owned(),
+    }
 }
 
 // TODO: Generate with bindgen
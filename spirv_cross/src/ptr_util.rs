@@ -29,14 +29,35 @@ pub unsafe fn read_string_from_ptr(ptr: *const std::os::raw::c_char) -> Result<S
     string
 }
 
+/// Reads a C string into `buffer`, reusing its allocation instead of allocating a new string.
+pub unsafe fn read_string_into(
+    ptr: *const std::os::raw::c_char,
+    buffer: &mut String,
+) -> Result<(), ErrorCode> {
+    #[cfg(not(target_arch = "wasm32"))]
+    let string = CStr::from_ptr(ptr).to_str();
+    #[cfg(target_arch = "wasm32")]
+    let bytes = emscripten::get_module().read_bytes_into_vec_while(
+        emscripten::Pointer::from_offset(ptr as u32),
+        |byte, _| 0 != byte,
+        false,
+    );
+    #[cfg(target_arch = "wasm32")]
+    let string = std::str::from_utf8(&bytes);
+
+    buffer.clear();
+    buffer.push_str(string.map_err(|_| ErrorCode::Unhandled)?);
+    Ok(())
+}
+
 pub unsafe fn read_from_ptr<T>(ptr: *const T) -> T {
     #[cfg(not(target_arch = "wasm32"))]
     let value = ptr.read();
     #[cfg(target_arch = "wasm32")]
     let value = {
         let num_bytes_to_read = std::mem::size_of::<T>();
-        let mut t_val: T = std::mem::uninitialized();
-        let t_ptr = &mut t_val as *mut T as *mut u8;
+        let mut t_val = std::mem::MaybeUninit::<T>::uninit();
+        let t_ptr = t_val.as_mut_ptr() as *mut u8;
         let bytes = emscripten::get_module().read_bytes_into_vec_while(
             emscripten::Pointer::from_offset(ptr as u32),
             |_, bytes_read| bytes_read < num_bytes_to_read,
@@ -45,7 +66,7 @@ pub unsafe fn read_from_ptr<T>(ptr: *const T) -> T {
         for (offset, byte) in bytes.iter().enumerate() {
             *t_ptr.offset(offset as isize) = *byte;
         }
-        t_val
+        t_val.assume_init()
     };
     value
 }
@@ -0,0 +1,80 @@
+use crate::bindings as br;
+use crate::{compiler, spirv, ErrorCode};
+use std::marker::PhantomData;
+use std::ptr;
+
+/// A target which only reflects modules, for tools such as asset databases and validators that
+/// never emit source.
+///
+/// Parsing constructs the SPIRV-Cross base compiler instead of one of the backends, so every
+/// reflection query of `Ast` is available but `Ast::compile` is not. Setting a variable type
+/// remap callback fails, as there is no output to remap types in.
+#[derive(Debug, Clone)]
+pub enum Target {}
+
+pub struct TargetData;
+
+impl spirv::Target for Target {
+    type Data = TargetData;
+}
+
+impl spirv::Parse<Target> for spirv::Ast<Target> {
+    fn parse(module: &spirv::Module) -> Result<Self, ErrorCode> {
+        let compiler = spirv::parse_words(&module.words, |words| {
+            let mut compiler = ptr::null_mut();
+            unsafe {
+                check!(br::sc_internal_compiler_reflect_new(
+                    &mut compiler,
+                    words.as_ptr() as *const u32,
+                    words.len() as usize,
+                ));
+            }
+            Ok(compiler)
+        })?;
+
+        Ok(Self::from_sc_compiler(compiler))
+    }
+
+    fn from_parsed(module: &spirv::ParsedModule) -> Result<Self, ErrorCode> {
+        let mut compiler = ptr::null_mut();
+        unsafe {
+            check!(br::sc_internal_compiler_reflect_new_from_ir(
+                &mut compiler,
+                module.ir.sc_parsed_ir,
+            ));
+        }
+
+        Ok(Self::from_sc_compiler(compiler))
+    }
+}
+
+impl spirv::Ast<Target> {
+    fn from_sc_compiler(sc_compiler: *mut br::ScInternalCompilerBase) -> Self {
+        spirv::Ast {
+            compiler: compiler::Compiler {
+                sc_compiler,
+                target_data: TargetData,
+                remove_unused_variables: false,
+                keep_unused_resources: false,
+                strip_names: false,
+                preserve_names: false,
+                identifier_naming: None,
+                unsupported_features: spirv::UnsupportedFeaturePolicy::Relaxed,
+                #[cfg(not(target_arch = "wasm32"))]
+                variable_type_remap: None,
+            },
+            target_type: PhantomData,
+            state: PhantomData,
+        }
+    }
+
+    /// Takes ownership of a SPIRV-Cross compiler previously released with `Ast::into_raw`.
+    ///
+    /// # Safety
+    ///
+    /// `sc_compiler` must come from `into_raw` on an `Ast` of this target, and must not be
+    /// used through another `Ast` afterwards.
+    pub unsafe fn from_raw(sc_compiler: *mut br::ScInternalCompilerBase) -> Self {
+        Self::from_sc_compiler(sc_compiler)
+    }
+}
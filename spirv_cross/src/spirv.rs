@@ -1,6 +1,17 @@
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use crate::{compiler, ErrorCode};
+use std::fs::File;
+use std::io;
 use std::marker::PhantomData;
+use std::mem;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// The opaque SPIRV-Cross compiler behind an `Ast`, for use with C bridge functions maintained
+/// outside of this crate.
+pub use crate::bindings::ScInternalCompilerBase;
 
 /// A stage or compute kernel.
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
@@ -10,6 +21,14 @@ pub struct CombinedImageSampler {
     pub sampler_id: u32,
 }
 
+/// A separate image and sampler which shaders combine to sample the image, as returned by
+/// `get_image_sampler_pairs`.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ImageSamplerPair {
+    pub image_id: u32,
+    pub sampler_id: u32,
+}
+
 /// A stage or compute kernel.
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub enum ExecutionModel {
@@ -22,8 +41,25 @@ pub enum ExecutionModel {
     Kernel,
 }
 
+/// An execution mode which has arguments.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ExecutionMode {
+    Invocations,
+    LocalSize,
+    LocalSizeHint,
+    OutputVertices,
+    VecTypeHint,
+    SubgroupSize,
+    SubgroupsPerWorkgroup,
+    SubgroupsPerWorkgroupId,
+    LocalSizeId,
+    LocalSizeHintId,
+    /// `OutputPrimitivesNV` of mesh shaders.
+    OutputPrimitives,
+}
+
 /// A decoration.
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub enum Decoration {
     RelaxedPrecision,
     SpecId,
@@ -72,6 +108,9 @@ pub enum Decoration {
     PassthroughNv,
     ViewportRelativeNv,
     SecondaryViewportRelativeNv,
+    HlslCounterBufferGoogle,
+    HlslSemanticGoogle,
+    UserTypeGoogle,
 }
 
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
@@ -80,7 +119,7 @@ pub enum VertexAttributeStep {
     Instance,
 }
 
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub enum BuiltIn {
     Position,
     PointSize,
@@ -175,7 +214,6 @@ pub enum BuiltIn {
     IncomingRayFlagsNv,
 }
 
-#[cfg(feature = "msl")]
 pub(crate) fn built_in_as_raw(built_in: Option<BuiltIn>) -> crate::bindings::spv::BuiltIn {
     use crate::bindings as br;
     use BuiltIn::*;
@@ -275,24 +313,332 @@ pub(crate) fn built_in_as_raw(built_in: Option<BuiltIn>) -> crate::bindings::spv
     }
 }
 
+pub(crate) fn built_in_from_raw(raw: crate::bindings::spv::BuiltIn) -> Result<BuiltIn, ErrorCode> {
+    use crate::bindings as br;
+    use BuiltIn::*;
+    match raw {
+        br::spv::BuiltIn::BuiltInPosition => Ok(Position),
+        br::spv::BuiltIn::BuiltInPointSize => Ok(PointSize),
+        br::spv::BuiltIn::BuiltInClipDistance => Ok(ClipDistance),
+        br::spv::BuiltIn::BuiltInCullDistance => Ok(CullDistance),
+        br::spv::BuiltIn::BuiltInVertexId => Ok(VertexId),
+        br::spv::BuiltIn::BuiltInInstanceId => Ok(InstanceId),
+        br::spv::BuiltIn::BuiltInPrimitiveId => Ok(PrimitiveId),
+        br::spv::BuiltIn::BuiltInInvocationId => Ok(InvocationId),
+        br::spv::BuiltIn::BuiltInLayer => Ok(Layer),
+        br::spv::BuiltIn::BuiltInViewportIndex => Ok(ViewportIndex),
+        br::spv::BuiltIn::BuiltInTessLevelOuter => Ok(TessLevelOuter),
+        br::spv::BuiltIn::BuiltInTessLevelInner => Ok(TessLevelInner),
+        br::spv::BuiltIn::BuiltInTessCoord => Ok(TessCoord),
+        br::spv::BuiltIn::BuiltInPatchVertices => Ok(PatchVertices),
+        br::spv::BuiltIn::BuiltInFragCoord => Ok(FragCoord),
+        br::spv::BuiltIn::BuiltInPointCoord => Ok(PointCoord),
+        br::spv::BuiltIn::BuiltInFrontFacing => Ok(FrontFacing),
+        br::spv::BuiltIn::BuiltInSampleId => Ok(SampleId),
+        br::spv::BuiltIn::BuiltInSamplePosition => Ok(SamplePosition),
+        br::spv::BuiltIn::BuiltInSampleMask => Ok(SampleMask),
+        br::spv::BuiltIn::BuiltInFragDepth => Ok(FragDepth),
+        br::spv::BuiltIn::BuiltInHelperInvocation => Ok(HelperInvocation),
+        br::spv::BuiltIn::BuiltInNumWorkgroups => Ok(NumWorkgroups),
+        br::spv::BuiltIn::BuiltInWorkgroupSize => Ok(WorkgroupSize),
+        br::spv::BuiltIn::BuiltInWorkgroupId => Ok(WorkgroupId),
+        br::spv::BuiltIn::BuiltInLocalInvocationId => Ok(LocalInvocationId),
+        br::spv::BuiltIn::BuiltInGlobalInvocationId => Ok(GlobalInvocationId),
+        br::spv::BuiltIn::BuiltInLocalInvocationIndex => Ok(LocalInvocationIndex),
+        br::spv::BuiltIn::BuiltInWorkDim => Ok(WorkDim),
+        br::spv::BuiltIn::BuiltInGlobalSize => Ok(GlobalSize),
+        br::spv::BuiltIn::BuiltInEnqueuedWorkgroupSize => Ok(EnqueuedWorkgroupSize),
+        br::spv::BuiltIn::BuiltInGlobalOffset => Ok(GlobalOffset),
+        br::spv::BuiltIn::BuiltInGlobalLinearId => Ok(GlobalLinearId),
+        br::spv::BuiltIn::BuiltInSubgroupSize => Ok(SubgroupSize),
+        br::spv::BuiltIn::BuiltInSubgroupMaxSize => Ok(SubgroupMaxSize),
+        br::spv::BuiltIn::BuiltInNumSubgroups => Ok(NumSubgroups),
+        br::spv::BuiltIn::BuiltInNumEnqueuedSubgroups => Ok(NumEnqueuedSubgroups),
+        br::spv::BuiltIn::BuiltInSubgroupId => Ok(SubgroupId),
+        br::spv::BuiltIn::BuiltInSubgroupLocalInvocationId => Ok(SubgroupLocalInvocationId),
+        br::spv::BuiltIn::BuiltInVertexIndex => Ok(VertexIndex),
+        br::spv::BuiltIn::BuiltInInstanceIndex => Ok(InstanceIndex),
+        br::spv::BuiltIn::BuiltInSubgroupEqMask => Ok(SubgroupEqMask),
+        br::spv::BuiltIn::BuiltInSubgroupGeMask => Ok(SubgroupGeMask),
+        br::spv::BuiltIn::BuiltInSubgroupGtMask => Ok(SubgroupGtMask),
+        br::spv::BuiltIn::BuiltInSubgroupLeMask => Ok(SubgroupLeMask),
+        br::spv::BuiltIn::BuiltInSubgroupLtMask => Ok(SubgroupLtMask),
+        br::spv::BuiltIn::BuiltInBaseVertex => Ok(BaseVertex),
+        br::spv::BuiltIn::BuiltInBaseInstance => Ok(BaseInstance),
+        br::spv::BuiltIn::BuiltInDrawIndex => Ok(DrawIndex),
+        br::spv::BuiltIn::BuiltInDeviceIndex => Ok(DeviceIndex),
+        br::spv::BuiltIn::BuiltInViewIndex => Ok(ViewIndex),
+        br::spv::BuiltIn::BuiltInBaryCoordNoPerspAMD => Ok(BaryCoordNoPerspAmd),
+        br::spv::BuiltIn::BuiltInBaryCoordNoPerspCentroidAMD => Ok(BaryCoordNoPerspCentroidAmd),
+        br::spv::BuiltIn::BuiltInBaryCoordNoPerspSampleAMD => Ok(BaryCoordNoPerspSampleAmd),
+        br::spv::BuiltIn::BuiltInBaryCoordSmoothAMD => Ok(BaryCoordSmoothAmd),
+        br::spv::BuiltIn::BuiltInBaryCoordSmoothCentroidAMD => Ok(BaryCoordSmoothCentroidAmd),
+        br::spv::BuiltIn::BuiltInBaryCoordSmoothSampleAMD => Ok(BaryCoordSmoothSampleAmd),
+        br::spv::BuiltIn::BuiltInBaryCoordPullModelAMD => Ok(BaryCoordPullModelAmd),
+        br::spv::BuiltIn::BuiltInFragStencilRefEXT => Ok(FragStencilRefExt),
+        br::spv::BuiltIn::BuiltInViewportMaskNV => Ok(ViewportMaskNv),
+        br::spv::BuiltIn::BuiltInSecondaryPositionNV => Ok(SecondaryPositionNv),
+        br::spv::BuiltIn::BuiltInSecondaryViewportMaskNV => Ok(SecondaryViewportMaskNv),
+        br::spv::BuiltIn::BuiltInPositionPerViewNV => Ok(PositionPerViewNv),
+        br::spv::BuiltIn::BuiltInViewportMaskPerViewNV => Ok(ViewportMaskPerViewNv),
+        br::spv::BuiltIn::BuiltInFullyCoveredEXT => Ok(FullyCoveredExt),
+        br::spv::BuiltIn::BuiltInTaskCountNV => Ok(TaskCountNv),
+        br::spv::BuiltIn::BuiltInPrimitiveCountNV => Ok(PrimitiveCountNv),
+        br::spv::BuiltIn::BuiltInPrimitiveIndicesNV => Ok(PrimitiveIndicesNv),
+        br::spv::BuiltIn::BuiltInClipDistancePerViewNV => Ok(ClipDistancePerViewNv),
+        br::spv::BuiltIn::BuiltInCullDistancePerViewNV => Ok(CullDistancePerViewNv),
+        br::spv::BuiltIn::BuiltInLayerPerViewNV => Ok(LayerPerViewNv),
+        br::spv::BuiltIn::BuiltInMeshViewCountNV => Ok(MeshViewCountNv),
+        br::spv::BuiltIn::BuiltInMeshViewIndicesNV => Ok(MeshViewIndicesNv),
+        br::spv::BuiltIn::BuiltInBaryCoordNV => Ok(BaryCoordNv),
+        br::spv::BuiltIn::BuiltInBaryCoordNoPerspNV => Ok(BaryCoordNoPerspNv),
+        br::spv::BuiltIn::BuiltInFragSizeEXT => Ok(FragSizeExt),
+        br::spv::BuiltIn::BuiltInFragInvocationCountEXT => Ok(FragInvocationCountExt),
+        br::spv::BuiltIn::BuiltInLaunchIdNV => Ok(LaunchIdNv),
+        br::spv::BuiltIn::BuiltInLaunchSizeNV => Ok(LaunchSizeNv),
+        br::spv::BuiltIn::BuiltInWorldRayOriginNV => Ok(WorldRayOriginNv),
+        br::spv::BuiltIn::BuiltInWorldRayDirectionNV => Ok(WorldRayDirectionNv),
+        br::spv::BuiltIn::BuiltInObjectRayOriginNV => Ok(ObjectRayOriginNv),
+        br::spv::BuiltIn::BuiltInObjectRayDirectionNV => Ok(ObjectRayDirectionNv),
+        br::spv::BuiltIn::BuiltInRayTminNV => Ok(RayTminNv),
+        br::spv::BuiltIn::BuiltInRayTmaxNV => Ok(RayTmaxNv),
+        br::spv::BuiltIn::BuiltInInstanceCustomIndexNV => Ok(InstanceCustomIndexNv),
+        br::spv::BuiltIn::BuiltInObjectToWorldNV => Ok(ObjectToWorldNv),
+        br::spv::BuiltIn::BuiltInWorldToObjectNV => Ok(WorldToObjectNv),
+        br::spv::BuiltIn::BuiltInHitTNV => Ok(HitTNv),
+        br::spv::BuiltIn::BuiltInHitKindNV => Ok(HitKindNv),
+        br::spv::BuiltIn::BuiltInIncomingRayFlagsNV => Ok(IncomingRayFlagsNv),
+        _ => Err(ErrorCode::Unhandled),
+    }
+}
+
 /// A work group size.
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct WorkGroupSize {
     pub x: u32,
     pub y: u32,
     pub z: u32,
+    /// The constants of a size declared with the `LocalSizeId` execution mode of SPIR-V 1.6,
+    /// rather than with literals. Dimensions given by a specialization constant are 0, as their
+    /// value is only known once the constant is specialized.
+    pub constant_ids: Option<[u32; 3]>,
 }
 
-/// An entry point for a SPIR-V module.
+/// Checks that an entry point selected in compiler options can be passed to SPIRV-Cross.
+#[cfg(any(feature = "glsl", feature = "hlsl", feature = "msl"))]
+pub(crate) fn validate_entry_point(
+    entry_point: &Option<(String, ExecutionModel)>,
+) -> Result<(), ErrorCode> {
+    match entry_point {
+        Some((name, _)) if name.contains('\0') => Err(ErrorCode::InvalidOptions(format!(
+            "entry point name {:?} contains a nul byte",
+            name
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Checks that compiler options do not ask to both strip and preserve names.
+#[cfg(any(feature = "glsl", feature = "hlsl", feature = "msl"))]
+pub(crate) fn validate_names(strip_names: bool, preserve_names: bool) -> Result<(), ErrorCode> {
+    if strip_names && preserve_names {
+        return Err(ErrorCode::InvalidOptions(String::from(
+            "names cannot be both stripped and preserved",
+        )));
+    }
+
+    Ok(())
+}
+
+/// Checks that an entry point selected in compiler options is one of the entry points of the
+/// parsed module, as options set after parsing cannot add one.
+#[cfg(any(feature = "glsl", feature = "hlsl", feature = "msl"))]
+pub(crate) fn validate_entry_point_in_module(
+    entry_point: &Option<(String, ExecutionModel)>,
+    entry_points: &[EntryPoint],
+) -> Result<(), ErrorCode> {
+    match entry_point {
+        Some((name, execution_model))
+            if !entry_points.iter().any(|entry_point| {
+                &entry_point.name == name && entry_point.execution_model == *execution_model
+            }) =>
+        {
+            Err(ErrorCode::InvalidOptions(format!(
+                "entry point `{}` ({:?}) is not in the module",
+                name, execution_model
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Lints a module with several entry points that is compiled without selecting one.
+#[cfg(any(feature = "glsl", feature = "hlsl", feature = "msl"))]
+pub(crate) fn entry_point_lints(
+    entry_point: &Option<(String, ExecutionModel)>,
+    entry_points: &[EntryPoint],
+) -> Vec<String> {
+    match (entry_point, entry_points.first()) {
+        (None, Some(first)) if entry_points.len() > 1 => vec![format!(
+            "module has {} entry points but none was selected, compiled `{}`",
+            entry_points.len(),
+            first.name
+        )],
+        _ => Vec::new(),
+    }
+}
+
+/// How compiling handles the lints `compile_with_lints` reports: an entry point chosen because
+/// none was selected, an ignored or unmatched MSL option, or GLSL transform feedback layouts
+/// used before GLSL 4.40. Other features the target cannot express fail to compile, or are
+/// lowered by SPIRV-Cross without a lint, the same way under either policy.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum UnsupportedFeaturePolicy {
+    /// Fails to compile when there are lints, with the lints as the error message.
+    Strict,
+    /// Compiles regardless, leaving the lints to `compile_with_lints`.
+    Relaxed,
+}
+
+impl Default for UnsupportedFeaturePolicy {
+    fn default() -> Self {
+        UnsupportedFeaturePolicy::Relaxed
+    }
+}
+
+/// Overrides for the prefixes of the identifiers SPIRV-Cross generates: the `_123` names of
+/// unnamed IDs, the `param` temporaries passed to function calls and the `SPIRV_Cross_` helpers.
+/// The identifiers are renamed in the compiled output, so the prefixes should not clash with
+/// other identifiers of the shader.
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct IdentifierNaming {
+    /// Replaces the `_` of `_123`.
+    pub temporary_prefix: String,
+    /// Whether to number unnamed IDs in the order they first appear in the output instead of by
+    /// ID, so that adding or removing instructions only renames identifiers near the change.
+    pub renumber_temporaries: bool,
+    /// Replaces the `param` of `param` and `param_1`.
+    pub parameter_prefix: String,
+    /// Replaces the `SPIRV_Cross_` of helper functions, structs and variables.
+    pub helper_prefix: String,
+}
+
+impl Default for IdentifierNaming {
+    fn default() -> Self {
+        IdentifierNaming {
+            temporary_prefix: String::from("_"),
+            renumber_temporaries: false,
+            parameter_prefix: String::from("param"),
+            helper_prefix: String::from("SPIRV_Cross_"),
+        }
+    }
+}
+
+fn is_number(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Checks for the `_1` suffix SPIRV-Cross adds to tell apart identifiers with the same name.
+fn is_copy_suffix(s: &str) -> bool {
+    s.is_empty() || s.strip_prefix('_').map_or(false, is_number)
+}
+
+/// Copies compiled shader source, passing each identifier to `rename` to be written instead.
+fn rename_identifiers<'a>(source: &'a str, mut rename: impl FnMut(&'a str, &mut String)) -> String {
+    let mut output = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(c) = rest.chars().next() {
+        let length = if c == '_' || c.is_ascii_alphabetic() {
+            let length = rest
+                .find(|c: char| c != '_' && !c.is_ascii_alphanumeric())
+                .unwrap_or_else(|| rest.len());
+            rename(&rest[..length], &mut output);
+            length
+        } else {
+            let length = match c {
+                // Literals are skipped whole so that suffixes like `1u` are left alone
+                '0'..='9' => rest
+                    .find(|c: char| c != '.' && !c.is_ascii_alphanumeric())
+                    .unwrap_or_else(|| rest.len()),
+                '"' => rest[1..].find('"').map_or(rest.len(), |end| end + 2),
+                _ => c.len_utf8(),
+            };
+            output.push_str(&rest[..length]);
+            length
+        };
+        rest = &rest[length..];
+    }
+    output
+}
+
+impl IdentifierNaming {
+    /// Renames the generated identifiers of compiled shader source.
+    pub(crate) fn apply(&self, source: &str) -> String {
+        let mut temporaries = HashMap::new();
+        rename_identifiers(source, |identifier, output| {
+            self.rename(identifier, &mut temporaries, output)
+        })
+    }
+
+    fn rename<'a>(
+        &self,
+        identifier: &'a str,
+        temporaries: &mut HashMap<&'a str, usize>,
+        output: &mut String,
+    ) {
+        let parameter_suffix = identifier
+            .strip_prefix("param")
+            .filter(|s| is_copy_suffix(s));
+        if let Some(id) = identifier.strip_prefix('_').filter(|id| is_number(id)) {
+            output.push_str(&self.temporary_prefix);
+            if self.renumber_temporaries {
+                let next = temporaries.len();
+                let number = *temporaries.entry(identifier).or_insert(next);
+                output.push_str(&number.to_string());
+            } else {
+                output.push_str(id);
+            }
+        } else if let Some(suffix) = parameter_suffix {
+            output.push_str(&self.parameter_prefix);
+            output.push_str(suffix);
+        } else if let Some(name) = identifier.strip_prefix("SPIRV_Cross_") {
+            output.push_str(&self.helper_prefix);
+            output.push_str(name);
+        } else {
+            output.push_str(identifier);
+        }
+    }
+}
+
+/// An entry point for a SPIR-V module.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct EntryPoint {
     pub name: String,
     pub execution_model: ExecutionModel,
     pub work_group_size: WorkGroupSize,
 }
 
+/// An entry point passed to the visitor of `visit_entry_points`, whose name is only borrowed for
+/// the call.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct EntryPointRef<'a> {
+    pub name: &'a str,
+    pub execution_model: ExecutionModel,
+    pub work_group_size: WorkGroupSize,
+}
+
+impl EntryPointRef<'_> {
+    pub fn to_entry_point(&self) -> EntryPoint {
+        EntryPoint {
+            name: self.name.to_owned(),
+            execution_model: self.execution_model,
+            work_group_size: self.work_group_size,
+        }
+    }
+}
+
 /// Description of struct member's range.
-#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct BufferRange {
     /// An index. Useful for passing to `get_member_name` and `get_member_decoration`.
     pub index: u32,
@@ -302,8 +648,248 @@ pub struct BufferRange {
     pub range: usize,
 }
 
-/// A resource.
+/// The portion of a push constant block used by an entry point.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct PushConstantRange {
+    /// The push constant block.
+    pub id: u32,
+    /// Bytes from the start of the block to the first used member.
+    pub offset: usize,
+    /// Bytes from the start of the first used member to the end of the last.
+    pub size: usize,
+}
+
+/// The locations given to stage inputs and outputs by `assign_stage_locations`.
+#[derive(Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub struct StageLocations {
+    /// The location of each stage input which had none, by variable ID.
+    pub inputs: BTreeMap<u32, u32>,
+    /// The location of each stage output which had none, by variable ID.
+    pub outputs: BTreeMap<u32, u32>,
+}
+
+/// An interpolation qualifier which `set_interpolation_qualifier` can force on a stage input or
+/// output.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum InterpolationQualifier {
+    /// No interpolation, which integer and double fragment inputs require.
+    Flat,
+    /// Linear interpolation in screen space.
+    NoPerspective,
+    /// Sampling within the covered area of the pixel, in addition to the interpolation.
+    Centroid,
+}
+
+/// The rules a buffer block's explicit layout can be checked against.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum BufferLayout {
+    /// The layout of uniform buffers, which rounds the alignment of arrays and structs up to 16.
+    Std140,
+    /// The layout of storage buffers and push constants.
+    Std430,
+    /// The layout of `VK_EXT_scalar_block_layout`, which aligns everything to its scalar type.
+    Scalar,
+}
+
+/// The way a buffer block member breaks the layout rules.
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub enum LayoutViolationKind {
+    /// The offset is not a multiple of the member's alignment.
+    MisalignedOffset { offset: u32, alignment: u32 },
+    /// The offset is before the end of the previous member.
+    OverlappingOffset { offset: u32, minimum: u32 },
+    /// The array stride of the member's type differs from the one the rules give.
+    ArrayStride { stride: u32, expected: u32 },
+    /// The matrix stride of the member differs from the one the rules give.
+    MatrixStride { stride: u32, expected: u32 },
+}
+
+/// The first member of a buffer block found by `validate_buffer_layout` to break the layout rules.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct LayoutViolation {
+    /// The struct which declares the member, which is a nested struct for nested members.
+    pub struct_type_id: u32,
+    /// An index. Useful for passing to `get_member_name` and `get_member_decoration`.
+    pub member_index: u32,
+    pub member_name: String,
+    pub kind: LayoutViolationKind,
+}
+
+/// The buffer block member whose type is being laid out.
+struct MemberLayout {
+    struct_type_id: u32,
+    index: u32,
+    matrix_stride: u32,
+    row_major: bool,
+}
+
+/// Gets the number of components of a type, which is 1 for scalars and non-vector types.
+pub(crate) fn get_vecsize(ty: &Type) -> u32 {
+    match *ty {
+        Type::Boolean { vecsize, .. }
+        | Type::Int { vecsize, .. }
+        | Type::UInt { vecsize, .. }
+        | Type::Int64 { vecsize, .. }
+        | Type::UInt64 { vecsize, .. }
+        | Type::Half { vecsize, .. }
+        | Type::Float { vecsize, .. }
+        | Type::Double { vecsize, .. }
+        | Type::SByte { vecsize, .. }
+        | Type::UByte { vecsize, .. }
+        | Type::Short { vecsize, .. }
+        | Type::UShort { vecsize, .. } => vecsize,
+        _ => 1,
+    }
+}
+
+/// Gets the array dimensions of a type, with the outermost last and 0 for runtime arrays.
+pub(crate) fn get_array(ty: &Type) -> &[u32] {
+    match ty {
+        Type::Boolean { array, .. }
+        | Type::Char { array, .. }
+        | Type::Int { array, .. }
+        | Type::UInt { array, .. }
+        | Type::Int64 { array, .. }
+        | Type::UInt64 { array, .. }
+        | Type::AtomicCounter { array, .. }
+        | Type::Half { array, .. }
+        | Type::Float { array, .. }
+        | Type::Double { array, .. }
+        | Type::Struct { array, .. }
+        | Type::Image { array, .. }
+        | Type::SampledImage { array, .. }
+        | Type::Sampler { array, .. }
+        | Type::SByte { array, .. }
+        | Type::UByte { array, .. }
+        | Type::Short { array, .. }
+        | Type::UShort { array, .. } => array,
+        _ => &[],
+    }
+}
+
+/// Gets the component type of a scalar, vector or matrix type, or an array of them.
+fn get_basic_type(ty: &Type) -> Option<BasicType> {
+    let (kind, width) = match ty {
+        // Booleans have no bit width in SPIR-V
+        Type::Boolean { .. } => (ScalarKind::Boolean, 1),
+        Type::SByte { .. } => (ScalarKind::Int, 8),
+        Type::Short { .. } => (ScalarKind::Int, 16),
+        Type::Int { .. } => (ScalarKind::Int, 32),
+        Type::Int64 { .. } => (ScalarKind::Int, 64),
+        Type::UByte { .. } => (ScalarKind::UInt, 8),
+        Type::UShort { .. } => (ScalarKind::UInt, 16),
+        Type::UInt { .. } => (ScalarKind::UInt, 32),
+        Type::UInt64 { .. } => (ScalarKind::UInt, 64),
+        Type::Half { .. } => (ScalarKind::Float, 16),
+        Type::Float { .. } => (ScalarKind::Float, 32),
+        Type::Double { .. } => (ScalarKind::Float, 64),
+        _ => return None,
+    };
+    Some(BasicType { kind, width })
+}
+
+/// A 64-bit FNV-1a hasher, which unlike `DefaultHasher` has a specified output that does not
+/// depend on the platform or the Rust version.
+/// Integers are hashed as little-endian bytes.
+pub(crate) struct StableHasher(u64);
+
+impl StableHasher {
+    pub(crate) fn new() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    pub(crate) fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    pub(crate) fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    #[cfg(any(feature = "glsl", feature = "hlsl", feature = "msl"))]
+    pub(crate) fn write_bool(&mut self, value: bool) {
+        self.write(&[value as u8]);
+    }
+
+    /// Hashes a string preceded by its length, so that consecutive strings cannot run into each
+    /// other.
+    pub(crate) fn write_str(&mut self, value: &str) {
+        self.write_u64(value.len() as u64);
+        self.write(value.as_bytes());
+    }
+
+    #[cfg(any(feature = "glsl", feature = "hlsl", feature = "msl"))]
+    pub(crate) fn write_option<T>(&mut self, value: &Option<T>, write: impl FnOnce(&mut Self, &T)) {
+        self.write_bool(value.is_some());
+        if let Some(value) = value {
+            write(self, value);
+        }
+    }
+
+    /// Hashes the options which every target shares.
+    #[cfg(any(feature = "glsl", feature = "hlsl", feature = "msl"))]
+    pub(crate) fn write_common_options(
+        &mut self,
+        strip_names: bool,
+        preserve_names: bool,
+        identifier_naming: &Option<IdentifierNaming>,
+        unsupported_features: UnsupportedFeaturePolicy,
+        entry_point: &Option<(String, ExecutionModel)>,
+    ) {
+        self.write_bool(strip_names);
+        self.write_bool(preserve_names);
+        self.write_option(identifier_naming, |hasher, naming| {
+            let IdentifierNaming {
+                temporary_prefix,
+                renumber_temporaries,
+                parameter_prefix,
+                helper_prefix,
+            } = naming;
+            hasher.write_str(temporary_prefix);
+            hasher.write_bool(*renumber_temporaries);
+            hasher.write_str(parameter_prefix);
+            hasher.write_str(helper_prefix);
+        });
+        self.write_u32(unsupported_features as u32);
+        self.write_option(entry_point, |hasher, (name, execution_model)| {
+            hasher.write_str(name);
+            hasher.write_u32(*execution_model as u32);
+        });
+    }
+
+    fn write_resource(&mut self, resource: &Resource) {
+        self.write_u32(resource.id);
+        self.write_u32(resource.type_id);
+        self.write_u32(resource.base_type_id);
+        self.write_str(&resource.name);
+    }
+
+    pub(crate) fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+fn round_up(value: u32, alignment: u32) -> u32 {
+    (value + alignment - 1) / alignment * alignment
+}
+
+/// Gets the alignment of a vector of `vecsize` components which are `width` bytes each.
+fn get_vector_alignment(width: u32, vecsize: u32, layout: BufferLayout) -> u32 {
+    match (layout, vecsize) {
+        (BufferLayout::Scalar, _) | (_, 1) => width,
+        (_, 2) => width * 2,
+        _ => width * 4,
+    }
+}
+
+/// A resource.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Resource {
     pub id: u32,
     pub type_id: u32,
@@ -311,265 +897,3834 @@ pub struct Resource {
     pub name: String,
 }
 
-/// Specialization constant reference.
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
-pub struct SpecializationConstant {
+impl Resource {
+    /// The ID of the resource variable, as a key which cannot be mixed up with other numbers.
+    pub fn resource_id(&self) -> ResourceId {
+        ResourceId(self.id)
+    }
+}
+
+/// The SPIR-V ID of a resource variable. Unlike a bare `u32`, it cannot be confused with a type
+/// ID, a descriptor set or a binding when resources of several stages are collected in maps.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResourceId(pub u32);
+
+impl From<ResourceId> for u32 {
+    fn from(id: ResourceId) -> u32 {
+        id.0
+    }
+}
+
+/// The descriptor set and binding of a resource in the module.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DescriptorBinding {
+    pub desc_set: u32,
+    pub binding: u32,
+}
+
+/// A resource passed to the visitor of `visit_shader_resources`, whose name is only borrowed for
+/// the call.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ResourceRef<'a> {
     pub id: u32,
-    pub constant_id: u32,
+    pub type_id: u32,
+    pub base_type_id: u32,
+    pub name: &'a str,
 }
 
-/// Work group size specialization constants.
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
-pub struct WorkGroupSizeSpecializationConstants {
-    pub x: SpecializationConstant,
-    pub y: SpecializationConstant,
-    pub z: SpecializationConstant,
+impl ResourceRef<'_> {
+    pub fn to_resource(&self) -> Resource {
+        Resource {
+            id: self.id,
+            type_id: self.type_id,
+            base_type_id: self.base_type_id,
+            name: self.name.to_owned(),
+        }
+    }
+
+    /// The ID of the resource variable, as `Resource::resource_id` returns it.
+    pub fn resource_id(&self) -> ResourceId {
+        ResourceId(self.id)
+    }
 }
 
-/// Shader resources.
-#[derive(Debug, Clone)]
-pub struct ShaderResources {
-    pub uniform_buffers: Vec<Resource>,
-    pub storage_buffers: Vec<Resource>,
-    pub stage_inputs: Vec<Resource>,
-    pub stage_outputs: Vec<Resource>,
-    pub subpass_inputs: Vec<Resource>,
-    pub storage_images: Vec<Resource>,
-    pub sampled_images: Vec<Resource>,
-    pub atomic_counters: Vec<Resource>,
-    pub push_constant_buffers: Vec<Resource>,
-    pub separate_images: Vec<Resource>,
-    pub separate_samplers: Vec<Resource>,
+/// A built-in stage input or output.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct BuiltInResource {
+    pub builtin: BuiltIn,
+    /// The type of the built-in value, which is a member type for built-in blocks.
+    pub value_type_id: u32,
+    /// The variable declaring the built-in.
+    pub resource: Resource,
 }
 
-#[derive(Debug, Clone)]
-pub enum Dim {
-    Dim1D = 0,
-    Dim2D = 1,
-    Dim3D = 2,
-    DimCube = 3,
-    DimRect = 4,
-    DimBuffer = 5,
-    DimSubpassData = 6,
+/// A resource of the module with whether an entry point uses it, as returned by
+/// `get_shader_resources_with_activity`.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ResourceActivity {
+    pub kind: ResourceKind,
+    pub resource: Resource,
+    /// Whether the entry point statically uses the resource.
+    pub is_active_in_entry_point: bool,
 }
 
-#[derive(Debug, Clone)]
-pub enum ImageFormat {
-    Unknown = 0,
-    Rgba32f = 1,
-    Rgba16f = 2,
-    R32f = 3,
-    Rgba8 = 4,
-    Rgba8Snorm = 5,
-    Rg32f = 6,
-    Rg16f = 7,
-    R11fG11fB10f = 8,
-    R16f = 9,
-    Rgba16 = 10,
-    Rgb10A2 = 11,
-    Rg16 = 12,
-    Rg8 = 13,
-    R16 = 14,
-    R8 = 15,
-    Rgba16Snorm = 16,
-    Rg16Snorm = 17,
-    Rg8Snorm = 18,
-    R16Snorm = 19,
-    R8Snorm = 20,
-    Rgba32i = 21,
-    Rgba16i = 22,
-    Rgba8i = 23,
-    R32i = 24,
-    Rg32i = 25,
-    Rg16i = 26,
-    Rg8i = 27,
-    R16i = 28,
-    R8i = 29,
-    Rgba32ui = 30,
-    Rgba16ui = 31,
-    Rgba8ui = 32,
-    R32ui = 33,
-    Rgb10a2ui = 34,
-    Rg32ui = 35,
-    Rg16ui = 36,
-    Rg8ui = 37,
-    R16ui = 38,
-    R8ui = 39,
-    R64ui = 40,
-    R64i = 41,
+/// An entry point which writes the `Layer` or `ViewportIndex` built-in, as returned by
+/// `get_layer_and_viewport_outputs`.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct LayerAndViewportOutput {
+    pub entry_point: String,
+    pub execution_model: ExecutionModel,
+    /// Whether the entry point writes `Layer`, which vertex and tessellation evaluation shaders
+    /// can only do with `shaderOutputLayer`.
+    pub layer: bool,
+    /// Whether the entry point writes `ViewportIndex`, which vertex and tessellation evaluation
+    /// shaders can only do with `shaderOutputViewportIndex`.
+    pub viewport_index: bool,
 }
 
-#[derive(Debug, Clone)]
-pub struct ImageType {
-    pub type_id: u32,
-    pub dim: Dim,
-    pub depth: bool,
-    pub arrayed: bool,
-    pub ms: bool,
-    pub sampled: u32,
-    pub format: ImageFormat,
+/// A stage output captured by transform feedback, as returned by
+/// `get_transform_feedback_outputs`.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct TransformFeedbackOutput {
+    /// The output variable, which may be a built-in block like `gl_PerVertex`.
+    pub resource: Resource,
+    /// The `XfbBuffer` the output is captured to.
+    pub buffer: u32,
+    /// The `XfbStride` of the buffer, or 0 if the output is not decorated with one.
+    pub stride: u32,
+    /// The byte `Offset` of the output in the buffer, which blocks leave to their members.
+    pub offset: Option<u32>,
+    /// The member indices and byte offsets of the captured members of a block. Members without
+    /// an `Offset` are not captured.
+    pub member_offsets: Vec<(u32, u32)>,
 }
 
-#[derive(Debug, Clone)]
-#[non_exhaustive]
-pub enum Type {
-    // TODO: Add missing fields to relevant variants from SPIRType
-    Unknown,
-    Void,
-    Boolean {
-        vecsize: u32,
-        columns: u32,
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-    },
-    Char {
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-    },
-    Int {
-        vecsize: u32,
-        columns: u32,
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-    },
-    UInt {
-        vecsize: u32,
-        columns: u32,
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-    },
-    Int64 {
-        vecsize: u32,
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-    },
-    UInt64 {
-        vecsize: u32,
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-    },
-    AtomicCounter {
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-    },
-    Half {
-        vecsize: u32,
-        columns: u32,
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-    },
-    Float {
-        vecsize: u32,
-        columns: u32,
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-    },
-    Double {
-        vecsize: u32,
-        columns: u32,
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-    },
-    Struct {
-        member_types: Vec<u32>,
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-    },
-    Image {
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-        image: ImageType,
-    },
-    SampledImage {
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-        image: ImageType,
-    },
-    Sampler {
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-    },
-    SByte {
-        vecsize: u32,
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-    },
-    UByte {
-        vecsize: u32,
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-    },
-    Short {
-        vecsize: u32,
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-    },
-    UShort {
-        vecsize: u32,
-        array: Vec<u32>,
-        array_size_literal: Vec<bool>,
-    },
-    ControlPointArray,
-    AccelerationStructure,
-    RayQuery,
-    Interpolant,
+/// A set of built-ins, with one bit for each `BuiltIn` variant.
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub struct BuiltInSet {
+    bits: u128,
 }
 
-/// A SPIR-V shader module.
-#[derive(Debug, Clone)]
-pub struct Module<'a> {
-    pub(crate) words: &'a [u32],
+impl BuiltInSet {
+    /// Creates a set without any built-ins.
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    fn bit(built_in: BuiltIn) -> u128 {
+        1 << built_in as u32
+    }
+
+    pub fn contains(&self, built_in: BuiltIn) -> bool {
+        self.bits & Self::bit(built_in) != 0
+    }
+
+    pub fn insert(&mut self, built_in: BuiltIn) {
+        self.bits |= Self::bit(built_in);
+    }
+
+    pub fn remove(&mut self, built_in: BuiltIn) {
+        self.bits &= !Self::bit(built_in);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// The number of built-ins in the set.
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// The raw bits of the set, indexed by the declaration order of the `BuiltIn` variants.
+    pub fn bits(&self) -> u128 {
+        self.bits
+    }
 }
 
-impl<'a> Module<'a> {
-    /// Creates a shader module from SPIR-V words.
-    pub fn from_words(words: &[u32]) -> Module {
-        Module { words }
+impl std::iter::FromIterator<BuiltIn> for BuiltInSet {
+    fn from_iter<I: IntoIterator<Item = BuiltIn>>(iter: I) -> Self {
+        let mut set = Self::empty();
+        for built_in in iter {
+            set.insert(built_in);
+        }
+        set
     }
 }
 
-pub trait Target {
-    type Data;
+impl std::ops::BitOr for BuiltInSet {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self {
+            bits: self.bits | other.bits,
+        }
+    }
 }
 
-/// An abstract syntax tree that corresponds to a SPIR-V module.
-pub struct Ast<TTarget>
-where
-    TTarget: Target,
-{
-    pub(crate) compiler: compiler::Compiler<TTarget::Data>,
-    pub(crate) target_type: PhantomData<TTarget>,
+impl std::ops::BitAnd for BuiltInSet {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        Self {
+            bits: self.bits & other.bits,
+        }
+    }
 }
 
-pub trait Parse<TTarget>: Sized {
-    fn parse(module: &Module) -> Result<Self, ErrorCode>;
+/// The built-ins an entry point reads and writes.
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub struct ActiveBuiltIns {
+    pub inputs: BuiltInSet,
+    pub outputs: BuiltInSet,
+}
+
+/// Reflection of a stage input or output variable.
+#[derive(Clone, Debug)]
+pub struct InterfaceVariable {
+    pub id: u32,
+    pub name: String,
+    /// The `Location` decoration, which variables such as built-in blocks do not have.
+    pub location: Option<u32>,
+    /// The `Component` decoration, which is only present on variables packed into a location
+    /// after another one.
+    pub component: Option<u32>,
+    /// The blend equation input a fragment output is written to, which is 1 for the second
+    /// source of dual-source blending and 0 otherwise.
+    pub index: u32,
+    /// The number of components, which is 1 for scalars and non-vector types.
+    pub vecsize: u32,
+    pub base_type: Type,
+    pub flat: bool,
+    pub no_perspective: bool,
+}
+
+/// Reflection of a member of a stage input or output block.
+#[derive(Clone, Debug)]
+pub struct InterfaceBlockMember {
+    /// An index. Useful for passing to `get_member_name` and `get_member_decoration`.
+    pub index: u32,
+    pub name: String,
+    pub type_id: u32,
+    /// Whether the member is a built-in, such as `gl_Position` in `gl_PerVertex`, which has no
+    /// location.
+    pub built_in: bool,
+    /// The location of the member, which follows the locations of the previous member unless the
+    /// member is decorated with one. Built-ins, and members of blocks without a `Location`
+    /// decoration that are not decorated with one themselves, have none.
+    pub location: Option<u32>,
+    /// The `Component` decoration of the member.
+    pub component: Option<u32>,
+    /// The number of components, which is 1 for scalars and non-vector types.
+    pub vecsize: u32,
+    pub base_type: Type,
+    pub flat: bool,
+    pub no_perspective: bool,
+}
+
+/// The scalar type of the components of a vertex attribute.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum VertexComponentType {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    F16,
+    F32,
+    F64,
+}
+
+impl VertexComponentType {
+    /// The size of one component in bytes.
+    pub fn size(self) -> u32 {
+        use VertexComponentType::*;
+        match self {
+            I8 | U8 => 1,
+            I16 | U16 | F16 => 2,
+            I32 | U32 | F32 => 4,
+            I64 | U64 | F64 => 8,
+        }
+    }
+}
+
+impl std::fmt::Display for VertexComponentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        use VertexComponentType::*;
+        f.write_str(match self {
+            I8 => "i8",
+            U8 => "u8",
+            I16 => "i16",
+            U16 => "u16",
+            I32 => "i32",
+            U32 => "u32",
+            I64 => "i64",
+            U64 => "u64",
+            F16 => "f16",
+            F32 => "f32",
+            F64 => "f64",
+        })
+    }
+}
+
+/// The kind of the components of a type.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum ScalarKind {
+    Boolean,
+    Int,
+    UInt,
+    Float,
+}
+
+/// The component type of a scalar, vector or matrix type, as returned by `Ast::get_basic_type`.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct BasicType {
+    pub kind: ScalarKind,
+    /// The width in bits, which is 1 for booleans.
+    pub width: u32,
+}
+
+/// The format of a vertex attribute, displayed as in `4 x f32`. Normalized and scaled formats
+/// cannot be told apart from the shader, so integer components are the type the shader declares.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct VertexFormat {
+    pub component_type: VertexComponentType,
+    /// The number of components, from 1 to 4.
+    pub component_count: u32,
+}
+
+impl std::fmt::Display for VertexFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} x {}", self.component_count, self.component_type)
+    }
+}
+
+/// A vertex attribute read by a vertex shader, as returned by `Ast::get_vertex_attributes`.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct VertexAttribute {
+    /// The stage input declaring the attribute.
+    pub id: u32,
+    pub name: String,
+    pub location: u32,
+    pub component: u32,
+    pub format: VertexFormat,
+}
+
+/// The number of descriptors a resource binding consumes.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum DescriptorCount {
+    /// A fixed number of descriptors, which is 1 unless the resource is an array.
+    Fixed(u32),
+    /// A runtime-sized array, whose size is only known when the descriptor set is allocated.
+    RuntimeSized,
+}
+
+/// A resource bound to a descriptor set, as returned by `get_descriptor_sets`.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct BindingInfo {
+    pub binding: u32,
+    pub kind: ResourceKind,
+    pub resource: Resource,
+    pub count: DescriptorCount,
+    /// The execution models of the entry points which statically use the resource, sorted and
+    /// without duplicates.
+    pub stages: Vec<ExecutionModel>,
+}
+
+/// The resources bound to one descriptor set, sorted by binding.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct DescriptorSet {
+    pub set: u32,
+    pub bindings: Vec<BindingInfo>,
+}
+
+/// A binding declared by any of several modules, as returned by `Ast::merge_descriptor_sets`.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct MergedBindingInfo {
+    pub binding: u32,
+    pub kind: ResourceKind,
+    /// The largest number of descriptors any of the modules uses.
+    pub count: DescriptorCount,
+    /// The execution models of the entry points of any module which statically use the
+    /// binding, sorted and without duplicates.
+    pub stages: Vec<ExecutionModel>,
+    /// Whether each module declares the binding, in the order the modules were given.
+    pub usage_mask: Vec<bool>,
+}
+
+/// The bindings of one descriptor set across several modules, sorted by binding.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct MergedDescriptorSet {
+    pub set: u32,
+    pub bindings: Vec<MergedBindingInfo>,
+}
+
+/// How shaders access a storage image, as returned by `get_image_access`.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum ImageAccess {
+    ReadOnly,
+    WriteOnly,
+    ReadWrite,
+}
+
+/// Specialization constant reference.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct SpecializationConstant {
+    pub id: u32,
+    pub constant_id: u32,
+}
+
+/// Work group size specialization constants.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct WorkGroupSizeSpecializationConstants {
+    pub x: SpecializationConstant,
+    pub y: SpecializationConstant,
+    pub z: SpecializationConstant,
+}
+
+/// Classes of subgroup operations, matching `VkSubgroupFeatureFlags`.
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub struct SubgroupFeatures {
+    pub basic: bool,
+    pub vote: bool,
+    pub arithmetic: bool,
+    pub ballot: bool,
+    pub shuffle: bool,
+    pub shuffle_relative: bool,
+    pub clustered: bool,
+    pub quad: bool,
+}
+
+/// Complexity metrics of the functions of a module, as returned by `get_shader_statistics`.
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub struct ShaderStatistics {
+    /// The instructions in function bodies, not counting the `OpFunction`, `OpLabel` and other
+    /// instructions which delimit functions and blocks, nor debug line information.
+    pub instruction_count: u32,
+    pub function_count: u32,
+    /// The deepest nesting of structured selections and loops within a function.
+    pub max_control_flow_depth: u32,
+    /// The deepest nesting of structured loops within a function.
+    pub max_loop_depth: u32,
+    /// The instructions sampling or gathering from an image, including sparse ones. Fetches and
+    /// reads are not counted.
+    pub texture_sample_count: u32,
+}
+
+/// The descriptor indexing features which a resource bound to a descriptor set relies on, as
+/// returned by `get_descriptor_indexing`.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct DescriptorIndexing {
+    pub kind: ResourceKind,
+    pub resource: Resource,
+    /// Whether the resource is an array of descriptors without a size, such as a bindless
+    /// texture table, which needs a variable descriptor count.
+    pub runtime_sized: bool,
+    /// Whether any function of the module indexes the resource with a `NonUniform` decorated
+    /// index, or decorates the access chain into it or the value loaded from it.
+    pub non_uniform_indexing: bool,
+}
+
+impl DescriptorIndexing {
+    /// Whether the binding relies on descriptor indexing, so its layout binding should be
+    /// created with the update after bind and partially bound flags.
+    pub fn requires_update_after_bind(&self) -> bool {
+        self.runtime_sized || self.non_uniform_indexing
+    }
+}
+
+/// Shader resources.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct ShaderResources {
+    pub uniform_buffers: Vec<Resource>,
+    pub storage_buffers: Vec<Resource>,
+    pub stage_inputs: Vec<Resource>,
+    pub stage_outputs: Vec<Resource>,
+    pub subpass_inputs: Vec<Resource>,
+    pub storage_images: Vec<Resource>,
+    pub sampled_images: Vec<Resource>,
+    pub atomic_counters: Vec<Resource>,
+    pub push_constant_buffers: Vec<Resource>,
+    pub separate_images: Vec<Resource>,
+    pub separate_samplers: Vec<Resource>,
+    /// Built-in stage inputs, with one entry per built-in member of a block like `gl_PerVertex`.
+    pub builtin_inputs: Vec<BuiltInResource>,
+    /// Built-in stage outputs, with one entry per built-in member of a block like `gl_PerVertex`.
+    pub builtin_outputs: Vec<BuiltInResource>,
+}
+
+/// One of the resource lists of `ShaderResources`, excluding built-ins.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ResourceKind {
+    UniformBuffers,
+    StorageBuffers,
+    StageInputs,
+    StageOutputs,
+    SubpassInputs,
+    StorageImages,
+    SampledImages,
+    AtomicCounters,
+    PushConstantBuffers,
+    SeparateImages,
+    SeparateSamplers,
+}
+
+impl ResourceKind {
+    /// Every kind, in the order of the fields of `ShaderResources`.
+    pub const ALL: [ResourceKind; 11] = [
+        ResourceKind::UniformBuffers,
+        ResourceKind::StorageBuffers,
+        ResourceKind::StageInputs,
+        ResourceKind::StageOutputs,
+        ResourceKind::SubpassInputs,
+        ResourceKind::StorageImages,
+        ResourceKind::SampledImages,
+        ResourceKind::AtomicCounters,
+        ResourceKind::PushConstantBuffers,
+        ResourceKind::SeparateImages,
+        ResourceKind::SeparateSamplers,
+    ];
+}
+
+/// A set of resource kinds, built by combining kinds with `|`.
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub struct ResourceKinds(u32);
+
+impl ResourceKinds {
+    /// Every kind of resource.
+    pub fn all() -> Self {
+        ResourceKind::ALL
+            .iter()
+            .fold(Self::default(), |kinds, &kind| kinds | kind)
+    }
+
+    pub fn contains(self, kind: ResourceKind) -> bool {
+        self.0 & ResourceKinds::from(kind).0 != 0
+    }
+
+    /// The bits of the kinds, with bit N set for the kind numbered N.
+    pub(crate) fn bits(self) -> u32 {
+        self.0
+    }
 }
 
-pub trait Compile<TTarget> {
-    type CompilerOptions;
+impl From<ResourceKind> for ResourceKinds {
+    fn from(kind: ResourceKind) -> Self {
+        ResourceKinds(1 << kind as u32)
+    }
+}
+
+impl std::ops::BitOr<ResourceKind> for ResourceKinds {
+    type Output = ResourceKinds;
+
+    fn bitor(self, kind: ResourceKind) -> ResourceKinds {
+        ResourceKinds(self.0 | ResourceKinds::from(kind).0)
+    }
+}
+
+impl std::ops::BitOr for ResourceKind {
+    type Output = ResourceKinds;
+
+    fn bitor(self, kind: ResourceKind) -> ResourceKinds {
+        ResourceKinds::from(self) | kind
+    }
+}
+
+impl ShaderResources {
+    /// Returns the resources of a kind.
+    pub fn get(&self, kind: ResourceKind) -> &[Resource] {
+        match kind {
+            ResourceKind::UniformBuffers => &self.uniform_buffers,
+            ResourceKind::StorageBuffers => &self.storage_buffers,
+            ResourceKind::StageInputs => &self.stage_inputs,
+            ResourceKind::StageOutputs => &self.stage_outputs,
+            ResourceKind::SubpassInputs => &self.subpass_inputs,
+            ResourceKind::StorageImages => &self.storage_images,
+            ResourceKind::SampledImages => &self.sampled_images,
+            ResourceKind::AtomicCounters => &self.atomic_counters,
+            ResourceKind::PushConstantBuffers => &self.push_constant_buffers,
+            ResourceKind::SeparateImages => &self.separate_images,
+            ResourceKind::SeparateSamplers => &self.separate_samplers,
+        }
+    }
+
+    /// Returns every resource with its kind, excluding built-ins.
+    pub fn iter(&self) -> impl Iterator<Item = (ResourceKind, &Resource)> {
+        ResourceKind::ALL
+            .iter()
+            .flat_map(move |&kind| self.get(kind).iter().map(move |resource| (kind, resource)))
+    }
+}
+
+/// Sorts `items` by the keys `key` returns, keeping the relative order of equal keys.
+fn sort_by_keys<T, K: Ord>(
+    items: &mut Vec<T>,
+    key: impl Fn(&T) -> Result<K, ErrorCode>,
+) -> Result<(), ErrorCode> {
+    let keys = items
+        .iter()
+        .map(key)
+        .collect::<Result<Vec<_>, ErrorCode>>()?;
+    let mut keyed = keys.into_iter().zip(items.drain(..)).collect::<Vec<_>>();
+    keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+    items.extend(keyed.into_iter().map(|(_, item)| item));
+    Ok(())
+}
+
+/// A resource which exists in both compared `ShaderResources`.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct ResourceChange {
+    pub old_kind: ResourceKind,
+    pub old: Resource,
+    pub new_kind: ResourceKind,
+    pub new: Resource,
+}
+
+/// The differences between two `ShaderResources`, as returned by `diff_resources`.
+#[derive(Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub struct ResourceDiff {
+    /// Resources which only exist in the new resources.
+    pub added: Vec<(ResourceKind, Resource)>,
+    /// Resources which only exist in the old resources.
+    pub removed: Vec<(ResourceKind, Resource)>,
+    /// Resources whose descriptor set, binding or location changed.
+    pub rebound: Vec<ResourceChange>,
+    /// Resources whose kind, descriptor count, declared size or type changed, such as a uniform
+    /// buffer which became a storage buffer or a block whose members moved.
+    pub retyped: Vec<ResourceChange>,
+}
+
+impl ResourceDiff {
+    /// Whether no differences were found, so a pipeline layout created for the old resources
+    /// can be used with the new ones.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.rebound.is_empty()
+            && self.retyped.is_empty()
+    }
+}
+
+/// The parts of a type compared by `diff_resources`, without the ids which differ between
+/// modules.
+#[derive(Debug, PartialEq)]
+struct TypeShape {
+    variant: mem::Discriminant<Type>,
+    vecsize: u32,
+    columns: u32,
+    /// The array sizes, `None` for sizes set by a specialization constant.
+    array: Vec<Option<u32>>,
+    /// The dimension, depth, arrayed, multisampled, sampled and format operands of an image.
+    image: Option<(u32, bool, bool, bool, u32, u32)>,
+    /// The shape and offset of each member of a struct.
+    members: Vec<(TypeShape, u32)>,
+}
+
+/// The parts of a resource compared by `diff_resources`.
+#[derive(Debug, PartialEq)]
+struct ResourceLayout {
+    /// The descriptor set, binding and location.
+    bindings: (u32, u32, u32),
+    count: DescriptorCount,
+    /// The declared size of a buffer block, or 0 for other resources.
+    declared_size: u32,
+    shape: TypeShape,
+}
+
+/// Compares the resources of two versions of a shader, such as before and after a hot-reload.
+/// `old` and `new` must come from `old_ast` and `new_ast`, whose decorations and types are
+/// compared.
+///
+/// Resources are matched by name, as ids are not stable across compilations, so a renamed
+/// resource is reported as removed and added. Stage inputs and outputs are only matched with
+/// resources of the same kind, while descriptors and push constant buffers can change kind.
+/// Types are compared by their structure and member offsets, while array sizes set by
+/// specialization constants are only compared through their current values in the descriptor
+/// count. Built-ins are not compared.
+pub fn diff_resources<TOld, TOldState, TNew, TNewState>(
+    old_ast: &Ast<TOld, TOldState>,
+    old: &ShaderResources,
+    new_ast: &Ast<TNew, TNewState>,
+    new: &ShaderResources,
+) -> Result<ResourceDiff, ErrorCode>
+where
+    TOld: Target,
+    TNew: Target,
+{
+    let interface = |kind| match kind {
+        ResourceKind::StageInputs | ResourceKind::StageOutputs => Some(kind),
+        _ => None,
+    };
+
+    let mut diff = ResourceDiff::default();
+    let mut unmatched = new.iter().collect::<Vec<_>>();
+    for (old_kind, old_resource) in old.iter() {
+        let matching = unmatched.iter().position(|&(new_kind, new_resource)| {
+            interface(new_kind) == interface(old_kind) && new_resource.name == old_resource.name
+        });
+        let (new_kind, new_resource) = match matching {
+            Some(index) => unmatched.remove(index),
+            None => {
+                diff.removed.push((old_kind, old_resource.clone()));
+                continue;
+            }
+        };
+
+        let change = || ResourceChange {
+            old_kind,
+            old: old_resource.clone(),
+            new_kind,
+            new: new_resource.clone(),
+        };
+        let old_layout = old_ast.get_resource_layout(old_kind, old_resource)?;
+        let new_layout = new_ast.get_resource_layout(new_kind, new_resource)?;
+        if old_layout.bindings != new_layout.bindings {
+            diff.rebound.push(change());
+        }
+        if old_kind != new_kind
+            || old_layout.count != new_layout.count
+            || old_layout.declared_size != new_layout.declared_size
+            || old_layout.shape != new_layout.shape
+        {
+            diff.retyped.push(change());
+        }
+    }
+
+    diff.added = unmatched
+        .into_iter()
+        .map(|(kind, resource)| (kind, resource.clone()))
+        .collect();
+    Ok(diff)
+}
+
+#[derive(Debug, Clone)]
+pub enum Dim {
+    Dim1D = 0,
+    Dim2D = 1,
+    Dim3D = 2,
+    DimCube = 3,
+    DimRect = 4,
+    DimBuffer = 5,
+    DimSubpassData = 6,
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum ImageFormat {
+    Unknown = 0,
+    Rgba32f = 1,
+    Rgba16f = 2,
+    R32f = 3,
+    Rgba8 = 4,
+    Rgba8Snorm = 5,
+    Rg32f = 6,
+    Rg16f = 7,
+    R11fG11fB10f = 8,
+    R16f = 9,
+    Rgba16 = 10,
+    Rgb10A2 = 11,
+    Rg16 = 12,
+    Rg8 = 13,
+    R16 = 14,
+    R8 = 15,
+    Rgba16Snorm = 16,
+    Rg16Snorm = 17,
+    Rg8Snorm = 18,
+    R16Snorm = 19,
+    R8Snorm = 20,
+    Rgba32i = 21,
+    Rgba16i = 22,
+    Rgba8i = 23,
+    R32i = 24,
+    Rg32i = 25,
+    Rg16i = 26,
+    Rg8i = 27,
+    R16i = 28,
+    R8i = 29,
+    Rgba32ui = 30,
+    Rgba16ui = 31,
+    Rgba8ui = 32,
+    R32ui = 33,
+    Rgb10a2ui = 34,
+    Rg32ui = 35,
+    Rg16ui = 36,
+    Rg8ui = 37,
+    R16ui = 38,
+    R8ui = 39,
+    R64ui = 40,
+    R64i = 41,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageType {
+    pub type_id: u32,
+    pub dim: Dim,
+    pub depth: bool,
+    pub arrayed: bool,
+    pub ms: bool,
+    pub sampled: u32,
+    pub format: ImageFormat,
+}
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Type {
+    // TODO: Add missing fields to relevant variants from SPIRType
+    Unknown,
+    Void,
+    Boolean {
+        vecsize: u32,
+        columns: u32,
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+    },
+    Char {
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+    },
+    Int {
+        vecsize: u32,
+        columns: u32,
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+    },
+    UInt {
+        vecsize: u32,
+        columns: u32,
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+    },
+    Int64 {
+        vecsize: u32,
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+    },
+    UInt64 {
+        vecsize: u32,
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+    },
+    AtomicCounter {
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+    },
+    Half {
+        vecsize: u32,
+        columns: u32,
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+    },
+    Float {
+        vecsize: u32,
+        columns: u32,
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+    },
+    Double {
+        vecsize: u32,
+        columns: u32,
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+    },
+    Struct {
+        member_types: Vec<u32>,
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+    },
+    Image {
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+        image: ImageType,
+    },
+    SampledImage {
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+        image: ImageType,
+    },
+    Sampler {
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+    },
+    SByte {
+        vecsize: u32,
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+    },
+    UByte {
+        vecsize: u32,
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+    },
+    Short {
+        vecsize: u32,
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+    },
+    UShort {
+        vecsize: u32,
+        array: Vec<u32>,
+        array_size_literal: Vec<bool>,
+    },
+    ControlPointArray,
+    AccelerationStructure,
+    RayQuery,
+    Interpolant,
+}
+
+/// A source file named by the debug information of a module.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct SourceFile {
+    /// The `OpString` holding the file name.
+    pub id: u32,
+    pub name: String,
+    /// The text of the file, if an `OpSource` instruction embeds it.
+    pub source: Option<String>,
+}
+
+/// A SPIR-V shader module.
+#[derive(Debug, Clone)]
+pub struct Module<'a> {
+    pub(crate) words: Cow<'a, [u32]>,
+}
+
+/// Compiles shader source with shaderc, forwarding its warnings to the `log` crate.
+#[cfg(all(feature = "shaderc", not(target_arch = "wasm32")))]
+fn compile_source(
+    source: &str,
+    language: shaderc::SourceLanguage,
+    execution_model: ExecutionModel,
+    entry_point: &str,
+) -> Result<Module<'static>, ErrorCode> {
+    let shader_kind = match execution_model {
+        ExecutionModel::Vertex => shaderc::ShaderKind::Vertex,
+        ExecutionModel::TessellationControl => shaderc::ShaderKind::TessControl,
+        ExecutionModel::TessellationEvaluation => shaderc::ShaderKind::TessEvaluation,
+        ExecutionModel::Geometry => shaderc::ShaderKind::Geometry,
+        ExecutionModel::Fragment => shaderc::ShaderKind::Fragment,
+        ExecutionModel::GlCompute => shaderc::ShaderKind::Compute,
+        ExecutionModel::Kernel => {
+            return Err(ErrorCode::InvalidOptions(
+                "shaderc cannot compile kernels".to_owned(),
+            ))
+        }
+    };
+
+    let mut compiler = shaderc::Compiler::new()
+        .ok_or_else(|| ErrorCode::CompilationError("failed to create a shaderc compiler".into()))?;
+    let mut options = shaderc::CompileOptions::new()
+        .ok_or_else(|| ErrorCode::CompilationError("failed to create shaderc options".into()))?;
+    options.set_source_language(language);
+    let artifact = compiler
+        .compile_into_spirv(source, shader_kind, "source", entry_point, Some(&options))
+        .map_err(|e| ErrorCode::CompilationError(e.to_string()))?;
+    if artifact.get_num_warnings() > 0 {
+        log_warn!("{}", artifact.get_warning_messages());
+    }
+
+    Ok(Module {
+        words: Cow::Owned(artifact.as_binary().to_vec()),
+    })
+}
+
+/// The first word of every SPIR-V module.
+const MAGIC_NUMBER: u32 = 0x0723_0203;
+
+/// The number of words in the SPIR-V module header.
+const HEADER_WORD_COUNT: usize = 5;
+
+impl<'a> Module<'a> {
+    /// Creates a shader module from SPIR-V words.
+    pub fn from_words(words: &[u32]) -> Module {
+        Module {
+            words: Cow::Borrowed(words),
+        }
+    }
+
+    /// Creates a shader module from the bytes of a SPIR-V binary.
+    ///
+    /// The bytes must contain a whole number of words starting with the SPIR-V magic number.
+    /// Aligned bytes in the native endianness are borrowed, while unaligned bytes (as produced by
+    /// `include_bytes!`) and modules with the opposite endianness are copied into native words.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Module<'a>, ErrorCode> {
+        let swapped = check_header(bytes)?;
+        if swapped || bytes.as_ptr() as usize % mem::align_of::<u32>() != 0 {
+            return Ok(Module {
+                words: Cow::Owned(copy_words(bytes, swapped)),
+            });
+        }
+
+        #[allow(clippy::cast_ptr_alignment)]
+        let words = unsafe {
+            std::slice::from_raw_parts(bytes.as_ptr() as *const u32, bytes.len() / WORD_SIZE)
+        };
+        Ok(Module {
+            words: Cow::Borrowed(words),
+        })
+    }
+
+    /// Creates a shader module by reading a SPIR-V binary from `reader` until it is exhausted.
+    pub fn from_reader<R: io::Read>(mut reader: R) -> Result<Module<'static>, ErrorCode> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .map_err(|e| ErrorCode::Io(e.to_string()))?;
+        let swapped = check_header(&bytes)?;
+        Ok(Module {
+            words: Cow::Owned(copy_words(&bytes, swapped)),
+        })
+    }
+
+    /// Creates a shader module from a SPIR-V binary file, such as a `.spv` file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Module<'static>, ErrorCode> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .map_err(|e| ErrorCode::Io(format!("failed to open {}: {}", path.display(), e)))?;
+        Module::from_reader(file)
+    }
+
+    /// Compiles GLSL source for a stage into a shader module with shaderc. The entry point is
+    /// always `main`.
+    #[cfg(all(feature = "shaderc", not(target_arch = "wasm32")))]
+    pub fn from_glsl_source(
+        source: &str,
+        execution_model: ExecutionModel,
+    ) -> Result<Module<'static>, ErrorCode> {
+        compile_source(
+            source,
+            shaderc::SourceLanguage::GLSL,
+            execution_model,
+            "main",
+        )
+    }
+
+    /// Compiles HLSL source for a stage into a shader module with shaderc, starting from the
+    /// given entry point.
+    #[cfg(all(feature = "shaderc", not(target_arch = "wasm32")))]
+    pub fn from_hlsl_source(
+        source: &str,
+        execution_model: ExecutionModel,
+        entry_point: &str,
+    ) -> Result<Module<'static>, ErrorCode> {
+        compile_source(
+            source,
+            shaderc::SourceLanguage::HLSL,
+            execution_model,
+            entry_point,
+        )
+    }
+
+    /// Gets the source files named by `OpSource` and `OpLine` instructions, in the order their
+    /// `OpString` instructions are declared. Files only named by `OpLine` have no source text.
+    pub fn get_source_files(&self) -> Result<Vec<SourceFile>, ErrorCode> {
+        validate_words(&self.words)?;
+        let words: Cow<[u32]> = if self.words[0] == MAGIC_NUMBER {
+            Cow::Borrowed(&self.words)
+        } else {
+            Cow::Owned(self.words.iter().map(|word| word.swap_bytes()).collect())
+        };
+
+        let mut strings = Vec::new();
+        let mut files = HashSet::new();
+        let mut sources = HashMap::new();
+        let mut continued_file = None;
+        let mut offset = HEADER_WORD_COUNT;
+        while offset < words.len() {
+            let word_count = (words[offset] >> 16) as usize;
+            let instruction = &words[offset..offset + word_count];
+            match (instruction[0] & 0xffff, instruction.len()) {
+                (OP_STRING, len) if len >= 2 => {
+                    strings.push((instruction[1], decode_string(&instruction[2..])?));
+                }
+                (OP_SOURCE, len) if len >= 4 => {
+                    let file = instruction[3];
+                    files.insert(file);
+                    continued_file = if len > 4 {
+                        sources.insert(file, decode_string(&instruction[4..])?);
+                        Some(file)
+                    } else {
+                        None
+                    };
+                }
+                (OP_SOURCE_CONTINUED, _) => {
+                    if let Some(source) = continued_file.and_then(|file| sources.get_mut(&file)) {
+                        source.push_str(&decode_string(&instruction[1..])?);
+                    }
+                }
+                (OP_LINE, len) if len >= 2 => {
+                    files.insert(instruction[1]);
+                }
+                _ => (),
+            }
+            offset += word_count;
+        }
+
+        Ok(strings
+            .into_iter()
+            .filter(|(id, _)| files.contains(id))
+            .map(|(id, name)| SourceFile {
+                id,
+                name,
+                source: sources.remove(&id),
+            })
+            .collect())
+    }
+}
+
+const OP_SOURCE_CONTINUED: u32 = 2;
+const OP_SOURCE: u32 = 3;
+const OP_NAME: u32 = 5;
+const OP_MEMBER_NAME: u32 = 6;
+const OP_STRING: u32 = 7;
+const OP_LINE: u32 = 8;
+const OP_ENTRY_POINT: u32 = 15;
+const OP_TYPE_VOID: u32 = 19;
+const OP_TYPE_BOOL: u32 = 20;
+const OP_TYPE_INT: u32 = 21;
+const OP_TYPE_FLOAT: u32 = 22;
+const OP_TYPE_VECTOR: u32 = 23;
+const OP_TYPE_MATRIX: u32 = 24;
+const OP_TYPE_IMAGE: u32 = 25;
+const OP_TYPE_STRUCT: u32 = 30;
+const OP_TYPE_POINTER: u32 = 32;
+const OP_TYPE_PIPE_STORAGE: u32 = 38;
+const OP_CONSTANT_TRUE: u32 = 41;
+const OP_CONSTANT_FALSE: u32 = 42;
+const OP_CONSTANT: u32 = 43;
+const OP_CONSTANT_NULL: u32 = 46;
+const OP_SPEC_CONSTANT_TRUE: u32 = 48;
+const OP_SPEC_CONSTANT_FALSE: u32 = 49;
+const OP_SPEC_CONSTANT: u32 = 50;
+const OP_SPEC_CONSTANT_COMPOSITE: u32 = 51;
+const OP_SPEC_CONSTANT_OP: u32 = 52;
+const OP_FUNCTION: u32 = 54;
+const OP_FUNCTION_PARAMETER: u32 = 55;
+const OP_FUNCTION_END: u32 = 56;
+const OP_FUNCTION_CALL: u32 = 57;
+const OP_VARIABLE: u32 = 59;
+const OP_IMAGE_TEXEL_POINTER: u32 = 60;
+const OP_LOAD: u32 = 61;
+const OP_ACCESS_CHAIN: u32 = 65;
+const OP_IN_BOUNDS_ACCESS_CHAIN: u32 = 66;
+const OP_DECORATE: u32 = 71;
+const OP_MEMBER_DECORATE: u32 = 72;
+const OP_DECORATION_GROUP: u32 = 73;
+const OP_COPY_OBJECT: u32 = 83;
+const OP_SAMPLED_IMAGE: u32 = 86;
+const OP_IMAGE_SAMPLE_IMPLICIT_LOD: u32 = 87;
+const OP_IMAGE_SAMPLE_PROJ_DREF_EXPLICIT_LOD: u32 = 94;
+const OP_IMAGE_GATHER: u32 = 96;
+const OP_IMAGE_DREF_GATHER: u32 = 97;
+const OP_IMAGE_READ: u32 = 98;
+const OP_IMAGE_WRITE: u32 = 99;
+const OP_LOOP_MERGE: u32 = 246;
+const OP_SELECTION_MERGE: u32 = 247;
+const OP_LABEL: u32 = 248;
+const OP_IMAGE_SPARSE_SAMPLE_IMPLICIT_LOD: u32 = 305;
+const OP_IMAGE_SPARSE_SAMPLE_PROJ_DREF_EXPLICIT_LOD: u32 = 312;
+const OP_IMAGE_SPARSE_GATHER: u32 = 314;
+const OP_IMAGE_SPARSE_DREF_GATHER: u32 = 315;
+const OP_NO_LINE: u32 = 317;
+const OP_IMAGE_SPARSE_READ: u32 = 320;
+const OP_EXECUTION_MODE_ID: u32 = 331;
+
+const DECORATION_NON_UNIFORM: u32 = 5300;
+
+/// Whether an instruction belongs to the sections of a module before `OpName`, from
+/// `OpCapability` to the debug source instructions such as `OpSource`.
+fn is_before_names(opcode: u32) -> bool {
+    // OpSourceContinued, OpSource, OpSourceExtension, OpString, OpExtension, OpExtInstImport,
+    // OpMemoryModel, OpEntryPoint, OpExecutionMode, OpCapability and OpExecutionModeId
+    matches!(opcode, 2 | 3 | 4 | 7 | 10 | 11 | 14 | 15 | 16 | 17 | 331)
+}
+
+/// Whether an instruction is a name, `OpModuleProcessed` or an annotation, which come between
+/// the debug source instructions and the type declarations.
+fn is_debug_or_annotation(opcode: u32) -> bool {
+    // OpName, OpMemberName, OpDecorate to OpGroupMemberDecorate, OpModuleProcessed, OpDecorateId,
+    // OpDecorateString and OpMemberDecorateString
+    matches!(opcode, 5 | 6 | 71..=75 | 330 | 332 | 5632 | 5633)
+}
+
+/// The decorations which `to_spirv_words` writes from the state of SPIRV-Cross, with whether
+/// they have an operand. SPIRV-Cross keeps the operands of these exactly.
+const REWRITTEN_DECORATIONS: &[(Decoration, bool)] = &[
+    (Decoration::RelaxedPrecision, false),
+    (Decoration::SpecId, true),
+    (Decoration::Block, false),
+    (Decoration::BufferBlock, false),
+    (Decoration::RowMajor, false),
+    (Decoration::ColMajor, false),
+    (Decoration::ArrayStride, true),
+    (Decoration::MatrixStride, true),
+    (Decoration::BuiltIn, true),
+    (Decoration::NoPerspective, false),
+    (Decoration::Flat, false),
+    (Decoration::Patch, false),
+    (Decoration::Centroid, false),
+    (Decoration::Sample, false),
+    (Decoration::Invariant, false),
+    (Decoration::Restrict, false),
+    (Decoration::Aliased, false),
+    (Decoration::Volatile, false),
+    (Decoration::Coherent, false),
+    (Decoration::NonWritable, false),
+    (Decoration::NonReadable, false),
+    (Decoration::Stream, true),
+    (Decoration::Location, true),
+    (Decoration::Component, true),
+    (Decoration::Index, true),
+    (Decoration::Binding, true),
+    (Decoration::DescriptorSet, true),
+    (Decoration::Offset, true),
+    (Decoration::XfbBuffer, true),
+    (Decoration::XfbStride, true),
+    (Decoration::InputAttachmentIndex, true),
+];
+
+fn is_rewritten_decoration(raw: u32) -> bool {
+    REWRITTEN_DECORATIONS
+        .iter()
+        .any(|&(decoration, _)| decoration.as_raw() as u32 == raw)
+}
+
+/// Appends an instruction to `words`, with a literal string after the other operands.
+fn encode_instruction(words: &mut Vec<u32>, opcode: u32, operands: &[u32], string: Option<&str>) {
+    let start = words.len();
+    words.push(0);
+    words.extend_from_slice(operands);
+    if let Some(string) = string {
+        // The string is nul-terminated and padded with nuls to a whole number of words
+        let mut bytes = string.as_bytes().to_vec();
+        bytes.resize(bytes.len() / 4 * 4 + 4, 0);
+        words.extend(
+            bytes
+                .chunks_exact(4)
+                .map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]])),
+        );
+    }
+    words[start] = ((words.len() - start) as u32) << 16 | opcode;
+}
+
+/// The width of a scalar integer type, where booleans are 1-bit integers. Signedness is given by
+/// the opcodes instead.
+#[derive(Copy, Clone)]
+struct ScalarType {
+    width: u32,
+}
+
+impl ScalarType {
+    fn truncate(self, value: u64) -> u64 {
+        if self.width >= 64 {
+            value
+        } else {
+            value & ((1 << self.width) - 1)
+        }
+    }
+
+    fn sign_extend(self, value: u64) -> i64 {
+        let shift = 64 - self.width.min(64);
+        ((value << shift) as i64) >> shift
+    }
+}
+
+/// A scalar integer or boolean constant declared by a module.
+enum ScalarConstant {
+    Value(u64),
+    Specialization(u64),
+    Operation { opcode: u32, operands: Vec<u32> },
+}
+
+/// Finds the variables behind every id of a module, following loads, access chains, copies and
+/// function arguments back to the variables.
+fn trace_variables(words: &[u32]) -> HashMap<u32, HashSet<u32>> {
+    let mut flows = Vec::new();
+    let mut parameters = HashMap::<u32, Vec<u32>>::new();
+    let mut calls = Vec::new();
+    let mut variables = HashMap::<u32, HashSet<u32>>::new();
+    let mut function = 0;
+    let mut offset = HEADER_WORD_COUNT;
+    while offset < words.len() {
+        let word_count = (words[offset] >> 16) as usize;
+        let instruction = &words[offset..offset + word_count];
+        offset += word_count;
+
+        match (instruction[0] & 0xffff, instruction.len()) {
+            (OP_FUNCTION, 5..) => function = instruction[2],
+            (OP_FUNCTION_PARAMETER, 3) => {
+                parameters.entry(function).or_default().push(instruction[2])
+            }
+            (OP_FUNCTION_CALL, 4..) => calls.push((instruction[3], &instruction[4..])),
+            (OP_VARIABLE, 4..) => {
+                variables.insert(instruction[2], std::iter::once(instruction[2]).collect());
+            }
+            (OP_LOAD, 4..)
+            | (OP_ACCESS_CHAIN, 4..)
+            | (OP_IN_BOUNDS_ACCESS_CHAIN, 4..)
+            | (OP_COPY_OBJECT, 4) => flows.push((instruction[3], instruction[2])),
+            _ => (),
+        }
+    }
+    for (callee, arguments) in calls {
+        if let Some(parameters) = parameters.get(&callee) {
+            flows.extend(arguments.iter().copied().zip(parameters.iter().copied()));
+        }
+    }
+
+    // Function arguments can flow to earlier instructions, so flows are repeated until the
+    // variables behind every id are known
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &(from, to) in &flows {
+            if let Some(sources) = variables.get(&from).cloned() {
+                let targets = variables.entry(to).or_default();
+                let count = targets.len();
+                targets.extend(sources);
+                changed |= targets.len() != count;
+            }
+        }
+    }
+    variables
+}
+
+/// Finds the variables which any function of a module reads or writes as images. Returns
+/// whether each variable is read and whether it is written.
+fn read_image_usage(words: &[u32]) -> HashMap<u32, (bool, bool)> {
+    let mut uses = Vec::new();
+    let mut offset = HEADER_WORD_COUNT;
+    while offset < words.len() {
+        let word_count = (words[offset] >> 16) as usize;
+        let instruction = &words[offset..offset + word_count];
+        offset += word_count;
+
+        match (instruction[0] & 0xffff, instruction.len()) {
+            (OP_IMAGE_READ, 5..) | (OP_IMAGE_SPARSE_READ, 5..) => {
+                uses.push((instruction[3], true, false))
+            }
+            (OP_IMAGE_WRITE, 4..) => uses.push((instruction[1], false, true)),
+            (OP_IMAGE_TEXEL_POINTER, 6) => uses.push((instruction[3], true, true)),
+            _ => (),
+        }
+    }
+
+    let variables = trace_variables(words);
+    let mut usage = HashMap::new();
+    for (id, read, written) in uses {
+        for &variable in variables.get(&id).into_iter().flatten() {
+            let entry = usage.entry(variable).or_insert((false, false));
+            entry.0 |= read;
+            entry.1 |= written;
+        }
+    }
+    usage
+}
+
+/// Finds the pairs of image and sampler variables which any function of a module combines with
+/// `OpSampledImage`.
+fn read_image_sampler_pairs(words: &[u32]) -> BTreeSet<ImageSamplerPair> {
+    let mut combinations = Vec::new();
+    let mut offset = HEADER_WORD_COUNT;
+    while offset < words.len() {
+        let word_count = (words[offset] >> 16) as usize;
+        let instruction = &words[offset..offset + word_count];
+        offset += word_count;
+
+        if let (OP_SAMPLED_IMAGE, 5) = (instruction[0] & 0xffff, instruction.len()) {
+            combinations.push((instruction[3], instruction[4]));
+        }
+    }
+
+    let variables = trace_variables(words);
+    let mut pairs = BTreeSet::new();
+    for (image, sampler) in combinations {
+        for &image_id in variables.get(&image).into_iter().flatten() {
+            for &sampler_id in variables.get(&sampler).into_iter().flatten() {
+                pairs.insert(ImageSamplerPair {
+                    image_id,
+                    sampler_id,
+                });
+            }
+        }
+    }
+    pairs
+}
+
+/// Finds the variables which any function of a module indexes non-uniformly. Indices, access
+/// chains and loads are looked at for the `NonUniform` decoration, which is how both
+/// `nonuniformEXT` and `NonUniformResourceIndex` are lowered.
+fn read_non_uniform_indexing(words: &[u32]) -> HashSet<u32> {
+    let mut decorated = HashSet::new();
+    let mut chains = Vec::new();
+    let mut loads = Vec::new();
+    let mut offset = HEADER_WORD_COUNT;
+    while offset < words.len() {
+        let word_count = (words[offset] >> 16) as usize;
+        let instruction = &words[offset..offset + word_count];
+        offset += word_count;
+
+        match (instruction[0] & 0xffff, instruction.len()) {
+            (OP_DECORATE, 3) if instruction[2] == DECORATION_NON_UNIFORM => {
+                decorated.insert(instruction[1]);
+            }
+            (OP_ACCESS_CHAIN, 5..) | (OP_IN_BOUNDS_ACCESS_CHAIN, 5..) => {
+                chains.push((instruction[2], instruction[3], &instruction[4..]))
+            }
+            (OP_LOAD, 4..) => loads.push((instruction[2], instruction[3])),
+            _ => (),
+        }
+    }
+
+    let loaded_non_uniform = loads
+        .into_iter()
+        .filter(|(id, _)| decorated.contains(id))
+        .map(|(_, pointer)| pointer)
+        .collect::<HashSet<_>>();
+    let variables = trace_variables(words);
+    let mut indexed = HashSet::new();
+    for (id, base, indices) in chains {
+        if decorated.contains(&id)
+            || loaded_non_uniform.contains(&id)
+            || indices.iter().any(|index| decorated.contains(index))
+        {
+            indexed.extend(variables.get(&base).into_iter().flatten());
+        }
+    }
+    indexed
+}
+
+/// Finds the ids which an entry point refers to: the words of the functions it calls directly
+/// or indirectly, its interface and the operands of its `OpExecutionModeId` modes, along with
+/// the types, constants and variables those are declared with. Literal operands of
+/// instructions in functions are scanned like ids. Returns `None` if the module does not
+/// declare the entry point.
+fn read_entry_point_references(
+    words: &[u32],
+    name: &str,
+    execution_model: u32,
+) -> Result<Option<HashSet<u32>>, ErrorCode> {
+    let mut entry_point = None;
+    let mut execution_mode_ids = Vec::new();
+    let mut declarations = HashMap::<u32, Vec<u32>>::new();
+    let mut functions = HashMap::<u32, Vec<u32>>::new();
+    let mut function = None;
+    let mut offset = HEADER_WORD_COUNT;
+    while offset < words.len() {
+        let word_count = (words[offset] >> 16) as usize;
+        let instruction = &words[offset..offset + word_count];
+        offset += word_count;
+
+        let opcode = instruction[0] & 0xffff;
+        if let (OP_FUNCTION, 5..) = (opcode, instruction.len()) {
+            function = Some(instruction[2]);
+        }
+        if let Some(id) = function {
+            functions.entry(id).or_default().extend(&instruction[1..]);
+            if opcode == OP_FUNCTION_END {
+                function = None;
+            }
+            continue;
+        }
+
+        match (opcode, instruction.len()) {
+            (OP_ENTRY_POINT, 4..)
+                if instruction[1] == execution_model
+                    && decode_string(&instruction[3..])? == name =>
+            {
+                // The name is followed by a nul terminator and padded to whole words
+                let interface = &instruction[3 + name.len() / WORD_SIZE + 1..];
+                entry_point = Some((instruction[2], interface));
+            }
+            (OP_EXECUTION_MODE_ID, 3..) => {
+                execution_mode_ids.push((instruction[1], &instruction[3..]))
+            }
+            (OP_TYPE_INT, _) | (OP_TYPE_FLOAT, _) => (),
+            (OP_TYPE_VECTOR, 4) | (OP_TYPE_MATRIX, 4) | (OP_TYPE_IMAGE, 9..) => {
+                declarations.insert(instruction[1], vec![instruction[2]]);
+            }
+            (OP_TYPE_POINTER, 4) => {
+                declarations.insert(instruction[1], vec![instruction[3]]);
+            }
+            (OP_TYPE_VOID..=OP_TYPE_PIPE_STORAGE, 2..) => {
+                declarations.insert(instruction[1], instruction[2..].to_vec());
+            }
+            (OP_CONSTANT, 3..) | (OP_SPEC_CONSTANT, 3..) => {
+                declarations.insert(instruction[2], vec![instruction[1]]);
+            }
+            (OP_SPEC_CONSTANT_OP, 4..) => {
+                let mut operands = vec![instruction[1]];
+                operands.extend(&instruction[4..]);
+                declarations.insert(instruction[2], operands);
+            }
+            (OP_CONSTANT_TRUE..=OP_SPEC_CONSTANT_COMPOSITE, 3..) => {
+                let mut operands = vec![instruction[1]];
+                operands.extend(&instruction[3..]);
+                declarations.insert(instruction[2], operands);
+            }
+            (OP_VARIABLE, 4..) => {
+                let mut operands = vec![instruction[1]];
+                operands.extend(&instruction[4..]);
+                declarations.insert(instruction[2], operands);
+            }
+            _ => (),
+        }
+    }
+
+    let (entry_function, interface) = match entry_point {
+        Some(entry_point) => entry_point,
+        None => return Ok(None),
+    };
+    let mut pending = vec![entry_function];
+    pending.extend(interface);
+    for (target, ids) in execution_mode_ids {
+        if target == entry_function {
+            pending.extend(ids);
+        }
+    }
+
+    let mut references = HashSet::new();
+    while let Some(id) = pending.pop() {
+        if references.insert(id) {
+            let operands = functions.get(&id).or_else(|| declarations.get(&id));
+            pending.extend(operands.into_iter().flatten());
+        }
+    }
+    Ok(Some(references))
+}
+
+/// Reads the structs of a module with their member counts, and the functions which are entry
+/// points.
+fn read_structs_and_entry_functions(words: &[u32]) -> (Vec<(u32, u32)>, HashSet<u32>) {
+    let mut structs = Vec::new();
+    let mut entry_functions = HashSet::new();
+    let mut offset = HEADER_WORD_COUNT;
+    while offset < words.len() {
+        let word_count = (words[offset] >> 16) as usize;
+        let instruction = &words[offset..offset + word_count];
+        offset += word_count;
+
+        match (instruction[0] & 0xffff, instruction.len()) {
+            (OP_ENTRY_POINT, 4..) => {
+                entry_functions.insert(instruction[2]);
+            }
+            (OP_TYPE_STRUCT, 2..) => structs.push((instruction[1], instruction.len() as u32 - 2)),
+            _ => (),
+        }
+    }
+    (structs, entry_functions)
+}
+
+/// The names of the IDs and struct members of a module, as read before a compile which
+/// preserves names.
+#[derive(Default)]
+struct Names {
+    ids: HashMap<u32, String>,
+    members: HashMap<(u32, u32), String>,
+}
+
+/// Measures the functions of a module. The merge blocks of structured control flow follow the
+/// blocks of their construct, so a construct is left at the label of its merge block.
+fn read_statistics(words: &[u32]) -> ShaderStatistics {
+    let mut statistics = ShaderStatistics::default();
+    // The merge blocks of the constructs the current block is in, with whether they are loops
+    let mut constructs = Vec::<(u32, bool)>::new();
+    let mut in_function = false;
+    let mut offset = HEADER_WORD_COUNT;
+    while offset < words.len() {
+        let word_count = (words[offset] >> 16) as usize;
+        let instruction = &words[offset..offset + word_count];
+        offset += word_count;
+
+        let opcode = instruction[0] & 0xffff;
+        match opcode {
+            OP_FUNCTION => {
+                statistics.function_count += 1;
+                in_function = true;
+                continue;
+            }
+            OP_FUNCTION_END => {
+                constructs.clear();
+                in_function = false;
+                continue;
+            }
+            OP_FUNCTION_PARAMETER | OP_LINE | OP_NO_LINE => continue,
+            OP_LABEL => {
+                while constructs.last().map(|&(merge, _)| merge) == instruction.get(1).copied() {
+                    constructs.pop();
+                }
+                continue;
+            }
+            OP_SELECTION_MERGE | OP_LOOP_MERGE if instruction.len() >= 2 => {
+                constructs.push((instruction[1], opcode == OP_LOOP_MERGE));
+                let depth = constructs.len() as u32;
+                let loop_depth = constructs.iter().filter(|&&(_, is_loop)| is_loop).count() as u32;
+                statistics.max_control_flow_depth = statistics.max_control_flow_depth.max(depth);
+                statistics.max_loop_depth = statistics.max_loop_depth.max(loop_depth);
+            }
+            OP_IMAGE_SAMPLE_IMPLICIT_LOD..=OP_IMAGE_SAMPLE_PROJ_DREF_EXPLICIT_LOD
+            | OP_IMAGE_GATHER
+            | OP_IMAGE_DREF_GATHER
+            | OP_IMAGE_SPARSE_SAMPLE_IMPLICIT_LOD..=OP_IMAGE_SPARSE_SAMPLE_PROJ_DREF_EXPLICIT_LOD
+            | OP_IMAGE_SPARSE_GATHER
+            | OP_IMAGE_SPARSE_DREF_GATHER => statistics.texture_sample_count += 1,
+            _ => (),
+        }
+        if in_function {
+            statistics.instruction_count += 1;
+        }
+    }
+    statistics
+}
+
+/// Reads the scalar integer and boolean constants of a module in declaration order, with their
+/// result types. Other constants, such as floats and composites, are left out.
+fn read_scalar_constants(words: &[u32]) -> Vec<(u32, ScalarType, ScalarConstant)> {
+    let mut types = HashMap::new();
+    let mut constants = Vec::new();
+    let mut offset = HEADER_WORD_COUNT;
+    while offset < words.len() {
+        let word_count = (words[offset] >> 16) as usize;
+        let instruction = &words[offset..offset + word_count];
+        offset += word_count;
+
+        match (instruction[0] & 0xffff, instruction.len()) {
+            (OP_TYPE_BOOL, 2) => {
+                types.insert(instruction[1], ScalarType { width: 1 });
+                continue;
+            }
+            (OP_TYPE_INT, 4) => {
+                let ty = ScalarType {
+                    width: instruction[2],
+                };
+                types.insert(instruction[1], ty);
+                continue;
+            }
+            _ => (),
+        }
+
+        let ty = match instruction.get(1).and_then(|type_id| types.get(type_id)) {
+            Some(&ty) if instruction.len() >= 3 => ty,
+            _ => continue,
+        };
+        let literal = || match instruction[3..] {
+            [low] => Some(u64::from(low)),
+            [low, high] => Some(u64::from(high) << 32 | u64::from(low)),
+            _ => None,
+        };
+        let constant = match instruction[0] & 0xffff {
+            OP_CONSTANT_TRUE => ScalarConstant::Value(1),
+            OP_CONSTANT_FALSE | OP_CONSTANT_NULL => ScalarConstant::Value(0),
+            OP_CONSTANT => match literal() {
+                Some(value) => ScalarConstant::Value(value),
+                None => continue,
+            },
+            OP_SPEC_CONSTANT_TRUE => ScalarConstant::Specialization(1),
+            OP_SPEC_CONSTANT_FALSE => ScalarConstant::Specialization(0),
+            OP_SPEC_CONSTANT => match literal() {
+                Some(value) => ScalarConstant::Specialization(value),
+                None => continue,
+            },
+            OP_SPEC_CONSTANT_OP if instruction.len() >= 4 => ScalarConstant::Operation {
+                opcode: instruction[3],
+                operands: instruction[4..].to_vec(),
+            },
+            _ => continue,
+        };
+        constants.push((instruction[2], ty, constant));
+    }
+
+    constants
+}
+
+/// Evaluates an `OpSpecConstantOp` whose operands have been evaluated, returning the result
+/// before it is truncated to the width of its type.
+fn evaluate_operation(opcode: u32, operands: &[(u64, ScalarType)]) -> Result<u64, ErrorCode> {
+    let unsupported = || {
+        ErrorCode::CompilationError(format!(
+            "opcode {} with {} operands cannot be evaluated",
+            opcode,
+            operands.len()
+        ))
+    };
+    let division_by_zero = || ErrorCode::CompilationError(String::from("division by zero"));
+    let signed = |(value, ty): (u64, ScalarType)| ty.sign_extend(value);
+    let shift = |amount: u64| {
+        if amount < 64 {
+            Some(amount as u32)
+        } else {
+            None
+        }
+    };
+
+    let result = match *operands {
+        [a] => match opcode {
+            // OpUConvert
+            113 => a.0,
+            // OpSConvert
+            114 => signed(a) as u64,
+            // OpSNegate
+            126 => signed(a).wrapping_neg() as u64,
+            // OpLogicalNot
+            168 => (a.0 == 0) as u64,
+            // OpNot
+            200 => !a.0,
+            _ => return Err(unsupported()),
+        },
+        [a, b] => match opcode {
+            // OpIAdd, OpISub and OpIMul
+            128 => a.0.wrapping_add(b.0),
+            130 => a.0.wrapping_sub(b.0),
+            132 => a.0.wrapping_mul(b.0),
+            // OpUDiv and OpUMod
+            134 => a.0.checked_div(b.0).ok_or_else(division_by_zero)?,
+            137 => a.0.checked_rem(b.0).ok_or_else(division_by_zero)?,
+            // OpSDiv, OpSRem and OpSMod, where the sign of OpSMod follows the divisor
+            135 | 138 | 139 => {
+                let (a, b) = (signed(a), signed(b));
+                if b == 0 {
+                    return Err(division_by_zero());
+                }
+                let result = match opcode {
+                    135 => a.wrapping_div(b),
+                    138 => a.wrapping_rem(b),
+                    _ => match a.wrapping_rem(b) {
+                        rem if rem != 0 && (rem < 0) != (b < 0) => rem + b,
+                        rem => rem,
+                    },
+                };
+                result as u64
+            }
+            // OpLogicalEqual, OpLogicalNotEqual, OpLogicalOr and OpLogicalAnd
+            164 => (a.0 == b.0) as u64,
+            165 => (a.0 != b.0) as u64,
+            166 => (a.0 != 0 || b.0 != 0) as u64,
+            167 => (a.0 != 0 && b.0 != 0) as u64,
+            // OpIEqual, OpINotEqual and the integer comparisons
+            170 => (a.0 == b.0) as u64,
+            171 => (a.0 != b.0) as u64,
+            172 => (a.0 > b.0) as u64,
+            173 => (signed(a) > signed(b)) as u64,
+            174 => (a.0 >= b.0) as u64,
+            175 => (signed(a) >= signed(b)) as u64,
+            176 => (a.0 < b.0) as u64,
+            177 => (signed(a) < signed(b)) as u64,
+            178 => (a.0 <= b.0) as u64,
+            179 => (signed(a) <= signed(b)) as u64,
+            // OpShiftRightLogical, OpShiftRightArithmetic and OpShiftLeftLogical
+            194 => shift(b.0).map_or(0, |b| a.0 >> b),
+            195 => (signed(a) >> shift(b.0).unwrap_or(63)) as u64,
+            196 => shift(b.0).map_or(0, |b| a.0 << b),
+            // OpBitwiseOr, OpBitwiseXor and OpBitwiseAnd
+            197 => a.0 | b.0,
+            198 => a.0 ^ b.0,
+            199 => a.0 & b.0,
+            _ => return Err(unsupported()),
+        },
+        // OpSelect
+        [condition, a, b] if opcode == 169 => {
+            if condition.0 != 0 {
+                a.0
+            } else {
+                b.0
+            }
+        }
+        _ => return Err(unsupported()),
+    };
+
+    Ok(result)
+}
+
+/// Decodes a nul-terminated literal string from the words of an instruction.
+fn decode_string(words: &[u32]) -> Result<String, ErrorCode> {
+    let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+    let len = bytes
+        .iter()
+        .position(|&byte| byte == 0)
+        .unwrap_or(bytes.len());
+    String::from_utf8(bytes[..len].to_vec())
+        .map_err(|_| ErrorCode::InvalidSpirv(String::from("literal string is not valid UTF-8")))
+}
+
+const WORD_SIZE: usize = mem::size_of::<u32>();
+
+/// Validates the length and magic number of a SPIR-V binary and returns whether its words are
+/// byte-swapped relative to the native endianness.
+fn check_header(bytes: &[u8]) -> Result<bool, ErrorCode> {
+    if bytes.len() % WORD_SIZE != 0 {
+        return Err(ErrorCode::InvalidSpirv(format!(
+            "length of {} bytes is not a multiple of {}",
+            bytes.len(),
+            WORD_SIZE
+        )));
+    }
+    if bytes.len() < HEADER_WORD_COUNT * WORD_SIZE {
+        return Err(ErrorCode::InvalidSpirv(format!(
+            "length of {} bytes is too short for a SPIR-V header",
+            bytes.len()
+        )));
+    }
+
+    match u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) {
+        MAGIC_NUMBER => Ok(false),
+        magic if magic == MAGIC_NUMBER.swap_bytes() => Ok(true),
+        magic => Err(ErrorCode::InvalidSpirv(format!(
+            "invalid magic number {:#010x}",
+            magic
+        ))),
+    }
+}
+
+/// The largest id bound allowed by the universal limits of the SPIR-V specification.
+const MAX_ID_BOUND: u32 = 0x003f_ffff;
+
+/// Returns the positions among `operands` of the ids of an instruction which the SPIRV-Cross
+/// parser looks up while building its representation of the module: fixed positions, which
+/// optional operands may leave out, and the position from which every remaining operand is an
+/// id. Other instructions are only stored by the parser, so their ids are left to it.
+#[allow(non_upper_case_globals)]
+fn id_operands(opcode: u32, operands: &[u32]) -> (&'static [usize], Option<usize>) {
+    use crate::bindings::spv::*;
+    match opcode {
+        Op_OpEntryPoint => {
+            // The interface ids follow the name, whose last word holds its null terminator
+            let name_length = operands
+                .get(2..)
+                .and_then(|name| name.iter().position(|word| word.to_le_bytes().contains(&0)));
+            (&[1], name_length.map(|length| 3 + length))
+        }
+        Op_OpSource => (&[2], None),
+        Op_OpName | Op_OpMemberName | Op_OpString | Op_OpLine | Op_OpExtInstImport => (&[0], None),
+        Op_OpExecutionMode | Op_OpDecorate | Op_OpMemberDecorate | Op_OpDecorationGroup => {
+            (&[0], None)
+        }
+        Op_OpGroupMemberDecorate => (&[0], None),
+        Op_OpExecutionModeId | Op_OpDecorateId => (&[0], Some(2)),
+        Op_OpGroupDecorate => (&[], Some(0)),
+        Op_OpTypeVoid | Op_OpTypeBool | Op_OpTypeInt | Op_OpTypeFloat | Op_OpTypeSampler => {
+            (&[0], None)
+        }
+        Op_OpTypeOpaque | Op_OpTypeForwardPointer | Op_OpTypeAccelerationStructureKHR => {
+            (&[0], None)
+        }
+        Op_OpTypeRayQueryKHR => (&[0], None),
+        Op_OpTypeVector | Op_OpTypeMatrix | Op_OpTypeImage | Op_OpTypeSampledImage => {
+            (&[0, 1], None)
+        }
+        Op_OpTypeRuntimeArray => (&[0, 1], None),
+        Op_OpTypeArray => (&[0, 1, 2], None),
+        Op_OpTypePointer => (&[0, 2], None),
+        Op_OpTypeStruct | Op_OpTypeFunction => (&[], Some(0)),
+        Op_OpUndef | Op_OpConstantTrue | Op_OpConstantFalse | Op_OpConstant => (&[0, 1], None),
+        Op_OpConstantSampler | Op_OpConstantNull | Op_OpSpecConstantTrue => (&[0, 1], None),
+        Op_OpSpecConstantFalse | Op_OpSpecConstant | Op_OpFunctionParameter => (&[0, 1], None),
+        // The operands of the wrapped operation may be literals, as for OpCompositeExtract
+        Op_OpSpecConstantOp => (&[0, 1], None),
+        Op_OpConstantComposite | Op_OpSpecConstantComposite | Op_OpPhi => (&[], Some(0)),
+        Op_OpFunction | Op_OpVariable => (&[0, 1, 3], None),
+        Op_OpFunctionCall | Op_OpAccessChain | Op_OpInBoundsAccessChain => (&[], Some(0)),
+        Op_OpExtInst => (&[0, 1, 2], Some(4)),
+        Op_OpLoad => (&[0, 1, 2], None),
+        Op_OpStore | Op_OpLoopMerge | Op_OpSwitch => (&[0, 1], None),
+        Op_OpLabel | Op_OpSelectionMerge | Op_OpBranch | Op_OpReturnValue => (&[0], None),
+        Op_OpBranchConditional => (&[0, 1, 2], None),
+        _ => (&[], None),
+    }
+}
+
+/// Checks the structure of a module before it is passed to the SPIRV-Cross parser, which
+/// assumes that the id bound is sane, that every instruction fits inside the module and that the
+/// ids it looks up are inside the bound.
+fn validate_words(words: &[u32]) -> Result<(), ErrorCode> {
+    if words.len() < HEADER_WORD_COUNT {
+        return Err(ErrorCode::InvalidSpirv(format!(
+            "length of {} words is too short for a SPIR-V header",
+            words.len()
+        )));
+    }
+
+    let swapped = match words[0] {
+        MAGIC_NUMBER => false,
+        magic if magic == MAGIC_NUMBER.swap_bytes() => true,
+        magic => {
+            return Err(ErrorCode::InvalidSpirv(format!(
+                "invalid magic number {:#010x}",
+                magic
+            )))
+        }
+    };
+    let word = |offset: usize| {
+        if swapped {
+            words[offset].swap_bytes()
+        } else {
+            words[offset]
+        }
+    };
+
+    let bound = word(3);
+    if bound == 0 || bound > MAX_ID_BOUND {
+        return Err(ErrorCode::InvalidSpirv(format!(
+            "id bound {} is outside of 1..={}",
+            bound, MAX_ID_BOUND
+        )));
+    }
+
+    let mut offset = HEADER_WORD_COUNT;
+    while offset < words.len() {
+        let word_count = (word(offset) >> 16) as usize;
+        if word_count == 0 {
+            return Err(ErrorCode::InvalidSpirv(format!(
+                "instruction at word {} has a word count of 0",
+                offset
+            )));
+        }
+        if word_count > words.len() - offset {
+            return Err(ErrorCode::InvalidSpirv(format!(
+                "instruction at word {} has a word count of {} but only {} words remain",
+                offset,
+                word_count,
+                words.len() - offset
+            )));
+        }
+
+        let operands: Cow<[u32]> = if swapped {
+            Cow::Owned(
+                words[offset + 1..offset + word_count]
+                    .iter()
+                    .map(|word| word.swap_bytes())
+                    .collect(),
+            )
+        } else {
+            Cow::Borrowed(&words[offset + 1..offset + word_count])
+        };
+        let (ids, trailing_ids) = id_operands(word(offset) & 0xffff, &operands);
+        let trailing_ids = trailing_ids.map_or(0..0, |first| first..operands.len());
+        let ids = ids.iter().copied().chain(trailing_ids);
+        for id in ids.filter_map(|index| operands.get(index)) {
+            if *id == 0 || *id >= bound {
+                return Err(ErrorCode::InvalidSpirv(format!(
+                    "instruction at word {} refers to id {} outside of the id bound {}",
+                    offset, id, bound
+                )));
+            }
+        }
+        offset += word_count;
+    }
+
+    Ok(())
+}
+
+/// Validates `words` and parses them with `parse`, reporting any failure of the SPIRV-Cross
+/// parser as invalid SPIR-V.
+pub(crate) fn parse_words<T>(
+    words: &[u32],
+    parse: impl FnOnce(&[u32]) -> Result<T, ErrorCode>,
+) -> Result<T, ErrorCode> {
+    if let Err(error) = validate_words(words) {
+        log_debug!("rejected a module of {} words: {:?}", words.len(), error);
+        return Err(error);
+    }
+    parse(words).map_err(|error| {
+        log_debug!(
+            "SPIRV-Cross failed to parse a module of {} words: {:?}",
+            words.len(),
+            error
+        );
+        match error {
+            ErrorCode::CompilationError(message) => ErrorCode::InvalidSpirv(message),
+            ErrorCode::Unhandled => {
+                ErrorCode::InvalidSpirv(String::from("SPIRV-Cross failed to parse the module"))
+            }
+            error => error,
+        }
+    })
+}
+
+/// Copies the bytes of a SPIR-V binary into native words, regardless of their alignment.
+fn copy_words(bytes: &[u8], swapped: bool) -> Vec<u32> {
+    bytes
+        .chunks_exact(WORD_SIZE)
+        .map(|word| {
+            let word = u32::from_ne_bytes([word[0], word[1], word[2], word[3]]);
+            if swapped {
+                word.swap_bytes()
+            } else {
+                word
+            }
+        })
+        .collect()
+}
+
+/// A SPIR-V module which has already been parsed.
+///
+/// Creating an `Ast` from a `ParsedModule` copies the parsed representation instead of parsing
+/// the SPIR-V words again, which is useful when compiling the same module to several targets.
+#[derive(Debug)]
+pub struct ParsedModule {
+    pub(crate) ir: compiler::ParsedIr,
+}
+
+impl ParsedModule {
+    /// Parses a shader module.
+    pub fn parse(module: &Module) -> Result<ParsedModule, ErrorCode> {
+        Ok(ParsedModule {
+            ir: compiler::ParsedIr::new(&module.words)?,
+        })
+    }
+}
+
+pub trait Target {
+    type Data;
+}
+
+/// The state of an `Ast` that can still be modified before compilation.
+#[derive(Debug, Clone)]
+pub enum Parsed {}
+
+/// The state of an `Ast` that has been compiled by `Ast::into_compiled`.
+#[derive(Debug, Clone)]
+pub enum Compiled {}
+
+/// An abstract syntax tree that corresponds to a SPIR-V module.
+///
+/// `TState` is either `Parsed` or `Compiled`. Modifications are only available while parsed,
+/// and queries that depend on the results of compilation are only available once compiled.
+pub struct Ast<TTarget, TState = Parsed>
+where
+    TTarget: Target,
+{
+    pub(crate) compiler: compiler::Compiler<TTarget::Data>,
+    pub(crate) target_type: PhantomData<TTarget>,
+    pub(crate) state: PhantomData<TState>,
+}
+
+pub trait Parse<TTarget>: Sized {
+    fn parse(module: &Module) -> Result<Self, ErrorCode>;
+    fn from_parsed(module: &ParsedModule) -> Result<Self, ErrorCode>;
+}
+
+pub trait Compile<TTarget> {
+    type CompilerOptions;
+
+    fn set_compiler_options(
+        &mut self,
+        compiler_options: &Self::CompilerOptions,
+    ) -> Result<(), ErrorCode>;
+    fn get_compiler_options(&self) -> Self::CompilerOptions;
+    fn set_entry_point(
+        &mut self,
+        name: &str,
+        execution_model: ExecutionModel,
+    ) -> Result<(), ErrorCode>;
+    fn compile(&mut self) -> Result<String, ErrorCode>;
+    fn get_lints(&self) -> Result<Vec<String>, ErrorCode>;
+    /// Hashes the target and every compiler option with a fixed encoding, as
+    /// `Ast::get_reflection_hash` does.
+    fn hash_compiler_options(&self) -> u64;
+    fn compile_into<W: io::Write>(&mut self, writer: W) -> Result<(), ErrorCode>;
+}
+
+impl<TTarget, TState> Ast<TTarget, TState>
+where
+    TTarget: Target,
+{
+    /// Gets a decoration.
+    pub fn get_decoration(&self, id: u32, decoration: Decoration) -> Result<u32, ErrorCode> {
+        self.compiler.get_decoration(id, decoration)
+    }
+
+    /// Gets several decorations of several IDs in one call, which is much faster than calling
+    /// `get_decoration` for each of them when reflecting large modules. The result has one entry
+    /// per pair of ID and decoration, with the decorations of `ids[0]` first, and is `None` where
+    /// the ID lacks the decoration.
+    pub fn get_decorations_bulk(
+        &self,
+        ids: &[u32],
+        decorations: &[Decoration],
+    ) -> Result<Vec<Option<u32>>, ErrorCode> {
+        self.compiler.get_decorations(ids, decorations)
+    }
+
+    /// Gets the string operand of a decoration, such as `HlslSemanticGoogle`. If the decoration
+    /// is not set, an empty string will be returned.
+    pub fn get_decoration_string(
+        &self,
+        id: u32,
+        decoration: Decoration,
+    ) -> Result<String, ErrorCode> {
+        self.compiler.get_decoration_string(id, decoration)
+    }
+
+    /// Checks whether a buffer is the counter buffer of an HLSL `RWStructuredBuffer`, i.e. it
+    /// is referenced by another buffer's `HlslCounterBufferGoogle` decoration.
+    pub fn buffer_is_hlsl_counter_buffer(&self, id: u32) -> Result<bool, ErrorCode> {
+        self.compiler.buffer_is_hlsl_counter_buffer(id)
+    }
+
+    /// Gets the id of the counter buffer associated with an HLSL `RWStructuredBuffer`, if any.
+    pub fn buffer_get_hlsl_counter_buffer(&self, id: u32) -> Result<Option<u32>, ErrorCode> {
+        self.compiler.buffer_get_hlsl_counter_buffer(id)
+    }
+
+    /// Checks whether the entry point reads a separate image without a sampler, e.g. with
+    /// `texelFetch` or size queries, so that `build_dummy_sampler_for_combined_images` would
+    /// add a sampler. This does not modify the module.
+    pub fn needs_dummy_sampler_for_combined_images(&self) -> Result<bool, ErrorCode> {
+        self.compiler.needs_dummy_sampler_for_combined_images()
+    }
+
+    /// Checks whether a variable is a depth image or is used for depth comparisons by the entry
+    /// point, e.g. the separate image and sampler of a shadow map lookup, which need a sampler
+    /// with comparison enabled or a `SamplerComparisonState` in HLSL.
+    pub fn variable_is_depth_or_compare(&self, id: u32) -> Result<bool, ErrorCode> {
+        self.compiler.variable_is_depth_or_compare(id)
+    }
+
+    /// Gets a name. If not defined, an empty string will be returned.
+    pub fn get_name(&mut self, id: u32) -> Result<String, ErrorCode> {
+        self.compiler.get_name(id)
+    }
+
+    /// Gets the name a uniform or storage buffer block is declared with in the output, which the
+    /// backend may have changed from the name in the module to avoid collisions. This is the
+    /// name to look the block up by, e.g. with `glGetUniformBlockIndex`, and is only final once
+    /// `compile` has been called.
+    pub fn get_remapped_declared_block_name(&self, id: u32) -> Result<String, ErrorCode> {
+        self.compiler.get_remapped_declared_block_name(id)
+    }
+
+    /// Serializes the module back into SPIR-V words, with the names and decorations it has now,
+    /// so edits like `set_name` and `set_decoration` can be saved without a textual backend.
+    ///
+    /// Every name is rewritten, as are the decorations SPIRV-Cross keeps the operands of, such as
+    /// `Binding`, `Location` and `Offset`. Other decorations, like `HlslSemanticGoogle`, are kept
+    /// as they were parsed, and edits to anything other than names and decorations are not
+    /// applied. Modules with decoration groups cannot be serialized.
+    pub fn to_spirv_words(&self) -> Result<Vec<u32>, ErrorCode> {
+        self.serialize_words(false)
+    }
+
+    /// Serializes the module as `to_spirv_words` does. With `keep_decoration_groups`, decoration
+    /// groups are kept as they were parsed instead of failing, next to the decorations they
+    /// applied, which SPIRV-Cross records on each target. The output then is not a valid module,
+    /// but still covers every instruction and edit, which is all `get_reflection_hash` needs.
+    fn serialize_words(&self, keep_decoration_groups: bool) -> Result<Vec<u32>, ErrorCode> {
+        let words = self.compiler.get_spirv()?;
+        let mut instructions = Vec::new();
+        let mut struct_member_counts = HashMap::new();
+        let mut offset = HEADER_WORD_COUNT;
+        while offset < words.len() {
+            let word_count = (words[offset] >> 16) as usize;
+            let instruction = &words[offset..offset + word_count];
+            offset += word_count;
+
+            let rewritten = match instruction[0] & 0xffff {
+                OP_DECORATION_GROUP if keep_decoration_groups => false,
+                OP_DECORATION_GROUP => {
+                    return Err(ErrorCode::CompilationError(String::from(
+                        "modules with decoration groups cannot be serialized",
+                    )))
+                }
+                OP_TYPE_STRUCT => {
+                    struct_member_counts.insert(instruction[1], word_count as u32 - 2);
+                    false
+                }
+                OP_NAME | OP_MEMBER_NAME => true,
+                OP_DECORATE => is_rewritten_decoration(instruction[2]),
+                OP_MEMBER_DECORATE => is_rewritten_decoration(instruction[3]),
+                _ => false,
+            };
+            if !rewritten {
+                instructions.push(instruction);
+            }
+        }
+
+        // IDs created by SPIRV-Cross, such as a dummy sampler, are beyond the bound of the module
+        let bound = words[3];
+        let mut ids = self.compiler.get_meta_ids()?;
+        ids.retain(|&id| id < bound);
+        ids.sort_unstable();
+        let mut names = Vec::new();
+        let mut decorations = Vec::new();
+        for id in ids {
+            let name = self.compiler.get_name(id)?;
+            if !name.is_empty() {
+                encode_instruction(&mut names, OP_NAME, &[id], Some(&name));
+            }
+            for &(decoration, has_operand) in REWRITTEN_DECORATIONS {
+                if self.has_decoration(id, decoration)? {
+                    let mut operands = vec![id, decoration.as_raw() as u32];
+                    if has_operand {
+                        operands.push(self.get_decoration(id, decoration)?);
+                    }
+                    encode_instruction(&mut decorations, OP_DECORATE, &operands, None);
+                }
+            }
+
+            let member_count = struct_member_counts.get(&id).copied().unwrap_or(0);
+            for index in 0..member_count {
+                let name = self.get_member_name(id, index)?;
+                if !name.is_empty() {
+                    encode_instruction(&mut names, OP_MEMBER_NAME, &[id, index], Some(&name));
+                }
+                for &(decoration, has_operand) in REWRITTEN_DECORATIONS {
+                    if self.has_member_decoration(id, index, decoration)? {
+                        let mut operands = vec![id, index, decoration.as_raw() as u32];
+                        if has_operand {
+                            operands.push(self.get_member_decoration(id, index, decoration)?);
+                        }
+                        encode_instruction(&mut decorations, OP_MEMBER_DECORATE, &operands, None);
+                    }
+                }
+            }
+        }
+
+        // Names follow the debug source instructions, and decorations follow the annotations
+        // which are kept
+        let mut output = words[..HEADER_WORD_COUNT].to_vec();
+        let mut names = Some(names);
+        let mut decorations = Some(decorations);
+        for instruction in instructions {
+            let opcode = instruction[0] & 0xffff;
+            if !is_before_names(opcode) {
+                output.extend(names.take().unwrap_or_default());
+            }
+            if !is_before_names(opcode) && !is_debug_or_annotation(opcode) {
+                output.extend(decorations.take().unwrap_or_default());
+            }
+            output.extend_from_slice(instruction);
+        }
+        output.extend(names.unwrap_or_default());
+        output.extend(decorations.unwrap_or_default());
+        Ok(output)
+    }
+
+    /// Gets entry points.
+    pub fn get_entry_points(&self) -> Result<Vec<EntryPoint>, ErrorCode> {
+        self.compiler.get_entry_points()
+    }
+
+    /// Calls `visitor` with each entry point, in the order of `get_entry_points`. Names are read
+    /// into one buffer reused between calls instead of allocating a `String` for each.
+    pub fn visit_entry_points<F>(&self, visitor: F) -> Result<(), ErrorCode>
+    where
+        F: FnMut(EntryPointRef),
+    {
+        self.compiler.visit_entry_points(visitor)
+    }
+
+    /// Checks whether the module has an entry point for `execution_model`.
+    pub fn has_stage(&self, execution_model: ExecutionModel) -> Result<bool, ErrorCode> {
+        let mut found = false;
+        self.visit_entry_points(|entry_point| {
+            found |= entry_point.execution_model == execution_model;
+        })?;
+        Ok(found)
+    }
+
+    /// Checks whether the module has a compute entry point, either `GlCompute` or an OpenCL
+    /// `Kernel`.
+    pub fn is_compute(&self) -> Result<bool, ErrorCode> {
+        Ok(self.has_stage(ExecutionModel::GlCompute)? || self.has_stage(ExecutionModel::Kernel)?)
+    }
+
+    /// Checks whether the module has a tessellation control or evaluation entry point.
+    pub fn is_tessellation_shader(&self) -> Result<bool, ErrorCode> {
+        Ok(self.has_stage(ExecutionModel::TessellationControl)?
+            || self.has_stage(ExecutionModel::TessellationEvaluation)?)
+    }
+
+    /// Gets the stage shared by every entry point of the module. Returns `None` when the module
+    /// has no entry points, or entry points for more than one stage.
+    pub fn primary_stage(&self) -> Result<Option<ExecutionModel>, ErrorCode> {
+        let mut stage = None;
+        let mut mixed = false;
+        self.visit_entry_points(|entry_point| match stage {
+            None => stage = Some(entry_point.execution_model),
+            Some(execution_model) => mixed |= execution_model != entry_point.execution_model,
+        })?;
+        Ok(if mixed { None } else { stage })
+    }
+
+    /// Gets the name and execution model of the entry point that reflection and compilation
+    /// currently apply to. This is the first entry point unless another was selected through
+    /// the compiler options.
+    pub fn get_current_entry_point(&self) -> Result<(String, ExecutionModel), ErrorCode> {
+        self.compiler.get_current_entry_point()
+    }
+
+    /// Gets active buffer ragnes.  Useful for push constants.
+    pub fn get_active_buffer_ranges(&self, id: u32) -> Result<Vec<BufferRange>, ErrorCode> {
+        self.compiler.get_active_buffer_ranges(id)
+    }
+
+    /// Gets the used portion of each push constant block of the current entry point, which can
+    /// be used for tight push constant ranges in a pipeline layout. Blocks without used members
+    /// are left out.
+    pub fn get_push_constant_ranges(&self) -> Result<Vec<PushConstantRange>, ErrorCode> {
+        let (entry_point_name, execution_model) = self.get_current_entry_point()?;
+        let resources =
+            self.get_shader_resources_for_entry_point(&entry_point_name, execution_model)?;
+
+        let mut ranges = Vec::new();
+        for resource in resources.push_constant_buffers {
+            let buffer_ranges = self.get_active_buffer_ranges(resource.id)?;
+            let start = buffer_ranges.iter().map(|range| range.offset).min();
+            let end = buffer_ranges
+                .iter()
+                .map(|range| range.offset + range.range)
+                .max();
+            if let (Some(start), Some(end)) = (start, end) {
+                ranges.push(PushConstantRange {
+                    id: resource.id,
+                    offset: start,
+                    size: end - start,
+                });
+            }
+        }
+        Ok(ranges)
+    }
+
+    /// Gets the instance name of the push constant block of the current entry point, such as
+    /// `pc` in `pc.member`, or `None` if it has none. Blocks without a name are emitted with a
+    /// fallback name like `_13`, and the backend may rename a block to avoid collisions, so the
+    /// result is only final once `compile` has been called.
+    pub fn get_push_constant_instance_name(&self) -> Result<Option<String>, ErrorCode> {
+        let (entry_point_name, execution_model) = self.get_current_entry_point()?;
+        let resources =
+            self.get_shader_resources_for_entry_point(&entry_point_name, execution_model)?;
+        match resources.push_constant_buffers.first() {
+            Some(resource) => match self.compiler.get_name(resource.id)? {
+                name if name.is_empty() => Ok(Some(format!("_{}", resource.id))),
+                name => Ok(Some(name)),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Gets all specialization constants.
+    pub fn get_specialization_constants(&self) -> Result<Vec<SpecializationConstant>, ErrorCode> {
+        self.compiler.get_specialization_constants()
+    }
+
+    /// Gets the specialization constants that a scalar integer or boolean constant depends on,
+    /// directly or through `OpSpecConstantOp` expressions, sorted by ID. A specialization
+    /// constant depends on itself, while constants which cannot be specialized depend on none.
+    pub fn get_specialization_constant_dependencies(
+        &self,
+        id: u32,
+    ) -> Result<Vec<SpecializationConstant>, ErrorCode> {
+        let constant_ids = self
+            .get_specialization_constants()?
+            .into_iter()
+            .map(|constant| (constant.id, constant.constant_id))
+            .collect::<HashMap<_, _>>();
+
+        let mut dependencies = HashMap::<u32, BTreeMap<u32, u32>>::new();
+        for (constant_id, _, constant) in read_scalar_constants(&self.compiler.get_spirv()?) {
+            let mut constant_dependencies = BTreeMap::new();
+            match constant {
+                ScalarConstant::Value(_) => {}
+                ScalarConstant::Specialization(_) => {
+                    if let Some(&spec_id) = constant_ids.get(&constant_id) {
+                        constant_dependencies.insert(constant_id, spec_id);
+                    }
+                }
+                ScalarConstant::Operation { operands, .. } => {
+                    for operand in operands {
+                        if let Some(operand_dependencies) = dependencies.get(&operand) {
+                            constant_dependencies.extend(operand_dependencies);
+                        }
+                    }
+                }
+            }
+
+            if constant_id == id {
+                return Ok(constant_dependencies
+                    .into_iter()
+                    .map(|(id, constant_id)| SpecializationConstant { id, constant_id })
+                    .collect());
+            }
+            dependencies.insert(constant_id, constant_dependencies);
+        }
+
+        Err(ErrorCode::CompilationError(format!(
+            "{} is not a scalar integer or boolean constant",
+            id
+        )))
+    }
+
+    /// Evaluates a scalar integer or boolean constant, including `OpSpecConstantOp` expressions,
+    /// as if the specialization constants were given the values in `values`, by constant ID.
+    /// Specialization constants missing from `values` keep their default values from the module.
+    ///
+    /// The result is truncated to the width of the constant's type, so negative values are in
+    /// two's complement, and booleans are 0 or 1.
+    pub fn evaluate_specialization_constant(
+        &self,
+        id: u32,
+        values: &HashMap<u32, u64>,
+    ) -> Result<u64, ErrorCode> {
+        let constant_ids = self
+            .get_specialization_constants()?
+            .into_iter()
+            .map(|constant| (constant.id, constant.constant_id))
+            .collect::<HashMap<_, _>>();
+
+        let mut results = HashMap::new();
+        for (constant_id, ty, constant) in read_scalar_constants(&self.compiler.get_spirv()?) {
+            let value = match constant {
+                ScalarConstant::Value(value) => value,
+                ScalarConstant::Specialization(default) => constant_ids
+                    .get(&constant_id)
+                    .and_then(|spec_id| values.get(spec_id))
+                    .copied()
+                    .unwrap_or(default),
+                ScalarConstant::Operation { opcode, operands } => {
+                    let operands = operands
+                        .iter()
+                        .map(|operand| {
+                            results.get(operand).copied().ok_or_else(|| {
+                                ErrorCode::CompilationError(format!(
+                                    "operand {} of {} is not a scalar integer or boolean constant",
+                                    operand, constant_id
+                                ))
+                            })
+                        })
+                        .collect::<Result<Vec<_>, _>>();
+                    match operands.and_then(|operands| evaluate_operation(opcode, &operands)) {
+                        Ok(value) => value,
+                        // Only the constants `id` depends on have to be evaluated
+                        Err(error) if constant_id == id => return Err(error),
+                        Err(_) => continue,
+                    }
+                }
+            };
+
+            let value = if ty.width == 1 {
+                (value != 0) as u64
+            } else {
+                ty.truncate(value)
+            };
+            if constant_id == id {
+                return Ok(value);
+            }
+            results.insert(constant_id, (value, ty));
+        }
+
+        Err(ErrorCode::CompilationError(format!(
+            "{} is not a scalar integer or boolean constant",
+            id
+        )))
+    }
+
+    /// Gets shader resources, in the order they are declared in the module.
+    pub fn get_shader_resources(&self) -> Result<ShaderResources, ErrorCode> {
+        self.compiler.get_shader_resources()
+    }
+
+    /// Gets shader resources sorted with `sort_shader_resources`, whose order is stable across
+    /// edits of the module which keep the bindings, locations and names of the resources, as
+    /// snapshot tests need.
+    pub fn get_sorted_shader_resources(&self) -> Result<ShaderResources, ErrorCode> {
+        let mut resources = self.get_shader_resources()?;
+        self.sort_shader_resources(&mut resources)?;
+        Ok(resources)
+    }
+
+    /// Sorts the resources of each kind by descriptor set, binding, location and name, so their
+    /// order only changes when one of these does, rather than with the order of the module.
+    /// Resources which compare equal, such as the built-in members of one block, keep their
+    /// relative order. The decorations are those of this `Ast`, which `resources` must come from.
+    pub fn sort_shader_resources(&self, resources: &mut ShaderResources) -> Result<(), ErrorCode> {
+        let key = |resource: &Resource| -> Result<_, ErrorCode> {
+            Ok((
+                self.get_decoration(resource.id, Decoration::DescriptorSet)?,
+                self.get_decoration(resource.id, Decoration::Binding)?,
+                self.get_decoration(resource.id, Decoration::Location)?,
+                resource.name.clone(),
+            ))
+        };
+
+        for resources in [
+            &mut resources.uniform_buffers,
+            &mut resources.storage_buffers,
+            &mut resources.stage_inputs,
+            &mut resources.stage_outputs,
+            &mut resources.subpass_inputs,
+            &mut resources.storage_images,
+            &mut resources.sampled_images,
+            &mut resources.atomic_counters,
+            &mut resources.push_constant_buffers,
+            &mut resources.separate_images,
+            &mut resources.separate_samplers,
+        ]
+        .iter_mut()
+        {
+            sort_by_keys(resources, key)?;
+        }
+        for builtins in [
+            &mut resources.builtin_inputs,
+            &mut resources.builtin_outputs,
+        ]
+        .iter_mut()
+        {
+            sort_by_keys(builtins, |builtin| key(&builtin.resource))?;
+        }
+        Ok(())
+    }
+
+    /// Gets the resources of one kind, in the order they are declared in the module. Only that
+    /// list is copied across the FFI and has its names read, so a tool which
+    /// needs a single kind does not pay for all of `get_shader_resources`.
+    pub fn get_resources(&self, kind: ResourceKind) -> Result<Vec<Resource>, ErrorCode> {
+        self.compiler.get_shader_resource_list(kind)
+    }
+
+    /// Gets the uniform buffers, as `get_resources` does.
+    pub fn get_uniform_buffers(&self) -> Result<Vec<Resource>, ErrorCode> {
+        self.get_resources(ResourceKind::UniformBuffers)
+    }
+
+    /// Gets the storage buffers, as `get_resources` does.
+    pub fn get_storage_buffers(&self) -> Result<Vec<Resource>, ErrorCode> {
+        self.get_resources(ResourceKind::StorageBuffers)
+    }
+
+    /// Gets the stage inputs, excluding built-ins, as `get_resources` does.
+    pub fn get_stage_inputs(&self) -> Result<Vec<Resource>, ErrorCode> {
+        self.get_resources(ResourceKind::StageInputs)
+    }
+
+    /// Gets the stage outputs, excluding built-ins, as `get_resources` does.
+    pub fn get_stage_outputs(&self) -> Result<Vec<Resource>, ErrorCode> {
+        self.get_resources(ResourceKind::StageOutputs)
+    }
+
+    /// Gets the subpass inputs, as `get_resources` does.
+    pub fn get_subpass_inputs(&self) -> Result<Vec<Resource>, ErrorCode> {
+        self.get_resources(ResourceKind::SubpassInputs)
+    }
+
+    /// Gets the storage images, as `get_resources` does.
+    pub fn get_storage_images(&self) -> Result<Vec<Resource>, ErrorCode> {
+        self.get_resources(ResourceKind::StorageImages)
+    }
+
+    /// Gets the combined image samplers, as `get_resources` does.
+    pub fn get_sampled_images(&self) -> Result<Vec<Resource>, ErrorCode> {
+        self.get_resources(ResourceKind::SampledImages)
+    }
+
+    /// Gets the atomic counters, as `get_resources` does.
+    pub fn get_atomic_counters(&self) -> Result<Vec<Resource>, ErrorCode> {
+        self.get_resources(ResourceKind::AtomicCounters)
+    }
+
+    /// Gets the push constant blocks, as `get_resources` does.
+    pub fn get_push_constant_buffers(&self) -> Result<Vec<Resource>, ErrorCode> {
+        self.get_resources(ResourceKind::PushConstantBuffers)
+    }
+
+    /// Gets the separate images, as `get_resources` does.
+    pub fn get_separate_images(&self) -> Result<Vec<Resource>, ErrorCode> {
+        self.get_resources(ResourceKind::SeparateImages)
+    }
+
+    /// Gets the separate samplers, as `get_resources` does.
+    pub fn get_separate_samplers(&self) -> Result<Vec<Resource>, ErrorCode> {
+        self.get_resources(ResourceKind::SeparateSamplers)
+    }
+
+    /// Calls `visitor` with each resource of the given kinds, in the order of
+    /// `ShaderResources::iter`. Unlike `get_shader_resources`, no vectors are built and names are
+    /// read into one buffer reused between calls. Built-ins are not visited.
+    pub fn visit_shader_resources<F>(
+        &self,
+        kinds: impl Into<ResourceKinds>,
+        visitor: F,
+    ) -> Result<(), ErrorCode>
+    where
+        F: FnMut(ResourceKind, ResourceRef),
+    {
+        self.compiler.visit_shader_resources(kinds.into(), visitor)
+    }
+
+    /// Gets the shader resources statically used by an entry point, including push constant
+    /// blocks. The specialization constants it uses are given by
+    /// `get_specialization_constants_for_entry_point`.
+    pub fn get_shader_resources_for_entry_point(
+        &self,
+        entry_point_name: &str,
+        execution_model: ExecutionModel,
+    ) -> Result<ShaderResources, ErrorCode> {
+        self.compiler
+            .get_shader_resources_for_entry_point(entry_point_name, execution_model)
+    }
+
+    /// Gets the specialization constants an entry point refers to from the functions it calls,
+    /// its interface or its execution modes, directly or through the constants and types
+    /// declared with them. The work group size specialization constants are included for
+    /// compute entry points, as SPIRV-Cross applies the `WorkgroupSize` built-in to all of them.
+    pub fn get_specialization_constants_for_entry_point(
+        &self,
+        entry_point_name: &str,
+        execution_model: ExecutionModel,
+    ) -> Result<Vec<SpecializationConstant>, ErrorCode> {
+        let references = read_entry_point_references(
+            &self.compiler.get_spirv()?,
+            entry_point_name,
+            execution_model.as_raw() as u32,
+        )?
+        .ok_or_else(|| {
+            ErrorCode::InvalidOptions(format!(
+                "entry point `{}` ({:?}) is not in the module",
+                entry_point_name, execution_model
+            ))
+        })?;
+
+        let mut work_group_size_ids = Vec::new();
+        if let ExecutionModel::GlCompute | ExecutionModel::Kernel = execution_model {
+            let WorkGroupSizeSpecializationConstants { x, y, z } =
+                self.get_work_group_size_specialization_constants()?;
+            work_group_size_ids.extend(vec![x.id, y.id, z.id]);
+        }
+        Ok(self
+            .get_specialization_constants()?
+            .into_iter()
+            .filter(|constant| {
+                references.contains(&constant.id) || work_group_size_ids.contains(&constant.id)
+            })
+            .collect())
+    }
+
+    /// Gets every resource of the module, in the order of `ShaderResources::iter`, flagged with
+    /// whether an entry point statically uses it as `get_shader_resources_for_entry_point`
+    /// decides. Built-ins are not included.
+    pub fn get_shader_resources_with_activity(
+        &self,
+        entry_point_name: &str,
+        execution_model: ExecutionModel,
+    ) -> Result<Vec<ResourceActivity>, ErrorCode> {
+        let active_ids = self
+            .get_shader_resources_for_entry_point(entry_point_name, execution_model)?
+            .iter()
+            .map(|(_, resource)| resource.id)
+            .collect::<HashSet<_>>();
+        Ok(self
+            .get_shader_resources()?
+            .iter()
+            .map(|(kind, resource)| ResourceActivity {
+                kind,
+                resource: resource.clone(),
+                is_active_in_entry_point: active_ids.contains(&resource.id),
+            })
+            .collect())
+    }
+
+    /// Gets the location, component packing, dual-source blending index and interpolation of a
+    /// stage input or output.
+    pub fn get_interface_variable(
+        &self,
+        resource: &Resource,
+    ) -> Result<InterfaceVariable, ErrorCode> {
+        let base_type = self.get_type(resource.base_type_id)?;
+        let optional_decoration = |decoration| -> Result<Option<u32>, ErrorCode> {
+            Ok(if self.has_decoration(resource.id, decoration)? {
+                Some(self.get_decoration(resource.id, decoration)?)
+            } else {
+                None
+            })
+        };
+        Ok(InterfaceVariable {
+            id: resource.id,
+            name: resource.name.clone(),
+            location: optional_decoration(Decoration::Location)?,
+            component: optional_decoration(Decoration::Component)?,
+            index: self.get_decoration(resource.id, Decoration::Index)?,
+            vecsize: get_vecsize(&base_type),
+            base_type,
+            flat: self.get_decoration(resource.id, Decoration::Flat)? != 0,
+            no_perspective: self.get_decoration(resource.id, Decoration::NoPerspective)? != 0,
+        })
+    }
+
+    /// Gets the members of a stage input or output block, such as `out VertexOut { ... }`, with
+    /// the locations and interpolation they are given by their own decorations or the block's.
+    pub fn get_interface_block_members(
+        &self,
+        resource: &Resource,
+    ) -> Result<Vec<InterfaceBlockMember>, ErrorCode> {
+        let member_types = match self.get_type(resource.base_type_id)? {
+            Type::Struct { member_types, .. } => member_types,
+            _ => {
+                return Err(ErrorCode::CompilationError(format!(
+                    "`{}` is not an interface block",
+                    resource.name
+                )))
+            }
+        };
+        let block_id = resource.base_type_id;
+        let flat = self.get_decoration(resource.id, Decoration::Flat)? != 0;
+        let no_perspective = self.get_decoration(resource.id, Decoration::NoPerspective)? != 0;
+
+        let optional_member_decoration = |index, decoration| {
+            if self.has_member_decoration(block_id, index, decoration)? {
+                self.get_member_decoration(block_id, index, decoration)
+                    .map(Some)
+            } else {
+                Ok(None)
+            }
+        };
+
+        let mut members = Vec::new();
+        let mut next_location = if self.has_decoration(resource.id, Decoration::Location)? {
+            Some(self.get_decoration(resource.id, Decoration::Location)?)
+        } else {
+            None
+        };
+        for (index, type_id) in (0..).zip(member_types) {
+            let built_in = self.has_member_decoration(block_id, index, Decoration::BuiltIn)?;
+            let location = match optional_member_decoration(index, Decoration::Location)? {
+                Some(location) => Some(location),
+                None if built_in => None,
+                None => next_location,
+            };
+            if !built_in {
+                if let Some(location) = location {
+                    next_location = Some(location + self.get_location_count(type_id)?);
+                }
+            }
+
+            let member_flat = self.get_member_decoration(block_id, index, Decoration::Flat)? != 0;
+            let member_no_perspective =
+                self.get_member_decoration(block_id, index, Decoration::NoPerspective)? != 0;
+            let base_type = self.get_type(type_id)?;
+            members.push(InterfaceBlockMember {
+                index,
+                name: self.get_member_name(block_id, index)?,
+                type_id,
+                built_in,
+                location,
+                component: optional_member_decoration(index, Decoration::Component)?,
+                vecsize: get_vecsize(&base_type),
+                base_type,
+                flat: flat || member_flat,
+                no_perspective: no_perspective || member_no_perspective,
+            });
+        }
+        Ok(members)
+    }
+
+    /// Gets the vertex attributes read by the stage inputs of a vertex shader, sorted by location.
+    /// Matrices and arrays are split into one attribute per column or element at consecutive
+    /// locations, so a `mat4` input becomes four `4 x f32` attributes. 64-bit vectors with more
+    /// than two components consume two locations each.
+    pub fn get_vertex_attributes(&self) -> Result<Vec<VertexAttribute>, ErrorCode> {
+        let mut attributes = Vec::new();
+        for resource in self.get_shader_resources()?.stage_inputs {
+            use VertexComponentType::*;
+            let (component_type, component_count, columns) =
+                match self.get_type(resource.base_type_id)? {
+                    Type::SByte { vecsize, .. } => (I8, vecsize, 1),
+                    Type::UByte { vecsize, .. } => (U8, vecsize, 1),
+                    Type::Short { vecsize, .. } => (I16, vecsize, 1),
+                    Type::UShort { vecsize, .. } => (U16, vecsize, 1),
+                    Type::Int {
+                        vecsize, columns, ..
+                    } => (I32, vecsize, columns),
+                    Type::UInt {
+                        vecsize, columns, ..
+                    } => (U32, vecsize, columns),
+                    Type::Int64 { vecsize, .. } => (I64, vecsize, 1),
+                    Type::UInt64 { vecsize, .. } => (U64, vecsize, 1),
+                    Type::Half {
+                        vecsize, columns, ..
+                    } => (F16, vecsize, columns),
+                    Type::Float {
+                        vecsize, columns, ..
+                    } => (F32, vecsize, columns),
+                    Type::Double {
+                        vecsize, columns, ..
+                    } => (F64, vecsize, columns),
+                    _ => {
+                        return Err(ErrorCode::CompilationError(format!(
+                            "stage input {} has no vertex format",
+                            resource.name
+                        )))
+                    }
+                };
+
+            // Vertex inputs cannot be runtime-sized, and arrays sized by specialization
+            // constants use the constant's current value
+            let elements = match self.get_descriptor_count(resource.id)? {
+                DescriptorCount::Fixed(count) => count,
+                DescriptorCount::RuntimeSized => {
+                    return Err(ErrorCode::InvalidSpirv(format!(
+                        "stage input {} is runtime-sized",
+                        resource.name
+                    )))
+                }
+            };
+
+            let format = VertexFormat {
+                component_type,
+                component_count,
+            };
+            let locations = if component_type.size() == 8 && component_count > 2 {
+                2
+            } else {
+                1
+            };
+            let location = self.get_decoration(resource.id, Decoration::Location)?;
+            let component = self.get_decoration(resource.id, Decoration::Component)?;
+            for i in 0..elements * columns {
+                attributes.push(VertexAttribute {
+                    id: resource.id,
+                    name: resource.name.clone(),
+                    location: location + i * locations,
+                    component,
+                    format,
+                });
+            }
+        }
+
+        attributes.sort_by_key(|attribute| (attribute.location, attribute.component));
+        Ok(attributes)
+    }
+
+    /// Gets the number of descriptors consumed by the resource with the given ID. Arrays sized by
+    /// specialization constants use the constant's current value.
+    pub fn get_descriptor_count(&self, id: u32) -> Result<DescriptorCount, ErrorCode> {
+        self.compiler.get_descriptor_count(id)
+    }
+
+    /// Gets how a storage image is accessed. The `NonWritable` and `NonReadable` decorations take
+    /// precedence, and otherwise the loads and stores of every function in the module decide,
+    /// with atomics counting as both. Images which are neither read nor written, or only used in
+    /// ways the analysis does not follow, are reported as read-write.
+    pub fn get_image_access(&self, id: u32) -> Result<ImageAccess, ErrorCode> {
+        if self.has_decoration(id, Decoration::NonWritable)? {
+            return Ok(ImageAccess::ReadOnly);
+        }
+        if self.has_decoration(id, Decoration::NonReadable)? {
+            return Ok(ImageAccess::WriteOnly);
+        }
+
+        let usage = read_image_usage(&self.compiler.get_spirv()?);
+        Ok(match usage.get(&id) {
+            Some((true, false)) => ImageAccess::ReadOnly,
+            Some((false, true)) => ImageAccess::WriteOnly,
+            _ => ImageAccess::ReadWrite,
+        })
+    }
+
+    /// Gets which separate images are sampled with which separate samplers, sorted by image and
+    /// sampler. Every function of the module is considered, following loads, access chains and
+    /// function arguments back to the variables, so an image combined with several samplers
+    /// appears once per sampler.
+    pub fn get_image_sampler_pairs(&self) -> Result<Vec<ImageSamplerPair>, ErrorCode> {
+        let pairs = read_image_sampler_pairs(&self.compiler.get_spirv()?);
+        Ok(pairs.into_iter().collect())
+    }
+
+    /// Measures the complexity of every function of the module, such as to flag pathological
+    /// shaders in an asset pipeline. Functions which no entry point calls are included.
+    pub fn get_shader_statistics(&self) -> Result<ShaderStatistics, ErrorCode> {
+        Ok(read_statistics(&self.compiler.get_spirv()?))
+    }
+
+    /// Gets which resources bound to descriptor sets rely on descriptor indexing, in the order of
+    /// `ShaderResources::iter`. Stage inputs, stage outputs and push constant blocks are left out.
+    pub fn get_descriptor_indexing(&self) -> Result<Vec<DescriptorIndexing>, ErrorCode> {
+        let non_uniform = read_non_uniform_indexing(&self.compiler.get_spirv()?);
+        let mut indexing = Vec::new();
+        for (kind, resource) in self.get_shader_resources()?.iter() {
+            match kind {
+                ResourceKind::StageInputs
+                | ResourceKind::StageOutputs
+                | ResourceKind::PushConstantBuffers => continue,
+                _ => {}
+            }
+
+            indexing.push(DescriptorIndexing {
+                kind,
+                runtime_sized: self.get_descriptor_count(resource.id)?
+                    == DescriptorCount::RuntimeSized,
+                non_uniform_indexing: non_uniform.contains(&resource.id),
+                resource: resource.clone(),
+            });
+        }
+        Ok(indexing)
+    }
+
+    /// Gets the resources bound to descriptor sets, grouped by set and sorted by set and binding.
+    /// Stage inputs, stage outputs and push constant blocks have no descriptor set and are left
+    /// out.
+    pub fn get_descriptor_sets(&self) -> Result<Vec<DescriptorSet>, ErrorCode> {
+        let mut stages_by_id = HashMap::<u32, Vec<ExecutionModel>>::new();
+        for entry_point in self.get_entry_points()? {
+            let resources = self.get_shader_resources_for_entry_point(
+                &entry_point.name,
+                entry_point.execution_model,
+            )?;
+            for (_, resource) in resources.iter() {
+                stages_by_id
+                    .entry(resource.id)
+                    .or_default()
+                    .push(entry_point.execution_model);
+            }
+        }
+
+        let mut sets = BTreeMap::<u32, Vec<BindingInfo>>::new();
+        for (kind, resource) in self.get_shader_resources()?.iter() {
+            match kind {
+                ResourceKind::StageInputs
+                | ResourceKind::StageOutputs
+                | ResourceKind::PushConstantBuffers => continue,
+                _ => {}
+            }
+
+            let mut stages = stages_by_id.remove(&resource.id).unwrap_or_default();
+            stages.sort();
+            stages.dedup();
+            sets.entry(self.get_decoration(resource.id, Decoration::DescriptorSet)?)
+                .or_default()
+                .push(BindingInfo {
+                    binding: self.get_decoration(resource.id, Decoration::Binding)?,
+                    kind,
+                    resource: resource.clone(),
+                    count: self.get_descriptor_count(resource.id)?,
+                    stages,
+                });
+        }
+
+        Ok(sets
+            .into_iter()
+            .map(|(set, mut bindings)| {
+                bindings.sort_by_key(|binding| (binding.binding, binding.resource.id));
+                DescriptorSet { set, bindings }
+            })
+            .collect())
+    }
+
+    /// Merges the descriptor sets of several modules, such as all permutations of a material,
+    /// into one layout which can be shared by all of them. Each binding records which of the
+    /// modules declare it, the largest descriptor count any of them uses and the stages which
+    /// use it in any module.
+    ///
+    /// Fails if modules, or resources within one module, declare the same binding with
+    /// different kinds of resource.
+    pub fn merge_descriptor_sets(asts: &[&Self]) -> Result<Vec<MergedDescriptorSet>, ErrorCode> {
+        let mut sets = BTreeMap::<u32, BTreeMap<u32, MergedBindingInfo>>::new();
+        for (index, ast) in asts.iter().enumerate() {
+            for set in ast.get_descriptor_sets()? {
+                let bindings = sets.entry(set.set).or_default();
+                for info in set.bindings {
+                    let empty = MergedBindingInfo {
+                        binding: info.binding,
+                        kind: info.kind,
+                        count: DescriptorCount::Fixed(0),
+                        stages: Vec::new(),
+                        usage_mask: vec![false; asts.len()],
+                    };
+                    let merged = bindings.entry(info.binding).or_insert(empty);
+                    if merged.kind != info.kind {
+                        return Err(ErrorCode::CompilationError(format!(
+                            "binding {} of set {} is declared as both {:?} and {:?}",
+                            info.binding, set.set, merged.kind, info.kind
+                        )));
+                    }
+                    merged.count = match (merged.count, info.count) {
+                        (DescriptorCount::Fixed(a), DescriptorCount::Fixed(b)) => {
+                            DescriptorCount::Fixed(a.max(b))
+                        }
+                        _ => DescriptorCount::RuntimeSized,
+                    };
+                    merged.stages.extend(info.stages);
+                    merged.usage_mask[index] = true;
+                }
+            }
+        }
+
+        Ok(sets
+            .into_iter()
+            .map(|(set, bindings)| MergedDescriptorSet {
+                set,
+                bindings: bindings
+                    .into_iter()
+                    .map(|(_, mut binding)| {
+                        binding.stages.sort();
+                        binding.stages.dedup();
+                        binding
+                    })
+                    .collect(),
+            })
+            .collect())
+    }
+
+    /// Gets the SPIR-V type associated with an ID.
+    pub fn get_type(&self, id: u32) -> Result<Type, ErrorCode> {
+        self.compiler.get_type(id)
+    }
+
+    /// Gets the type a pointer type points to, through any number of pointers. Other types are
+    /// returned as they are.
+    ///
+    /// The `type_id` of a `Resource` is usually a pointer type, whose `get_type` mirrors the
+    /// pointee but whose ID cannot be used to look up member names or decorations.
+    pub fn resolve_pointee_type(&self, id: u32) -> Result<u32, ErrorCode> {
+        let mut id = id;
+        while let Some((true, pointee)) = self.compiler.get_type_parent(id)? {
+            id = pointee;
+        }
+        Ok(id)
+    }
+
+    /// Gets the element type of an array type, through all of its dimensions, e.g. the struct of
+    /// an array of blocks. Other types, including pointers to arrays, are returned as they are.
+    pub fn strip_arrays(&self, id: u32) -> Result<u32, ErrorCode> {
+        let mut id = id;
+        while let Some((false, element)) = self.compiler.get_type_parent(id)? {
+            id = element;
+        }
+        Ok(id)
+    }
+
+    /// Gets the struct type behind any pointers and arrays wrapping a type, as `base_type_id`
+    /// is for a `Resource`, or `None` if the type does not wrap a struct.
+    pub fn underlying_struct(&self, id: u32) -> Result<Option<u32>, ErrorCode> {
+        let mut id = id;
+        while let Some((_, parent)) = self.compiler.get_type_parent(id)? {
+            id = parent;
+        }
+        Ok(match self.get_type(id)? {
+            Type::Struct { .. } => Some(id),
+            _ => None,
+        })
+    }
+
+    /// Gets the component type of a scalar, vector or matrix type, or an array of them, such as
+    /// a 32-bit float for `vec4[2]`. Returns `None` for other types.
+    pub fn get_basic_type(&self, id: u32) -> Result<Option<BasicType>, ErrorCode> {
+        Ok(get_basic_type(&self.get_type(id)?))
+    }
+
+    /// Gets the component type shared by every member of a struct, recursing into nested
+    /// structs, or `None` if the members have different component types. Other types give the
+    /// same result as `get_basic_type`.
+    pub fn get_common_basic_type(&self, id: u32) -> Result<Option<BasicType>, ErrorCode> {
+        let member_types = match self.get_type(id)? {
+            Type::Struct { member_types, .. } => member_types,
+            ty => return Ok(get_basic_type(&ty)),
+        };
+
+        let mut common = None;
+        for member_type in member_types {
+            let basic_type = match self.get_common_basic_type(member_type)? {
+                Some(basic_type) => basic_type,
+                None => return Ok(None),
+            };
+            if common.map_or(false, |common| common != basic_type) {
+                return Ok(None);
+            }
+            common = Some(basic_type);
+        }
+        Ok(common)
+    }
+
+    /// Gets the identifier for a member located at `index` within an `OpTypeStruct`.
+    pub fn get_member_name(&self, id: u32, index: u32) -> Result<String, ErrorCode> {
+        self.compiler.get_member_name(id, index)
+    }
+
+    /// Checks whether an ID has a decoration, which `get_decoration` cannot tell apart from a
+    /// decoration with the value 0.
+    pub fn has_decoration(&self, id: u32, decoration: Decoration) -> Result<bool, ErrorCode> {
+        self.compiler.has_decoration(id, decoration)
+    }
+
+    /// Checks whether a member located at `index` within an `OpTypeStruct` has a decoration.
+    pub fn has_member_decoration(
+        &self,
+        id: u32,
+        index: u32,
+        decoration: Decoration,
+    ) -> Result<bool, ErrorCode> {
+        self.compiler.has_member_decoration(id, index, decoration)
+    }
+
+    /// Gets the number of locations a stage input or output of the given type consumes.
+    fn get_resource_layout(
+        &self,
+        kind: ResourceKind,
+        resource: &Resource,
+    ) -> Result<ResourceLayout, ErrorCode> {
+        let declared_size = match kind {
+            ResourceKind::UniformBuffers
+            | ResourceKind::StorageBuffers
+            | ResourceKind::PushConstantBuffers => {
+                self.get_declared_struct_size(resource.base_type_id)?
+            }
+            _ => 0,
+        };
+        Ok(ResourceLayout {
+            bindings: (
+                self.get_decoration(resource.id, Decoration::DescriptorSet)?,
+                self.get_decoration(resource.id, Decoration::Binding)?,
+                self.get_decoration(resource.id, Decoration::Location)?,
+            ),
+            count: self.get_descriptor_count(resource.id)?,
+            declared_size,
+            shape: self.get_type_shape(resource.base_type_id)?,
+        })
+    }
+
+    fn get_type_shape(&self, type_id: u32) -> Result<TypeShape, ErrorCode> {
+        let ty = self.get_type(type_id)?;
+        let (vecsize, columns, array, array_size_literal) = match &ty {
+            Type::Boolean {
+                vecsize,
+                columns,
+                array,
+                array_size_literal,
+            }
+            | Type::Int {
+                vecsize,
+                columns,
+                array,
+                array_size_literal,
+            }
+            | Type::UInt {
+                vecsize,
+                columns,
+                array,
+                array_size_literal,
+            }
+            | Type::Half {
+                vecsize,
+                columns,
+                array,
+                array_size_literal,
+            }
+            | Type::Float {
+                vecsize,
+                columns,
+                array,
+                array_size_literal,
+            }
+            | Type::Double {
+                vecsize,
+                columns,
+                array,
+                array_size_literal,
+            } => (*vecsize, *columns, &array[..], &array_size_literal[..]),
+            Type::Int64 {
+                vecsize,
+                array,
+                array_size_literal,
+            }
+            | Type::UInt64 {
+                vecsize,
+                array,
+                array_size_literal,
+            }
+            | Type::SByte {
+                vecsize,
+                array,
+                array_size_literal,
+            }
+            | Type::UByte {
+                vecsize,
+                array,
+                array_size_literal,
+            }
+            | Type::Short {
+                vecsize,
+                array,
+                array_size_literal,
+            }
+            | Type::UShort {
+                vecsize,
+                array,
+                array_size_literal,
+            } => (*vecsize, 1, &array[..], &array_size_literal[..]),
+            Type::Char {
+                array,
+                array_size_literal,
+            }
+            | Type::AtomicCounter {
+                array,
+                array_size_literal,
+            }
+            | Type::Sampler {
+                array,
+                array_size_literal,
+            }
+            | Type::Struct {
+                array,
+                array_size_literal,
+                ..
+            }
+            | Type::Image {
+                array,
+                array_size_literal,
+                ..
+            }
+            | Type::SampledImage {
+                array,
+                array_size_literal,
+                ..
+            } => (1, 1, &array[..], &array_size_literal[..]),
+            _ => (1, 1, &[][..], &[][..]),
+        };
+
+        let image = match &ty {
+            Type::Image { image, .. } | Type::SampledImage { image, .. } => Some((
+                image.dim.clone() as u32,
+                image.depth,
+                image.arrayed,
+                image.ms,
+                image.sampled,
+                image.format.clone() as u32,
+            )),
+            _ => None,
+        };
+        let mut members = Vec::new();
+        if let Type::Struct { member_types, .. } = &ty {
+            for (index, &member_type) in (0..).zip(member_types) {
+                members.push((
+                    self.get_type_shape(member_type)?,
+                    self.get_member_decoration(type_id, index, Decoration::Offset)?,
+                ));
+            }
+        }
+
+        Ok(TypeShape {
+            variant: mem::discriminant(&ty),
+            vecsize,
+            columns,
+            array: array
+                .iter()
+                .zip(array_size_literal)
+                .map(|(&size, &literal)| if literal { Some(size) } else { None })
+                .collect(),
+            image,
+            members,
+        })
+    }
+
+    fn get_location_count(&self, type_id: u32) -> Result<u32, ErrorCode> {
+        let (count, array, array_size_literal) = match self.get_type(type_id)? {
+            Type::Struct {
+                member_types,
+                array,
+                array_size_literal,
+            } => {
+                let mut count = 0;
+                for member_type in member_types {
+                    count += self.get_location_count(member_type)?;
+                }
+                (count, array, array_size_literal)
+            }
+            // 64-bit vectors with more than two components take two locations each
+            Type::Double {
+                vecsize,
+                columns,
+                array,
+                array_size_literal,
+            } => (
+                columns * if vecsize > 2 { 2 } else { 1 },
+                array,
+                array_size_literal,
+            ),
+            Type::Int64 {
+                vecsize,
+                array,
+                array_size_literal,
+            }
+            | Type::UInt64 {
+                vecsize,
+                array,
+                array_size_literal,
+            } => (if vecsize > 2 { 2 } else { 1 }, array, array_size_literal),
+            Type::Boolean {
+                columns,
+                array,
+                array_size_literal,
+                ..
+            }
+            | Type::Int {
+                columns,
+                array,
+                array_size_literal,
+                ..
+            }
+            | Type::UInt {
+                columns,
+                array,
+                array_size_literal,
+                ..
+            }
+            | Type::Half {
+                columns,
+                array,
+                array_size_literal,
+                ..
+            }
+            | Type::Float {
+                columns,
+                array,
+                array_size_literal,
+                ..
+            } => (columns, array, array_size_literal),
+            Type::SByte {
+                array,
+                array_size_literal,
+                ..
+            }
+            | Type::UByte {
+                array,
+                array_size_literal,
+                ..
+            }
+            | Type::Short {
+                array,
+                array_size_literal,
+                ..
+            }
+            | Type::UShort {
+                array,
+                array_size_literal,
+                ..
+            } => (1, array, array_size_literal),
+            _ => {
+                return Err(ErrorCode::CompilationError(format!(
+                    "type {} cannot be a stage input or output",
+                    type_id
+                )))
+            }
+        };
+
+        array
+            .iter()
+            .zip(array_size_literal)
+            .try_fold(count, |count, (&size, literal)| {
+                if literal {
+                    Ok(count * size)
+                } else {
+                    Err(ErrorCode::CompilationError(format!(
+                        "type {} has an array sized by a specialization constant",
+                        type_id
+                    )))
+                }
+            })
+    }
+
+    /// Gets a decoration for a member located at `index` within an `OpTypeStruct`.
+    pub fn get_member_decoration(
+        &self,
+        id: u32,
+        index: u32,
+        decoration: Decoration,
+    ) -> Result<u32, ErrorCode> {
+        self.compiler.get_member_decoration(id, index, decoration)
+    }
+
+    /// Gets the effective size of a buffer block.
+    pub fn get_declared_struct_size(&self, id: u32) -> Result<u32, ErrorCode> {
+        self.compiler.get_declared_struct_size(id)
+    }
+
+    /// Gets the size of a buffer block struct ending in a runtime array when the array has
+    /// `array_size` elements. Other structs give the same result as `get_declared_struct_size`.
+    pub fn get_declared_struct_size_runtime_array(
+        &self,
+        id: u32,
+        array_size: u32,
+    ) -> Result<u32, ErrorCode> {
+        let size = self.get_declared_struct_size(id)?;
+        Ok(match self.get_runtime_array_stride(id)? {
+            Some(stride) => size + stride * array_size,
+            None => size,
+        })
+    }
+
+    /// Gets the array stride of the runtime array a buffer block struct ends in, or `None` if its
+    /// last member is not a runtime array. `get_declared_struct_size` gives the size of the
+    /// members before it.
+    pub fn get_runtime_array_stride(&self, id: u32) -> Result<Option<u32>, ErrorCode> {
+        let member_types = match self.get_type(id)? {
+            Type::Struct { member_types, .. } => member_types,
+            _ => {
+                return Err(ErrorCode::CompilationError(format!(
+                    "type {} is not a struct",
+                    id
+                )))
+            }
+        };
+        let last_type_id = match member_types.last() {
+            Some(&last_type_id) => last_type_id,
+            None => return Ok(None),
+        };
+        if get_array(&self.get_type(last_type_id)?).last() != Some(&0) {
+            return Ok(None);
+        }
+        let stride = self.get_decoration(last_type_id, Decoration::ArrayStride)?;
+        Ok(Some(stride))
+    }
+
+    /// Gets the effective size of a buffer block struct member.
+    pub fn get_declared_struct_member_size(&self, id: u32, index: u32) -> Result<u32, ErrorCode> {
+        self.compiler.get_declared_struct_member_size(id, index)
+    }
+
+    /// Checks the explicit offsets, array strides and matrix strides of a buffer block struct
+    /// against the given layout rules, including those of nested structs, and returns the first
+    /// member which breaks them.
+    pub fn validate_buffer_layout(
+        &self,
+        type_id: u32,
+        layout: BufferLayout,
+    ) -> Result<Option<LayoutViolation>, ErrorCode> {
+        let mut violation = None;
+        self.get_struct_layout(type_id, layout, &mut violation)?;
+        Ok(violation)
+    }
+
+    /// Gets the alignment and size of a buffer block struct, stopping at the first violation.
+    fn get_struct_layout(
+        &self,
+        type_id: u32,
+        layout: BufferLayout,
+        violation: &mut Option<LayoutViolation>,
+    ) -> Result<(u32, u32), ErrorCode> {
+        let member_types = match self.get_type(type_id)? {
+            Type::Struct { member_types, .. } => member_types,
+            _ => {
+                return Err(ErrorCode::CompilationError(format!(
+                    "type {} is not a struct",
+                    type_id
+                )))
+            }
+        };
+
+        let mut alignment = match layout {
+            BufferLayout::Std140 => 16,
+            _ => 1,
+        };
+        let mut end = 0;
+        for (index, member_type) in (0..).zip(member_types) {
+            let offset = self.get_member_decoration(type_id, index, Decoration::Offset)?;
+            let member = MemberLayout {
+                struct_type_id: type_id,
+                index,
+                matrix_stride: self.get_member_decoration(
+                    type_id,
+                    index,
+                    Decoration::MatrixStride,
+                )?,
+                row_major: self.has_member_decoration(type_id, index, Decoration::RowMajor)?,
+            };
+            let (member_alignment, member_size) =
+                self.get_member_type_layout(member_type, &member, layout, violation)?;
+
+            if violation.is_none() {
+                if offset % member_alignment != 0 {
+                    self.set_layout_violation(
+                        &member,
+                        LayoutViolationKind::MisalignedOffset {
+                            offset,
+                            alignment: member_alignment,
+                        },
+                        violation,
+                    )?;
+                } else if offset < end {
+                    self.set_layout_violation(
+                        &member,
+                        LayoutViolationKind::OverlappingOffset {
+                            offset,
+                            minimum: end,
+                        },
+                        violation,
+                    )?;
+                }
+            }
+            if violation.is_some() {
+                return Ok((alignment, end));
+            }
+
+            alignment = alignment.max(member_alignment);
+            end = offset + member_size;
+        }
+
+        match layout {
+            BufferLayout::Scalar => Ok((alignment, end)),
+            _ => Ok((alignment, round_up(end, alignment))),
+        }
+    }
+
+    /// Gets the alignment and size of the type of a buffer block struct member.
+    fn get_member_type_layout(
+        &self,
+        type_id: u32,
+        member: &MemberLayout,
+        layout: BufferLayout,
+        violation: &mut Option<LayoutViolation>,
+    ) -> Result<(u32, u32), ErrorCode> {
+        let (width, vecsize, columns, array) = match self.get_type(type_id)? {
+            Type::Struct { array, .. } if array.is_empty() => {
+                return self.get_struct_layout(type_id, layout, violation)
+            }
+            Type::Struct { array, .. } => (0, 0, 0, array),
+            Type::SByte { vecsize, array, .. } | Type::UByte { vecsize, array, .. } => {
+                (1, vecsize, 1, array)
+            }
+            Type::Short { vecsize, array, .. } | Type::UShort { vecsize, array, .. } => {
+                (2, vecsize, 1, array)
+            }
+            Type::Int64 { vecsize, array, .. } | Type::UInt64 { vecsize, array, .. } => {
+                (8, vecsize, 1, array)
+            }
+            Type::Half {
+                vecsize,
+                columns,
+                array,
+                ..
+            } => (2, vecsize, columns, array),
+            Type::Int {
+                vecsize,
+                columns,
+                array,
+                ..
+            }
+            | Type::UInt {
+                vecsize,
+                columns,
+                array,
+                ..
+            }
+            | Type::Float {
+                vecsize,
+                columns,
+                array,
+                ..
+            } => (4, vecsize, columns, array),
+            Type::Double {
+                vecsize,
+                columns,
+                array,
+                ..
+            } => (8, vecsize, columns, array),
+            _ => {
+                return Err(ErrorCode::CompilationError(format!(
+                    "type {} has no explicit layout",
+                    type_id
+                )))
+            }
+        };
+
+        if !array.is_empty() {
+            let (element_type_id, length) = self.compiler.get_outer_array(type_id)?;
+            let (element_alignment, element_size) =
+                self.get_member_type_layout(element_type_id, member, layout, violation)?;
+            let alignment = match layout {
+                BufferLayout::Std140 => round_up(element_alignment, 16),
+                _ => element_alignment,
+            };
+            let expected = match layout {
+                BufferLayout::Scalar => element_size,
+                _ => round_up(element_size, alignment),
+            };
+            let stride = self.get_decoration(type_id, Decoration::ArrayStride)?;
+            if violation.is_none() && stride != expected {
+                self.set_layout_violation(
+                    member,
+                    LayoutViolationKind::ArrayStride { stride, expected },
+                    violation,
+                )?;
+            }
+            return Ok((alignment, stride * length));
+        }
+
+        if columns <= 1 {
+            return Ok((
+                get_vector_alignment(width, vecsize, layout),
+                width * vecsize,
+            ));
+        }
+
+        // Matrices are laid out as arrays of their columns, or of their rows if row major
+        let (vector_size, count) = if member.row_major {
+            (columns, vecsize)
+        } else {
+            (vecsize, columns)
+        };
+        let alignment = match layout {
+            BufferLayout::Std140 => round_up(get_vector_alignment(width, vector_size, layout), 16),
+            _ => get_vector_alignment(width, vector_size, layout),
+        };
+        let expected = match layout {
+            BufferLayout::Scalar => width * vector_size,
+            _ => alignment,
+        };
+        if violation.is_none() && member.matrix_stride != expected {
+            self.set_layout_violation(
+                member,
+                LayoutViolationKind::MatrixStride {
+                    stride: member.matrix_stride,
+                    expected,
+                },
+                violation,
+            )?;
+        }
+        Ok((alignment, member.matrix_stride * count))
+    }
+
+    fn set_layout_violation(
+        &self,
+        member: &MemberLayout,
+        kind: LayoutViolationKind,
+        violation: &mut Option<LayoutViolation>,
+    ) -> Result<(), ErrorCode> {
+        *violation = Some(LayoutViolation {
+            struct_type_id: member.struct_type_id,
+            member_index: member.index,
+            member_name: self.get_member_name(member.struct_type_id, member.index)?,
+            kind,
+        });
+        Ok(())
+    }
+
+    /// get the active interface variable.
+    pub fn get_active_interface_variables(&mut self) -> Result<HashSet<u32>, ErrorCode> {
+        self.compiler
+            .get_active_interface_variables()
+    }
+
+    /// Gets the built-ins the current entry point reads and writes, in a single call. Built-ins
+    /// which are declared in the interface but never accessed are not included.
+    pub fn get_active_builtins(&self) -> Result<ActiveBuiltIns, ErrorCode> {
+        self.compiler.get_active_builtins()
+    }
+
+    /// Gets the subgroup size an entry point requires via the `SubgroupSize` execution mode, if
+    /// it declares one.
+    pub fn get_required_subgroup_size(
+        &self,
+        entry_point_name: &str,
+        execution_model: ExecutionModel,
+    ) -> Result<Option<u32>, ErrorCode> {
+        self.compiler
+            .get_required_subgroup_size(entry_point_name, execution_model)
+    }
+
+    /// Gets an argument of an execution mode of the current entry point, such as the number of
+    /// `OutputVertices`, or `None` if the entry point does not declare the mode or the mode has
+    /// no argument at `index`. The arguments of `LocalSize` and `LocalSizeId` are indexed by
+    /// dimension, and those of the `Id` modes are the ids of constants.
+    pub fn get_execution_mode_argument(
+        &self,
+        mode: ExecutionMode,
+        index: u32,
+    ) -> Result<Option<u32>, ErrorCode> {
+        self.compiler.get_execution_mode_argument(mode, index)
+    }
+
+    /// Checks whether the current entry point declares the `EarlyFragmentTests` execution mode,
+    /// i.e. runs the depth and stencil tests before the fragment shader, including after a call
+    /// to `set_early_fragment_tests`.
+    pub fn uses_early_fragment_tests(&self) -> Result<bool, ErrorCode> {
+        self.compiler.uses_early_fragment_tests()
+    }
+
+    /// Checks whether the current entry point declares the `Xfb` execution mode, i.e. captures
+    /// the outputs of `get_transform_feedback_outputs` to transform feedback buffers.
+    pub fn uses_transform_feedback(&self) -> Result<bool, ErrorCode> {
+        self.compiler.uses_transform_feedback()
+    }
+
+    /// Gets the stage outputs decorated with an `XfbBuffer`, along with their `XfbStride` and
+    /// `Offset` decorations. The GLSL backend emits these as `xfb_buffer`, `xfb_stride` and
+    /// `xfb_offset` layout qualifiers, which need GLSL 4.40 or `GL_ARB_enhanced_layouts`.
+    pub fn get_transform_feedback_outputs(
+        &self,
+    ) -> Result<Vec<TransformFeedbackOutput>, ErrorCode> {
+        let resources = self.get_shader_resources()?;
+        let mut variables = resources.stage_outputs;
+        for builtin in resources.builtin_outputs {
+            if variables
+                .iter()
+                .all(|resource| resource.id != builtin.resource.id)
+            {
+                variables.push(builtin.resource);
+            }
+        }
+
+        let mut outputs = Vec::new();
+        for resource in variables {
+            if !self.has_decoration(resource.id, Decoration::XfbBuffer)? {
+                continue;
+            }
+            let offset = if self.has_decoration(resource.id, Decoration::Offset)? {
+                Some(self.get_decoration(resource.id, Decoration::Offset)?)
+            } else {
+                None
+            };
+            let mut member_offsets = Vec::new();
+            if let Some(block) = self.underlying_struct(resource.type_id)? {
+                if let Type::Struct { member_types, .. } = self.get_type(block)? {
+                    for index in 0..member_types.len() as u32 {
+                        if self.has_member_decoration(block, index, Decoration::Offset)? {
+                            let member_offset =
+                                self.get_member_decoration(block, index, Decoration::Offset)?;
+                            member_offsets.push((index, member_offset));
+                        }
+                    }
+                }
+            }
+            outputs.push(TransformFeedbackOutput {
+                buffer: self.get_decoration(resource.id, Decoration::XfbBuffer)?,
+                stride: self.get_decoration(resource.id, Decoration::XfbStride)?,
+                offset,
+                member_offsets,
+                resource,
+            });
+        }
+        Ok(outputs)
+    }
+
+    /// Gets the classes of subgroup operations the functions of the module use, from their
+    /// instructions and the subgroup built-ins they read. Capabilities declared without any
+    /// instruction using them are not included.
+    pub fn get_subgroup_features(&self) -> Result<SubgroupFeatures, ErrorCode> {
+        self.compiler.get_subgroup_features()
+    }
+
+    /// Gets the total size in bytes of the `Workgroup` variables used by an entry point, laid out
+    /// with std430 rules. Arrays sized by specialization constants use the constant's current
+    /// value, and an error is returned if a size cannot be resolved. Modules older than SPIR-V
+    /// 1.4 do not list `Workgroup` variables per entry point, so all of them are counted.
+    pub fn get_workgroup_shared_memory_size(
+        &self,
+        entry_point_name: &str,
+        execution_model: ExecutionModel,
+    ) -> Result<u32, ErrorCode> {
+        self.compiler
+            .get_workgroup_shared_memory_size(entry_point_name, execution_model)
+    }
+
+    /// Gets the `Input` and `Output` variables forming the interface of an entry point, in the
+    /// order the entry point declares them. Unlike `get_active_interface_variables` this works
+    /// for any entry point in the module and includes variables the entry point never accesses.
+    pub fn get_entry_point_interface_variables(
+        &self,
+        entry_point_name: &str,
+        execution_model: ExecutionModel,
+    ) -> Result<Vec<u32>, ErrorCode> {
+        self.compiler
+            .get_entry_point_interface_variables(entry_point_name, execution_model)
+    }
+
+    /// Checks whether a fragment entry point runs once per sample rather than once per pixel,
+    /// because it reads the `SampleId` or `SamplePosition` built-ins or a `sample` qualified
+    /// input. Pipelines using it need per-sample shading, i.e. a `minSampleShading` of 1.0.
+    pub fn uses_sample_rate_shading(&self, entry_point_name: &str) -> Result<bool, ErrorCode> {
+        let resources =
+            self.get_shader_resources_for_entry_point(entry_point_name, ExecutionModel::Fragment)?;
+        let sample_built_ins = [BuiltIn::SampleId, BuiltIn::SamplePosition];
+        if resources
+            .builtin_inputs
+            .iter()
+            .any(|built_in| sample_built_ins.contains(&built_in.builtin))
+        {
+            return Ok(true);
+        }
+
+        for input in &resources.stage_inputs {
+            if self.has_decoration(input.id, Decoration::Sample)? {
+                return Ok(true);
+            }
+            if let Type::Struct { member_types, .. } = self.get_type(input.base_type_id)? {
+                for index in 0..member_types.len() as u32 {
+                    if self.has_member_decoration(input.base_type_id, index, Decoration::Sample)? {
+                        return Ok(true);
+                    }
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Gets the entry points which statically use the `Layer` or `ViewportIndex` built-in
+    /// outputs, with the stage writing them. Fragment shaders reading them as inputs are not
+    /// included.
+    pub fn get_layer_and_viewport_outputs(&self) -> Result<Vec<LayerAndViewportOutput>, ErrorCode> {
+        let mut outputs = Vec::new();
+        for entry_point in self.get_entry_points()? {
+            let resources = self.get_shader_resources_for_entry_point(
+                &entry_point.name,
+                entry_point.execution_model,
+            )?;
+            let writes = |built_in| {
+                resources
+                    .builtin_outputs
+                    .iter()
+                    .any(|output| output.builtin == built_in)
+            };
+            let layer = writes(BuiltIn::Layer);
+            let viewport_index = writes(BuiltIn::ViewportIndex);
+            if layer || viewport_index {
+                outputs.push(LayerAndViewportOutput {
+                    entry_point: entry_point.name,
+                    execution_model: entry_point.execution_model,
+                    layer,
+                    viewport_index,
+                });
+            }
+        }
+        Ok(outputs)
+    }
+
+    /// Gets work group size specialization constants.
+    pub fn get_work_group_size_specialization_constants(
+        &self,
+    ) -> Result<WorkGroupSizeSpecializationConstants, ErrorCode> {
+        self.compiler.get_work_group_size_specialization_constants()
+    }
+
+    /// Gets the underlying SPIRV-Cross compiler, which remains owned by the `Ast`.
+    ///
+    /// # Safety
+    ///
+    /// The compiler must not be deleted, and must not be used after the `Ast` is dropped. Changes
+    /// made through it are not reflected in the options and state this crate tracks, such as the
+    /// result of `get_compiler_options`.
+    pub unsafe fn as_raw(&self) -> *mut ScInternalCompilerBase {
+        self.compiler.sc_compiler
+    }
 
-    fn set_compiler_options(
-        &mut self,
-        compiler_options: &Self::CompilerOptions,
-    ) -> Result<(), ErrorCode>;
-    fn compile(&mut self) -> Result<String, ErrorCode>;
+    /// Releases the underlying SPIRV-Cross compiler without deleting it, so that it can be
+    /// passed to `Ast::from_raw` of the same target.
+    ///
+    /// Compiler options are not carried over. A variable type remap callback, if one was set, is
+    /// leaked since the compiler may still call it.
+    pub fn into_raw(self) -> *mut ScInternalCompilerBase {
+        let mut ast = mem::ManuallyDrop::new(self);
+        unsafe {
+            std::ptr::drop_in_place(&mut ast.compiler.target_data);
+        }
+        ast.compiler.sc_compiler
+    }
 }
 
-impl<TTarget> Ast<TTarget>
+impl<TTarget> Ast<TTarget, Parsed>
 where
-    Self: Parse<TTarget> + Compile<TTarget>,
+    Self: Parse<TTarget>,
     TTarget: Target,
 {
-    /// Gets a decoration.
-    pub fn get_decoration(&self, id: u32, decoration: Decoration) -> Result<u32, ErrorCode> {
-        self.compiler.get_decoration(id, decoration)
-    }
-
-    /// Gets a name. If not defined, an empty string will be returned.
-    pub fn get_name(&mut self, id: u32) -> Result<String, ErrorCode> {
-        self.compiler.get_name(id)
-    }
-
     /// Sets a name.
     pub fn set_name(&mut self, id: u32, name: &str) -> Result<(), ErrorCode> {
         self.compiler.set_name(id, name)
@@ -580,6 +4735,254 @@ where
         self.compiler.set_member_name(id, index, name)
     }
 
+    /// Names the push constant blocks of the module and their instances, so the output does not
+    /// depend on whether the module carries debug names.
+    pub fn set_push_constant_block_name(
+        &mut self,
+        block_name: &str,
+        instance_name: &str,
+    ) -> Result<(), ErrorCode> {
+        for resource in self.get_shader_resources()?.push_constant_buffers {
+            self.compiler.set_name(resource.base_type_id, block_name)?;
+            self.compiler.set_name(resource.id, instance_name)?;
+        }
+        Ok(())
+    }
+
+    /// Decorates the stage inputs and outputs which have no `Location` with the lowest free
+    /// locations, as some hand-written modules and HLSL toolchains leave them out. Variables
+    /// are assigned in order of name, so the outputs of one stage match the inputs of the next
+    /// with the same names, and variables with existing locations are left unchanged.
+    pub fn assign_stage_locations(&mut self) -> Result<StageLocations, ErrorCode> {
+        let resources = self.get_shader_resources()?;
+        Ok(StageLocations {
+            inputs: self.assign_locations(&resources.stage_inputs)?,
+            outputs: self.assign_locations(&resources.stage_outputs)?,
+        })
+    }
+
+    fn assign_locations(
+        &mut self,
+        resources: &[Resource],
+    ) -> Result<BTreeMap<u32, u32>, ErrorCode> {
+        let mut used = HashSet::new();
+        let mut unassigned = Vec::new();
+        for resource in resources {
+            if self.has_decoration(resource.id, Decoration::Location)? {
+                let location = self.get_decoration(resource.id, Decoration::Location)?;
+                let count = self.get_location_count(resource.type_id)?;
+                used.extend(location..location + count);
+                continue;
+            }
+
+            // Blocks may place each member instead of the whole variable
+            let mut has_member_locations = false;
+            if let Type::Struct { member_types, .. } = self.get_type(resource.base_type_id)? {
+                for (index, member_type) in (0..).zip(member_types) {
+                    if self.has_member_decoration(
+                        resource.base_type_id,
+                        index,
+                        Decoration::Location,
+                    )? {
+                        let location = self.get_member_decoration(
+                            resource.base_type_id,
+                            index,
+                            Decoration::Location,
+                        )?;
+                        let count = self.get_location_count(member_type)?;
+                        used.extend(location..location + count);
+                        has_member_locations = true;
+                    }
+                }
+            }
+            if !has_member_locations {
+                unassigned.push(resource);
+            }
+        }
+
+        unassigned.sort_by(|a, b| (&a.name, a.id).cmp(&(&b.name, b.id)));
+        let mut locations = BTreeMap::new();
+        for resource in unassigned {
+            let count = self.get_location_count(resource.type_id)?;
+            let mut location = 0;
+            while (location..location + count).any(|l| used.contains(&l)) {
+                location += 1;
+            }
+            used.extend(location..location + count);
+            self.set_decoration(resource.id, Decoration::Location, location)?;
+            locations.insert(resource.id, location);
+        }
+
+        Ok(locations)
+    }
+
+    /// Prefixes the names of the resources of the given kinds and of their blocks, such as with
+    /// `prefix_resource_names("mat_", ResourceKind::UniformBuffers | ResourceKind::SampledImages)`,
+    /// to avoid collisions between the symbols of shaders from different authors. Unnamed
+    /// variables keep their generated names, and the members of blocks are not renamed.
+    pub fn prefix_resource_names(
+        &mut self,
+        prefix: &str,
+        kinds: impl Into<ResourceKinds>,
+    ) -> Result<(), ErrorCode> {
+        let kinds = kinds.into();
+        let compiler = &mut self.compiler;
+        let mut renamed_blocks = HashSet::new();
+        for (kind, resource) in compiler.get_shader_resources()?.iter() {
+            if !kinds.contains(kind) {
+                continue;
+            }
+
+            let name = compiler.get_name(resource.id)?;
+            if !name.is_empty() {
+                compiler.set_name(resource.id, &format!("{}{}", prefix, name))?;
+            }
+
+            // Blocks may be shared by several resources, so each is only renamed once
+            let block = resource.base_type_id;
+            let is_block = compiler.get_decoration(block, Decoration::Block)? != 0
+                || compiler.get_decoration(block, Decoration::BufferBlock)? != 0;
+            if is_block && renamed_blocks.insert(block) {
+                let name = compiler.get_name(block)?;
+                if !name.is_empty() {
+                    compiler.set_name(block, &format!("{}{}", prefix, name))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Emits the resource variable `alias_id` through the declaration of `target_id`, such as to
+    /// collapse several textures into one atlas: every use of the alias reads the target instead,
+    /// and the alias is no longer declared or reflected. Both variables must have the same type.
+    /// Offsets or swizzles distinguishing the aliased data are left to the caller.
+    pub fn alias_resource(&mut self, alias_id: u32, target_id: u32) -> Result<(), ErrorCode> {
+        if alias_id == target_id {
+            return Err(ErrorCode::InvalidOptions(String::from(
+                "a resource cannot alias itself",
+            )));
+        }
+        self.compiler.alias_variable(alias_id, target_id)
+    }
+
+    /// Forces the `EarlyFragmentTests` execution mode of the current entry point on or off in the
+    /// output, e.g. `layout(early_fragment_tests) in;` in GLSL. Some mobile drivers need it forced
+    /// on for correct depth behavior in shaders which store to images. Fails with
+    /// `ErrorCode::InvalidOptions` if the current entry point is not a fragment shader.
+    pub fn set_early_fragment_tests(&mut self, enable: bool) -> Result<(), ErrorCode> {
+        let (name, execution_model) = self.get_current_entry_point()?;
+        if execution_model != ExecutionModel::Fragment {
+            return Err(ErrorCode::InvalidOptions(format!(
+                "early fragment tests only apply to fragment shaders, but the current entry point \
+                 {:?} is a {:?} shader",
+                name, execution_model
+            )));
+        }
+        self.compiler.set_early_fragment_tests(enable)
+    }
+
+    /// Removes the names of all IDs and struct members so that the generated source only uses
+    /// anonymized identifiers, which is useful when shipping shaders.
+    ///
+    /// Entry point names are kept. Interface variables can no longer be matched by name between
+    /// stages afterwards, so they should have explicit locations.
+    ///
+    /// Names are otherwise preserved, except where a backend renames an identifier that clashes
+    /// with a keyword of the target language or with another identifier. The `preserve_names`
+    /// compiler option keeps the former, while the `strip_names` option strips names on every
+    /// compile.
+    pub fn strip_names(&mut self) -> Result<(), ErrorCode> {
+        self.compiler.strip_names()
+    }
+
+    /// Sets a callback that can override the type name a variable is declared with, for instance
+    /// to replace a struct with a type provided by an engine header.
+    ///
+    /// The callback receives the ID of the variable's type, the variable name and the type name
+    /// that would be emitted, and returns a replacement type name or `None` to keep it.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_variable_type_remap_callback<F>(&mut self, callback: F) -> Result<(), ErrorCode>
+    where
+        F: FnMut(u32, &str, &str) -> Option<String> + Send + 'static,
+    {
+        self.compiler.set_variable_type_remap_callback(callback)
+    }
+
+    /// Forces an interpolation qualifier on a stage input or output, replacing the other of
+    /// `Flat` and `NoPerspective`. Qualifiers on vertex inputs and fragment outputs, which are
+    /// not interpolated, and `NoPerspective` on integer and double varyings, which can only be
+    /// flat, are rejected.
+    pub fn set_interpolation_qualifier(
+        &mut self,
+        id: u32,
+        qualifier: InterpolationQualifier,
+    ) -> Result<(), ErrorCode> {
+        let resources = self.get_shader_resources()?;
+        let resource = match resources
+            .stage_inputs
+            .iter()
+            .chain(&resources.stage_outputs)
+            .find(|resource| resource.id == id)
+        {
+            Some(resource) => resource.clone(),
+            None => {
+                return Err(ErrorCode::CompilationError(format!(
+                    "{} is not a stage input or output",
+                    id
+                )))
+            }
+        };
+
+        let is_input = resources.stage_inputs.iter().any(|input| input.id == id);
+        for entry_point in self.get_entry_points()? {
+            let is_interpolated = match entry_point.execution_model {
+                ExecutionModel::Vertex => !is_input,
+                ExecutionModel::Fragment => is_input,
+                _ => true,
+            };
+            let interface = self.get_entry_point_interface_variables(
+                &entry_point.name,
+                entry_point.execution_model,
+            )?;
+            if !is_interpolated && interface.contains(&id) {
+                return Err(ErrorCode::CompilationError(format!(
+                    "`{}` of {:?} entry point `{}` is not interpolated",
+                    resource.name, entry_point.execution_model, entry_point.name
+                )));
+            }
+        }
+
+        let is_flat_only = match self.get_type(resource.base_type_id)? {
+            Type::Int { .. }
+            | Type::UInt { .. }
+            | Type::Int64 { .. }
+            | Type::UInt64 { .. }
+            | Type::Double { .. }
+            | Type::SByte { .. }
+            | Type::UByte { .. }
+            | Type::Short { .. }
+            | Type::UShort { .. } => true,
+            _ => false,
+        };
+        match qualifier {
+            InterpolationQualifier::Flat => {
+                self.unset_decoration(id, Decoration::NoPerspective)?;
+                self.set_decoration(id, Decoration::Flat, 0)
+            }
+            InterpolationQualifier::NoPerspective if is_flat_only => {
+                Err(ErrorCode::CompilationError(format!(
+                    "`{}` has an integer or double type, which can only be flat",
+                    resource.name
+                )))
+            }
+            InterpolationQualifier::NoPerspective => {
+                self.unset_decoration(id, Decoration::Flat)?;
+                self.set_decoration(id, Decoration::NoPerspective, 0)
+            }
+            InterpolationQualifier::Centroid => self.set_decoration(id, Decoration::Centroid, 0),
+        }
+    }
+
     /// Unsets a decoration.
     pub fn unset_decoration(&mut self, id: u32, decoration: Decoration) -> Result<(), ErrorCode> {
         self.compiler.unset_decoration(id, decoration)
@@ -595,35 +4998,52 @@ where
         self.compiler.set_decoration(id, decoration, argument)
     }
 
-    /// Gets entry points.
-    pub fn get_entry_points(&self) -> Result<Vec<EntryPoint>, ErrorCode> {
-        self.compiler.get_entry_points()
-    }
-
-    /// Gets cleansed entry point names. `compile` must be called first.
-    pub fn get_cleansed_entry_point_name(
-        &self,
-        entry_point_name: &str,
-        execution_model: ExecutionModel,
-    ) -> Result<String, ErrorCode> {
-        if self.compiler.has_been_compiled {
-            self.compiler
-                .get_cleansed_entry_point_name(entry_point_name, execution_model)
-        } else {
-            Err(ErrorCode::CompilationError(String::from(
-                "`compile` must be called first",
-            )))
+    /// Sets the decorations of several IDs, such as when remapping the bindings of a module,
+    /// with all-or-nothing semantics. Every entry is validated before any is applied, so an
+    /// `ErrorCode::InvalidOptions` for an ID outside the module or a decoration with a string
+    /// operand leaves all decorations as they were. Later entries win over earlier ones for the
+    /// same ID and decoration.
+    pub fn apply_decorations(
+        &mut self,
+        decorations: &[(u32, Decoration, u32)],
+    ) -> Result<(), ErrorCode> {
+        let string_decoration = decorations.iter().position(|(_, decoration, _)| {
+            matches!(
+                decoration,
+                Decoration::HlslSemanticGoogle | Decoration::UserTypeGoogle
+            )
+        });
+        if let Some(index) = string_decoration {
+            let (id, decoration, _) = decorations[index];
+            return Err(ErrorCode::InvalidOptions(format!(
+                "entry {}: {:?} of id {} takes a string operand, use `set_decoration_string`",
+                index, decoration, id
+            )));
+        }
+        match self.compiler.set_decorations(decorations)? {
+            Some(index) => Err(ErrorCode::InvalidOptions(format!(
+                "entry {}: id {} is not in the module",
+                index, decorations[index].0
+            ))),
+            None => Ok(()),
         }
     }
 
-    /// Gets active buffer ragnes.  Useful for push constants.
-    pub fn get_active_buffer_ranges(&self, id: u32) -> Result<Vec<BufferRange>, ErrorCode> {
-        self.compiler.get_active_buffer_ranges(id)
+    /// Sets a decoration with a string operand, such as `HlslSemanticGoogle`.
+    pub fn set_decoration_string(
+        &mut self,
+        id: u32,
+        decoration: Decoration,
+        argument: &str,
+    ) -> Result<(), ErrorCode> {
+        self.compiler.set_decoration_string(id, decoration, argument)
     }
 
-    /// Gets all specialization constants.
-    pub fn get_specialization_constants(&self) -> Result<Vec<SpecializationConstant>, ErrorCode> {
-        self.compiler.get_specialization_constants()
+    /// Adds a sampler for separate images which are read without one, so that combined image
+    /// samplers can be built for targets lacking separate images. Returns the id of the new
+    /// sampler, or `None` if no sampler was needed.
+    pub fn build_dummy_sampler_for_combined_images(&mut self) -> Result<Option<u32>, ErrorCode> {
+        self.compiler.build_dummy_sampler_for_combined_images()
     }
 
     /// Set reference of a scalar constant to a value, overriding the default.
@@ -633,31 +5053,6 @@ where
         self.compiler.set_scalar_constant(id, value)
     }
 
-    /// Gets shader resources.
-    pub fn get_shader_resources(&self) -> Result<ShaderResources, ErrorCode> {
-        self.compiler.get_shader_resources()
-    }
-
-    /// Gets the SPIR-V type associated with an ID.
-    pub fn get_type(&self, id: u32) -> Result<Type, ErrorCode> {
-        self.compiler.get_type(id)
-    }
-
-    /// Gets the identifier for a member located at `index` within an `OpTypeStruct`.
-    pub fn get_member_name(&self, id: u32, index: u32) -> Result<String, ErrorCode> {
-        self.compiler.get_member_name(id, index)
-    }
-
-    /// Gets a decoration for a member located at `index` within an `OpTypeStruct`.
-    pub fn get_member_decoration(
-        &self,
-        id: u32,
-        index: u32,
-        decoration: Decoration,
-    ) -> Result<u32, ErrorCode> {
-        self.compiler.get_member_decoration(id, index, decoration)
-    }
-
     /// Sets a decoration for a member located at `index` within an `OpTypeStruct`.
     pub fn set_member_decoration(
         &mut self,
@@ -670,16 +5065,6 @@ where
             .set_member_decoration(id, index, decoration, argument)
     }
 
-    /// Gets the effective size of a buffer block.
-    pub fn get_declared_struct_size(&self, id: u32) -> Result<u32, ErrorCode> {
-        self.compiler.get_declared_struct_size(id)
-    }
-
-    /// Gets the effective size of a buffer block struct member.
-    pub fn get_declared_struct_member_size(&self, id: u32, index: u32) -> Result<u32, ErrorCode> {
-        self.compiler.get_declared_struct_member_size(id, index)
-    }
-
     /// Renames an interface variable.
     pub fn rename_interface_variable(
         &mut self,
@@ -690,26 +5075,46 @@ where
         self.compiler
             .rename_interface_variable(resources, location, name)
     }
-    
-    /// get the active interface variable.
-    pub fn get_active_interface_variables(&mut self) -> Result<HashSet<u32>, ErrorCode> {
-        self.compiler
-            .get_active_interface_variables()
-    }
-
-    /// Gets work group size specialization constants.
-    pub fn get_work_group_size_specialization_constants(
-        &self,
-    ) -> Result<WorkGroupSizeSpecializationConstants, ErrorCode> {
-        self.compiler.get_work_group_size_specialization_constants()
-    }
 
     /// Parses a module into `Ast`.
     pub fn parse(module: &Module) -> Result<Self, ErrorCode> {
         Parse::<TTarget>::parse(&module)
     }
 
-    /// Sets compile options.
+    /// Creates an `Ast` from an already parsed module without parsing it again.
+    pub fn from_parsed(module: &ParsedModule) -> Result<Self, ErrorCode> {
+        Parse::<TTarget>::from_parsed(module)
+    }
+}
+
+impl<TTarget> Ast<TTarget, Parsed>
+where
+    Self: Parse<TTarget> + Compile<TTarget>,
+    TTarget: Target,
+{
+    /// Renames an entry point, e.g. so entry points of separate modules with the same name can
+    /// be combined into one source. The entry point keeps being compiled if it is the current
+    /// one, and is selected by its new name from then on.
+    pub fn rename_entry_point(
+        &mut self,
+        old_name: &str,
+        new_name: &str,
+        execution_model: ExecutionModel,
+    ) -> Result<(), ErrorCode> {
+        let (current_name, current_execution_model) = self.get_current_entry_point()?;
+        self.compiler
+            .rename_entry_point(old_name, new_name, execution_model)?;
+        if current_name == old_name && current_execution_model == execution_model {
+            Compile::<TTarget>::set_entry_point(self, new_name, execution_model)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets compile options. This may be called again between compiles to change the options
+    /// without parsing the module again. The options are validated first, including that the
+    /// selected entry point is in the module, and the previous options stay in effect if they
+    /// are rejected.
     pub fn set_compiler_options(
         &mut self,
         options: &<Self as Compile<TTarget>>::CompilerOptions,
@@ -717,9 +5122,436 @@ where
         Compile::<TTarget>::set_compiler_options(self, options)
     }
 
+    /// Gets the compile options last set with `set_compiler_options`, or the default options if
+    /// none have been set.
+    pub fn get_compiler_options(&self) -> <Self as Compile<TTarget>>::CompilerOptions {
+        Compile::<TTarget>::get_compiler_options(self)
+    }
+
     /// Compiles an abstract syntax tree to a `String` in the specified `TTarget` language.
     pub fn compile(&mut self) -> Result<String, ErrorCode> {
-        self.compiler.has_been_compiled = true;
-        Compile::<TTarget>::compile(self)
+        if self.compiler.strip_names {
+            self.compiler.strip_names()?;
+        }
+        let names = if self.compiler.preserve_names {
+            Some(self.read_names()?)
+        } else {
+            None
+        };
+
+        self.compiler.update_interface_variable_filter()?;
+        let shader = Compile::<TTarget>::compile(self)?;
+        let shader = match &names {
+            Some(names) => self.restore_names(names, &shader)?,
+            None => shader,
+        };
+        self.check_unsupported_features()?;
+        let shader = match &self.compiler.identifier_naming {
+            Some(naming) => naming.apply(&shader),
+            None => shader,
+        };
+        self.log_lints();
+        Ok(shader)
+    }
+
+    /// Compiles an abstract syntax tree in the specified `TTarget` language and writes the
+    /// output to `writer` without building an intermediate `String`, unless the options preserve
+    /// names, rename generated identifiers or handle unsupported features strictly.
+    pub fn compile_into<W: io::Write>(&mut self, mut writer: W) -> Result<(), ErrorCode> {
+        if self.compiler.preserve_names
+            || self.compiler.identifier_naming.is_some()
+            || self.compiler.unsupported_features == UnsupportedFeaturePolicy::Strict
+        {
+            let shader = self.compile()?;
+            return writer
+                .write_all(shader.as_bytes())
+                .and_then(|_| writer.flush())
+                .map_err(|e| ErrorCode::Io(e.to_string()));
+        }
+
+        if self.compiler.strip_names {
+            self.compiler.strip_names()?;
+        }
+        self.compiler.update_interface_variable_filter()?;
+        Compile::<TTarget>::compile_into(self, writer)?;
+        self.log_lints();
+        Ok(())
+    }
+
+    /// Reads the names of every ID and struct member which has one. Entry point functions are
+    /// left out, as the names they are emitted with come from their entry points.
+    fn read_names(&self) -> Result<Names, ErrorCode> {
+        let words = self.compiler.get_spirv()?;
+        let (structs, entry_functions) = read_structs_and_entry_functions(&words);
+        let mut names = Names::default();
+        for id in 1..words.get(3).copied().unwrap_or(0) {
+            let name = self.compiler.get_name(id)?;
+            if !name.is_empty() && !entry_functions.contains(&id) {
+                names.ids.insert(id, name);
+            }
+        }
+        for (id, member_count) in structs {
+            for index in 0..member_count {
+                let name = self.compiler.get_member_name(id, index)?;
+                if !name.is_empty() {
+                    names.members.insert((id, index), name);
+                }
+            }
+        }
+        Ok(names)
+    }
+
+    /// Restores the names which the last compile changed, both in the module and in `shader`.
+    /// A name is only restored if no other ID, or no other member of the same struct, had it
+    /// before, as the backend then renamed it to tell the two apart, and if the name it was
+    /// changed to is not one that the module already used.
+    fn restore_names(&mut self, names: &Names, shader: &str) -> Result<String, ErrorCode> {
+        let compiled_names = self.read_names()?;
+        let used = names
+            .ids
+            .values()
+            .chain(names.members.values())
+            .map(String::as_str)
+            .collect::<HashSet<_>>();
+        let mut id_name_counts = HashMap::<&str, usize>::new();
+        for name in names.ids.values() {
+            *id_name_counts.entry(name).or_default() += 1;
+        }
+        let mut member_name_counts = HashMap::<(u32, &str), usize>::new();
+        for (&(id, _), name) in &names.members {
+            *member_name_counts.entry((id, name)).or_default() += 1;
+        }
+
+        let mut restored = HashMap::new();
+        for (&id, name) in &names.ids {
+            if let Some(compiled_name) = compiled_names.ids.get(&id) {
+                if compiled_name != name
+                    && id_name_counts[name.as_str()] == 1
+                    && !used.contains(compiled_name.as_str())
+                {
+                    self.compiler.set_name(id, name)?;
+                    restored.insert(compiled_name.as_str(), name.as_str());
+                }
+            }
+        }
+        for (&(id, index), name) in &names.members {
+            if let Some(compiled_name) = compiled_names.members.get(&(id, index)) {
+                if compiled_name != name
+                    && member_name_counts[&(id, name.as_str())] == 1
+                    && !used.contains(compiled_name.as_str())
+                {
+                    self.compiler.set_member_name(id, index, name)?;
+                    restored.insert(compiled_name.as_str(), name.as_str());
+                }
+            }
+        }
+
+        Ok(rename_identifiers(shader, |identifier, output| {
+            output.push_str(restored.get(identifier).copied().unwrap_or(identifier))
+        }))
+    }
+
+    /// Fails if the last compile has lints while the options ask for strict handling.
+    fn check_unsupported_features(&self) -> Result<(), ErrorCode> {
+        if self.compiler.unsupported_features == UnsupportedFeaturePolicy::Relaxed {
+            return Ok(());
+        }
+        let lints = Compile::<TTarget>::get_lints(self)?;
+        if lints.is_empty() {
+            Ok(())
+        } else {
+            Err(ErrorCode::CompilationError(lints.join("\n")))
+        }
+    }
+
+    /// Forwards the lints of the last compile to the `log` crate.
+    #[cfg(feature = "log")]
+    fn log_lints(&self) {
+        match Compile::<TTarget>::get_lints(self) {
+            Ok(lints) => lints.iter().for_each(|lint| log::warn!("{}", lint)),
+            Err(error) => log::debug!("failed to collect lints: {:?}", error),
+        }
+    }
+
+    #[cfg(not(feature = "log"))]
+    fn log_lints(&self) {}
+
+    /// Compiles an abstract syntax tree and lints the current options against the module, such as
+    /// overrides that did not match anything in it. The lints are checks made by this crate
+    /// rather than diagnostics of SPIRV-Cross, which reports nothing short of an error.
+    pub fn compile_with_lints(&mut self) -> Result<CompiledShader, ErrorCode> {
+        let source = self.compile()?;
+        let entry_points = self
+            .get_entry_points()?
+            .into_iter()
+            .map(|entry_point| {
+                let cleansed_name = self.compiler.get_cleansed_entry_point_name(
+                    &entry_point.name,
+                    entry_point.execution_model,
+                )?;
+                Ok(CompiledEntryPoint {
+                    name: entry_point.name,
+                    execution_model: entry_point.execution_model,
+                    cleansed_name,
+                })
+            })
+            .collect::<Result<_, ErrorCode>>()?;
+        Ok(CompiledShader {
+            source,
+            entry_points,
+            lints: Compile::<TTarget>::get_lints(self)?,
+        })
+    }
+
+    /// Compiles an abstract syntax tree with `options` applied for this call only.
+    ///
+    /// The options returned by `get_compiler_options` are restored afterwards, whether or not
+    /// compilation succeeded, and the result of the compile is returned either way. An entry
+    /// point selected by `options` stays selected if the restored options do not name one.
+    pub fn compile_with(
+        &mut self,
+        options: &<Self as Compile<TTarget>>::CompilerOptions,
+    ) -> Result<String, ErrorCode> {
+        let previous_options = self.get_compiler_options();
+        self.set_compiler_options(options)?;
+        let result = self.compile();
+        // The previous options were accepted when they were set, so restoring them can only fail
+        // in the bridge, which must not discard a compile that already finished
+        let _ = self.set_compiler_options(&previous_options);
+        result
+    }
+
+    /// Compiles every entry point of the module, keyed by name and execution model.
+    ///
+    /// Each entry point is selected in turn and compiled from the same parsed module. The
+    /// options and entry point that were selected before the call are restored afterwards.
+    pub fn compile_all_entry_points(
+        &mut self,
+    ) -> Result<BTreeMap<(String, ExecutionModel), String>, ErrorCode> {
+        let previous_options = self.get_compiler_options();
+        let (previous_name, previous_model) = self.get_current_entry_point()?;
+
+        let result = self
+            .get_entry_points()?
+            .into_iter()
+            .map(|entry_point| {
+                let EntryPoint {
+                    name,
+                    execution_model,
+                    ..
+                } = entry_point;
+                Compile::<TTarget>::set_entry_point(self, &name, execution_model)?;
+                Ok(((name, execution_model), self.compile()?))
+            })
+            .collect();
+
+        Compile::<TTarget>::set_entry_point(self, &previous_name, previous_model)?;
+        self.set_compiler_options(&previous_options)?;
+        result
+    }
+
+    /// Computes a fingerprint of the module as `to_spirv_words` serializes it, of its shader
+    /// resources and of the compiler options, for keying caches of compiled output.
+    ///
+    /// The fingerprint is the same on every platform and Rust version, and for the same module
+    /// and options it only changes with the minor version of this crate, when reflection data or
+    /// options may be added. Every instruction of the module is covered, so any edit of a shader,
+    /// including of function bodies, changes the fingerprint, as do names and decorations set
+    /// through this `Ast`, and the same module and options hash differently for each target.
+    pub fn get_reflection_hash(&self) -> Result<u64, ErrorCode> {
+        let mut hasher = StableHasher::new();
+        let words = self.serialize_words(true)?;
+        hasher.write_u64(words.len() as u64);
+        for &word in &words {
+            hasher.write_u32(word);
+        }
+
+        let resources = self.get_shader_resources()?;
+        for (kind, resource) in resources.iter() {
+            hasher.write_u32(kind as u32);
+            hasher.write_resource(resource);
+        }
+        for built_ins in &[&resources.builtin_inputs, &resources.builtin_outputs] {
+            hasher.write_u64(built_ins.len() as u64);
+            for built_in in built_ins.iter() {
+                hasher.write_u32(built_in_as_raw(Some(built_in.builtin)) as u32);
+                hasher.write_u32(built_in.value_type_id);
+                hasher.write_resource(&built_in.resource);
+            }
+        }
+
+        hasher.write_u64(Compile::<TTarget>::hash_compiler_options(self));
+        Ok(hasher.finish())
+    }
+
+    /// Compiles an abstract syntax tree and moves it into the `Compiled` state, where queries
+    /// that are only meaningful after compilation are available.
+    pub fn into_compiled(mut self) -> Result<(String, Ast<TTarget, Compiled>), ErrorCode> {
+        let shader = self.compile()?;
+        let Ast { compiler, .. } = self;
+        Ok((
+            shader,
+            Ast {
+                compiler,
+                target_type: PhantomData,
+                state: PhantomData,
+            },
+        ))
+    }
+}
+
+impl<TTarget> Ast<TTarget, Compiled>
+where
+    TTarget: Target,
+{
+    /// Gets cleansed entry point names.
+    pub fn get_cleansed_entry_point_name(
+        &self,
+        entry_point_name: &str,
+        execution_model: ExecutionModel,
+    ) -> Result<String, ErrorCode> {
+        self.compiler
+            .get_cleansed_entry_point_name(entry_point_name, execution_model)
+    }
+
+    /// Moves a compiled abstract syntax tree back into the `Parsed` state so that it can be
+    /// modified and compiled again.
+    pub fn into_parsed(self) -> Ast<TTarget, Parsed> {
+        let Ast { compiler, .. } = self;
+        Ast {
+            compiler,
+            target_type: PhantomData,
+            state: PhantomData,
+        }
+    }
+}
+
+/// The target and options used to compile a `CompileJob`.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum CompileTarget {
+    #[cfg(feature = "glsl")]
+    Glsl(crate::glsl::CompilerOptions),
+    #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+    Hlsl(crate::hlsl::CompilerOptions),
+    #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+    Msl(crate::msl::CompilerOptions),
+}
+
+/// A SPIR-V module to compile as part of `compile_batch`.
+#[derive(Debug, Clone)]
+pub struct CompileJob {
+    /// The SPIR-V words of the module.
+    pub words: Vec<u32>,
+    /// The target to compile the module to.
+    pub target: CompileTarget,
+}
+
+impl CompileJob {
+    /// Parses and compiles the module on the current thread.
+    pub fn compile(&self) -> Result<CompiledShader, ErrorCode> {
+        match self.target {
+            #[cfg(feature = "glsl")]
+            CompileTarget::Glsl(ref options) => {
+                compile_words::<crate::glsl::Target>(&self.words, options)
+            }
+            #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+            CompileTarget::Hlsl(ref options) => {
+                compile_words::<crate::hlsl::Target>(&self.words, options)
+            }
+            #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+            CompileTarget::Msl(ref options) => {
+                compile_words::<crate::msl::Target>(&self.words, options)
+            }
+        }
+    }
+}
+
+#[cfg(any(feature = "glsl", feature = "hlsl", feature = "msl"))]
+fn compile_words<TTarget>(
+    words: &[u32],
+    options: &<Ast<TTarget> as Compile<TTarget>>::CompilerOptions,
+) -> Result<CompiledShader, ErrorCode>
+where
+    Ast<TTarget>: Parse<TTarget> + Compile<TTarget>,
+    TTarget: Target,
+{
+    let mut ast = Ast::<TTarget>::parse(&Module::from_words(words))?;
+    ast.set_compiler_options(options)?;
+    ast.compile_with_lints()
+}
+
+/// An entry point of a `CompiledShader`.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct CompiledEntryPoint {
+    /// The name of the entry point in the module.
+    pub name: String,
+    pub execution_model: ExecutionModel,
+    /// The name of the entry point function in the generated source, as returned by
+    /// `get_cleansed_entry_point_name`.
+    pub cleansed_name: String,
+}
+
+/// The output of a successful compile.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct CompiledShader {
+    /// The generated shader source.
+    pub source: String,
+    /// The entry points of the module with their names in the generated source.
+    pub entry_points: Vec<CompiledEntryPoint>,
+    /// The lints of the options the module was compiled with, as returned by
+    /// `compile_with_lints`.
+    pub lints: Vec<String>,
+}
+
+/// Runs `f` with the bridge's results allocated from a per-thread arena.
+///
+/// Every string and array handed back by the C++ bridge while `f` runs comes from a few large
+/// chunks that are released together when `f` returns, rather than being allocated and freed one
+/// at a time. Results are always copied into Rust-owned values, so nothing returned by `f` refers
+/// to the arena. Calls may be nested; each one releases only its own arena.
+pub fn with_bridge_arena<R, F: FnOnce() -> R>(f: F) -> Result<R, ErrorCode> {
+    let _scope = compiler::ArenaScope::begin()?;
+    Ok(f())
+}
+
+/// Compiles several modules in parallel.
+///
+/// Jobs are distributed over one thread per available core. Results are returned in the same
+/// order as `jobs`.
+pub fn compile_batch(jobs: Vec<CompileJob>) -> Vec<Result<CompiledShader, ErrorCode>> {
+    let worker_count = thread::available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(1)
+        .min(jobs.len());
+    if worker_count <= 1 {
+        return jobs.iter().map(CompileJob::compile).collect();
     }
+
+    let next_job = AtomicUsize::new(0);
+    let mut results = thread::scope(|scope| {
+        let workers = (0..worker_count)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next_job.fetch_add(1, Ordering::Relaxed);
+                        match jobs.get(index) {
+                            Some(job) => results.push((index, job.compile())),
+                            None => return results,
+                        }
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("compile worker panicked"))
+            .collect::<Vec<_>>()
+    });
+
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
 }
@@ -1,13 +1,426 @@
 #pragma GCC diagnostic ignored "-Wunknown-pragmas"
 #pragma warning(disable : 4996 4101)
 
-#include "vendor/SPIRV-Cross/spirv_cross_util.hpp"
-#include "vendor/SPIRV-Cross/spirv_hlsl.hpp"
-#include "vendor/SPIRV-Cross/spirv_msl.hpp"
-#include "vendor/SPIRV-Cross/spirv_glsl.hpp"
 #include "wrapper.hpp"
+#include <algorithm>
+#include <cstddef>
+#include <cstring>
 
-static const char *latest_exception_message;
+// Each thread tracks its own message so independent compilers can be used concurrently
+static thread_local const char *latest_exception_message;
+
+// While an arena is active on a thread, the strings and arrays returned to Rust are carved out of
+// a few large chunks which are released together, instead of being allocated one by one
+struct ScInternalArena
+{
+    static const size_t chunk_size = 64 * 1024;
+
+    ScInternalArena *previous;
+    std::vector<std::pair<char *, size_t>> chunks;
+    size_t used = chunk_size;
+
+    explicit ScInternalArena(ScInternalArena *previous)
+        : previous(previous)
+    {
+    }
+
+    ~ScInternalArena()
+    {
+        for (auto &chunk : chunks)
+            free(chunk.first);
+    }
+
+    void *allocate(size_t size)
+    {
+        auto const alignment = alignof(std::max_align_t);
+        size = (size + alignment - 1) / alignment * alignment;
+        if (size > chunk_size / 4)
+        {
+            // Large arrays get a chunk of their own, keeping the current chunk for small strings
+            auto chunk = (char *)malloc(size);
+            chunks.insert(chunks.begin(), std::make_pair(chunk, size));
+            return chunk;
+        }
+        if (chunks.empty() || used + size > chunk_size)
+        {
+            chunks.push_back(std::make_pair((char *)malloc(chunk_size), chunk_size));
+            used = 0;
+        }
+        auto pointer = chunks.back().first + used;
+        used += size;
+        return pointer;
+    }
+
+    bool owns(const void *pointer) const
+    {
+        auto const address = (const char *)pointer;
+        return std::any_of(chunks.begin(), chunks.end(), [&](const std::pair<char *, size_t> &chunk) {
+            return address >= chunk.first && address < chunk.first + chunk.second;
+        });
+    }
+};
+
+static thread_local ScInternalArena *current_arena;
+
+static void *sc_internal_malloc(size_t size)
+{
+    if (!current_arena)
+        return malloc(size);
+    return current_arena->allocate(size);
+}
+
+static char *sc_internal_strdup(const char *string)
+{
+    auto const size = strlen(string) + 1;
+    auto copy = (char *)sc_internal_malloc(size);
+    memcpy(copy, string, size);
+    return copy;
+}
+
+// SPIRV-Cross keeps the parsed IR of a compiler protected, this grants the wrapper access to it
+struct ScInternalCompilerIrAccess : spirv_cross::Compiler
+{
+    static spirv_cross::ParsedIR &get(spirv_cross::Compiler *compiler)
+    {
+        return compiler->*(&ScInternalCompilerIrAccess::ir);
+    }
+};
+
+// Copies the work group size of an entry point. The `LocalSizeId` operands are kept, and the
+// size is read from those which are constants rather than specialization constants.
+static void sc_internal_set_work_group_size(spirv_cross::Compiler *compiler, const spirv_cross::SPIREntryPoint &sc_entry_point, ScEntryPoint &entry_point)
+{
+    auto const &size = sc_entry_point.workgroup_size;
+    entry_point.work_group_size_x = size.x;
+    entry_point.work_group_size_y = size.y;
+    entry_point.work_group_size_z = size.z;
+    entry_point.work_group_size_id_x = size.id_x;
+    entry_point.work_group_size_id_y = size.id_y;
+    entry_point.work_group_size_id_z = size.id_z;
+
+    auto &ir = ScInternalCompilerIrAccess::get(compiler);
+    auto const resolve = [&](uint32_t id, uint32_t &value) {
+        if (id == 0 || ir.ids[id].get_type() != spirv_cross::TypeConstant)
+            return;
+        auto const &constant = compiler->get_constant(id);
+        if (!constant.specialization)
+            value = constant.scalar();
+    };
+    resolve(size.id_x, entry_point.work_group_size_x);
+    resolve(size.id_y, entry_point.work_group_size_y);
+    resolve(size.id_z, entry_point.work_group_size_z);
+}
+
+// Runs the analysis `build_dummy_sampler_for_combined_images` uses without adding the sampler
+struct ScInternalCompilerDummySamplerAccess : spirv_cross::Compiler
+{
+    static bool needs_dummy_sampler(spirv_cross::Compiler *compiler)
+    {
+        auto &parsed_ir = ScInternalCompilerIrAccess::get(compiler);
+        auto const &entry_point = parsed_ir.ids[parsed_ir.default_entry_point].get<spirv_cross::SPIRFunction>();
+
+        bool (spirv_cross::Compiler::*traverse)(const spirv_cross::SPIRFunction &, OpcodeHandler &) const =
+            &ScInternalCompilerDummySamplerAccess::traverse_all_reachable_opcodes;
+        DummySamplerForCombinedImageHandler handler(*compiler);
+        (compiler->*traverse)(entry_point, handler);
+        return handler.need_dummy_sampler;
+    }
+};
+
+// Runs the image and sampler usage analysis SPIRV-Cross performs when compiling, which finds the
+// images and samplers used for depth comparisons
+struct ScInternalCompilerComparisonAccess : spirv_cross::Compiler
+{
+    static bool is_depth_or_compare(spirv_cross::Compiler *compiler, uint32_t id)
+    {
+        void (spirv_cross::Compiler::*analyze)() = &ScInternalCompilerComparisonAccess::analyze_image_and_sampler_usage;
+        bool (spirv_cross::Compiler::*variable_is_depth_or_compare)(spirv_cross::VariableID) const =
+            &ScInternalCompilerComparisonAccess::variable_is_depth_or_compare;
+        (compiler->*analyze)();
+        return (compiler->*variable_is_depth_or_compare)(id);
+    }
+};
+
+// Turns off the interface variable filter that `set_enabled_interface_variables` turns on
+struct ScInternalCompilerInterfaceAccess : spirv_cross::Compiler
+{
+    static void disable_interface_variable_filter(spirv_cross::Compiler *compiler)
+    {
+        compiler->*(&ScInternalCompilerInterfaceAccess::check_active_interface_variables) = false;
+    }
+
+    // Marks every resource of the module as used by the default entry point. SPIR-V 1.4 modules
+    // only list the resources an entry point uses in its interface, so the rest are added to it.
+    static void keep_unused_resources(spirv_cross::Compiler *compiler)
+    {
+        auto &parsed_ir = ScInternalCompilerIrAccess::get(compiler);
+        auto &interface_variables = parsed_ir.entry_points[parsed_ir.default_entry_point].interface_variables;
+        auto &active_variables = compiler->*(&ScInternalCompilerInterfaceAccess::active_interface_variables);
+        parsed_ir.for_each_typed_id<spirv_cross::SPIRVariable>([&](uint32_t id, const spirv_cross::SPIRVariable &var) {
+            switch (var.storage)
+            {
+            case spv::StorageClassUniform:
+            case spv::StorageClassUniformConstant:
+            case spv::StorageClassStorageBuffer:
+            case spv::StorageClassPushConstant:
+            case spv::StorageClassAtomicCounter:
+                break;
+            default:
+                return;
+            }
+
+            if (std::find(interface_variables.begin(), interface_variables.end(), spirv_cross::VariableID(id)) ==
+                interface_variables.end())
+                interface_variables.push_back(id);
+            active_variables.insert(id);
+        });
+    }
+};
+
+// Redirects every use of a variable to another variable of the same type, and hides the first so
+// that it is no longer declared
+struct ScInternalCompilerAliasAccess : spirv_cross::Compiler
+{
+    static void alias_variable(spirv_cross::Compiler *compiler, uint32_t alias_id, uint32_t target_id)
+    {
+        auto &parsed_ir = ScInternalCompilerIrAccess::get(compiler);
+        auto &alias = parsed_ir.ids[alias_id].get<spirv_cross::SPIRVariable>();
+        auto &target = parsed_ir.ids[target_id].get<spirv_cross::SPIRVariable>();
+        if (alias.basetype != target.basetype)
+            SPIRV_CROSS_THROW("aliased variables must have the same type");
+
+        parsed_ir.for_each_typed_id<spirv_cross::SPIRBlock>([&](uint32_t, spirv_cross::SPIRBlock &block) {
+            for (auto &op : block.ops)
+            {
+                auto *words = &parsed_ir.spirv[op.offset];
+                auto replace = [&](uint32_t index) {
+                    if (index < op.length && words[index] == alias_id)
+                        words[index] = target_id;
+                };
+                // Only pointer operands are replaced, as literals may hold the same value
+                switch (op.op)
+                {
+                case spv::OpLoad:
+                case spv::OpCopyObject:
+                case spv::OpAccessChain:
+                case spv::OpInBoundsAccessChain:
+                case spv::OpPtrAccessChain:
+                case spv::OpImageTexelPointer:
+                case spv::OpArrayLength:
+                    replace(2);
+                    break;
+                case spv::OpStore:
+                    replace(0);
+                    break;
+                case spv::OpFunctionCall:
+                    for (uint32_t i = 3; i < op.length; i++)
+                        replace(i);
+                    break;
+                default:
+                    break;
+                }
+            }
+        });
+
+        alias.remapped_variable = true;
+        for (auto &entry_point : parsed_ir.entry_points)
+        {
+            auto &interface_variables = entry_point.second.interface_variables;
+            auto has_variable = [&](uint32_t id) {
+                return std::find(interface_variables.begin(), interface_variables.end(), spirv_cross::VariableID(id)) !=
+                       interface_variables.end();
+            };
+            if (has_variable(alias_id) && !has_variable(target_id))
+                interface_variables.push_back(target_id);
+        }
+    }
+};
+
+// Exposes the built-ins the default entry point reads and writes
+struct ScInternalCompilerBuiltInAccess : spirv_cross::Compiler
+{
+    static void get_active_builtins(spirv_cross::Compiler *compiler, std::vector<spv::BuiltIn> &inputs, std::vector<spv::BuiltIn> &outputs)
+    {
+        compiler->update_active_builtins();
+        (compiler->*(&ScInternalCompilerBuiltInAccess::active_input_builtins)).for_each_bit([&](uint32_t bit) {
+            inputs.push_back(spv::BuiltIn(bit));
+        });
+        (compiler->*(&ScInternalCompilerBuiltInAccess::active_output_builtins)).for_each_bit([&](uint32_t bit) {
+            outputs.push_back(spv::BuiltIn(bit));
+        });
+    }
+};
+
+#ifdef SPIRV_CROSS_WRAPPER_GLSL
+// Exposes the extensions the GLSL backend requested while emitting the shader
+struct ScInternalCompilerGlslAccess : spirv_cross::CompilerGLSL
+{
+    static const spirv_cross::SmallVector<std::string> &get_forced_extensions(spirv_cross::CompilerGLSL *compiler)
+    {
+        return compiler->*(&ScInternalCompilerGlslAccess::forced_extensions);
+    }
+};
+
+// A GLSL compiler which can read selected subpass inputs through combined image samplers, for
+// render passes which were split so the attachment is written by an earlier pass, and which can
+// override the format qualifiers emitted for storage images
+class ScInternalSubpassSamplingCompilerGlsl : public spirv_cross::CompilerGLSL
+{
+public:
+    using spirv_cross::CompilerGLSL::CompilerGLSL;
+
+    bool omit_write_only_image_formats = false;
+
+    void set_image_format(uint32_t id, spv::ImageFormat format)
+    {
+        auto const &type = get_type_from_variable(id);
+        if (type.basetype != spirv_cross::SPIRType::Image || type.image.sampled != 2)
+            SPIRV_CROSS_THROW("Only storage images have format qualifiers.");
+
+        image_formats[id] = format;
+    }
+
+    spv::ImageFormat get_image_format(uint32_t id) const
+    {
+        auto const &type = get_type_from_variable(id);
+        if (type.basetype != spirv_cross::SPIRType::Image)
+            SPIRV_CROSS_THROW("Only images have formats.");
+
+        if (type.image.sampled != 2)
+            return type.image.format;
+
+        auto const format = image_formats.find(id);
+        if (format != image_formats.end())
+            return format->second;
+
+        if (omit_write_only_image_formats && has_decoration(id, spv::DecorationNonReadable))
+            return spv::ImageFormatUnknown;
+
+        return type.image.format;
+    }
+
+    void remap_subpass_input_to_sampler(uint32_t id)
+    {
+        auto const &type = get_type_from_variable(id);
+        if (type.basetype != spirv_cross::SPIRType::Image || type.image.dim != spv::DimSubpassData)
+            SPIRV_CROSS_THROW("Only subpass inputs can be remapped to samplers.");
+
+        unset_decoration(id, spv::DecorationInputAttachmentIndex);
+        sampled_subpass_inputs.insert(id);
+    }
+
+protected:
+    std::string image_type_glsl(const spirv_cross::SPIRType &type, uint32_t id) override
+    {
+        if (type.image.dim != spv::DimSubpassData || sampled_subpass_inputs.count(id) == 0)
+            return spirv_cross::CompilerGLSL::image_type_glsl(type, id);
+
+        std::string prefix;
+        switch (get<spirv_cross::SPIRType>(type.image.type).basetype)
+        {
+        case spirv_cross::SPIRType::Int:
+            prefix = "i";
+            break;
+        case spirv_cross::SPIRType::UInt:
+            prefix = "u";
+            break;
+        default:
+            break;
+        }
+        return spirv_cross::join(prefix, type.image.ms ? "sampler2DMS" : "sampler2D");
+    }
+
+    void emit_instruction(const spirv_cross::Instruction &instruction) override
+    {
+        auto const *ops = stream(instruction);
+        if (static_cast<spv::Op>(instruction.op) == spv::OpImageRead)
+        {
+            auto const *var = maybe_get_backing_variable(ops[2]);
+            if (var && sampled_subpass_inputs.count(var->self) != 0)
+            {
+                // Subpass reads always address the current fragment
+                std::string sample = "0";
+                if (instruction.length >= 6 && (ops[4] & spv::ImageOperandsSampleMask) != 0)
+                    sample = to_expression(ops[5]);
+
+                emit_op(ops[0], ops[1],
+                        spirv_cross::join("texelFetch(", to_expression(ops[2]), ", ivec2(gl_FragCoord.xy), ", sample, ")"),
+                        should_forward(ops[2]));
+                return;
+            }
+        }
+
+        spirv_cross::CompilerGLSL::emit_instruction(instruction);
+    }
+
+    void emit_uniform(const spirv_cross::SPIRVariable &var) override
+    {
+        // The qualifier is taken from the variable's type, which may be shared with other images,
+        // so the chosen format is only applied while this declaration is emitted
+        auto &type = get<spirv_cross::SPIRType>(var.basetype);
+        if (type.basetype != spirv_cross::SPIRType::Image || type.image.sampled != 2)
+        {
+            spirv_cross::CompilerGLSL::emit_uniform(var);
+            return;
+        }
+
+        struct FormatRestore
+        {
+            spv::ImageFormat &format;
+            spv::ImageFormat original;
+            ~FormatRestore() { format = original; }
+        } restore{type.image.format, type.image.format};
+
+        type.image.format = get_image_format(var.self);
+        spirv_cross::CompilerGLSL::emit_uniform(var);
+    }
+
+private:
+    std::unordered_set<uint32_t> sampled_subpass_inputs;
+    std::unordered_map<uint32_t, spv::ImageFormat> image_formats;
+};
+#endif
+
+// Computes the size and alignment of a workgroup type, using std430 rules as Workgroup variables
+// often carry no explicit layout
+static void get_workgroup_type_layout(spirv_cross::Compiler &compiler, const spirv_cross::SPIRType &type, uint32_t &size, uint32_t &alignment)
+{
+    if (!type.array.empty())
+    {
+        uint32_t element_size = 0;
+        uint32_t element_alignment = 0;
+        get_workgroup_type_layout(compiler, compiler.get_type(type.parent_type), element_size, element_alignment);
+
+        // Arrays sized by specialization constants use the current value of the constant
+        uint32_t const length = type.array_size_literal.back() ? type.array.back() : compiler.get_constant(type.array.back()).scalar();
+        uint32_t const stride = (element_size + element_alignment - 1) / element_alignment * element_alignment;
+        size = stride * length;
+        alignment = element_alignment;
+        return;
+    }
+
+    if (type.basetype == spirv_cross::SPIRType::Struct)
+    {
+        uint32_t offset = 0;
+        alignment = 1;
+        for (auto const member_type_id : type.member_types)
+        {
+            uint32_t member_size = 0;
+            uint32_t member_alignment = 0;
+            get_workgroup_type_layout(compiler, compiler.get_type(member_type_id), member_size, member_alignment);
+            offset = (offset + member_alignment - 1) / member_alignment * member_alignment + member_size;
+            alignment = std::max(alignment, member_alignment);
+        }
+        size = (offset + alignment - 1) / alignment * alignment;
+        return;
+    }
+
+    uint32_t const component_size = type.basetype == spirv_cross::SPIRType::Boolean ? 4 : type.width / 8;
+    uint32_t const vector_alignment = component_size * (type.vecsize == 3 ? 4 : type.vecsize);
+    uint32_t const vector_size = component_size * type.vecsize;
+    alignment = vector_alignment;
+    size = type.columns > 1 ? type.columns * vector_alignment : vector_size;
+}
 
 #ifdef SPIRV_CROSS_WRAPPER_NO_EXCEPTIONS
 #define INTERNAL_RESULT(block_to_attempt)                 \
@@ -53,12 +466,45 @@ extern "C"
         INTERNAL_RESULT(*message = latest_exception_message;)
     }
 
+    ScInternalResult sc_internal_parsed_ir_new(ScInternalParsedIr **ir, const uint32_t *words, const size_t size)
+    {
+        INTERNAL_RESULT(
+            do {
+                spirv_cross::Parser parser(words, size);
+                parser.parse();
+                *ir = new spirv_cross::ParsedIR(std::move(parser.get_parsed_ir()));
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_parsed_ir_delete(ScInternalParsedIr *ir)
+    {
+        INTERNAL_RESULT(delete (spirv_cross::ParsedIR *)ir;)
+    }
+
+#ifdef SPIRV_CROSS_WRAPPER_REFLECT
+    // The base compiler parses and reflects the module without the state of any backend
+    ScInternalResult sc_internal_compiler_reflect_new(ScInternalCompilerBase **compiler, const uint32_t *ir, const size_t size)
+    {
+        INTERNAL_RESULT(*compiler = new spirv_cross::Compiler(ir, size);)
+    }
+
+    ScInternalResult sc_internal_compiler_reflect_new_from_ir(ScInternalCompilerBase **compiler, const ScInternalParsedIr *ir)
+    {
+        INTERNAL_RESULT(*compiler = new spirv_cross::Compiler(*(const spirv_cross::ParsedIR *)ir);)
+    }
+#endif
+
 #ifdef SPIRV_CROSS_WRAPPER_HLSL
     ScInternalResult sc_internal_compiler_hlsl_new(ScInternalCompilerHlsl **compiler, const uint32_t *ir, const size_t size)
     {
         INTERNAL_RESULT(*compiler = new spirv_cross::CompilerHLSL(ir, size);)
     }
 
+    ScInternalResult sc_internal_compiler_hlsl_new_from_ir(ScInternalCompilerHlsl **compiler, const ScInternalParsedIr *ir)
+    {
+        INTERNAL_RESULT(*compiler = new spirv_cross::CompilerHLSL(*(const spirv_cross::ParsedIR *)ir);)
+    }
+
     ScInternalResult sc_internal_compiler_hlsl_set_options(const ScInternalCompilerHlsl *compiler, const ScHlslCompilerOptions *options)
     {
         INTERNAL_RESULT(
@@ -68,6 +514,9 @@ extern "C"
                 glsl_options.vertex.fixup_clipspace = options->vertex_transform_clip_space;
                 glsl_options.vertex.flip_vert_y = options->vertex_invert_y;
                 glsl_options.force_zero_initialized_variables = options->force_zero_initialized_variables;
+                glsl_options.emit_line_directives = options->emit_line_directives;
+                glsl_options.force_temporary = options->force_temporary;
+                glsl_options.flatten_multidimensional_arrays = options->flatten_multidimensional_arrays;
                 compiler_glsl->set_common_options(glsl_options);
 
                 auto compiler_hlsl = (spirv_cross::CompilerHLSL *)compiler;
@@ -77,6 +526,7 @@ extern "C"
                 hlsl_options.point_coord_compat = options->point_coord_compat;
                 hlsl_options.force_storage_buffer_as_uav = options->force_storage_buffer_as_uav;
                 hlsl_options.nonwritable_uav_texture_as_srv = options->nonwritable_uav_texture_as_srv;
+                hlsl_options.preserve_structured_buffers = options->preserve_structured_buffers;
 
                 compiler_hlsl->set_hlsl_options(hlsl_options);
             } while (0);)
@@ -101,6 +551,30 @@ extern "C"
                 compiler_hlsl->set_root_constant_layouts(root_constants);
             } while (0);)
     }
+
+    ScInternalResult sc_internal_compiler_hlsl_add_resource_bindings(const ScInternalCompilerHlsl *compiler, const ScHlslResourceBinding *bindings, size_t count)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto compiler_hlsl = (spirv_cross::CompilerHLSL *)compiler;
+                for (size_t i = 0; i < count; i++)
+                {
+                    spirv_cross::HLSLResourceBinding binding;
+                    binding.stage = bindings[i].stage;
+                    binding.desc_set = bindings[i].desc_set;
+                    binding.binding = bindings[i].binding;
+                    binding.cbv.register_space = bindings[i].cbv.register_space;
+                    binding.cbv.register_binding = bindings[i].cbv.register_binding;
+                    binding.uav.register_space = bindings[i].uav.register_space;
+                    binding.uav.register_binding = bindings[i].uav.register_binding;
+                    binding.srv.register_space = bindings[i].srv.register_space;
+                    binding.srv.register_binding = bindings[i].srv.register_binding;
+                    binding.sampler.register_space = bindings[i].sampler.register_space;
+                    binding.sampler.register_binding = bindings[i].sampler.register_binding;
+                    compiler_hlsl->add_hlsl_resource_binding(binding);
+                }
+            } while (0);)
+    }
 #endif
 
 #ifdef SPIRV_CROSS_WRAPPER_MSL
@@ -109,6 +583,16 @@ extern "C"
         INTERNAL_RESULT(*compiler = new spirv_cross::CompilerMSL(ir, size);)
     }
 
+    ScInternalResult sc_internal_compiler_msl_new_from_ir(ScInternalCompilerMsl **compiler, const ScInternalParsedIr *ir)
+    {
+        INTERNAL_RESULT(*compiler = new spirv_cross::CompilerMSL(*(const spirv_cross::ParsedIR *)ir);)
+    }
+
+    ScInternalResult sc_internal_compiler_msl_new_from_compiler(ScInternalCompilerMsl **compiler, const ScInternalCompilerBase *source)
+    {
+        INTERNAL_RESULT(*compiler = new spirv_cross::CompilerMSL(((const spirv_cross::Compiler *)source)->get_ir());)
+    }
+
     ScInternalResult sc_internal_compiler_msl_compile(const ScInternalCompilerBase *compiler, const char **shader,
                                                       const spirv_cross::MSLShaderInput *p_vat_overrides, const size_t vat_override_count,
                                                       const spirv_cross::MSLResourceBinding *p_res_overrides, const size_t res_override_count,
@@ -131,10 +615,17 @@ extern "C"
                 for (size_t i = 0; i < const_sampler_count; i++)
                 {
                     const auto& mapping = p_const_samplers[i];
-                    compiler_msl->remap_constexpr_sampler_by_binding(mapping.desc_set, mapping.binding, mapping.sampler);
+                    if (mapping.id != 0)
+                    {
+                        compiler_msl->remap_constexpr_sampler(mapping.id, mapping.sampler);
+                    }
+                    else
+                    {
+                        compiler_msl->remap_constexpr_sampler_by_binding(mapping.desc_set, mapping.binding, mapping.sampler);
+                    }
                 }
 
-                *shader = strdup(compiler_msl->compile().c_str());
+                *shader = sc_internal_strdup(compiler_msl->compile().c_str());
             } while (0);)
     }
 
@@ -148,6 +639,9 @@ extern "C"
                 glsl_options.vertex.fixup_clipspace = options->vertex_transform_clip_space;
                 glsl_options.vertex.flip_vert_y = options->vertex_invert_y;
                 glsl_options.force_zero_initialized_variables = options->force_zero_initialized_variables;
+                glsl_options.emit_line_directives = options->emit_line_directives;
+                glsl_options.force_temporary = options->force_temporary;
+                glsl_options.flatten_multidimensional_arrays = options->flatten_multidimensional_arrays;
                 compiler_msl->set_common_options(glsl_options);
 
                 auto msl_options = compiler_msl->get_msl_options();
@@ -168,6 +662,14 @@ extern "C"
                 msl_options.pad_fragment_output_components = options->pad_fragment_output_components;
                 msl_options.force_native_arrays = options->force_native_arrays;
                 msl_options.force_active_argument_buffer_resources = options->force_active_argument_buffer_resources;
+                msl_options.texture_buffer_native = options->texture_buffer_native;
+                msl_options.texel_buffer_texture_width = options->texel_buffer_texture_width;
+                msl_options.emulate_cube_array = options->emulate_cube_array;
+                msl_options.device_index = options->device_index;
+                msl_options.dispatch_base = options->dispatch_base;
+                msl_options.emulate_subgroups = options->emulate_subgroups;
+                msl_options.fixed_subgroup_size = options->fixed_subgroup_size;
+                msl_options.ios_use_simdgroup_functions = options->ios_use_simdgroup_functions;
                 compiler_msl->set_msl_options(msl_options);
             } while (0);)
     }
@@ -176,12 +678,42 @@ extern "C"
     {
         INTERNAL_RESULT(*is_rasterization_disabled = ((spirv_cross::CompilerMSL *)compiler)->get_is_rasterization_disabled();)
     }
+
+    ScInternalResult sc_internal_compiler_msl_is_resource_binding_used(const ScInternalCompilerMsl *compiler, const spv::ExecutionModel model, const uint32_t desc_set, const uint32_t binding, bool *is_used)
+    {
+        INTERNAL_RESULT(*is_used = ((spirv_cross::CompilerMSL *)compiler)->is_msl_resource_binding_used(model, desc_set, binding);)
+    }
+
+    ScInternalResult sc_internal_compiler_msl_is_shader_input_used(const ScInternalCompilerMsl *compiler, const uint32_t location, bool *is_used)
+    {
+        INTERNAL_RESULT(*is_used = ((spirv_cross::CompilerMSL *)compiler)->is_msl_shader_input_used(location);)
+    }
+
+    ScInternalResult sc_internal_compiler_msl_needs_buffer_size_buffer(const ScInternalCompilerMsl *compiler, bool *needs_buffer_size_buffer)
+    {
+        INTERNAL_RESULT(*needs_buffer_size_buffer = ((spirv_cross::CompilerMSL *)compiler)->needs_buffer_size_buffer();)
+    }
+
+    ScInternalResult sc_internal_compiler_msl_get_automatic_resource_binding(const ScInternalCompilerMsl *compiler, const uint32_t id, uint32_t *binding, uint32_t *secondary_binding)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const &compiler_msl = (const spirv_cross::CompilerMSL *)compiler;
+                *binding = compiler_msl->get_automatic_msl_resource_binding(id);
+                *secondary_binding = compiler_msl->get_automatic_msl_resource_binding_secondary(id);
+            } while (0);)
+    }
 #endif
 
 #ifdef SPIRV_CROSS_WRAPPER_GLSL
     ScInternalResult sc_internal_compiler_glsl_new(ScInternalCompilerGlsl **compiler, const uint32_t *ir, const size_t size)
     {
-        INTERNAL_RESULT(*compiler = new spirv_cross::CompilerGLSL(ir, size);)
+        INTERNAL_RESULT(*compiler = new ScInternalSubpassSamplingCompilerGlsl(ir, size);)
+    }
+
+    ScInternalResult sc_internal_compiler_glsl_new_from_ir(ScInternalCompilerGlsl **compiler, const ScInternalParsedIr *ir)
+    {
+        INTERNAL_RESULT(*compiler = new ScInternalSubpassSamplingCompilerGlsl(*(const spirv_cross::ParsedIR *)ir);)
     }
 
     ScInternalResult sc_internal_compiler_glsl_set_options(const ScInternalCompilerGlsl *compiler, const ScGlslCompilerOptions *options)
@@ -207,6 +739,7 @@ extern "C"
                 glsl_options.vertex.support_nonzero_base_instance = options->vertex_support_nonzero_base_instance;
                 glsl_options.fragment.default_float_precision = static_cast<spirv_cross::CompilerGLSL::Options::Precision>(options->fragment_default_float_precision);
                 glsl_options.fragment.default_int_precision = static_cast<spirv_cross::CompilerGLSL::Options::Precision>(options->fragment_default_int_precision);
+                ((ScInternalSubpassSamplingCompilerGlsl *)compiler)->omit_write_only_image_formats = options->omit_write_only_image_formats;
                 compiler_glsl->set_common_options(glsl_options);
             } while (0);)
     }
@@ -244,6 +777,37 @@ extern "C"
                 ((spirv_cross::CompilerGLSL *)compiler)->flatten_buffer_block(id);
             } while (0);)
     }
+
+    ScInternalResult sc_internal_compiler_glsl_remap_subpass_input_to_sampler(const ScInternalCompilerBase *compiler, const uint32_t id)
+    {
+        INTERNAL_RESULT(((ScInternalSubpassSamplingCompilerGlsl *)compiler)->remap_subpass_input_to_sampler(id);)
+    }
+
+    ScInternalResult sc_internal_compiler_glsl_set_image_format(const ScInternalCompilerBase *compiler, const uint32_t id, const spv::ImageFormat format)
+    {
+        INTERNAL_RESULT(((ScInternalSubpassSamplingCompilerGlsl *)compiler)->set_image_format(id, format);)
+    }
+
+    ScInternalResult sc_internal_compiler_glsl_get_image_format(const ScInternalCompilerBase *compiler, const uint32_t id, spv::ImageFormat *format)
+    {
+        INTERNAL_RESULT(*format = ((ScInternalSubpassSamplingCompilerGlsl *)compiler)->get_image_format(id);)
+    }
+
+    ScInternalResult sc_internal_compiler_glsl_get_required_extensions(const ScInternalCompilerBase *compiler, const char ***extensions, size_t *size)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const &sc_extensions = ScInternalCompilerGlslAccess::get_forced_extensions((spirv_cross::CompilerGLSL *)compiler);
+                auto const sc_size = sc_extensions.size();
+
+                *extensions = (const char **)sc_internal_malloc(sc_size * sizeof(const char *));
+                *size = sc_size;
+                for (size_t i = 0; i < sc_size; i++)
+                {
+                    (*extensions)[i] = sc_internal_strdup(sc_extensions[i].c_str());
+                }
+            } while (0);)
+    }
 #endif
 
     ScInternalResult sc_internal_compiler_get_decoration(const ScInternalCompilerBase *compiler, uint32_t *result, const uint32_t id, const spv::Decoration decoration)
@@ -251,6 +815,68 @@ extern "C"
         INTERNAL_RESULT(*result = ((spirv_cross::Compiler *)compiler)->get_decoration(id, decoration);)
     }
 
+    ScInternalResult sc_internal_compiler_get_decorations(const ScInternalCompilerBase *compiler, const uint32_t *ids, const size_t id_count, const spv::Decoration *decorations, const size_t decoration_count, bool *has_decorations, uint32_t *values)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const _compiler = (spirv_cross::Compiler *)compiler;
+                for (size_t i = 0; i < id_count; i++)
+                {
+                    for (size_t j = 0; j < decoration_count; j++)
+                    {
+                        auto const index = i * decoration_count + j;
+                        has_decorations[index] = _compiler->has_decoration(ids[i], decorations[j]);
+                        values[index] = has_decorations[index] ? _compiler->get_decoration(ids[i], decorations[j]) : 0;
+                    }
+                }
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_has_decoration(const ScInternalCompilerBase *compiler, const uint32_t id, const spv::Decoration decoration, bool *result)
+    {
+        INTERNAL_RESULT(*result = ((spirv_cross::Compiler *)compiler)->has_decoration(id, decoration);)
+    }
+
+    ScInternalResult sc_internal_compiler_has_member_decoration(const ScInternalCompilerBase *compiler, const uint32_t id, const uint32_t index, const spv::Decoration decoration, bool *result)
+    {
+        INTERNAL_RESULT(*result = ((spirv_cross::Compiler *)compiler)->has_member_decoration(id, index, decoration);)
+    }
+
+    ScInternalResult sc_internal_compiler_get_decoration_string(const ScInternalCompilerBase *compiler, const char **result, const uint32_t id, const spv::Decoration decoration)
+    {
+        INTERNAL_RESULT(*result = sc_internal_strdup(((spirv_cross::Compiler *)compiler)->get_decoration_string(id, decoration).c_str());)
+    }
+
+    ScInternalResult sc_internal_compiler_buffer_is_hlsl_counter_buffer(const ScInternalCompilerBase *compiler, const uint32_t id, bool *is_counter_buffer)
+    {
+        INTERNAL_RESULT(*is_counter_buffer = ((spirv_cross::Compiler *)compiler)->buffer_is_hlsl_counter_buffer(id);)
+    }
+
+    ScInternalResult sc_internal_compiler_buffer_get_hlsl_counter_buffer(const ScInternalCompilerBase *compiler, const uint32_t id, bool *has_counter_buffer, uint32_t *counter_id)
+    {
+        INTERNAL_RESULT(
+            do {
+                uint32_t _counter_id = 0;
+                *has_counter_buffer = ((spirv_cross::Compiler *)compiler)->buffer_get_hlsl_counter_buffer(id, _counter_id);
+                *counter_id = _counter_id;
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_needs_dummy_sampler_for_combined_images(const ScInternalCompilerBase *compiler, bool *needs_dummy_sampler)
+    {
+        INTERNAL_RESULT(*needs_dummy_sampler = ScInternalCompilerDummySamplerAccess::needs_dummy_sampler((spirv_cross::Compiler *)compiler);)
+    }
+
+    ScInternalResult sc_internal_compiler_variable_is_depth_or_compare(const ScInternalCompilerBase *compiler, const uint32_t id, bool *is_depth_or_compare)
+    {
+        INTERNAL_RESULT(*is_depth_or_compare = ScInternalCompilerComparisonAccess::is_depth_or_compare((spirv_cross::Compiler *)compiler, id);)
+    }
+
+    ScInternalResult sc_internal_compiler_build_dummy_sampler_for_combined_images(const ScInternalCompilerBase *compiler, uint32_t *sampler_id)
+    {
+        INTERNAL_RESULT(*sampler_id = ((spirv_cross::Compiler *)compiler)->build_dummy_sampler_for_combined_images();)
+    }
+
     ScInternalResult sc_internal_compiler_unset_decoration(const ScInternalCompilerBase *compiler, const uint32_t id, const spv::Decoration decoration)
     {
         INTERNAL_RESULT(((spirv_cross::Compiler *)compiler)->unset_decoration(id, decoration);)
@@ -261,12 +887,77 @@ extern "C"
         INTERNAL_RESULT(((spirv_cross::Compiler *)compiler)->set_decoration(id, decoration, argument);)
     }
 
+    ScInternalResult sc_internal_compiler_set_decorations(const ScInternalCompilerBase *compiler, const uint32_t *ids, const spv::Decoration *decorations, const uint32_t *arguments, const size_t count, size_t *invalid_entry)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const _compiler = (spirv_cross::Compiler *)compiler;
+                auto const bound = ScInternalCompilerIrAccess::get(_compiler).ids.size();
+                *invalid_entry = count;
+                for (size_t i = 0; i < count; i++)
+                {
+                    if (ids[i] == 0 || ids[i] >= bound)
+                    {
+                        *invalid_entry = i;
+                        return ScInternalResult::Success;
+                    }
+                }
+
+                // Entries are applied in order, so they are rolled back in reverse to undo repeated ids
+                std::vector<std::pair<bool, uint32_t>> previous;
+                previous.reserve(count);
+                auto const rollback = [&]() {
+                    for (size_t i = previous.size(); i-- > 0;)
+                    {
+                        if (previous[i].first)
+                            _compiler->set_decoration(ids[i], decorations[i], previous[i].second);
+                        else
+                            _compiler->unset_decoration(ids[i], decorations[i]);
+                    }
+                };
+#ifndef SPIRV_CROSS_WRAPPER_NO_EXCEPTIONS
+                try
+#endif
+                {
+                    for (size_t i = 0; i < count; i++)
+                    {
+                        auto const had_decoration = _compiler->has_decoration(ids[i], decorations[i]);
+                        previous.emplace_back(had_decoration, had_decoration ? _compiler->get_decoration(ids[i], decorations[i]) : 0);
+                        _compiler->set_decoration(ids[i], decorations[i], arguments[i]);
+                    }
+                }
+#ifndef SPIRV_CROSS_WRAPPER_NO_EXCEPTIONS
+                catch (...)
+                {
+                    rollback();
+                    throw;
+                }
+#else
+                (void)rollback;
+#endif
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_set_decoration_string(const ScInternalCompilerBase *compiler, const uint32_t id, const spv::Decoration decoration, const char *argument)
+    {
+        INTERNAL_RESULT(((spirv_cross::Compiler *)compiler)->set_decoration_string(id, decoration, std::string(argument));)
+    }
+
     ScInternalResult sc_internal_compiler_get_name(const ScInternalCompilerBase *compiler, const uint32_t id, const char **name)
     {
         INTERNAL_RESULT(
             do {
                 auto const _name = ((spirv_cross::Compiler *)compiler)->get_name(id);
-                *name = strdup(_name.c_str());
+                *name = sc_internal_strdup(_name.c_str());
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_get_remapped_declared_block_name(const ScInternalCompilerBase *compiler, const uint32_t id, const char **name)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const _name = ((spirv_cross::Compiler *)compiler)->get_remapped_declared_block_name(id);
+                *name = sc_internal_strdup(_name.c_str());
             } while (0);)
     }
 
@@ -280,6 +971,49 @@ extern "C"
         INTERNAL_RESULT(((spirv_cross::Compiler *)compiler)->set_member_name(id, index, std::string(name));)
     }
 
+    ScInternalResult sc_internal_compiler_strip_names(const ScInternalCompilerBase *compiler)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto &ir = ScInternalCompilerIrAccess::get((spirv_cross::Compiler *)compiler);
+                for (auto &id_and_meta : ir.meta)
+                {
+                    id_and_meta.second.decoration.alias.clear();
+                    for (auto &member : id_and_meta.second.members)
+                    {
+                        member.alias.clear();
+                    }
+                }
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_set_variable_type_remap_callback(const ScInternalCompilerBase *compiler, ScVariableTypeRemapCallback callback, void *user_data)
+    {
+        // Every target emitting source derives from the GLSL compiler, which owns the callback,
+        // while the reflection target has nothing to remap
+        INTERNAL_RESULT(
+            do {
+#ifdef SPIRV_CROSS_WRAPPER_GLSL
+                auto const compiler_glsl = dynamic_cast<spirv_cross::CompilerGLSL *>((spirv_cross::Compiler *)compiler);
+                if (compiler_glsl == nullptr)
+                    return ScInternalResult::Unhandled;
+
+                compiler_glsl->set_variable_type_remap_callback([callback, user_data](const spirv_cross::SPIRType &type, const std::string &variable_name, std::string &type_name) {
+                    const char *remapped_type_name = nullptr;
+                    callback(user_data, type.self, variable_name.c_str(), type_name.c_str(), &remapped_type_name);
+                    if (remapped_type_name != nullptr)
+                        type_name = remapped_type_name;
+                });
+#else
+                // Without a backend, the GLSL compiler is not built
+                (void)compiler;
+                (void)callback;
+                (void)user_data;
+                return ScInternalResult::Unhandled;
+#endif
+            } while (0);)
+    }
+
     ScInternalResult sc_internal_compiler_get_entry_points(const ScInternalCompilerBase *compiler, ScEntryPoint **entry_points, size_t *size)
     {
         INTERNAL_RESULT(
@@ -288,22 +1022,36 @@ extern "C"
                 auto const &sc_entry_point_names_and_stages = comp.get_entry_points_and_stages();
                 auto const sc_size = sc_entry_point_names_and_stages.size();
 
-                *entry_points = (ScEntryPoint *)malloc(sc_size * sizeof(ScEntryPoint));
+                *entry_points = (ScEntryPoint *)sc_internal_malloc(sc_size * sizeof(ScEntryPoint));
                 *size = sc_size;
                 for (uint32_t i = 0; i < sc_size; i++)
                 {
                     auto const &sc_entry_point = sc_entry_point_names_and_stages[i];
                     auto const &sc_spir_entry_point = comp.get_entry_point(sc_entry_point.name, sc_entry_point.execution_model);
                     auto &entry_point = (*entry_points)[i];
-                    entry_point.name = strdup(sc_entry_point.name.c_str());
+                    entry_point.name = sc_internal_strdup(sc_entry_point.name.c_str());
                     entry_point.execution_model = sc_spir_entry_point.model;
-                    entry_point.work_group_size_x = sc_spir_entry_point.workgroup_size.x;
-                    entry_point.work_group_size_y = sc_spir_entry_point.workgroup_size.y;
-                    entry_point.work_group_size_z = sc_spir_entry_point.workgroup_size.z;
+                    sc_internal_set_work_group_size((spirv_cross::Compiler *)compiler, sc_spir_entry_point, entry_point);
                 }
             } while (0);)
     }
 
+    ScInternalResult sc_internal_compiler_get_current_entry_point(const ScInternalCompilerBase *compiler, ScEntryPoint **entry_point)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto &ir = ScInternalCompilerIrAccess::get((spirv_cross::Compiler *)compiler);
+                auto const sc_entry_point = ir.entry_points.find(ir.default_entry_point);
+                if (sc_entry_point == ir.entry_points.end())
+                    SPIRV_CROSS_THROW("Module has no entry point.");
+
+                *entry_point = (ScEntryPoint *)sc_internal_malloc(sizeof(ScEntryPoint));
+                (*entry_point)->name = sc_internal_strdup(sc_entry_point->second.orig_name.c_str());
+                (*entry_point)->execution_model = sc_entry_point->second.model;
+                sc_internal_set_work_group_size((spirv_cross::Compiler *)compiler, sc_entry_point->second, **entry_point);
+            } while (0);)
+    }
+
     ScInternalResult sc_internal_compiler_get_active_buffer_ranges(const ScInternalCompilerBase *compiler, uint32_t id, ScBufferRange **active_buffer_ranges, size_t *size)
     {
         INTERNAL_RESULT(
@@ -312,7 +1060,7 @@ extern "C"
                 auto const &sc_active_buffer_ranges = comp.get_active_buffer_ranges(id);
                 auto const sc_size = sc_active_buffer_ranges.size();
 
-                *active_buffer_ranges = (ScBufferRange *)malloc(sc_size * sizeof(ScBufferRange));
+                *active_buffer_ranges = (ScBufferRange *)sc_internal_malloc(sc_size * sizeof(ScBufferRange));
                 *size = sc_size;
                 for (uint32_t i = 0; i < sc_size; i++)
                 {
@@ -329,7 +1077,7 @@ extern "C"
     {
         INTERNAL_RESULT(
             do {
-                *compiled_entry_point_name = strdup(
+                *compiled_entry_point_name = sc_internal_strdup(
                     (*((spirv_cross::Compiler *)compiler))
                         .get_cleansed_entry_point_name(std::string(original_entry_point_name), execution_model)
                         .c_str());
@@ -348,14 +1096,105 @@ extern "C"
         }
 
         resources->num = sc_size;
-        resources->data = (ScResource *)malloc(sc_size * sizeof(ScResource));
+        resources->data = (ScResource *)sc_internal_malloc(sc_size * sizeof(ScResource));
         for (uint32_t i = 0; i < sc_size; i++)
         {
             auto const &resource = sc_resources[i];
             resources->data[i].id = resource.id;
             resources->data[i].type_id = resource.type_id;
             resources->data[i].base_type_id = resource.base_type_id;
-            resources->data[i].name = strdup(resource.name.c_str());
+            resources->data[i].name = sc_internal_strdup(resource.name.c_str());
+        }
+    }
+
+    void fill_builtin_resource_array(ScBuiltInResourceArray *resources, const std::vector<ScBuiltInResource> &sc_resources)
+    {
+        auto const sc_size = sc_resources.size();
+        resources->num = sc_size;
+        resources->data = (ScBuiltInResource *)sc_internal_malloc(sc_size * sizeof(ScBuiltInResource));
+        std::copy(sc_resources.begin(), sc_resources.end(), resources->data);
+    }
+
+    // Built-in variables are hidden from the other resource lists, so they are gathered from the
+    // interface of the selected entry point instead
+    void fill_builtin_resources(spirv_cross::Compiler *compiler, ScShaderResources *shader_resources, const std::function<bool(uint32_t)> &is_active)
+    {
+        auto &ir = ScInternalCompilerIrAccess::get(compiler);
+        auto const &entry_point = ir.entry_points[ir.default_entry_point];
+        std::vector<ScBuiltInResource> inputs;
+        std::vector<ScBuiltInResource> outputs;
+
+        for (uint32_t const variable_id : entry_point.interface_variables)
+        {
+            auto const &variable = ir.ids[variable_id].get<spirv_cross::SPIRVariable>();
+            if ((variable.storage != spv::StorageClassInput && variable.storage != spv::StorageClassOutput) || !is_active(variable_id))
+                continue;
+
+            auto const &type = compiler->get_type(variable.basetype);
+            auto &list = variable.storage == spv::StorageClassInput ? inputs : outputs;
+            auto const push_builtin = [&](uint32_t builtin, uint32_t value_type_id) {
+                ScBuiltInResource resource;
+                resource.builtin = (spv::BuiltIn)builtin;
+                resource.value_type_id = value_type_id;
+                resource.resource.id = variable_id;
+                resource.resource.type_id = variable.basetype;
+                resource.resource.base_type_id = type.self;
+                resource.resource.name = sc_internal_strdup(compiler->get_name(variable_id).c_str());
+                list.push_back(resource);
+            };
+
+            if (compiler->has_decoration(type.self, spv::DecorationBlock))
+            {
+                for (uint32_t i = 0; i < type.member_types.size(); i++)
+                {
+                    if (compiler->has_member_decoration(type.self, i, spv::DecorationBuiltIn))
+                        push_builtin(compiler->get_member_decoration(type.self, i, spv::DecorationBuiltIn), type.member_types[i]);
+                }
+            }
+            else if (compiler->has_decoration(variable_id, spv::DecorationBuiltIn))
+            {
+                push_builtin(compiler->get_decoration(variable_id, spv::DecorationBuiltIn), type.parent_type);
+            }
+        }
+
+        fill_builtin_resource_array(&shader_resources->builtin_inputs, inputs);
+        fill_builtin_resource_array(&shader_resources->builtin_outputs, outputs);
+    }
+
+    // Bit N of kinds selects the Nth list, in the order of the fields of ScShaderResources, and
+    // the lists which are not selected are left empty
+    void fill_shader_resources(ScShaderResources *shader_resources, const spirv_cross::ShaderResources &sc_resources, const uint32_t kinds = ~0u)
+    {
+        static const spirv_cross::SmallVector<spirv_cross::Resource> empty;
+        ScResourceArray *arrays[] = {
+            &shader_resources->uniform_buffers,
+            &shader_resources->storage_buffers,
+            &shader_resources->stage_inputs,
+            &shader_resources->stage_outputs,
+            &shader_resources->subpass_inputs,
+            &shader_resources->storage_images,
+            &shader_resources->sampled_images,
+            &shader_resources->atomic_counters,
+            &shader_resources->push_constant_buffers,
+            &shader_resources->separate_images,
+            &shader_resources->separate_samplers,
+        };
+        const spirv_cross::SmallVector<spirv_cross::Resource> *lists[] = {
+            &sc_resources.uniform_buffers,
+            &sc_resources.storage_buffers,
+            &sc_resources.stage_inputs,
+            &sc_resources.stage_outputs,
+            &sc_resources.subpass_inputs,
+            &sc_resources.storage_images,
+            &sc_resources.sampled_images,
+            &sc_resources.atomic_counters,
+            &sc_resources.push_constant_buffers,
+            &sc_resources.separate_images,
+            &sc_resources.separate_samplers,
+        };
+        for (uint32_t kind = 0; kind < sizeof(lists) / sizeof(lists[0]); kind++)
+        {
+            fill_resource_array(arrays[kind], (kinds & (1u << kind)) != 0 ? *lists[kind] : empty);
         }
     }
 
@@ -363,19 +1202,70 @@ extern "C"
     {
         INTERNAL_RESULT(
             do {
-                auto const sc_resources = ((const spirv_cross::Compiler *)compiler)->get_shader_resources();
+                auto const _compiler = (spirv_cross::Compiler *)compiler;
+                fill_shader_resources(shader_resources, _compiler->get_shader_resources());
+                fill_builtin_resources(_compiler, shader_resources, [](uint32_t) { return true; });
+            } while (0);)
+    }
 
-                fill_resource_array(&shader_resources->uniform_buffers, sc_resources.uniform_buffers);
-                fill_resource_array(&shader_resources->storage_buffers, sc_resources.storage_buffers);
-                fill_resource_array(&shader_resources->stage_inputs, sc_resources.stage_inputs);
-                fill_resource_array(&shader_resources->stage_outputs, sc_resources.stage_outputs);
-                fill_resource_array(&shader_resources->subpass_inputs, sc_resources.subpass_inputs);
-                fill_resource_array(&shader_resources->storage_images, sc_resources.storage_images);
-                fill_resource_array(&shader_resources->sampled_images, sc_resources.sampled_images);
-                fill_resource_array(&shader_resources->atomic_counters, sc_resources.atomic_counters);
-                fill_resource_array(&shader_resources->push_constant_buffers, sc_resources.push_constant_buffers);
-                fill_resource_array(&shader_resources->separate_images, sc_resources.separate_images);
-                fill_resource_array(&shader_resources->separate_samplers, sc_resources.separate_samplers);
+    ScInternalResult sc_internal_compiler_get_shader_resources_of_kinds(const ScInternalCompilerBase *compiler, const uint32_t kinds, ScShaderResources *shader_resources)
+    {
+        INTERNAL_RESULT(
+            do {
+                // Built-ins are not a resource kind and are always left empty
+                fill_shader_resources(shader_resources, ((spirv_cross::Compiler *)compiler)->get_shader_resources(), kinds);
+                shader_resources->builtin_inputs.num = 0;
+                shader_resources->builtin_inputs.data = 0x0;
+                shader_resources->builtin_outputs.num = 0;
+                shader_resources->builtin_outputs.data = 0x0;
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_get_shader_resource_list(const ScInternalCompilerBase *compiler, const uint32_t kind, ScResourceArray *resources)
+    {
+        INTERNAL_RESULT(
+            do {
+                // The kinds are numbered in the order of the fields of ScShaderResources
+                auto const sc_resources = ((spirv_cross::Compiler *)compiler)->get_shader_resources();
+                const spirv_cross::SmallVector<spirv_cross::Resource> *lists[] = {
+                    &sc_resources.uniform_buffers,
+                    &sc_resources.storage_buffers,
+                    &sc_resources.stage_inputs,
+                    &sc_resources.stage_outputs,
+                    &sc_resources.subpass_inputs,
+                    &sc_resources.storage_images,
+                    &sc_resources.sampled_images,
+                    &sc_resources.atomic_counters,
+                    &sc_resources.push_constant_buffers,
+                    &sc_resources.separate_images,
+                    &sc_resources.separate_samplers,
+                };
+                if (kind >= sizeof(lists) / sizeof(lists[0]))
+                    return ScInternalResult::Unhandled;
+
+                fill_resource_array(resources, *lists[kind]);
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_get_shader_resources_for_entry_point(const ScInternalCompilerBase *compiler, const char *name, const spv::ExecutionModel execution_model, ScShaderResources *shader_resources)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const _compiler = (spirv_cross::Compiler *)compiler;
+                // Active variables are gathered from the selected entry point, restore it afterwards
+                struct EntryPointGuard
+                {
+                    spirv_cross::ParsedIR &ir;
+                    decltype(spirv_cross::ParsedIR::default_entry_point) previous;
+                    ~EntryPointGuard() { ir.default_entry_point = previous; }
+                };
+                auto &ir = ScInternalCompilerIrAccess::get(_compiler);
+                EntryPointGuard guard{ir, ir.default_entry_point};
+
+                _compiler->set_entry_point(name, execution_model);
+                auto const active_variables = _compiler->get_active_interface_variables();
+                fill_shader_resources(shader_resources, _compiler->get_shader_resources(active_variables));
+                fill_builtin_resources(_compiler, shader_resources, [&](uint32_t id) { return active_variables.count(id) != 0; });
             } while (0);)
     }
 
@@ -386,7 +1276,7 @@ extern "C"
                 auto const sc_constants = ((const spirv_cross::Compiler *)compiler)->get_specialization_constants();
                 auto const sc_size = sc_constants.size();
 
-                auto p_constants = (ScSpecializationConstant *)malloc(sc_size * sizeof(ScSpecializationConstant));
+                auto p_constants = (ScSpecializationConstant *)sc_internal_malloc(sc_size * sizeof(ScSpecializationConstant));
                 *constants = p_constants;
                 *size = sc_size;
                 for (uint32_t i = 0; i < sc_size; i++)
@@ -407,6 +1297,17 @@ extern "C"
             } while (0);)
     }
 
+    ScInternalResult sc_internal_compiler_get_type_parent(const ScInternalCompilerBase *compiler, const uint32_t id, bool *is_pointer, uint32_t *parent_type)
+    {
+        INTERNAL_RESULT(
+            do {
+                // Vectors and matrices also have a parent type, their component type, which is not of interest here
+                auto const &type = ((spirv_cross::Compiler *)compiler)->get_type(id);
+                *is_pointer = type.pointer;
+                *parent_type = type.pointer || !type.array.empty() ? uint32_t(type.parent_type) : 0;
+            } while (0);)
+    }
+
     ScInternalResult sc_internal_compiler_get_type(const ScInternalCompilerBase *compiler, const uint32_t id, const ScType **spirv_type)
     {
         INTERNAL_RESULT(
@@ -415,7 +1316,7 @@ extern "C"
                 auto const member_types_size = type.member_types.size();
                 auto const array_size = type.array.size();
 
-                auto ty = (ScType *)malloc(sizeof(ScType));
+                auto ty = (ScType *)sc_internal_malloc(sizeof(ScType));
                 ty->type = type.basetype;
                 ty->width = type.width;
                 ty->vecsize = type.vecsize;
@@ -426,7 +1327,7 @@ extern "C"
 
                 if (member_types_size > 0)
                 {
-                    auto const &member_types = (uint32_t *)malloc(member_types_size * sizeof(uint32_t));
+                    auto const &member_types = (uint32_t *)sc_internal_malloc(member_types_size * sizeof(uint32_t));
 
                     for (size_t i = 0; i < member_types_size; i++)
                     {
@@ -438,8 +1339,8 @@ extern "C"
 
                 if (array_size > 0)
                 {
-                    auto const &array = (uint32_t *)malloc(array_size * sizeof(uint32_t));
-                    auto const &array_size_literal = (bool *)malloc(array_size * sizeof(bool));
+                    auto const &array = (uint32_t *)sc_internal_malloc(array_size * sizeof(uint32_t));
+                    auto const &array_size_literal = (bool *)sc_internal_malloc(array_size * sizeof(bool));
 
                     for (size_t i = 0; i < array_size; i++)
                     {
@@ -465,7 +1366,7 @@ extern "C"
         INTERNAL_RESULT(
             do {
                 auto const member_name = ((spirv_cross::Compiler *)compiler)->get_member_name(id, index);
-                *name = strdup(member_name.c_str());
+                *name = sc_internal_strdup(member_name.c_str());
             } while (0);)
     }
 
@@ -525,7 +1426,7 @@ extern "C"
             spirv_cross::SpecializationConstant wg_z;
             ((const spirv_cross::Compiler *)compiler)->get_work_group_size_specialization_constants(wg_x, wg_y, wg_z);
 
-            auto p_constants = (ScSpecializationConstant *)malloc(3 * sizeof(ScSpecializationConstant));
+            auto p_constants = (ScSpecializationConstant *)sc_internal_malloc(3 * sizeof(ScSpecializationConstant));
             p_constants[0].id = wg_x.id;
             p_constants[0].constant_id = wg_x.constant_id;
             p_constants[1].id = wg_y.id;
@@ -543,7 +1444,7 @@ extern "C"
             auto const sc_active_variables = ((const spirv_cross::Compiler *)compiler)->get_active_interface_variables();
             auto const sc_size = sc_active_variables.size();
 
-            *ids = (uint32_t *)malloc(sc_active_variables.size() * sizeof(uint32_t));
+            *ids = (uint32_t *)sc_internal_malloc(sc_active_variables.size() * sizeof(uint32_t));
             *size = sc_size;
             size_t index = 0;
             for (const auto& elem : sc_active_variables){
@@ -552,14 +1453,309 @@ extern "C"
         } while(0);)
     }
 
+    ScInternalResult sc_internal_compiler_get_active_builtins(const ScInternalCompilerBase *compiler, spv::BuiltIn **inputs, size_t *input_count, spv::BuiltIn **outputs, size_t *output_count)
+    {
+        INTERNAL_RESULT(
+            do {
+                std::vector<spv::BuiltIn> sc_inputs;
+                std::vector<spv::BuiltIn> sc_outputs;
+                ScInternalCompilerBuiltInAccess::get_active_builtins((spirv_cross::Compiler *)compiler, sc_inputs, sc_outputs);
+
+                *inputs = (spv::BuiltIn *)sc_internal_malloc(sc_inputs.size() * sizeof(spv::BuiltIn));
+                *input_count = sc_inputs.size();
+                std::copy(sc_inputs.begin(), sc_inputs.end(), *inputs);
+
+                *outputs = (spv::BuiltIn *)sc_internal_malloc(sc_outputs.size() * sizeof(spv::BuiltIn));
+                *output_count = sc_outputs.size();
+                std::copy(sc_outputs.begin(), sc_outputs.end(), *outputs);
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_set_remove_unused_variables(const ScInternalCompilerBase *compiler, const bool remove)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const _compiler = (spirv_cross::Compiler *)compiler;
+                if (remove)
+                    _compiler->set_enabled_interface_variables(_compiler->get_active_interface_variables());
+                else
+                    ScInternalCompilerInterfaceAccess::disable_interface_variable_filter(_compiler);
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_keep_unused_resources(const ScInternalCompilerBase *compiler)
+    {
+        INTERNAL_RESULT(
+            do {
+                ScInternalCompilerInterfaceAccess::keep_unused_resources((spirv_cross::Compiler *)compiler);
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_alias_variable(const ScInternalCompilerBase *compiler, const uint32_t alias_id, const uint32_t target_id)
+    {
+        INTERNAL_RESULT(
+            do {
+                ScInternalCompilerAliasAccess::alias_variable((spirv_cross::Compiler *)compiler, alias_id, target_id);
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_get_declared_capabilities(const ScInternalCompilerBase *compiler, uint32_t **capabilities, size_t *size)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const &sc_capabilities = ((const spirv_cross::Compiler *)compiler)->get_declared_capabilities();
+                auto const sc_size = sc_capabilities.size();
+
+                *capabilities = (uint32_t *)sc_internal_malloc(sc_size * sizeof(uint32_t));
+                *size = sc_size;
+                for (size_t i = 0; i < sc_size; i++)
+                {
+                    (*capabilities)[i] = (uint32_t)sc_capabilities[i];
+                }
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_get_used_opcodes(const ScInternalCompilerBase *compiler, uint32_t **opcodes, size_t *size)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto &ir = ScInternalCompilerIrAccess::get((spirv_cross::Compiler *)compiler);
+                std::unordered_set<uint32_t> sc_opcodes;
+                ir.for_each_typed_id<spirv_cross::SPIRBlock>([&](uint32_t, const spirv_cross::SPIRBlock &block) {
+                    for (auto const &instruction : block.ops)
+                        sc_opcodes.insert(instruction.op);
+                });
+
+                *opcodes = (uint32_t *)sc_internal_malloc(sc_opcodes.size() * sizeof(uint32_t));
+                *size = sc_opcodes.size();
+                size_t i = 0;
+                for (auto const opcode : sc_opcodes)
+                {
+                    (*opcodes)[i++] = opcode;
+                }
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_get_spirv(const ScInternalCompilerBase *compiler, uint32_t **words, size_t *size)
+    {
+        INTERNAL_RESULT(
+            do {
+                // The parser keeps the words it parsed, converted to the native endianness
+                auto const &sc_words = ScInternalCompilerIrAccess::get((spirv_cross::Compiler *)compiler).spirv;
+
+                *words = (uint32_t *)sc_internal_malloc(sc_words.size() * sizeof(uint32_t));
+                *size = sc_words.size();
+                std::copy(sc_words.begin(), sc_words.end(), *words);
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_get_meta_ids(const ScInternalCompilerBase *compiler, uint32_t **ids, size_t *size)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const &meta = ScInternalCompilerIrAccess::get((spirv_cross::Compiler *)compiler).meta;
+
+                *ids = (uint32_t *)sc_internal_malloc(meta.size() * sizeof(uint32_t));
+                *size = meta.size();
+                size_t i = 0;
+                for (auto const &id_and_meta : meta)
+                {
+                    (*ids)[i++] = id_and_meta.first;
+                }
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_get_descriptor_count(const ScInternalCompilerBase *compiler, const uint32_t id, uint32_t *count, bool *is_runtime_sized)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const _compiler = (spirv_cross::Compiler *)compiler;
+                auto const &type = _compiler->get_type_from_variable(id);
+
+                *count = 1;
+                *is_runtime_sized = false;
+                for (size_t i = 0; i < type.array.size(); i++)
+                {
+                    // Runtime arrays have a literal size of zero
+                    uint32_t const length = type.array_size_literal[i] ? type.array[i] : _compiler->get_constant(type.array[i]).scalar();
+                    if (length == 0)
+                        *is_runtime_sized = true;
+                    else
+                        *count *= length;
+                }
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_get_outer_array(const ScInternalCompilerBase *compiler, const uint32_t id, uint32_t *element_type_id, uint32_t *length)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const _compiler = (spirv_cross::Compiler *)compiler;
+                auto const &type = _compiler->get_type(id);
+                if (type.array.empty())
+                    SPIRV_CROSS_THROW("Type is not an array.");
+
+                // The outermost dimension is the last one, and the parent type drops it
+                *element_type_id = type.parent_type;
+                *length = type.array_size_literal.back() ? type.array.back() : _compiler->get_constant(type.array.back()).scalar();
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_get_workgroup_shared_memory_size(const ScInternalCompilerBase *compiler, const char *name, const spv::ExecutionModel execution_model, uint32_t *size)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const _compiler = (spirv_cross::Compiler *)compiler;
+                auto const &entry_point = _compiler->get_entry_point(name, execution_model);
+                auto &ir = ScInternalCompilerIrAccess::get(_compiler);
+
+                // Before SPIR-V 1.4 entry points do not list their Workgroup variables, so every one is counted
+                auto const lists_all_globals = ir.spirv.size() > 1 && ir.spirv[1] >= 0x10400;
+                uint32_t total_size = 0;
+                ir.for_each_typed_id<spirv_cross::SPIRVariable>([&](uint32_t id, const spirv_cross::SPIRVariable &variable) {
+                    if (variable.storage != spv::StorageClassWorkgroup)
+                        return;
+                    auto const &interface_variables = entry_point.interface_variables;
+                    auto const is_interface_variable = [&](uint32_t interface_id) { return interface_id == id; };
+                    if (lists_all_globals &&
+                        std::none_of(interface_variables.begin(), interface_variables.end(), is_interface_variable))
+                        return;
+
+                    uint32_t variable_size = 0;
+                    uint32_t variable_alignment = 0;
+                    auto const &pointer_type = _compiler->get_type(variable.basetype);
+                    get_workgroup_type_layout(*_compiler, _compiler->get_type(pointer_type.parent_type), variable_size, variable_alignment);
+                    total_size += variable_size;
+                });
+                *size = total_size;
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_get_entry_point_interface_variables(const ScInternalCompilerBase *compiler, const char *name, const spv::ExecutionModel execution_model, uint32_t **ids, size_t *size)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const _compiler = (spirv_cross::Compiler *)compiler;
+                auto const &entry_point = _compiler->get_entry_point(name, execution_model);
+
+                // From SPIR-V 1.4 the interface lists every global the entry point uses, so only keep stage variables
+                std::vector<uint32_t> sc_ids;
+                for (uint32_t const variable_id : entry_point.interface_variables)
+                {
+                    auto const storage = _compiler->get_storage_class(variable_id);
+                    if (storage == spv::StorageClassInput || storage == spv::StorageClassOutput)
+                        sc_ids.push_back(variable_id);
+                }
+
+                *ids = (uint32_t *)sc_internal_malloc(sc_ids.size() * sizeof(uint32_t));
+                *size = sc_ids.size();
+                std::copy(sc_ids.begin(), sc_ids.end(), *ids);
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_get_entry_point_subgroup_size(const ScInternalCompilerBase *compiler, const char *name, const spv::ExecutionModel execution_model, bool *has_subgroup_size, uint32_t *subgroup_size)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const _compiler = (spirv_cross::Compiler *)compiler;
+                auto const &entry_point = _compiler->get_entry_point(name, execution_model);
+                *has_subgroup_size = false;
+                *subgroup_size = 0;
+                if (!entry_point.flags.get(spv::ExecutionModeSubgroupSize))
+                    break;
+
+                // SPIRV-Cross records that SubgroupSize is declared but drops its operand, so it is
+                // read back from the execution modes, which precede the debug instructions
+                auto const &spirv = ScInternalCompilerIrAccess::get(_compiler).spirv;
+                size_t offset = 5;
+                while (offset < spirv.size())
+                {
+                    auto const op = spirv[offset] & 0xffff;
+                    auto const word_count = spirv[offset] >> 16;
+                    if (word_count == 0 || offset + word_count > spirv.size())
+                        break;
+
+                    if (op == spv::OpExecutionMode && word_count >= 4 && spirv[offset + 1] == uint32_t(entry_point.self) &&
+                        spirv[offset + 2] == spv::ExecutionModeSubgroupSize)
+                    {
+                        *has_subgroup_size = true;
+                        *subgroup_size = spirv[offset + 3];
+                        break;
+                    }
+                    if (op != spv::OpCapability && op != spv::OpExtension && op != spv::OpExtInstImport &&
+                        op != spv::OpMemoryModel && op != spv::OpEntryPoint && op != spv::OpExecutionMode &&
+                        op != spv::OpExecutionModeId)
+                        break;
+                    offset += word_count;
+                }
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_get_execution_mode_argument(const ScInternalCompilerBase *compiler, const spv::ExecutionMode mode, const uint32_t index, bool *has_argument, uint32_t *argument)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const _compiler = (spirv_cross::Compiler *)compiler;
+                auto const &parsed_ir = ScInternalCompilerIrAccess::get(_compiler);
+                uint32_t const entry_point_id = parsed_ir.default_entry_point;
+
+                // SPIRV-Cross keeps the arguments of only some modes, so they are read from the module
+                auto const &spirv = parsed_ir.spirv;
+                *has_argument = false;
+                *argument = 0;
+                size_t offset = 5;
+                while (offset < spirv.size())
+                {
+                    auto const op = spirv[offset] & 0xffff;
+                    auto const word_count = spirv[offset] >> 16;
+                    if (word_count == 0 || offset + word_count > spirv.size())
+                        break;
+
+                    if ((op == spv::OpExecutionMode || op == spv::OpExecutionModeId) && word_count >= 3 &&
+                        spirv[offset + 1] == entry_point_id && spirv[offset + 2] == mode)
+                    {
+                        if (index < word_count - 3)
+                        {
+                            *has_argument = true;
+                            *argument = spirv[offset + 3 + index];
+                        }
+                        break;
+                    }
+                    offset += word_count;
+                }
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_has_execution_mode(const ScInternalCompilerBase *compiler, const spv::ExecutionMode mode, bool *has_mode)
+    {
+        INTERNAL_RESULT(*has_mode = ((spirv_cross::Compiler *)compiler)->get_execution_mode_bitset().get(mode);)
+    }
+
+    ScInternalResult sc_internal_compiler_set_execution_mode(const ScInternalCompilerBase *compiler, const spv::ExecutionMode mode, const bool enable)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const _compiler = (spirv_cross::Compiler *)compiler;
+                if (enable)
+                    _compiler->set_execution_mode(mode);
+                else
+                    _compiler->unset_execution_mode(mode);
+            } while (0);)
+    }
+
     ScInternalResult sc_internal_compiler_set_entry_point(const ScInternalCompilerBase *compiler, const char *name, const spv::ExecutionModel execution_model)
     {
         INTERNAL_RESULT(((spirv_cross::Compiler *)compiler)->set_entry_point(name, execution_model);)
     }
 
+    ScInternalResult sc_internal_compiler_rename_entry_point(const ScInternalCompilerBase *compiler, const char *old_name, const char *new_name, const spv::ExecutionModel execution_model)
+    {
+        INTERNAL_RESULT(((spirv_cross::Compiler *)compiler)->rename_entry_point(old_name, new_name, execution_model);)
+    }
+
     ScInternalResult sc_internal_compiler_compile(const ScInternalCompilerBase *compiler, const char **shader)
     {
-        INTERNAL_RESULT(*shader = strdup(((spirv_cross::Compiler *)compiler)->compile().c_str());)
+        INTERNAL_RESULT(*shader = sc_internal_strdup(((spirv_cross::Compiler *)compiler)->compile().c_str());)
     }
 
     ScInternalResult sc_internal_compiler_delete(ScInternalCompilerBase *compiler)
@@ -569,6 +1765,33 @@ extern "C"
 
     ScInternalResult sc_internal_free_pointer(void *pointer)
     {
-        INTERNAL_RESULT(free(pointer);)
+        // Pointers from an active arena are released with that arena
+        INTERNAL_RESULT(
+            do {
+                for (auto arena = current_arena; arena; arena = arena->previous)
+                {
+                    if (arena->owns(pointer))
+                        return ScInternalResult::Success;
+                }
+                free(pointer);
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_arena_begin()
+    {
+        INTERNAL_RESULT(current_arena = new ScInternalArena(current_arena);)
+    }
+
+    ScInternalResult sc_internal_arena_end()
+    {
+        INTERNAL_RESULT(
+            do {
+                auto arena = current_arena;
+                if (arena)
+                {
+                    current_arena = arena->previous;
+                    delete arena;
+                }
+            } while (0);)
     }
 }
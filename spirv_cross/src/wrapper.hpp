@@ -1,9 +1,20 @@
+#ifdef SPIRV_CROSS_WRAPPER_SYSTEM
+#include <spirv_cross/spirv.hpp>
+#include <spirv_cross/spirv_parser.hpp>
+#include <spirv_cross/spirv_cross_util.hpp>
+#include <spirv_cross/spirv_hlsl.hpp>
+#include <spirv_cross/spirv_msl.hpp>
+#include <spirv_cross/spirv_glsl.hpp>
+#else
 #include "vendor/SPIRV-Cross/spirv.hpp"
+#include "vendor/SPIRV-Cross/spirv_parser.hpp"
 #include "vendor/SPIRV-Cross/spirv_cross_util.hpp"
 #include "vendor/SPIRV-Cross/spirv_hlsl.hpp"
 #include "vendor/SPIRV-Cross/spirv_msl.hpp"
 #include "vendor/SPIRV-Cross/spirv_glsl.hpp"
+#endif
 
+typedef void ScInternalParsedIr;
 typedef void ScInternalCompilerBase;
 typedef void ScInternalCompilerHlsl;
 typedef void ScInternalCompilerMsl;
@@ -25,6 +36,9 @@ extern "C"
         uint32_t work_group_size_x;
         uint32_t work_group_size_y;
         uint32_t work_group_size_z;
+        uint32_t work_group_size_id_x;
+        uint32_t work_group_size_id_y;
+        uint32_t work_group_size_id_z;
     } ScEntryPoint;
 
     typedef struct ScBufferRange
@@ -49,6 +63,23 @@ extern "C"
         uint32_t space;
     } ScHlslRootConstant;
 
+    typedef struct ScHlslRegister
+    {
+        uint32_t register_space;
+        uint32_t register_binding;
+    } ScHlslRegister;
+
+    typedef struct ScHlslResourceBinding
+    {
+        spv::ExecutionModel stage;
+        uint32_t desc_set;
+        uint32_t binding;
+        ScHlslRegister cbv;
+        ScHlslRegister uav;
+        ScHlslRegister srv;
+        ScHlslRegister sampler;
+    } ScHlslResourceBinding;
+
     typedef struct ScHlslCompilerOptions
     {
         int32_t shader_model;
@@ -59,6 +90,10 @@ extern "C"
         bool force_storage_buffer_as_uav;
         bool nonwritable_uav_texture_as_srv;
         bool force_zero_initialized_variables;
+        bool emit_line_directives;
+        bool force_temporary;
+        bool flatten_multidimensional_arrays;
+        bool preserve_structured_buffers;
     } ScHlslCompilerOptions;
 
     typedef struct ScMslCompilerOptions
@@ -83,6 +118,17 @@ extern "C"
         bool force_native_arrays;
         bool force_zero_initialized_variables;
         bool force_active_argument_buffer_resources;
+        bool emit_line_directives;
+        bool force_temporary;
+        bool flatten_multidimensional_arrays;
+        bool texture_buffer_native;
+        uint32_t texel_buffer_texture_width;
+        bool emulate_cube_array;
+        uint32_t device_index;
+        bool dispatch_base;
+        bool emulate_subgroups;
+        uint32_t fixed_subgroup_size;
+        bool ios_use_simdgroup_functions;
     } ScMslCompilerOptions;
 
     typedef struct ScGlslCompilerOptions
@@ -104,6 +150,7 @@ extern "C"
         bool emit_line_directives;
         bool enable_storage_image_qualifier_deduction;
         bool force_zero_initialized_variables;
+        bool omit_write_only_image_formats;
     } ScGlslCompilerOptions;
 
     typedef struct ScResource
@@ -120,6 +167,19 @@ extern "C"
         size_t num;
     } ScResourceArray;
 
+    typedef struct ScBuiltInResource
+    {
+        spv::BuiltIn builtin;
+        uint32_t value_type_id;
+        ScResource resource;
+    } ScBuiltInResource;
+
+    typedef struct ScBuiltInResourceArray
+    {
+        ScBuiltInResource *data;
+        size_t num;
+    } ScBuiltInResourceArray;
+
     typedef struct ScShaderResources
     {
         ScResourceArray uniform_buffers;
@@ -133,8 +193,12 @@ extern "C"
         ScResourceArray push_constant_buffers;
         ScResourceArray separate_images;
         ScResourceArray separate_samplers;
+        ScBuiltInResourceArray builtin_inputs;
+        ScBuiltInResourceArray builtin_outputs;
     } ScShaderResources;
 
+    typedef void (*ScVariableTypeRemapCallback)(void *user_data, uint32_t type_id, const char *variable_name, const char *type_name, const char **remapped_type_name);
+
     typedef struct ScSpecializationConstant
     {
         uint32_t id;
@@ -158,22 +222,40 @@ extern "C"
 
     ScInternalResult sc_internal_get_latest_exception_message(const char **message);
 
+    ScInternalResult sc_internal_parsed_ir_new(ScInternalParsedIr **ir, const uint32_t *words, const size_t size);
+    ScInternalResult sc_internal_parsed_ir_delete(ScInternalParsedIr *ir);
+
+#ifdef SPIRV_CROSS_WRAPPER_REFLECT
+    ScInternalResult sc_internal_compiler_reflect_new(ScInternalCompilerBase **compiler, const uint32_t *ir, const size_t size);
+    ScInternalResult sc_internal_compiler_reflect_new_from_ir(ScInternalCompilerBase **compiler, const ScInternalParsedIr *ir);
+#endif
+
 #ifdef SPIRV_CROSS_WRAPPER_HLSL
     ScInternalResult sc_internal_compiler_hlsl_new(ScInternalCompilerHlsl **compiler, const uint32_t *ir, const size_t size);
+    ScInternalResult sc_internal_compiler_hlsl_new_from_ir(ScInternalCompilerHlsl **compiler, const ScInternalParsedIr *ir);
     ScInternalResult sc_internal_compiler_hlsl_set_options(const ScInternalCompilerHlsl *compiler, const ScHlslCompilerOptions *options);
     ScInternalResult sc_internal_compiler_hlsl_set_root_constant_layout(const ScInternalCompilerHlsl *compiler, const ScHlslRootConstant *constants, size_t count);
+    ScInternalResult sc_internal_compiler_hlsl_add_resource_bindings(const ScInternalCompilerHlsl *compiler, const ScHlslResourceBinding *bindings, size_t count);
 #endif
 
 #ifdef SPIRV_CROSS_WRAPPER_MSL
     typedef struct ScMslConstSamplerMapping {
+        // The sampler variable to remap, or 0 to remap by descriptor set and binding
+        uint32_t id;
         uint32_t desc_set;
         uint32_t binding;
         spirv_cross::MSLConstexprSampler sampler;
     } ScMslConstSamplerMapping;
 
     ScInternalResult sc_internal_compiler_msl_new(ScInternalCompilerMsl **compiler, const uint32_t *ir, const size_t size);
+    ScInternalResult sc_internal_compiler_msl_new_from_ir(ScInternalCompilerMsl **compiler, const ScInternalParsedIr *ir);
+    ScInternalResult sc_internal_compiler_msl_new_from_compiler(ScInternalCompilerMsl **compiler, const ScInternalCompilerBase *source);
     ScInternalResult sc_internal_compiler_msl_set_options(const ScInternalCompilerMsl *compiler, const ScMslCompilerOptions *options);
     ScInternalResult sc_internal_compiler_msl_get_is_rasterization_disabled(const ScInternalCompilerMsl *compiler, bool *is_rasterization_disabled);
+    ScInternalResult sc_internal_compiler_msl_is_resource_binding_used(const ScInternalCompilerMsl *compiler, const spv::ExecutionModel model, const uint32_t desc_set, const uint32_t binding, bool *is_used);
+    ScInternalResult sc_internal_compiler_msl_is_shader_input_used(const ScInternalCompilerMsl *compiler, const uint32_t location, bool *is_used);
+    ScInternalResult sc_internal_compiler_msl_needs_buffer_size_buffer(const ScInternalCompilerMsl *compiler, bool *needs_buffer_size_buffer);
+    ScInternalResult sc_internal_compiler_msl_get_automatic_resource_binding(const ScInternalCompilerMsl *compiler, const uint32_t id, uint32_t *binding, uint32_t *secondary_binding);
     ScInternalResult sc_internal_compiler_msl_compile(const ScInternalCompilerBase *compiler, const char **shader,
                                                       const spirv_cross::MSLShaderInput *p_vat_overrides, const size_t vat_override_count,
                                                       const spirv_cross::MSLResourceBinding *p_res_overrides, const size_t res_override_count,
@@ -182,27 +264,51 @@ extern "C"
 
 #ifdef SPIRV_CROSS_WRAPPER_GLSL
     ScInternalResult sc_internal_compiler_glsl_new(ScInternalCompilerGlsl **compiler, const uint32_t *ir, const size_t size);
+    ScInternalResult sc_internal_compiler_glsl_new_from_ir(ScInternalCompilerGlsl **compiler, const ScInternalParsedIr *ir);
     ScInternalResult sc_internal_compiler_glsl_set_options(const ScInternalCompilerGlsl *compiler, const ScGlslCompilerOptions *options);
     ScInternalResult sc_internal_compiler_glsl_build_combined_image_samplers(const ScInternalCompilerBase *compiler);
     ScInternalResult sc_internal_compiler_glsl_get_combined_image_samplers(const ScInternalCompilerBase *compiler, const ScCombinedImageSampler **samplers, size_t *size);
     ScInternalResult sc_internal_compiler_glsl_add_header_line(const ScInternalCompilerBase *compiler, const char *str);
     ScInternalResult sc_internal_compiler_glsl_flatten_buffer_block(const ScInternalCompilerBase *compiler, const uint32_t id);
+    ScInternalResult sc_internal_compiler_glsl_remap_subpass_input_to_sampler(const ScInternalCompilerBase *compiler, const uint32_t id);
+    ScInternalResult sc_internal_compiler_glsl_set_image_format(const ScInternalCompilerBase *compiler, const uint32_t id, const spv::ImageFormat format);
+    ScInternalResult sc_internal_compiler_glsl_get_image_format(const ScInternalCompilerBase *compiler, const uint32_t id, spv::ImageFormat *format);
+    ScInternalResult sc_internal_compiler_glsl_get_required_extensions(const ScInternalCompilerBase *compiler, const char ***extensions, size_t *size);
 #endif
 
     ScInternalResult sc_internal_compiler_get_decoration(const ScInternalCompilerBase *compiler, uint32_t *result, const uint32_t id, const spv::Decoration decoration);
+    ScInternalResult sc_internal_compiler_get_decorations(const ScInternalCompilerBase *compiler, const uint32_t *ids, const size_t id_count, const spv::Decoration *decorations, const size_t decoration_count, bool *has_decorations, uint32_t *values);
+    ScInternalResult sc_internal_compiler_has_decoration(const ScInternalCompilerBase *compiler, const uint32_t id, const spv::Decoration decoration, bool *result);
+    ScInternalResult sc_internal_compiler_has_member_decoration(const ScInternalCompilerBase *compiler, const uint32_t id, const uint32_t index, const spv::Decoration decoration, bool *result);
+    ScInternalResult sc_internal_compiler_get_decoration_string(const ScInternalCompilerBase *compiler, const char **result, const uint32_t id, const spv::Decoration decoration);
+    ScInternalResult sc_internal_compiler_buffer_is_hlsl_counter_buffer(const ScInternalCompilerBase *compiler, const uint32_t id, bool *is_counter_buffer);
+    ScInternalResult sc_internal_compiler_buffer_get_hlsl_counter_buffer(const ScInternalCompilerBase *compiler, const uint32_t id, bool *has_counter_buffer, uint32_t *counter_id);
+    ScInternalResult sc_internal_compiler_needs_dummy_sampler_for_combined_images(const ScInternalCompilerBase *compiler, bool *needs_dummy_sampler);
+    ScInternalResult sc_internal_compiler_variable_is_depth_or_compare(const ScInternalCompilerBase *compiler, const uint32_t id, bool *is_depth_or_compare);
+    ScInternalResult sc_internal_compiler_build_dummy_sampler_for_combined_images(const ScInternalCompilerBase *compiler, uint32_t *sampler_id);
     ScInternalResult sc_internal_compiler_set_decoration(const ScInternalCompilerBase *compiler, const uint32_t id, const spv::Decoration decoration, const uint32_t argument);
+    ScInternalResult sc_internal_compiler_set_decorations(const ScInternalCompilerBase *compiler, const uint32_t *ids, const spv::Decoration *decorations, const uint32_t *arguments, const size_t count, size_t *invalid_entry);
+    ScInternalResult sc_internal_compiler_set_decoration_string(const ScInternalCompilerBase *compiler, const uint32_t id, const spv::Decoration decoration, const char *argument);
     ScInternalResult sc_internal_compiler_unset_decoration(const ScInternalCompilerBase *compiler, const uint32_t id, const spv::Decoration decoration);
     ScInternalResult sc_internal_compiler_get_name(const ScInternalCompilerBase *compiler, const uint32_t id, const char **name);
+    ScInternalResult sc_internal_compiler_get_remapped_declared_block_name(const ScInternalCompilerBase *compiler, const uint32_t id, const char **name);
     ScInternalResult sc_internal_compiler_set_name(const ScInternalCompilerBase *compiler, const uint32_t id, const char *name);
     ScInternalResult sc_internal_compiler_set_member_name(const ScInternalCompilerBase *compiler, const uint32_t id, const uint32_t index, const char *name);
+    ScInternalResult sc_internal_compiler_strip_names(const ScInternalCompilerBase *compiler);
+    ScInternalResult sc_internal_compiler_set_variable_type_remap_callback(const ScInternalCompilerBase *compiler, ScVariableTypeRemapCallback callback, void *user_data);
     ScInternalResult sc_internal_compiler_get_entry_points(const ScInternalCompilerBase *compiler, ScEntryPoint **entry_points, size_t *size);
+    ScInternalResult sc_internal_compiler_get_current_entry_point(const ScInternalCompilerBase *compiler, ScEntryPoint **entry_point);
     ScInternalResult sc_internal_compiler_get_active_buffer_ranges(const ScInternalCompilerBase *compiler, uint32_t id, ScBufferRange **active_buffer_ranges, size_t *size);
     ScInternalResult sc_internal_compiler_get_cleansed_entry_point_name(const ScInternalCompilerBase *compiler, const char *original_entry_point_name, const spv::ExecutionModel execution_model, const char **compiled_entry_point_name);
     ScInternalResult sc_internal_compiler_get_shader_resources(const ScInternalCompilerBase *compiler, ScShaderResources *shader_resources);
+    ScInternalResult sc_internal_compiler_get_shader_resources_of_kinds(const ScInternalCompilerBase *compiler, const uint32_t kinds, ScShaderResources *shader_resources);
+    ScInternalResult sc_internal_compiler_get_shader_resource_list(const ScInternalCompilerBase *compiler, const uint32_t kind, ScResourceArray *resources);
+    ScInternalResult sc_internal_compiler_get_shader_resources_for_entry_point(const ScInternalCompilerBase *compiler, const char *name, const spv::ExecutionModel execution_model, ScShaderResources *shader_resources);
     ScInternalResult sc_internal_compiler_get_specialization_constants(const ScInternalCompilerBase *compiler, ScSpecializationConstant **constants, size_t *size);
     // `uint64_t` isn't supported in Emscripten without implicitly splitting the value into two `uint32_t` - instead do it explicitly
     ScInternalResult sc_internal_compiler_set_scalar_constant(const ScInternalCompilerBase *compiler, const uint32_t id, const uint32_t constant_high_bits, const uint32_t constant_low_bits);
     ScInternalResult sc_internal_compiler_get_type(const ScInternalCompilerBase *compiler, const uint32_t id, const ScType **spirv_type);
+    ScInternalResult sc_internal_compiler_get_type_parent(const ScInternalCompilerBase *compiler, const uint32_t id, bool *is_pointer, uint32_t *parent_type);
     ScInternalResult sc_internal_compiler_get_member_name(const ScInternalCompilerBase *compiler, const uint32_t id, const uint32_t index, const char **name);
     ScInternalResult sc_internal_compiler_get_member_decoration(const ScInternalCompilerBase *compiler, const uint32_t id, const uint32_t index, const spv::Decoration decoration, uint32_t *result);
     ScInternalResult sc_internal_compiler_set_member_decoration(const ScInternalCompilerBase *compiler, const uint32_t id, const uint32_t index, const spv::Decoration decoration, const uint32_t argument);
@@ -211,9 +317,28 @@ extern "C"
     ScInternalResult sc_internal_compiler_rename_interface_variable(const ScInternalCompilerBase *compiler, const ScResource *resources, const size_t resources_size, uint32_t location, const char *name);
     ScInternalResult sc_internal_compiler_get_work_group_size_specialization_constants(const ScInternalCompilerBase *compiler, ScSpecializationConstant **constants);
     ScInternalResult sc_internal_compiler_set_entry_point(const ScInternalCompilerBase *compiler, const char *name, const spv::ExecutionModel execution_model);
+    ScInternalResult sc_internal_compiler_rename_entry_point(const ScInternalCompilerBase *compiler, const char *old_name, const char *new_name, const spv::ExecutionModel execution_model);
     ScInternalResult sc_internal_compiler_compile(const ScInternalCompilerBase *compiler, const char **shader);
     ScInternalResult sc_internal_compiler_delete(ScInternalCompilerBase *compiler);
     ScInternalResult sc_internal_compiler_get_active_interface_variables(const ScInternalCompilerBase *compiler, uint32_t **ids, size_t* size);
+    ScInternalResult sc_internal_compiler_get_active_builtins(const ScInternalCompilerBase *compiler, spv::BuiltIn **inputs, size_t *input_count, spv::BuiltIn **outputs, size_t *output_count);
+    ScInternalResult sc_internal_compiler_set_remove_unused_variables(const ScInternalCompilerBase *compiler, const bool remove);
+    ScInternalResult sc_internal_compiler_keep_unused_resources(const ScInternalCompilerBase *compiler);
+    ScInternalResult sc_internal_compiler_alias_variable(const ScInternalCompilerBase *compiler, const uint32_t alias_id, const uint32_t target_id);
+    ScInternalResult sc_internal_compiler_get_declared_capabilities(const ScInternalCompilerBase *compiler, uint32_t **capabilities, size_t *size);
+    ScInternalResult sc_internal_compiler_get_used_opcodes(const ScInternalCompilerBase *compiler, uint32_t **opcodes, size_t *size);
+    ScInternalResult sc_internal_compiler_get_spirv(const ScInternalCompilerBase *compiler, uint32_t **words, size_t *size);
+    ScInternalResult sc_internal_compiler_get_meta_ids(const ScInternalCompilerBase *compiler, uint32_t **ids, size_t *size);
+    ScInternalResult sc_internal_compiler_get_descriptor_count(const ScInternalCompilerBase *compiler, const uint32_t id, uint32_t *count, bool *is_runtime_sized);
+    ScInternalResult sc_internal_compiler_get_outer_array(const ScInternalCompilerBase *compiler, const uint32_t id, uint32_t *element_type_id, uint32_t *length);
+    ScInternalResult sc_internal_compiler_get_workgroup_shared_memory_size(const ScInternalCompilerBase *compiler, const char *name, const spv::ExecutionModel execution_model, uint32_t *size);
+    ScInternalResult sc_internal_compiler_get_entry_point_interface_variables(const ScInternalCompilerBase *compiler, const char *name, const spv::ExecutionModel execution_model, uint32_t **ids, size_t *size);
+    ScInternalResult sc_internal_compiler_get_entry_point_subgroup_size(const ScInternalCompilerBase *compiler, const char *name, const spv::ExecutionModel execution_model, bool *has_subgroup_size, uint32_t *subgroup_size);
+    ScInternalResult sc_internal_compiler_get_execution_mode_argument(const ScInternalCompilerBase *compiler, const spv::ExecutionMode mode, const uint32_t index, bool *has_argument, uint32_t *argument);
+    ScInternalResult sc_internal_compiler_has_execution_mode(const ScInternalCompilerBase *compiler, const spv::ExecutionMode mode, bool *has_mode);
+    ScInternalResult sc_internal_compiler_set_execution_mode(const ScInternalCompilerBase *compiler, const spv::ExecutionMode mode, const bool enable);
 
     ScInternalResult sc_internal_free_pointer(void *pointer);
+    ScInternalResult sc_internal_arena_begin();
+    ScInternalResult sc_internal_arena_end();
 }
@@ -47,6 +47,262 @@ void main()
     );
 }
 
+#[test]
+fn ast_from_parsed_module_compiles_to_glsl() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let parsed = spirv::ParsedModule::parse(&module).unwrap();
+
+    let mut options = glsl::CompilerOptions::default();
+    options.version = glsl::Version::V4_60;
+
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    ast.set_compiler_options(&options).unwrap();
+    let expected = ast.compile().unwrap();
+
+    for _ in 0..2 {
+        let mut ast = spirv::Ast::<glsl::Target>::from_parsed(&parsed).unwrap();
+        ast.set_compiler_options(&options).unwrap();
+        assert_eq!(ast.compile().unwrap(), expected);
+    }
+}
+
+#[test]
+fn ast_updates_compiler_options_between_compiles() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+
+    let options = glsl::CompilerOptions::builder().version(glsl::Version::V4_50);
+    ast.set_compiler_options(&options.clone().build().unwrap())
+        .unwrap();
+    assert!(ast.compile().unwrap().starts_with("#version 450"));
+
+    let options = options.version(glsl::Version::V3_30);
+    ast.set_compiler_options(&options.clone().build().unwrap())
+        .unwrap();
+    assert!(ast.compile().unwrap().starts_with("#version 330"));
+
+    let result = ast.set_compiler_options(
+        &options
+            .entry_point("missing", spirv::ExecutionModel::Vertex)
+            .build()
+            .unwrap(),
+    );
+    assert!(matches!(
+        result,
+        Err(spirv_cross::ErrorCode::InvalidOptions(_))
+    ));
+    assert_eq!(ast.get_compiler_options().version, glsl::Version::V3_30);
+    assert!(ast.compile().unwrap().starts_with("#version 330"));
+}
+
+#[test]
+fn compiles_batch_to_glsl() {
+    use spirv_cross::glsl::Version::*;
+
+    let words = words_from_bytes(include_bytes!("shaders/simple.vert.spv")).to_vec();
+    let versions = [V3_30, V4_50, V4_60, V3_00Es];
+    let jobs = versions
+        .iter()
+        .map(|&version| {
+            let mut options = glsl::CompilerOptions::default();
+            options.version = version;
+            spirv::CompileJob {
+                words: words.clone(),
+                target: spirv::CompileTarget::Glsl(options),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let results = spirv::compile_batch(jobs.clone());
+    assert_eq!(results.len(), jobs.len());
+    for (job, result) in jobs.iter().zip(results) {
+        assert_eq!(result.unwrap(), job.compile().unwrap());
+    }
+}
+
+#[test]
+fn ast_compiles_with_built_options() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let options = glsl::CompilerOptions::builder()
+        .version(glsl::Version::V4_60)
+        .vulkan_semantics(true)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&options).unwrap();
+
+    assert!(ast.compile().unwrap().starts_with("#version 460"));
+}
+
+#[test]
+fn glsl_compiler_options_builder_validates() {
+    let result = glsl::CompilerOptions::builder()
+        .version(glsl::Version::V3_00Es)
+        .vulkan_semantics(true)
+        .build();
+    assert!(matches!(
+        result,
+        Err(spirv_cross::ErrorCode::InvalidOptions(_))
+    ));
+
+    let result = glsl::CompilerOptions::builder()
+        .version(glsl::Version::V1_30)
+        .vulkan_semantics(true)
+        .build();
+    assert!(matches!(
+        result,
+        Err(spirv_cross::ErrorCode::InvalidOptions(_))
+    ));
+
+    glsl::CompilerOptions::builder()
+        .version(glsl::Version::V3_10Es)
+        .vulkan_semantics(true)
+        .build()
+        .unwrap();
+}
+
+#[test]
+fn ast_compiles_with_temporary_options() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let mut options = glsl::CompilerOptions::default();
+    options.version = glsl::Version::V4_50;
+    ast.set_compiler_options(&options).unwrap();
+
+    let mut temporary_options = glsl::CompilerOptions::default();
+    temporary_options.version = glsl::Version::V3_30;
+    let temporary = ast.compile_with(&temporary_options).unwrap();
+    assert!(temporary.starts_with("#version 330"));
+
+    assert_eq!(ast.get_compiler_options().version, glsl::Version::V4_50);
+    assert!(ast.compile().unwrap().starts_with("#version 450"));
+}
+
+#[test]
+fn ast_strips_names() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    ast.strip_names().unwrap();
+
+    let shader = ast.compile().unwrap();
+    assert!(shader.contains("void main()"));
+    for name in &["uniform_buffer_object", "u_model_view_projection", "a_position", "v_normal"] {
+        assert!(!shader.contains(name), "{} was not stripped", name);
+    }
+}
+
+#[test]
+fn ast_strips_names_with_option() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    ast.set_compiler_options(
+        &glsl::CompilerOptions::builder()
+            .strip_names(true)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+
+    let shader = ast.compile().unwrap();
+    assert!(shader.contains("void main()"));
+    assert!(!shader.contains("v_normal"));
+}
+
+#[test]
+fn ast_preserves_names_clashing_with_keywords() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let v_normal = ast.get_shader_resources().unwrap().stage_outputs[0].id;
+    ast.set_name(v_normal, "output").unwrap();
+
+    let shader = ast.compile().unwrap();
+    assert!(!shader.contains(" output;"));
+
+    ast.set_name(v_normal, "output").unwrap();
+    ast.set_compiler_options(
+        &glsl::CompilerOptions::builder()
+            .preserve_names(true)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    let shader = ast.compile().unwrap();
+    assert!(shader.contains("out vec3 output;"));
+    assert!(shader.contains("output = a_normal;"));
+    assert_eq!(ast.get_name(v_normal).unwrap(), "output");
+}
+
+#[test]
+fn options_reject_stripping_and_preserving_names() {
+    let result = glsl::CompilerOptions::builder()
+        .strip_names(true)
+        .preserve_names(true)
+        .build();
+    assert!(matches!(
+        result,
+        Err(spirv_cross::ErrorCode::InvalidOptions(_))
+    ));
+}
+
+#[test]
+fn ast_prefixes_resource_names() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    ast.prefix_resource_names(
+        "mat_",
+        spirv::ResourceKind::UniformBuffers | spirv::ResourceKind::StageInputs,
+    )
+    .unwrap();
+
+    let shader = ast.compile().unwrap();
+    assert!(shader.contains("uniform mat_uniform_buffer_object"));
+    assert!(shader.contains(" mat_a_position;"));
+    assert!(shader.contains(" mat_a_normal;"));
+    assert!(shader.contains(" v_normal;"));
+    assert!(!shader.contains("mat_v_normal"));
+}
+
+#[test]
+fn ast_remaps_variable_types() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    ast.set_variable_type_remap_callback(|_, variable_name, type_name| {
+        if variable_name == "v_normal" {
+            Some(format!("engine_{}", type_name))
+        } else {
+            None
+        }
+    })
+    .unwrap();
+
+    let shader = ast.compile().unwrap();
+    assert!(shader.contains("out engine_vec3 v_normal;"));
+    assert!(shader.contains("in vec3 a_normal;"));
+}
+
+#[test]
+fn ast_compiles_into_writer() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let mut options = glsl::CompilerOptions::default();
+    options.version = glsl::Version::V4_60;
+    ast.set_compiler_options(&options).unwrap();
+
+    let mut output = Vec::new();
+    ast.compile_into(&mut output).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), ast.compile().unwrap());
+}
+
 #[test]
 fn ast_compiles_all_versions_to_glsl() {
     use spirv_cross::glsl::Version::*;
@@ -222,6 +478,33 @@ void main()
     );
 }
 
+#[test]
+fn ast_gets_remapped_declared_block_names() {
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&spirv::Module::from_words(words_from_bytes(
+        include_bytes!("shaders/two_ubo.vert.spv"),
+    )))
+    .unwrap();
+    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
+
+    // Give both blocks the same name so that the second one has to be declared differently
+    ast.set_name(uniform_buffers[1].base_type_id, "ubo1")
+        .unwrap();
+    let output = ast.compile().unwrap();
+
+    let names = uniform_buffers
+        .iter()
+        .map(|uniform_buffer| {
+            ast.get_remapped_declared_block_name(uniform_buffer.id)
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(names[0], "ubo1");
+    assert_ne!(names[1], "ubo1");
+    for name in &names {
+        assert!(output.contains(&format!("uniform {}\n", name)));
+    }
+}
+
 #[test]
 fn flatten_uniform_buffers() {
     let mut ast = spirv::Ast::<glsl::Target>::parse(&spirv::Module::from_words(words_from_bytes(
@@ -411,3 +694,699 @@ void main()
         assert_eq!(&ast.compile().unwrap(), expected_result);
     }
 }
+
+#[test]
+fn ast_compiles_all_entry_points() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/vs_and_fs.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let shaders = ast.compile_all_entry_points().unwrap();
+
+    assert_eq!(shaders.len(), 2);
+    let vertex = &shaders[&(String::from("main_vs"), spirv::ExecutionModel::Vertex)];
+    assert!(vertex.contains("gl_Position = vec4(1.0);"));
+    let fragment = &shaders[&(String::from("main_fs"), spirv::ExecutionModel::Fragment)];
+    assert!(fragment.contains("layout(location = 0) out vec4 color;"));
+
+    assert_eq!(
+        ast.get_current_entry_point().unwrap(),
+        (String::from("main_vs"), spirv::ExecutionModel::Vertex)
+    );
+    assert_eq!(ast.get_compiler_options().entry_point, None);
+}
+
+#[test]
+fn ast_gets_specialization_constant_macro_names() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/specialization.comp.spv"
+    )));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let constants = ast.get_specialization_constants().unwrap();
+    let macro_name = ast
+        .get_specialization_constant_macro_name(&constants[0])
+        .unwrap();
+    assert_eq!("SPIRV_CROSS_CONSTANT_ID_10", macro_name);
+
+    let output = ast.compile().unwrap();
+    assert!(output.contains(&format!("#define {} 123", macro_name)));
+    assert!(output.contains(&format!("const int CONSTANT = {};", macro_name)));
+
+    let compiler_options = glsl::CompilerOptions::builder()
+        .specialization_constant_form(glsl::SpecializationConstantForm::Const)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    assert_eq!(
+        None,
+        ast.get_specialization_constant_macro_name(&constants[0])
+    );
+    let output = ast.compile().unwrap();
+    assert!(!output.contains(&macro_name));
+    assert!(output.contains("const int CONSTANT = 123;"));
+
+    let compiler_options = glsl::CompilerOptions::builder()
+        .specialization_constant_form(glsl::SpecializationConstantForm::Define)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    let output = ast.compile().unwrap();
+    assert!(!output.contains(&macro_name));
+    assert!(output.contains("#define CONSTANT 123"));
+    assert!(!output.contains("const int CONSTANT"));
+
+    let compiler_options = glsl::CompilerOptions::builder()
+        .vulkan_semantics(true)
+        .specialization_constant_form(glsl::SpecializationConstantForm::Define)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    assert_eq!(
+        None,
+        ast.get_specialization_constant_macro_name(&constants[0])
+    );
+    let output = ast.compile().unwrap();
+    assert!(output.contains("layout(constant_id = 10) const int CONSTANT = 123;"));
+}
+
+#[test]
+fn ast_sets_uniform_location() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/ycbcr.asm.spv")));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let resources = ast.get_shader_resources().unwrap();
+    let u_texture = resources.sampled_images[0].id;
+
+    let compiler_options = glsl::CompilerOptions::builder()
+        .version(glsl::Version::V4_10)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    assert!(matches!(
+        ast.set_uniform_location(u_texture, 3),
+        Err(spirv_cross::ErrorCode::InvalidOptions(_))
+    ));
+
+    let compiler_options = glsl::CompilerOptions::builder()
+        .version(glsl::Version::V4_30)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    ast.set_uniform_location(u_texture, 3).unwrap();
+    assert_eq!(
+        3,
+        ast.get_decoration(u_texture, spirv::Decoration::Location)
+            .unwrap()
+    );
+    let output = ast.compile().unwrap();
+    assert!(output.contains("location = 3"));
+    assert!(output.contains("uniform sampler2D u_texture;"));
+}
+
+#[test]
+fn ast_gets_required_extensions() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let (_, compiled) = ast.into_compiled().unwrap();
+    assert!(compiled.get_required_extensions().unwrap().is_empty());
+
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/texel_buffer.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let compiler_options = glsl::CompilerOptions::builder()
+        .version(glsl::Version::V3_10Es)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    let (output, compiled) = ast.into_compiled().unwrap();
+    let extensions = compiled.get_required_extensions().unwrap();
+    assert!(!extensions.is_empty());
+    for extension in &extensions {
+        assert!(output.contains(&format!("#extension {} : require", extension)));
+    }
+}
+
+#[test]
+fn ast_gets_feature_requirements() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/texel_buffer.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let compiler_options = glsl::CompilerOptions::builder()
+        .version(glsl::Version::V3_10Es)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    let (_, compiled) = ast.into_compiled().unwrap();
+    let requirements = compiled.get_feature_requirements().unwrap();
+    assert_eq!(requirements.version, glsl::Version::V3_10Es);
+    assert_eq!(
+        requirements.extensions,
+        compiled.get_required_extensions().unwrap()
+    );
+}
+
+#[test]
+fn ast_remaps_input_attachments() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/subpass_input.asm.spv"
+    )));
+    let compiler_options = glsl::CompilerOptions::builder()
+        .vulkan_semantics(true)
+        .build()
+        .unwrap();
+
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    let u_input = ast.get_shader_resources().unwrap().subpass_inputs[0].id;
+    ast.set_input_attachment_index(u_input, 2).unwrap();
+    let output = ast.compile().unwrap();
+    assert!(output.contains("input_attachment_index = 2"));
+    assert!(output.contains("uniform subpassInput u_input;"));
+    assert!(output.contains("subpassLoad(u_input)"));
+
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    ast.remap_subpass_input_to_sampler(u_input).unwrap();
+    let output = ast.compile().unwrap();
+    assert!(!output.contains("input_attachment_index"));
+    assert!(output.contains("uniform sampler2D u_input;"));
+    assert!(output.contains("texelFetch(u_input, ivec2(gl_FragCoord.xy), 0)"));
+}
+
+#[test]
+fn ast_controls_image_format_qualifiers() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/storage_image.asm.spv"
+    )));
+    let declaration = |output: &str, name: &str| {
+        output
+            .lines()
+            .find(|line| line.ends_with(&format!(" {};", name)))
+            .unwrap()
+            .to_owned()
+    };
+
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let resources = ast.get_shader_resources().unwrap();
+    let image_id = |name: &str| {
+        resources
+            .storage_images
+            .iter()
+            .find(|image| image.name == name)
+            .unwrap()
+            .id
+    };
+    let (u_output, u_input) = (image_id("u_output"), image_id("u_input"));
+    assert_eq!(
+        ast.get_image_format(u_output),
+        Ok(spirv::ImageFormat::Rgba8)
+    );
+    let output = ast.compile().unwrap();
+    assert!(declaration(&output, "u_output").contains("rgba8"));
+    assert!(declaration(&output, "u_input").contains("rgba8"));
+
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    ast.set_compiler_options(
+        &glsl::CompilerOptions::builder()
+            .omit_write_only_image_formats(true)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        ast.get_image_format(u_output),
+        Ok(spirv::ImageFormat::Unknown)
+    );
+    assert_eq!(ast.get_image_format(u_input), Ok(spirv::ImageFormat::Rgba8));
+    let output = ast.compile().unwrap();
+    assert!(!declaration(&output, "u_output").contains("rgba8"));
+    assert!(declaration(&output, "u_input").contains("rgba8"));
+
+    ast.set_image_format(u_output, spirv::ImageFormat::Rgba16f)
+        .unwrap();
+    ast.set_image_format(u_input, spirv::ImageFormat::Unknown)
+        .unwrap();
+    assert_eq!(
+        ast.get_image_format(u_output),
+        Ok(spirv::ImageFormat::Rgba16f)
+    );
+    let output = ast.compile().unwrap();
+    assert!(declaration(&output, "u_output").contains("rgba16f"));
+    assert!(!declaration(&output, "u_input").contains("rgba8"));
+}
+
+#[test]
+fn ast_gets_minimum_version() {
+    let minimum_versions = |bytes: &[u8]| {
+        let module = spirv::Module::from_words(words_from_bytes(bytes));
+        let ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+        (
+            ast.get_minimum_version(false).unwrap(),
+            ast.get_minimum_version(true).unwrap(),
+        )
+    };
+
+    // Uniform buffers
+    assert_eq!(
+        minimum_versions(include_bytes!("shaders/simple.vert.spv")),
+        (Some(glsl::Version::V1_40), Some(glsl::Version::V3_00Es))
+    );
+    // `texelFetch`
+    assert_eq!(
+        minimum_versions(include_bytes!("shaders/image_fetch.asm.spv")),
+        (Some(glsl::Version::V1_30), Some(glsl::Version::V3_00Es))
+    );
+    assert_eq!(
+        minimum_versions(include_bytes!("shaders/cube_array.asm.spv")),
+        (Some(glsl::Version::V4_00), Some(glsl::Version::V3_20Es))
+    );
+    assert_eq!(
+        minimum_versions(include_bytes!("shaders/workgroup.comp.spv")),
+        (Some(glsl::Version::V4_30), Some(glsl::Version::V3_10Es))
+    );
+
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    ast.set_compiler_options(
+        &glsl::CompilerOptions::builder()
+            .emit_uniform_buffer_as_plain_uniforms(true)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        ast.get_minimum_version(true).unwrap(),
+        Some(glsl::Version::V1_00Es)
+    );
+}
+
+#[test]
+fn ast_compiles_buffer_references() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/buffer_reference.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    assert_eq!(
+        (
+            ast.get_minimum_version(false).unwrap(),
+            ast.get_minimum_version(true).unwrap(),
+        ),
+        (Some(glsl::Version::V4_50), Some(glsl::Version::V3_20Es))
+    );
+
+    // Buffer references only exist in Vulkan GLSL
+    ast.set_compiler_options(
+        &glsl::CompilerOptions::builder()
+            .version(glsl::Version::V4_50)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    assert!(matches!(
+        ast.compile(),
+        Err(spirv_cross::ErrorCode::CompilationError(_))
+    ));
+
+    ast.set_compiler_options(
+        &glsl::CompilerOptions::builder()
+            .version(glsl::Version::V4_50)
+            .vulkan_semantics(true)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    let shader = ast.compile().unwrap();
+    assert!(shader.contains("#extension GL_EXT_buffer_reference : require"));
+    assert!(shader.contains("layout(buffer_reference) buffer Data;"));
+    assert!(shader.contains("    Data data;"));
+    assert!(shader.contains("push.data.value = 1u;"));
+}
+
+#[test]
+fn ast_sets_push_constant_block_name() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/push_constants.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    ast.set_compiler_options(
+        &glsl::CompilerOptions::builder()
+            .vulkan_semantics(true)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        ast.get_push_constant_instance_name().unwrap().as_deref(),
+        Some("pc")
+    );
+
+    // Without debug names, a fallback name is used
+    let pc = ast.get_shader_resources().unwrap().push_constant_buffers[0].id;
+    ast.set_name(pc, "").unwrap();
+    let instance_name = ast.get_push_constant_instance_name().unwrap().unwrap();
+    assert_eq!(instance_name, format!("_{}", pc));
+    assert!(ast
+        .compile()
+        .unwrap()
+        .contains(&format!("}} {};", instance_name)));
+
+    ast.set_push_constant_block_name("Constants", "constants")
+        .unwrap();
+    let shader = ast.compile().unwrap();
+    assert!(shader.contains("uniform Constants"));
+    assert!(shader.contains("} constants;"));
+    assert!(shader.contains("constants.color"));
+    assert_eq!(
+        ast.get_push_constant_instance_name().unwrap().as_deref(),
+        Some("constants")
+    );
+}
+
+#[test]
+fn ast_sets_interpolation_qualifiers() {
+    use spirv::InterpolationQualifier;
+
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/interpolation.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let resources = ast.get_shader_resources().unwrap();
+    let id = |name: &str| {
+        resources
+            .stage_inputs
+            .iter()
+            .chain(&resources.stage_outputs)
+            .find(|resource| resource.name == name)
+            .unwrap()
+            .id
+    };
+
+    ast.set_interpolation_qualifier(id("v_color"), InterpolationQualifier::Flat)
+        .unwrap();
+    ast.set_interpolation_qualifier(id("v_color"), InterpolationQualifier::NoPerspective)
+        .unwrap();
+    ast.set_interpolation_qualifier(id("v_color"), InterpolationQualifier::Centroid)
+        .unwrap();
+    ast.set_interpolation_qualifier(id("v_index"), InterpolationQualifier::Flat)
+        .unwrap();
+    let shader = ast.compile().unwrap();
+    assert!(shader.contains("noperspective centroid out vec4 v_color;"));
+    assert!(shader.contains("flat out uint v_index;"));
+
+    for (name, qualifier) in &[
+        ("a_position", InterpolationQualifier::Flat),
+        ("v_index", InterpolationQualifier::NoPerspective),
+    ] {
+        assert!(matches!(
+            ast.set_interpolation_qualifier(id(name), *qualifier),
+            Err(spirv_cross::ErrorCode::CompilationError(_))
+        ));
+    }
+}
+
+#[test]
+fn ast_renames_generated_identifiers() {
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&spirv::Module::from_words(words_from_bytes(
+        include_bytes!("shaders/simple.vert.spv"),
+    )))
+    .unwrap();
+    let naming = spirv::IdentifierNaming {
+        temporary_prefix: String::from("tmp"),
+        renumber_temporaries: true,
+        ..Default::default()
+    };
+    let options = glsl::CompilerOptions::builder()
+        .identifier_naming(naming)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&options).unwrap();
+
+    let shader = ast.compile().unwrap();
+    assert!(shader.contains("} tmp0;"));
+    let position = "gl_Position = (tmp0.u_model_view_projection * a_position) * tmp0.u_scale;";
+    assert!(shader.contains(position));
+    assert!(!shader.contains("_22"));
+
+    let mut written = Vec::new();
+    ast.compile_into(&mut written).unwrap();
+    assert_eq!(String::from_utf8(written).unwrap(), shader);
+}
+
+#[test]
+fn ast_omits_explicit_bindings_by_kind() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/runtime_array.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let options = glsl::CompilerOptions::default();
+    ast.set_compiler_options(&options).unwrap();
+    assert!(ast.get_omitted_bindings().unwrap().is_empty());
+    assert!(ast
+        .compile()
+        .unwrap()
+        .contains("layout(binding = 0, std430) buffer Particles"));
+
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let explicit_bindings = glsl::ExplicitBindings {
+        storage_buffers: false,
+        ..Default::default()
+    };
+    let options = glsl::CompilerOptions::builder()
+        .explicit_bindings(explicit_bindings)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&options).unwrap();
+    let omitted = ast.get_omitted_bindings().unwrap();
+    assert_eq!(omitted.len(), 1);
+    assert_eq!(omitted[0].kind, spirv::ResourceKind::StorageBuffers);
+    assert_eq!(omitted[0].resource.name, "particles");
+    assert_eq!(omitted[0].binding, 0);
+    assert!(ast
+        .compile()
+        .unwrap()
+        .contains("layout(std430) buffer Particles"));
+    // The decoration is only left out of the output
+    let id = omitted[0].resource.id;
+    assert!(ast.has_decoration(id, spirv::Decoration::Binding).unwrap());
+
+    // GLSL 4.10 cannot express bindings without the 420pack extension
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let options = glsl::CompilerOptions::builder()
+        .version(glsl::Version::V4_10)
+        .enable_420_pack_extension(false)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&options).unwrap();
+    assert_eq!(ast.get_omitted_bindings().unwrap().len(), 1);
+}
+
+#[test]
+fn ast_aliases_resources() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/image_sampler_pairs.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let resources = ast.get_shader_resources().unwrap();
+    let id = |name: &str| {
+        resources
+            .separate_images
+            .iter()
+            .chain(&resources.separate_samplers)
+            .find(|resource| resource.name == name)
+            .unwrap()
+            .id
+    };
+    let options = glsl::CompilerOptions::builder()
+        .version(glsl::Version::V1_00Es)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&options).unwrap();
+
+    assert!(ast.alias_resource(id("tex_b"), id("samp_point")).is_err());
+    ast.alias_resource(id("tex_b"), id("tex_a")).unwrap();
+    let output = ast.compile().unwrap();
+    assert!(!output.contains("tex_b"));
+    assert!(output.contains("SPIRV_Cross_Combinedtex_asamp_linear"));
+    assert!(output.contains("SPIRV_Cross_Combinedtex_asamp_point"));
+}
+
+#[test]
+fn ast_compiles_to_structured_output() {
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&spirv::Module::from_words(words_from_bytes(
+        include_bytes!("shaders/sampler.frag.spv"),
+    )))
+    .unwrap();
+    ast.set_compiler_options(
+        &glsl::CompilerOptions::builder()
+            .version(glsl::Version::V3_00Es)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    let output = ast.compile_output().unwrap();
+    assert!(output.shader.source.starts_with("#version 300 es"));
+    assert_eq!(output.shader.entry_points[0].cleansed_name, "main");
+    assert_eq!(output.requirements.version, glsl::Version::V3_00Es);
+    assert!(output.combined_image_samplers.is_empty());
+
+    let combined_image_samplers = ast.get_combined_image_samplers().unwrap();
+    let output = ast.compile_output().unwrap();
+    assert_eq!(output.combined_image_samplers, combined_image_samplers);
+    assert_eq!(output.combined_image_samplers.len(), 1);
+}
+
+#[test]
+fn ast_creates_combined_sampler_mapping() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/image_sampler_pairs.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let options = glsl::CompilerOptions::builder()
+        .version(glsl::Version::V3_30)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&options).unwrap();
+
+    let mut mapping = ast.create_combined_sampler_mapping().unwrap();
+    mapping.sort_by(|a, b| a.combined_name.cmp(&b.combined_name));
+    assert_eq!(
+        mapping,
+        vec![
+            glsl::CombinedSamplerMapping {
+                combined_name: String::from("SPIRV_Cross_Combinedtex_asamp_linear"),
+                image_name: String::from("tex_a"),
+                image: Some(glsl::DescriptorBinding {
+                    desc_set: 0,
+                    binding: 0,
+                }),
+                sampler_name: String::from("samp_linear"),
+                sampler: Some(glsl::DescriptorBinding {
+                    desc_set: 0,
+                    binding: 2,
+                }),
+            },
+            glsl::CombinedSamplerMapping {
+                combined_name: String::from("SPIRV_Cross_Combinedtex_bsamp_point"),
+                image_name: String::from("tex_b"),
+                image: Some(glsl::DescriptorBinding {
+                    desc_set: 0,
+                    binding: 1,
+                }),
+                sampler_name: String::from("samp_point"),
+                sampler: Some(glsl::DescriptorBinding {
+                    desc_set: 0,
+                    binding: 3,
+                }),
+            },
+        ]
+    );
+
+    let output = ast.compile().unwrap();
+    for entry in &mapping {
+        assert!(output.contains(&format!("uniform sampler2D {};", entry.combined_name)));
+    }
+}
+
+#[test]
+fn ast_reflects_and_emits_transform_feedback() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/transform_feedback.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    assert!(ast.uses_transform_feedback().unwrap());
+
+    let mut outputs = ast.get_transform_feedback_outputs().unwrap();
+    outputs.sort_by_key(|output| output.buffer);
+    assert_eq!(outputs.len(), 2);
+    assert_eq!(outputs[0].resource.name, "out_value");
+    assert_eq!(outputs[0].stride, 16);
+    assert_eq!(outputs[0].offset, Some(0));
+    assert!(outputs[0].member_offsets.is_empty());
+    assert_eq!(outputs[1].resource.name, "captured");
+    assert_eq!(outputs[1].buffer, 1);
+    assert_eq!(outputs[1].stride, 20);
+    assert_eq!(outputs[1].offset, None);
+    assert_eq!(outputs[1].member_offsets, vec![(0, 16), (2, 0)]);
+
+    let mut options = glsl::CompilerOptions::default();
+    options.version = glsl::Version::V4_40;
+    ast.set_compiler_options(&options).unwrap();
+    let output = ast.compile_with_lints().unwrap();
+    assert!(output.lints.is_empty());
+    assert!(output.source.contains("xfb_buffer = 0"));
+    assert!(output.source.contains("xfb_offset = 0"));
+
+    options.version = glsl::Version::V3_30;
+    ast.set_compiler_options(&options).unwrap();
+    let output = ast.compile_with_lints().unwrap();
+    assert_eq!(
+        output.lints,
+        vec![String::from(
+            "transform feedback layouts require GL_ARB_enhanced_layouts before GLSL 4.40"
+        )]
+    );
+    assert!(output.source.contains("GL_ARB_enhanced_layouts"));
+}
+
+#[test]
+fn ast_reports_binding_relocations() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/runtime_array.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let explicit_bindings = glsl::ExplicitBindings {
+        storage_buffers: false,
+        ..Default::default()
+    };
+    let options = glsl::CompilerOptions::builder()
+        .explicit_bindings(explicit_bindings)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&options).unwrap();
+    assert_eq!(
+        ast.get_binding_relocations().unwrap(),
+        vec![glsl::BindingRelocation {
+            name: String::from("particles"),
+            namespace: glsl::GlBindingNamespace::ShaderStorageBuffers,
+            sources: vec![glsl::DescriptorBinding {
+                desc_set: 0,
+                binding: 0,
+            }],
+            binding: Some(0),
+            explicit: false,
+        }]
+    );
+
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/image_sampler_pairs.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let output = ast.compile_output().unwrap();
+    let mut relocations = output.binding_relocations;
+    relocations.sort_by(|a, b| a.name.cmp(&b.name));
+    assert_eq!(relocations.len(), 2);
+    assert_eq!(relocations[0].name, "SPIRV_Cross_Combinedtex_asamp_linear");
+    assert_eq!(
+        relocations[0].namespace,
+        glsl::GlBindingNamespace::TextureUnits
+    );
+    assert_eq!(
+        relocations[0].sources,
+        vec![
+            glsl::DescriptorBinding {
+                desc_set: 0,
+                binding: 0,
+            },
+            glsl::DescriptorBinding {
+                desc_set: 0,
+                binding: 2,
+            },
+        ]
+    );
+    assert_eq!(relocations[0].binding, None);
+    assert!(!relocations[0].explicit);
+}
@@ -11,6 +11,59 @@ fn hlsl_compiler_options_has_default() {
     assert_eq!(compiler_options.point_coord_compat, false);
     assert_eq!(compiler_options.vertex.invert_y, false);
     assert_eq!(compiler_options.vertex.transform_clip_space, false);
+    assert_eq!(compiler_options.remove_unused_variables, false);
+    assert_eq!(compiler_options.keep_unused_resources, false);
+}
+
+#[test]
+fn hlsl_compiler_options_builder_sets_fields() {
+    let options = hlsl::CompilerOptions::builder()
+        .shader_model(hlsl::ShaderModel::V6_0)
+        .point_size_compat(true)
+        .entry_point("main", spirv::ExecutionModel::Vertex)
+        .build()
+        .unwrap();
+    assert_eq!(options.shader_model, hlsl::ShaderModel::V6_0);
+    assert!(options.point_size_compat);
+
+    let result = hlsl::CompilerOptions::builder()
+        .entry_point("ma\0in", spirv::ExecutionModel::Vertex)
+        .build();
+    assert!(matches!(result, Err(spirv_cross::ErrorCode::InvalidOptions(_))));
+}
+
+#[test]
+fn ast_validates_root_constant_shader_model() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/push_constants.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<hlsl::Target>::parse(&module).unwrap();
+    let layout = vec![hlsl::RootConstant {
+        start: 0,
+        end: 32,
+        binding: 0,
+        space: 1,
+    }];
+    let result = ast.set_root_constant_layout(layout.clone());
+    assert!(matches!(
+        result,
+        Err(spirv_cross::ErrorCode::InvalidOptions(_))
+    ));
+
+    let mut options = hlsl::CompilerOptions::default();
+    options.shader_model = hlsl::ShaderModel::V5_1;
+    ast.set_compiler_options(&options).unwrap();
+    ast.set_root_constant_layout(layout).unwrap();
+
+    options.shader_model = hlsl::ShaderModel::V5_0;
+    let result = ast.set_compiler_options(&options);
+    assert!(matches!(
+        result,
+        Err(spirv_cross::ErrorCode::InvalidOptions(_))
+    ));
+
+    ast.set_root_constant_layout(Vec::new()).unwrap();
+    ast.set_compiler_options(&options).unwrap();
 }
 
 #[test]
@@ -285,3 +338,301 @@ SPIRV_Cross_Output main()
         assert_eq!(&ast.compile().unwrap(), expected_result);
     }
 }
+
+#[test]
+fn preserves_structured_buffers() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/structured_buffer.asm.spv"
+    )));
+
+    for &(preserve, form, declaration) in &[
+        (
+            false,
+            hlsl::StorageBufferForm::ByteAddressBuffer,
+            "RWByteAddressBuffer buf",
+        ),
+        (
+            true,
+            hlsl::StorageBufferForm::StructuredBuffer,
+            "RWStructuredBuffer<Foo> buf",
+        ),
+    ] {
+        let mut ast = spirv::Ast::<hlsl::Target>::parse(&module).unwrap();
+        let compiler_options = hlsl::CompilerOptions::builder()
+            .shader_model(hlsl::ShaderModel::V5_1)
+            .preserve_structured_buffers(preserve)
+            .build()
+            .unwrap();
+        ast.set_compiler_options(&compiler_options).unwrap();
+        let resources = ast.get_shader_resources().unwrap();
+        assert_eq!(
+            form,
+            ast.get_storage_buffer_form(&resources.storage_buffers[0])
+                .unwrap()
+        );
+        assert!(ast.compile().unwrap().contains(declaration));
+    }
+}
+
+#[test]
+fn ast_flattens_buffer_blocks_to_float4_arrays() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<hlsl::Target>::parse(&module).unwrap();
+    let block = ast.get_shader_resources().unwrap().uniform_buffers[0].clone();
+
+    // mat4 u_model_view_projection, then float u_scale
+    let block_type = block.base_type_id;
+    assert_eq!(
+        ast.get_flattened_member_index(block_type, 0).unwrap(),
+        (0, 0)
+    );
+    assert_eq!(
+        ast.get_flattened_member_index(block_type, 1).unwrap(),
+        (4, 0)
+    );
+    assert_eq!(ast.get_flattened_array_length(block_type).unwrap(), 5);
+
+    ast.flatten_buffer_block(block.id, 2).unwrap();
+    assert_eq!(
+        ast.get_flattened_member_register(block.id, 0).unwrap(),
+        "c2"
+    );
+    assert_eq!(
+        ast.get_flattened_member_register(block.id, 1).unwrap(),
+        "c6"
+    );
+    let output = ast.compile().unwrap();
+    assert!(output.contains("uniform float4 uniform_buffer_object[5] : register(c2);"));
+    assert!(!output.contains("cbuffer"));
+}
+
+#[test]
+fn ast_removes_unused_variables() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/unused_resources.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<hlsl::Target>::parse(&module).unwrap();
+    let options = hlsl::CompilerOptions::builder().shader_model(hlsl::ShaderModel::V5_0);
+    ast.set_compiler_options(&options.clone().build().unwrap())
+        .unwrap();
+    let output = ast.compile().unwrap();
+    assert!(output.contains("u_texture"));
+    assert!(output.contains("u_unused"));
+
+    ast.set_compiler_options(&options.remove_unused_variables(true).build().unwrap())
+        .unwrap();
+    let output = ast.compile().unwrap();
+    assert!(output.contains("u_texture"));
+    assert!(!output.contains("u_unused"));
+}
+
+#[test]
+fn ast_keeps_unused_resources() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/unused_resources.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<hlsl::Target>::parse(&module).unwrap();
+    let options = hlsl::CompilerOptions::builder()
+        .shader_model(hlsl::ShaderModel::V5_0)
+        .remove_unused_variables(true)
+        .keep_unused_resources(true);
+    ast.set_compiler_options(&options.build().unwrap()).unwrap();
+    let output = ast.compile().unwrap();
+    assert!(output.contains("u_texture"));
+    assert!(output.contains("u_unused"));
+
+    // The entry point of a SPIR-V 1.4 module only lists the resources it uses
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/unused_resources_spv1_4.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<hlsl::Target>::parse(&module).unwrap();
+    let options = hlsl::CompilerOptions::builder().shader_model(hlsl::ShaderModel::V5_0);
+    ast.set_compiler_options(&options.clone().build().unwrap())
+        .unwrap();
+    let output = ast.compile().unwrap();
+    assert!(!output.contains("u_unused"));
+
+    ast.set_compiler_options(&options.keep_unused_resources(true).build().unwrap())
+        .unwrap();
+    let output = ast.compile().unwrap();
+    assert!(output.contains("u_texture"));
+    assert!(output.contains("u_unused"));
+}
+
+#[test]
+fn ast_shifts_registers_by_class() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/sampler.frag.spv")));
+    let register_shifts = hlsl::RegisterShifts {
+        shader_resources: hlsl::RegisterShift {
+            offset: 10,
+            space: Some(2),
+        },
+        samplers: hlsl::RegisterShift {
+            offset: 4,
+            space: None,
+        },
+        ..Default::default()
+    };
+
+    let mut ast = spirv::Ast::<hlsl::Target>::parse(&module).unwrap();
+    let options = hlsl::CompilerOptions::builder()
+        .shader_model(hlsl::ShaderModel::V5_1)
+        .register_shifts(register_shifts)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&options).unwrap();
+    let output = ast.compile().unwrap();
+    assert!(output.contains("register(t10, space2)"));
+    assert!(output.contains("register(s5, space0)"));
+
+    // Clearing the shifts restores the registers of the bindings
+    let options = hlsl::CompilerOptions::builder()
+        .shader_model(hlsl::ShaderModel::V5_1)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&options).unwrap();
+    let output = ast.compile().unwrap();
+    assert!(output.contains("register(t0, space0)"));
+    assert!(output.contains("register(s1, space0)"));
+
+    let result = hlsl::CompilerOptions::builder()
+        .shader_model(hlsl::ShaderModel::V5_0)
+        .register_shifts(register_shifts)
+        .build();
+    assert!(matches!(
+        result,
+        Err(spirv_cross::ErrorCode::InvalidOptions(_))
+    ));
+}
+
+#[test]
+fn ast_emits_line_directives() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/source_files.asm.spv"
+    )));
+    let compile = |emit_line_directives| {
+        let mut ast = spirv::Ast::<hlsl::Target>::parse(&module).unwrap();
+        let options = hlsl::CompilerOptions::builder()
+            .shader_model(hlsl::ShaderModel::V6_0)
+            .emit_line_directives(emit_line_directives)
+            .build()
+            .unwrap();
+        ast.set_compiler_options(&options).unwrap();
+        ast.compile().unwrap()
+    };
+    assert!(!compile(false).contains("#line"));
+    assert!(compile(true).contains("#line 3 \"shaders/common.hlsli\""));
+}
+
+#[test]
+fn ast_forces_temporaries() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let compile = |force_temporary| {
+        let mut ast = spirv::Ast::<hlsl::Target>::parse(&module).unwrap();
+        let options = hlsl::CompilerOptions::builder()
+            .shader_model(hlsl::ShaderModel::V6_0)
+            .force_temporary(force_temporary)
+            .build()
+            .unwrap();
+        ast.set_compiler_options(&options).unwrap();
+        ast.compile().unwrap()
+    };
+    let expression = "gl_Position = mul(a_position, _22_u_model_view_projection) * _22_u_scale;";
+    assert!(compile(false).contains(expression));
+
+    let output = compile(true);
+    assert!(!output.contains(expression));
+    assert!(output
+        .lines()
+        .any(|line| line.trim_start().starts_with("float4 _")));
+}
+
+#[test]
+fn ast_flattens_multidimensional_arrays() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/multidimensional_array.asm.spv"
+    )));
+    let compile = |flatten_multidimensional_arrays| {
+        let mut ast = spirv::Ast::<hlsl::Target>::parse(&module).unwrap();
+        let options = hlsl::CompilerOptions::builder()
+            .shader_model(hlsl::ShaderModel::V6_0)
+            .flatten_multidimensional_arrays(flatten_multidimensional_arrays)
+            .build()
+            .unwrap();
+        ast.set_compiler_options(&options).unwrap();
+        ast.compile().unwrap()
+    };
+    let output = compile(false);
+    assert!(output.contains("[2][3]"));
+    assert!(output.contains("[1][2]"));
+
+    let output = compile(true);
+    assert!(!output.contains("[2][3]"));
+    assert!(!output.contains("[1][2]"));
+}
+
+#[test]
+fn ast_gets_stage_semantics() {
+    fn assert_declared(output: &str, name: &str, semantic: Option<String>) {
+        let declaration = format!("{} : {};", name, semantic.unwrap());
+        assert!(output.contains(&declaration), "{}", declaration);
+    }
+
+    let shader_model_5 = hlsl::CompilerOptions::builder()
+        .shader_model(hlsl::ShaderModel::V5_0)
+        .build()
+        .unwrap();
+    for (bytes, output_semantics) in &[
+        (
+            &include_bytes!("shaders/simple.vert.spv")[..],
+            &["TEXCOORD0"][..],
+        ),
+        (
+            &include_bytes!("shaders/sampler.frag.spv")[..],
+            &["SV_Target0"][..],
+        ),
+        (
+            &include_bytes!("shaders/dual_source.asm.spv")[..],
+            &["SV_Target0", "SV_Target1"][..],
+        ),
+    ] {
+        let module = spirv::Module::from_words(words_from_bytes(bytes));
+        let mut ast = spirv::Ast::<hlsl::Target>::parse(&module).unwrap();
+        ast.set_compiler_options(&shader_model_5).unwrap();
+        let output = ast.compile().unwrap();
+        let resources = ast.get_shader_resources().unwrap();
+        for input in &resources.stage_inputs {
+            assert_declared(&output, &input.name, ast.get_input_semantic(input).unwrap());
+        }
+        let mut semantics = Vec::new();
+        for stage_output in &resources.stage_outputs {
+            let semantic = ast.get_output_semantic(stage_output).unwrap();
+            assert_declared(&output, &stage_output.name, semantic.clone());
+            semantics.push(semantic.unwrap());
+        }
+        semantics.sort();
+        assert_eq!(semantics, *output_semantics);
+        for built_in in &resources.builtin_outputs {
+            assert_eq!(ast.get_output_semantic(&built_in.resource).unwrap(), None);
+        }
+    }
+
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/sampler.frag.spv")));
+    let mut ast = spirv::Ast::<hlsl::Target>::parse(&module).unwrap();
+    ast.set_compiler_options(
+        &hlsl::CompilerOptions::builder()
+            .shader_model(hlsl::ShaderModel::V3_0)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    let output = ast.compile().unwrap();
+    let target0 = &ast.get_shader_resources().unwrap().stage_outputs[0];
+    let semantic = ast.get_output_semantic(target0).unwrap();
+    assert_eq!(semantic.as_deref(), Some("COLOR0"));
+    assert_declared(&output, "target0", semantic);
+}
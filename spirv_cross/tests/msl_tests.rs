@@ -12,6 +12,29 @@ fn msl_compiler_options_has_default() {
     assert_eq!(compiler_options.vertex.transform_clip_space, false);
     assert!(compiler_options.resource_binding_overrides.is_empty());
     assert!(compiler_options.vertex_attribute_overrides.is_empty());
+    assert_eq!(compiler_options.force_native_arrays, false);
+    assert_eq!(compiler_options.remove_unused_variables, false);
+}
+
+#[test]
+fn msl_compiler_options_builder_validates() {
+    let options = msl::CompilerOptions::builder()
+        .version(msl::Version::V2_0)
+        .argument_buffers(true)
+        .build()
+        .unwrap();
+    assert!(options.enable_argument_buffers);
+
+    let result = msl::CompilerOptions::builder()
+        .version(msl::Version::V1_2)
+        .argument_buffers(true)
+        .build();
+    assert!(matches!(result, Err(spirv_cross::ErrorCode::InvalidOptions(_))));
+
+    let result = msl::CompilerOptions::builder()
+        .texel_buffer_texture_width(0)
+        .build();
+    assert!(matches!(result, Err(spirv_cross::ErrorCode::InvalidOptions(_))));
 }
 
 #[test]
@@ -28,10 +51,53 @@ fn is_rasterization_enabled() {
             ))),
         ),
     ];
+    for (expected, module) in &modules {
+        let ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+        let (_, compiled) = ast.into_compiled().unwrap();
+        assert_eq!(*expected, compiled.is_rasterization_enabled().unwrap());
+    }
+
+    let (_, module) = &modules[0];
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    ast.set_compiler_options(
+        &msl::CompilerOptions::builder()
+            .enable_rasterization(false)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    let (shader, compiled) = ast.into_compiled().unwrap();
+    assert!(!compiled.is_rasterization_enabled().unwrap());
+    assert!(shader.contains("vertex void main0("));
+}
+
+#[test]
+fn needs_buffer_size_buffer() {
+    let modules = [
+        (
+            false,
+            spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/sampler.frag.spv"))),
+        ),
+        (
+            true,
+            spirv::Module::from_words(words_from_bytes(include_bytes!(
+                "shaders/buffer_size.asm.spv"
+            ))),
+        ),
+    ];
     for (expected, module) in &modules {
         let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
-        ast.compile().unwrap();
-        assert_eq!(*expected, ast.is_rasterization_enabled().unwrap());
+        let compiler_options = msl::CompilerOptions::builder()
+            .buffer_size_buffer_index(7)
+            .build()
+            .unwrap();
+        ast.set_compiler_options(&compiler_options).unwrap();
+        let (output, compiled) = ast.into_compiled().unwrap();
+        assert_eq!(*expected, compiled.needs_buffer_size_buffer().unwrap());
+        assert_eq!(
+            *expected,
+            output.contains("spvBufferSizeConstants [[buffer(7)]]")
+        );
     }
 }
 
@@ -58,8 +124,9 @@ fn ast_compiles_to_msl() {
     );
 
     ast.set_compiler_options(&compiler_options).unwrap();
+    let (shader, compiled) = ast.into_compiled().unwrap();
     assert_eq!(
-        ast.compile().unwrap(),
+        shader,
         "\
 #include <metal_stdlib>
 #include <simd/simd.h>
@@ -95,12 +162,79 @@ vertex main0_out main0(main0_in in [[stage_in]], constant uniform_buffer_object&
 "
     );
     assert_eq!(
-        ast.get_cleansed_entry_point_name("main", spirv::ExecutionModel::Vertex)
+        compiled
+            .get_cleansed_entry_point_name("main", spirv::ExecutionModel::Vertex)
             .unwrap(),
         "main0"
     );
 }
 
+#[test]
+fn ast_moves_between_parsed_and_compiled_states() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let (shader, compiled) = ast.into_compiled().unwrap();
+    assert!(shader.contains("vertex main0_out main0("));
+    assert_eq!(
+        compiled
+            .get_cleansed_entry_point_name("main", spirv::ExecutionModel::Vertex)
+            .unwrap(),
+        "main0"
+    );
+    assert!(compiled.is_rasterization_enabled().unwrap());
+
+    let mut ast = compiled.into_parsed();
+    assert_eq!(ast.compile().unwrap(), shader);
+}
+
+#[test]
+fn ast_compiles_with_lints_about_unused_overrides() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+
+    let mut compiler_options = msl::CompilerOptions::default();
+    for desc_set in 0..2 {
+        compiler_options.resource_binding_overrides.insert(
+            msl::ResourceBindingLocation {
+                stage: spirv::ExecutionModel::Vertex,
+                desc_set,
+                binding: 0,
+            },
+            msl::ResourceBinding {
+                buffer_id: 5,
+                texture_id: 6,
+                sampler_id: 7,
+                count: 0,
+            },
+        );
+    }
+    ast.set_compiler_options(&compiler_options).unwrap();
+
+    let output = ast.compile_with_lints().unwrap();
+    assert_eq!(output.source, ast.compile().unwrap());
+    assert_eq!(
+        output.lints,
+        vec![String::from(
+            "resource binding override for Vertex set 1 binding 0 is not used"
+        )]
+    );
+}
+
+#[test]
+fn ast_compiles_into_writer() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    ast.set_compiler_options(&msl::CompilerOptions::default())
+        .unwrap();
+
+    let mut output = Vec::new();
+    ast.compile_into(&mut output).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), ast.compile().unwrap());
+}
+
 #[test]
 fn captures_output_to_buffer() {
     let module =
@@ -494,6 +628,65 @@ vertex main0_out main0(main0_in in [[stage_in]])
     }
 }
 
+#[test]
+fn compiles_entry_points_into_one_source() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/push_constants.asm.spv"
+    )));
+    let parsed = spirv::ParsedModule::parse(&module).unwrap();
+    let mut asts = Vec::new();
+    for (name, execution_model) in &[
+        ("main_vs", spirv::ExecutionModel::Vertex),
+        ("main_fs", spirv::ExecutionModel::Fragment),
+    ] {
+        let mut ast = spirv::Ast::<msl::Target>::from_parsed(&parsed).unwrap();
+        let options = msl::CompilerOptions::builder()
+            .entry_point(*name, *execution_model)
+            .build()
+            .unwrap();
+        ast.set_compiler_options(&options).unwrap();
+        asts.push(ast);
+    }
+
+    let combined = msl::compile_combined(&asts).unwrap();
+    assert_eq!(
+        combined.source.matches("#include <metal_stdlib>").count(),
+        1
+    );
+    assert_eq!(combined.source.matches("using namespace metal;").count(), 1);
+    assert_eq!(combined.source.matches("struct PushConstants\n").count(), 1);
+    assert!(combined.source.contains("vertex main_vs_out main_vs("));
+    assert!(combined.source.contains("fragment main_fs_out main_fs("));
+    let cleansed_names = combined
+        .entry_points
+        .iter()
+        .map(|entry_point| entry_point.cleansed_name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(cleansed_names, ["main_vs", "main_fs"]);
+
+    // Both modules name their entry point `main`
+    let asts = vec![
+        spirv::Ast::<msl::Target>::parse(&spirv::Module::from_words(words_from_bytes(
+            include_bytes!("shaders/simple.vert.spv"),
+        )))
+        .unwrap(),
+        spirv::Ast::<msl::Target>::parse(&spirv::Module::from_words(words_from_bytes(
+            include_bytes!("shaders/sampler.frag.spv"),
+        )))
+        .unwrap(),
+    ];
+    let combined = msl::compile_combined(&asts).unwrap();
+    assert_eq!(combined.entry_points[0].cleansed_name, "main0");
+    assert_eq!(combined.entry_points[1].name, "main_1");
+    assert_eq!(combined.entry_points[1].cleansed_name, "main_1");
+    assert!(combined.source.contains(" main0("));
+    assert!(combined.source.contains(" main_1("));
+    assert_eq!(
+        asts[1].get_current_entry_point().unwrap(),
+        (String::from("main"), spirv::ExecutionModel::Fragment)
+    );
+}
+
 #[test]
 fn ast_sets_entry_point() {
     let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
@@ -577,3 +770,407 @@ fragment main_fs_out main_fs()
         assert_eq!(&ast.compile().unwrap(), expected_result);
     }
 }
+
+#[test]
+fn compiles_ycbcr_const_sampler() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/ycbcr.asm.spv")));
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let location = msl::SamplerLocation {
+        desc_set: 0,
+        binding: 0,
+    };
+    let sampler = msl::SamplerData {
+        planes: 2,
+        resolution: msl::FormatResolution::_420,
+        chroma_filter: msl::SamplerFilter::Linear,
+        ycbcr_conversion_enable: true,
+        ycbcr_model: msl::SamplerYCbCrModelConversion::YCbCrBt709,
+        ycbcr_range: msl::SamplerYCbCrRange::ItuNarrow,
+        ..Default::default()
+    };
+
+    let result = msl::CompilerOptions::builder()
+        .const_sampler(
+            location.clone(),
+            msl::SamplerData {
+                planes: 4,
+                ..sampler.clone()
+            },
+        )
+        .build();
+    assert!(matches!(
+        result,
+        Err(spirv_cross::ErrorCode::InvalidOptions(_))
+    ));
+
+    let compiler_options = msl::CompilerOptions::builder()
+        .version(msl::Version::V2_0)
+        .const_sampler(location, sampler)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    let output = ast.compile().unwrap();
+    assert!(output.contains("constexpr sampler u_textureSmplr"));
+    assert!(output.contains("spvChromaReconstructLinear420"));
+    assert!(output.contains("spvConvertYCbCrBT709"));
+    assert!(output.contains("spvExpandITUNarrowRange"));
+}
+
+#[test]
+fn compiles_const_sampler_by_id() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/ycbcr.asm.spv")));
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let texture = ast.get_shader_resources().unwrap().sampled_images[0].id;
+    let location = msl::SamplerLocation {
+        desc_set: 0,
+        binding: 0,
+    };
+    let sampler = msl::SamplerData {
+        planes: 2,
+        resolution: msl::FormatResolution::_420,
+        chroma_filter: msl::SamplerFilter::Linear,
+        ycbcr_conversion_enable: true,
+        ycbcr_model: msl::SamplerYCbCrModelConversion::YCbCrBt709,
+        ycbcr_range: msl::SamplerYCbCrRange::ItuNarrow,
+        ..Default::default()
+    };
+
+    let result = msl::CompilerOptions::builder()
+        .const_sampler_by_id(
+            texture,
+            msl::SamplerData {
+                planes: 0,
+                ..sampler.clone()
+            },
+        )
+        .build();
+    assert!(matches!(
+        result,
+        Err(spirv_cross::ErrorCode::InvalidOptions(_))
+    ));
+
+    let compiler_options = msl::CompilerOptions::builder()
+        .version(msl::Version::V2_0)
+        .const_sampler(location, msl::SamplerData::default())
+        .const_sampler_by_id(texture, sampler)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    let output = ast.compile().unwrap();
+    assert!(output.contains("constexpr sampler u_textureSmplr"));
+    assert!(output.contains("spvConvertYCbCrBT709"));
+}
+
+#[test]
+fn emits_native_texture_buffers() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/texel_buffer.asm.spv"
+    )));
+
+    let result = msl::CompilerOptions::builder()
+        .version(msl::Version::V2_0)
+        .texture_buffer_native(true)
+        .build();
+    assert!(matches!(
+        result,
+        Err(spirv_cross::ErrorCode::InvalidOptions(_))
+    ));
+
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let compiler_options = msl::CompilerOptions::builder()
+        .texel_buffer_texture_width(1024)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    let output = ast.compile().unwrap();
+    assert!(output.contains("texture2d<float> u_buffer"));
+    assert!(output.contains("% 1024"));
+
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let compiler_options = msl::CompilerOptions::builder()
+        .version(msl::Version::V2_1)
+        .texture_buffer_native(true)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    let output = ast.compile().unwrap();
+    assert!(output.contains("texture_buffer<float> u_buffer"));
+    assert!(ast.compile_with_lints().unwrap().lints.is_empty());
+
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let compiler_options = msl::CompilerOptions::builder()
+        .version(msl::Version::V2_1)
+        .texture_buffer_native(true)
+        .texel_buffer_texture_width(1024)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    let lints = ast.compile_with_lints().unwrap().lints;
+    assert!(lints
+        .iter()
+        .any(|lint| lint.contains("texel_buffer_texture_width is ignored")));
+}
+
+#[test]
+fn strict_policy_fails_instead_of_degrading() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/texel_buffer.asm.spv"
+    )));
+    let builder = || {
+        msl::CompilerOptions::builder()
+            .version(msl::Version::V2_1)
+            .texture_buffer_native(true)
+            .unsupported_features(spirv::UnsupportedFeaturePolicy::Strict)
+    };
+
+    // Nothing is degraded, so strict handling changes nothing
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let compiler_options = builder().build().unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    let output = ast.compile().unwrap();
+    assert!(output.contains("texture_buffer<float> u_buffer"));
+
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let compiler_options = builder().texel_buffer_texture_width(1024).build().unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    match ast.compile() {
+        Err(spirv_cross::ErrorCode::CompilationError(message)) => {
+            assert!(message.contains("texel_buffer_texture_width is ignored"))
+        }
+        result => panic!("expected a compilation error, got {:?}", result),
+    }
+    let mut written = Vec::new();
+    assert!(ast.compile_into(&mut written).is_err());
+    assert!(written.is_empty());
+}
+
+#[test]
+fn emulates_cube_arrays() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/cube_array.asm.spv"
+    )));
+
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let compiler_options = msl::CompilerOptions::builder()
+        .platform(msl::Platform::iOS)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    assert!(matches!(
+        ast.compile(),
+        Err(spirv_cross::ErrorCode::CompilationError(_))
+    ));
+
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let compiler_options = msl::CompilerOptions::builder()
+        .platform(msl::Platform::iOS)
+        .emulate_cube_array(true)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    let output = ast.compile().unwrap();
+    assert!(output.contains("texture2d_array<float> u_texture"));
+}
+
+#[test]
+fn ast_gets_feature_requirements() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/cube_array.asm.spv"
+    )));
+
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    ast.set_compiler_options(&msl::CompilerOptions::default())
+        .unwrap();
+    let (_, compiled) = ast.into_compiled().unwrap();
+    let requirements = compiled.get_feature_requirements().unwrap();
+    assert_eq!(requirements.platform, msl::Platform::macOS);
+    assert_eq!(
+        requirements.gpu_features,
+        [msl::GpuFeature::CubeTextureArrays]
+    );
+
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let compiler_options = msl::CompilerOptions::builder()
+        .platform(msl::Platform::iOS)
+        .version(msl::Version::V2_1)
+        .emulate_cube_array(true)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    let (_, compiled) = ast.into_compiled().unwrap();
+    let requirements = compiled.get_feature_requirements().unwrap();
+    assert_eq!(requirements.version, msl::Version::V2_1);
+    assert!(requirements.gpu_features.is_empty());
+}
+
+#[test]
+fn sets_subgroup_options() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/subgroup_ops.asm.spv"
+    )));
+    let compile = |compiler_options: &msl::CompilerOptions| {
+        let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+        ast.set_compiler_options(compiler_options).unwrap();
+        let (output, compiled) = ast.into_compiled().unwrap();
+        (
+            output,
+            compiled.get_feature_requirements().unwrap().gpu_features,
+        )
+    };
+
+    let compiler_options = msl::CompilerOptions::builder()
+        .platform(msl::Platform::iOS)
+        .version(msl::Version::V2_3)
+        .fixed_subgroup_size(4)
+        .build()
+        .unwrap();
+    let (output, gpu_features) = compile(&compiler_options);
+    assert!(output.contains("quad_is_first()"));
+    assert!(output.contains("gl_SubgroupSize = 4"));
+    assert_eq!(gpu_features, [msl::GpuFeature::QuadgroupFunctions]);
+
+    let compiler_options = msl::CompilerOptions::builder()
+        .platform(msl::Platform::iOS)
+        .version(msl::Version::V2_3)
+        .ios_use_simdgroup_functions(true)
+        .build()
+        .unwrap();
+    let (output, gpu_features) = compile(&compiler_options);
+    assert!(output.contains("simd_is_first()"));
+    assert_eq!(gpu_features, [msl::GpuFeature::SimdgroupFunctions]);
+
+    let invalid_options = msl::CompilerOptions::builder()
+        .emulate_subgroups(true)
+        .fixed_subgroup_size(32)
+        .build();
+    assert!(matches!(
+        invalid_options,
+        Err(spirv_cross::ErrorCode::InvalidOptions(_))
+    ));
+}
+
+#[test]
+fn sets_device_index_and_dispatch_base() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/device_group.asm.spv"
+    )));
+
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    ast.set_compiler_options(&msl::CompilerOptions::default())
+        .unwrap();
+    let output = ast.compile().unwrap();
+    assert!(output.contains("gl_DeviceIndex = 0"));
+    assert!(!output.contains("spvDispatchBase"));
+
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let compiler_options = msl::CompilerOptions::builder()
+        .device_index(3)
+        .dispatch_base(true)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&compiler_options).unwrap();
+    let output = ast.compile().unwrap();
+    assert!(output.contains("gl_DeviceIndex = 3"));
+    assert!(output.contains("spvDispatchBase [[grid_origin]]"));
+}
+
+#[test]
+fn compiles_to_structured_output() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/sampler.frag.spv")));
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let output = ast.compile_output().unwrap();
+    assert_eq!(output.shader.source, ast.compile().unwrap());
+    assert_eq!(
+        output.shader.entry_points,
+        vec![spirv::CompiledEntryPoint {
+            name: String::from("main"),
+            execution_model: spirv::ExecutionModel::Fragment,
+            cleansed_name: String::from("main0"),
+        }]
+    );
+    let (_, compiled) = spirv::Ast::<msl::Target>::parse(&module)
+        .unwrap()
+        .into_compiled()
+        .unwrap();
+    assert_eq!(
+        output.requirements,
+        compiled.get_feature_requirements().unwrap()
+    );
+    assert_eq!(output.threadgroup_memory_length, None);
+
+    let resources = ast.get_shader_resources().unwrap();
+    let texture = resources.separate_images[0].id;
+    let sampler = resources.separate_samplers[0].id;
+    assert_eq!(
+        output.automatic_bindings,
+        vec![
+            msl::AutomaticResourceBinding {
+                id: texture,
+                index: 0,
+                secondary_index: None,
+            },
+            msl::AutomaticResourceBinding {
+                id: sampler,
+                index: 0,
+                secondary_index: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn compiles_to_output_with_threadgroup_memory_length() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/shared_memory.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+
+    // float[64] + uvec3 + uint[16] is 332 bytes, rounded up to 336
+    let output = ast.compile_output().unwrap();
+    assert_eq!(output.threadgroup_memory_length, Some(336));
+    assert!(output.shader.source.contains("threadgroup"));
+}
+
+#[test]
+fn ast_emits_line_directives() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/source_files.asm.spv"
+    )));
+    let compile = |emit_line_directives| {
+        let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+        let options = msl::CompilerOptions::builder()
+            .emit_line_directives(emit_line_directives)
+            .build()
+            .unwrap();
+        ast.set_compiler_options(&options).unwrap();
+        ast.compile().unwrap()
+    };
+    assert!(!compile(false).contains("#line"));
+    assert!(compile(true).contains("#line 3 \"shaders/common.hlsli\""));
+}
+
+#[test]
+fn ast_forces_temporaries() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let compile = |force_temporary| {
+        let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+        let options = msl::CompilerOptions::builder()
+            .force_temporary(force_temporary)
+            .build()
+            .unwrap();
+        ast.set_compiler_options(&options).unwrap();
+        ast.compile().unwrap()
+    };
+    let expression = "(_22.u_model_view_projection * in.a_position) * _22.u_scale;";
+    assert!(compile(false).contains(expression));
+
+    let output = compile(true);
+    assert!(!output.contains(expression));
+    assert!(output
+        .lines()
+        .any(|line| line.trim_start().starts_with("float4 _")));
+}
@@ -0,0 +1,54 @@
+use spirv_cross::{reflect, spirv};
+
+mod common;
+use crate::common::words_from_bytes;
+
+#[test]
+fn ast_reflects_without_backend() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let ast = spirv::Ast::<reflect::Target>::parse(&module).unwrap();
+
+    let entry_points = ast.get_entry_points().unwrap();
+    assert_eq!(entry_points.len(), 1);
+    assert_eq!(
+        entry_points[0].execution_model,
+        spirv::ExecutionModel::Vertex
+    );
+
+    let shader_resources = ast.get_shader_resources().unwrap();
+    assert_eq!(shader_resources.uniform_buffers.len(), 1);
+    assert_eq!(
+        shader_resources.uniform_buffers[0].name,
+        "uniform_buffer_object"
+    );
+    assert_eq!(shader_resources.stage_inputs.len(), 2);
+    assert_eq!(shader_resources.stage_outputs.len(), 1);
+}
+
+#[test]
+fn ast_reflects_parsed_module_without_backend() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/descriptor_arrays.asm.spv"
+    )));
+    let parsed = spirv::ParsedModule::parse(&module).unwrap();
+    let ast = spirv::Ast::<reflect::Target>::from_parsed(&parsed).unwrap();
+
+    let sets = ast.get_descriptor_sets().unwrap();
+    assert_eq!(sets.len(), 2);
+    assert_eq!(sets[1].bindings[0].resource.name, "u_bindless");
+    assert_eq!(
+        sets[1].bindings[0].count,
+        spirv::DescriptorCount::RuntimeSized
+    );
+}
+
+#[test]
+fn ast_without_backend_rejects_variable_type_remap_callback() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<reflect::Target>::parse(&module).unwrap();
+    assert!(ast
+        .set_variable_type_remap_callback(|_, _, _| None)
+        .is_err());
+}
@@ -0,0 +1,45 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 15
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Vertex %main "main"
+               OpName %main "main"
+               OpName %Block "Block"
+               OpMemberName %Block 0 "a"
+               OpMemberName %Block 1 "b"
+               OpMemberName %Block 2 "c"
+               OpMemberName %Block 3 "m"
+               OpMemberName %Block 4 "arr"
+               OpMemberName %Block 5 "d"
+               OpName %ubo "ubo"
+               OpDecorate %_arr_float_uint_2 ArrayStride 16
+               OpMemberDecorate %Block 0 Offset 0
+               OpMemberDecorate %Block 1 Offset 16
+               OpMemberDecorate %Block 2 Offset 28
+               OpMemberDecorate %Block 3 Offset 32
+               OpMemberDecorate %Block 4 Offset 80
+               OpMemberDecorate %Block 5 Offset 112
+               OpMemberDecorate %Block 3 ColMajor
+               OpMemberDecorate %Block 3 MatrixStride 16
+               OpDecorate %Block Block
+               OpDecorate %ubo DescriptorSet 0
+               OpDecorate %ubo Binding 0
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v2float = OpTypeVector %float 2
+    %v3float = OpTypeVector %float 3
+%mat3v3float = OpTypeMatrix %v3float 3
+       %uint = OpTypeInt 32 0
+     %uint_2 = OpConstant %uint 2
+%_arr_float_uint_2 = OpTypeArray %float %uint_2
+      %Block = OpTypeStruct %float %v3float %float %mat3v3float %_arr_float_uint_2 %v2float
+%_ptr_Uniform_Block = OpTypePointer Uniform %Block
+        %ubo = OpVariable %_ptr_Uniform_Block Uniform
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+               OpReturn
+               OpFunctionEnd
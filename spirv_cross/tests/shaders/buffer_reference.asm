@@ -0,0 +1,41 @@
+; SPIR-V
+; Version: 1.5
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 19
+; Schema: 0
+               OpCapability Shader
+               OpCapability PhysicalStorageBufferAddresses
+               OpMemoryModel PhysicalStorageBuffer64 GLSL450
+               OpEntryPoint GLCompute %main "main" %push
+               OpExecutionMode %main LocalSize 1 1 1
+               OpName %main "main"
+               OpName %Data "Data"
+               OpMemberName %Data 0 "value"
+               OpName %Push "Push"
+               OpMemberName %Push 0 "data"
+               OpName %push "push"
+               OpDecorate %Data Block
+               OpMemberDecorate %Data 0 Offset 0
+               OpDecorate %Push Block
+               OpMemberDecorate %Push 0 Offset 0
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+       %uint = OpTypeInt 32 0
+       %Data = OpTypeStruct %uint
+%_ptr_PhysicalStorageBuffer_Data = OpTypePointer PhysicalStorageBuffer %Data
+       %Push = OpTypeStruct %_ptr_PhysicalStorageBuffer_Data
+%_ptr_PushConstant_Push = OpTypePointer PushConstant %Push
+       %push = OpVariable %_ptr_PushConstant_Push PushConstant
+        %int = OpTypeInt 32 1
+      %int_0 = OpConstant %int 0
+     %uint_1 = OpConstant %uint 1
+%_ptr_PushConstant__ptr_PhysicalStorageBuffer_Data = OpTypePointer PushConstant %_ptr_PhysicalStorageBuffer_Data
+%_ptr_PhysicalStorageBuffer_uint = OpTypePointer PhysicalStorageBuffer %uint
+       %main = OpFunction %void None %3
+         %15 = OpLabel
+         %16 = OpAccessChain %_ptr_PushConstant__ptr_PhysicalStorageBuffer_Data %push %int_0
+         %17 = OpLoad %_ptr_PhysicalStorageBuffer_Data %16
+         %18 = OpAccessChain %_ptr_PhysicalStorageBuffer_uint %17 %int_0
+               OpStore %18 %uint_1 Aligned 4
+               OpReturn
+               OpFunctionEnd
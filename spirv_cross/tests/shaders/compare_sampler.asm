@@ -0,0 +1,61 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 33
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Fragment %main "main" %target0 %v_uv
+               OpExecutionMode %main OriginUpperLeft
+               OpName %main "main"
+               OpName %target0 "target0"
+               OpName %v_uv "v_uv"
+               OpName %u_texture "u_texture"
+               OpName %u_shadow "u_shadow"
+               OpName %u_sampler "u_sampler"
+               OpName %u_shadow_sampler "u_shadow_sampler"
+               OpDecorate %target0 Location 0
+               OpDecorate %v_uv Location 0
+               OpDecorate %u_texture DescriptorSet 0
+               OpDecorate %u_texture Binding 0
+               OpDecorate %u_shadow DescriptorSet 0
+               OpDecorate %u_shadow Binding 1
+               OpDecorate %u_sampler DescriptorSet 0
+               OpDecorate %u_sampler Binding 2
+               OpDecorate %u_shadow_sampler DescriptorSet 0
+               OpDecorate %u_shadow_sampler Binding 3
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v2float = OpTypeVector %float 2
+    %v4float = OpTypeVector %float 4
+%_ptr_Output_float = OpTypePointer Output %float
+    %target0 = OpVariable %_ptr_Output_float Output
+%_ptr_Input_v2float = OpTypePointer Input %v2float
+       %v_uv = OpVariable %_ptr_Input_v2float Input
+         %12 = OpTypeImage %float 2D 0 0 0 1 Unknown
+%_ptr_UniformConstant_12 = OpTypePointer UniformConstant %12
+  %u_texture = OpVariable %_ptr_UniformConstant_12 UniformConstant
+   %u_shadow = OpVariable %_ptr_UniformConstant_12 UniformConstant
+         %16 = OpTypeSampler
+%_ptr_UniformConstant_16 = OpTypePointer UniformConstant %16
+  %u_sampler = OpVariable %_ptr_UniformConstant_16 UniformConstant
+%u_shadow_sampler = OpVariable %_ptr_UniformConstant_16 UniformConstant
+         %20 = OpTypeSampledImage %12
+  %float_0_5 = OpConstant %float 0.5
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+         %22 = OpLoad %12 %u_texture
+         %23 = OpLoad %16 %u_sampler
+         %24 = OpSampledImage %20 %22 %23
+         %25 = OpLoad %v2float %v_uv
+         %26 = OpImageSampleImplicitLod %v4float %24 %25
+         %27 = OpCompositeExtract %float %26 0
+         %28 = OpLoad %12 %u_shadow
+         %29 = OpLoad %16 %u_shadow_sampler
+         %30 = OpSampledImage %20 %28 %29
+         %31 = OpImageSampleDrefImplicitLod %float %30 %25 %float_0_5
+         %32 = OpFMul %float %27 %31
+               OpStore %target0 %32
+               OpReturn
+               OpFunctionEnd
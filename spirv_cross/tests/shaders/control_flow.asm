@@ -0,0 +1,69 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 40
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Fragment %main "main" %out_color
+               OpExecutionMode %main OriginUpperLeft
+               OpName %main "main"
+               OpName %helper "helper"
+               OpName %u_texture "u_texture"
+               OpName %out_color "out_color"
+               OpDecorate %u_texture DescriptorSet 0
+               OpDecorate %u_texture Binding 0
+               OpDecorate %out_color Location 0
+       %void = OpTypeVoid
+          %2 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+    %v2float = OpTypeVector %float 2
+          %6 = OpTypeImage %float 2D 0 0 0 1 Unknown
+          %7 = OpTypeSampledImage %6
+%_ptr_UniformConstant_7 = OpTypePointer UniformConstant %7
+  %u_texture = OpVariable %_ptr_UniformConstant_7 UniformConstant
+%_ptr_Output_v4float = OpTypePointer Output %v4float
+  %out_color = OpVariable %_ptr_Output_v4float Output
+        %int = OpTypeInt 32 1
+      %int_0 = OpConstant %int 0
+      %int_4 = OpConstant %int 4
+      %int_1 = OpConstant %int 1
+       %bool = OpTypeBool
+    %float_0 = OpConstant %float 0
+         %18 = OpConstantComposite %v2float %float_0 %float_0
+         %19 = OpTypeFunction %v4float
+       %main = OpFunction %void None %2
+         %21 = OpLabel
+         %22 = OpFunctionCall %v4float %helper
+               OpStore %out_color %22
+               OpReturn
+               OpFunctionEnd
+     %helper = OpFunction %v4float None %19
+         %24 = OpLabel
+               OpBranch %25
+         %25 = OpLabel
+         %26 = OpPhi %int %int_0 %24 %27 %28
+               OpLoopMerge %29 %28 None
+               OpBranch %30
+         %30 = OpLabel
+         %31 = OpSLessThan %bool %26 %int_4
+               OpBranchConditional %31 %32 %29
+         %32 = OpLabel
+         %34 = OpIEqual %bool %26 %int_1
+               OpSelectionMerge %33 None
+               OpBranchConditional %34 %35 %33
+         %35 = OpLabel
+         %36 = OpLoad %7 %u_texture
+         %37 = OpImageSampleImplicitLod %v4float %36 %18
+               OpBranch %33
+         %33 = OpLabel
+               OpBranch %28
+         %28 = OpLabel
+         %27 = OpIAdd %int %26 %int_1
+               OpBranch %25
+         %29 = OpLabel
+         %38 = OpLoad %7 %u_texture
+         %39 = OpImageSampleImplicitLod %v4float %38 %18
+               OpReturnValue %39
+               OpFunctionEnd
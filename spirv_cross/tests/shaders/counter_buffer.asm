@@ -0,0 +1,54 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 21
+; Schema: 0
+               OpCapability Shader
+               OpExtension "SPV_GOOGLE_hlsl_functionality1"
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main"
+               OpExecutionMode %main LocalSize 1 1 1
+               OpName %main "main"
+               OpName %Foo "Foo"
+               OpMemberName %Foo 0 "a"
+               OpName %Buf "Buf"
+               OpMemberName %Buf 0 "data"
+               OpName %buf "buf"
+               OpName %Counter "Counter"
+               OpMemberName %Counter 0 "count"
+               OpName %buf_count "buf_count"
+               OpMemberDecorate %Foo 0 Offset 0
+               OpDecorate %_runtimearr_Foo ArrayStride 4
+               OpMemberDecorate %Buf 0 Offset 0
+               OpDecorate %Buf BufferBlock
+               OpDecorate %buf DescriptorSet 0
+               OpDecorate %buf Binding 0
+               OpMemberDecorate %Counter 0 Offset 0
+               OpDecorate %Counter BufferBlock
+               OpDecorate %buf_count DescriptorSet 0
+               OpDecorate %buf_count Binding 1
+               OpDecorateId %buf CounterBuffer %buf_count
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+       %uint = OpTypeInt 32 0
+        %Foo = OpTypeStruct %uint
+%_runtimearr_Foo = OpTypeRuntimeArray %Foo
+        %Buf = OpTypeStruct %_runtimearr_Foo
+%_ptr_Uniform_Buf = OpTypePointer Uniform %Buf
+        %buf = OpVariable %_ptr_Uniform_Buf Uniform
+        %int = OpTypeInt 32 1
+      %int_0 = OpConstant %int 0
+%_ptr_Uniform_uint = OpTypePointer Uniform %uint
+     %uint_1 = OpConstant %uint 1
+    %Counter = OpTypeStruct %int
+%_ptr_Uniform_Counter = OpTypePointer Uniform %Counter
+  %buf_count = OpVariable %_ptr_Uniform_Counter Uniform
+%_ptr_Uniform_int = OpTypePointer Uniform %int
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+         %15 = OpAccessChain %_ptr_Uniform_uint %buf %int_0 %int_0 %int_0
+               OpStore %15 %uint_1
+         %20 = OpAccessChain %_ptr_Uniform_int %buf_count %int_0
+               OpStore %20 %int_0
+               OpReturn
+               OpFunctionEnd
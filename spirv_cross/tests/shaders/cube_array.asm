@@ -0,0 +1,39 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 19
+; Schema: 0
+               OpCapability Shader
+               OpCapability SampledCubeArray
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Fragment %main "main" %target0 %v_uv
+               OpExecutionMode %main OriginUpperLeft
+               OpName %main "main"
+               OpName %target0 "target0"
+               OpName %v_uv "v_uv"
+               OpName %u_texture "u_texture"
+               OpDecorate %target0 Location 0
+               OpDecorate %v_uv Location 0
+               OpDecorate %u_texture DescriptorSet 0
+               OpDecorate %u_texture Binding 0
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v2float = OpTypeVector %float 2
+    %v4float = OpTypeVector %float 4
+%_ptr_Output_v4float = OpTypePointer Output %v4float
+    %target0 = OpVariable %_ptr_Output_v4float Output
+%_ptr_Input_v4float = OpTypePointer Input %v4float
+       %v_uv = OpVariable %_ptr_Input_v4float Input
+         %12 = OpTypeImage %float Cube 0 1 0 1 Unknown
+         %13 = OpTypeSampledImage %12
+%_ptr_UniformConstant_13 = OpTypePointer UniformConstant %13
+  %u_texture = OpVariable %_ptr_UniformConstant_13 UniformConstant
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+         %16 = OpLoad %13 %u_texture
+         %17 = OpLoad %v4float %v_uv
+         %18 = OpImageSampleImplicitLod %v4float %16 %17
+               OpStore %target0 %18
+               OpReturn
+               OpFunctionEnd
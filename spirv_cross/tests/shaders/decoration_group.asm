@@ -0,0 +1,40 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 17
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main"
+               OpExecutionMode %main LocalSize 1 1 1
+               OpName %main "main"
+               OpName %Buf "Buf"
+               OpMemberName %Buf 0 "data"
+               OpName %a "a"
+               OpName %b "b"
+               OpDecorate %10 DescriptorSet 0
+         %10 = OpDecorationGroup
+               OpGroupDecorate %10 %a %b
+               OpMemberDecorate %Buf 0 Offset 0
+               OpDecorate %Buf BufferBlock
+               OpDecorate %a Binding 0
+               OpDecorate %b Binding 1
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+       %uint = OpTypeInt 32 0
+        %Buf = OpTypeStruct %uint
+%_ptr_Uniform_Buf = OpTypePointer Uniform %Buf
+          %a = OpVariable %_ptr_Uniform_Buf Uniform
+          %b = OpVariable %_ptr_Uniform_Buf Uniform
+        %int = OpTypeInt 32 1
+      %int_0 = OpConstant %int 0
+%_ptr_Uniform_uint = OpTypePointer Uniform %uint
+     %uint_1 = OpConstant %uint 1
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+         %15 = OpAccessChain %_ptr_Uniform_uint %a %int_0
+               OpStore %15 %uint_1
+         %16 = OpAccessChain %_ptr_Uniform_uint %b %int_0
+               OpStore %16 %uint_1
+               OpReturn
+               OpFunctionEnd
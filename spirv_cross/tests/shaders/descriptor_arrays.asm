@@ -0,0 +1,40 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 18
+; Schema: 0
+               OpCapability Shader
+               OpCapability RuntimeDescriptorArrayEXT
+               OpExtension "SPV_EXT_descriptor_indexing"
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Fragment %main "main"
+               OpExecutionMode %main OriginUpperLeft
+               OpName %main "main"
+               OpName %u_textures "u_textures"
+               OpName %u_bindless "u_bindless"
+               OpName %u_sampler "u_sampler"
+               OpDecorate %u_textures DescriptorSet 0
+               OpDecorate %u_textures Binding 1
+               OpDecorate %u_bindless DescriptorSet 1
+               OpDecorate %u_bindless Binding 0
+               OpDecorate %u_sampler DescriptorSet 0
+               OpDecorate %u_sampler Binding 0
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+          %6 = OpTypeImage %float 2D 0 0 0 1 Unknown
+       %uint = OpTypeInt 32 0
+     %uint_4 = OpConstant %uint 4
+%_arr_6_uint_4 = OpTypeArray %6 %uint_4
+%_ptr_UniformConstant__arr_6_uint_4 = OpTypePointer UniformConstant %_arr_6_uint_4
+ %u_textures = OpVariable %_ptr_UniformConstant__arr_6_uint_4 UniformConstant
+%_runtimearr_6 = OpTypeRuntimeArray %6
+%_ptr_UniformConstant__runtimearr_6 = OpTypePointer UniformConstant %_runtimearr_6
+ %u_bindless = OpVariable %_ptr_UniformConstant__runtimearr_6 UniformConstant
+         %15 = OpTypeSampler
+%_ptr_UniformConstant_15 = OpTypePointer UniformConstant %15
+  %u_sampler = OpVariable %_ptr_UniformConstant_15 UniformConstant
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+               OpReturn
+               OpFunctionEnd
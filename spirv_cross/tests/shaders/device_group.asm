@@ -0,0 +1,52 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 25
+; Schema: 0
+               OpCapability Shader
+               OpCapability DeviceGroup
+               OpExtension "SPV_KHR_device_group"
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main" %gl_WorkGroupID %gl_DeviceIndex
+               OpExecutionMode %main LocalSize 1 1 1
+               OpName %main "main"
+               OpName %Buf "Buf"
+               OpMemberName %Buf 0 "data"
+               OpName %buf "buf"
+               OpName %gl_WorkGroupID "gl_WorkGroupID"
+               OpName %gl_DeviceIndex "gl_DeviceIndex"
+               OpDecorate %_runtimearr_uint ArrayStride 4
+               OpMemberDecorate %Buf 0 Offset 0
+               OpDecorate %Buf BufferBlock
+               OpDecorate %buf DescriptorSet 0
+               OpDecorate %buf Binding 0
+               OpDecorate %gl_WorkGroupID BuiltIn WorkgroupId
+               OpDecorate %gl_DeviceIndex BuiltIn DeviceIndex
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+       %uint = OpTypeInt 32 0
+%_runtimearr_uint = OpTypeRuntimeArray %uint
+        %Buf = OpTypeStruct %_runtimearr_uint
+%_ptr_Uniform_Buf = OpTypePointer Uniform %Buf
+        %buf = OpVariable %_ptr_Uniform_Buf Uniform
+        %int = OpTypeInt 32 1
+      %int_0 = OpConstant %int 0
+%_ptr_Uniform_uint = OpTypePointer Uniform %uint
+     %v3uint = OpTypeVector %uint 3
+%_ptr_Input_v3uint = OpTypePointer Input %v3uint
+%gl_WorkGroupID = OpVariable %_ptr_Input_v3uint Input
+%_ptr_Input_int = OpTypePointer Input %int
+%gl_DeviceIndex = OpVariable %_ptr_Input_int Input
+      %int_1 = OpConstant %int 1
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+         %19 = OpLoad %v3uint %gl_WorkGroupID
+         %20 = OpCompositeExtract %uint %19 0
+         %21 = OpAccessChain %_ptr_Uniform_uint %buf %int_0 %int_0
+               OpStore %21 %20
+         %22 = OpLoad %int %gl_DeviceIndex
+         %23 = OpBitcast %uint %22
+         %24 = OpAccessChain %_ptr_Uniform_uint %buf %int_0 %int_1
+               OpStore %24 %23
+               OpReturn
+               OpFunctionEnd
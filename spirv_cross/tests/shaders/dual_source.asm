@@ -0,0 +1,27 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 10
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Fragment %main "main" %color0 %color1
+               OpExecutionMode %main OriginUpperLeft
+               OpName %main "main"
+               OpName %color0 "color0"
+               OpName %color1 "color1"
+               OpDecorate %color0 Location 0
+               OpDecorate %color0 Index 0
+               OpDecorate %color1 Location 0
+               OpDecorate %color1 Index 1
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+%_ptr_Output_v4float = OpTypePointer Output %v4float
+     %color0 = OpVariable %_ptr_Output_v4float Output
+     %color1 = OpVariable %_ptr_Output_v4float Output
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+               OpReturn
+               OpFunctionEnd
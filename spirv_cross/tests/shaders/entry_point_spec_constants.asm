@@ -0,0 +1,53 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 22
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Vertex %main_vs "main_vs" %out_scale
+               OpEntryPoint Fragment %main_fs "main_fs" %color
+               OpExecutionMode %main_fs OriginUpperLeft
+               OpName %main_vs "main_vs"
+               OpName %main_fs "main_fs"
+               OpName %get_scale "get_scale"
+               OpName %scale "scale"
+               OpName %tint "tint"
+               OpName %tint_doubled "tint_doubled"
+               OpName %unused "unused"
+               OpName %out_scale "out_scale"
+               OpName %color "color"
+               OpDecorate %scale SpecId 0
+               OpDecorate %tint SpecId 1
+               OpDecorate %unused SpecId 2
+               OpDecorate %out_scale Location 0
+               OpDecorate %color Location 0
+       %void = OpTypeVoid
+          %5 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+          %7 = OpTypeFunction %float
+        %int = OpTypeInt 32 1
+      %scale = OpSpecConstant %float 1
+       %tint = OpSpecConstant %int 3
+      %int_2 = OpConstant %int 2
+%tint_doubled = OpSpecConstantOp %int IMul %tint %int_2
+     %unused = OpSpecConstant %int 0
+%_ptr_Output_float = OpTypePointer Output %float
+  %out_scale = OpVariable %_ptr_Output_float Output
+%_ptr_Output_int = OpTypePointer Output %int
+      %color = OpVariable %_ptr_Output_int Output
+  %get_scale = OpFunction %float None %7
+         %21 = OpLabel
+               OpReturnValue %scale
+               OpFunctionEnd
+    %main_vs = OpFunction %void None %5
+         %18 = OpLabel
+         %19 = OpFunctionCall %float %get_scale
+               OpStore %out_scale %19
+               OpReturn
+               OpFunctionEnd
+    %main_fs = OpFunction %void None %5
+         %20 = OpLabel
+               OpStore %color %tint_doubled
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,30 @@
+; SPIR-V
+; Version: 1.2
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 10
+; Schema: 0
+               OpCapability Shader
+               OpCapability Geometry
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Geometry %geom "geom"
+               OpEntryPoint GLCompute %comp "comp"
+               OpExecutionMode %geom InputPoints
+               OpExecutionMode %geom Invocations 4
+               OpExecutionMode %geom OutputPoints
+               OpExecutionMode %geom OutputVertices 3
+               OpExecutionModeId %comp LocalSizeId %uint_8 %uint_1 %uint_1
+               OpName %geom "geom"
+               OpName %comp "comp"
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+       %uint = OpTypeInt 32 0
+     %uint_8 = OpConstant %uint 8
+     %uint_1 = OpConstant %uint 1
+       %geom = OpFunction %void None %3
+          %4 = OpLabel
+               OpReturn
+               OpFunctionEnd
+       %comp = OpFunction %void None %3
+          %6 = OpLabel
+               OpReturn
+               OpFunctionEnd
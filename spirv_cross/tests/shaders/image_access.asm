@@ -0,0 +1,56 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 27
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main"
+               OpExecutionMode %main LocalSize 1 1 1
+               OpName %main "main"
+               OpName %u_read "u_read"
+               OpName %u_written "u_written"
+               OpName %u_read_written "u_read_written"
+               OpName %u_non_writable "u_non_writable"
+               OpName %read_write "read_write"
+               OpDecorate %u_read DescriptorSet 0
+               OpDecorate %u_read Binding 0
+               OpDecorate %u_written DescriptorSet 0
+               OpDecorate %u_written Binding 1
+               OpDecorate %u_read_written DescriptorSet 0
+               OpDecorate %u_read_written Binding 2
+               OpDecorate %u_non_writable DescriptorSet 0
+               OpDecorate %u_non_writable Binding 3
+               OpDecorate %u_non_writable NonWritable
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+          %6 = OpTypeImage %float 2D 0 0 0 2 Rgba8
+%_ptr_UniformConstant_6 = OpTypePointer UniformConstant %6
+     %u_read = OpVariable %_ptr_UniformConstant_6 UniformConstant
+  %u_written = OpVariable %_ptr_UniformConstant_6 UniformConstant
+%u_read_written = OpVariable %_ptr_UniformConstant_6 UniformConstant
+%u_non_writable = OpVariable %_ptr_UniformConstant_6 UniformConstant
+        %int = OpTypeInt 32 1
+      %v2int = OpTypeVector %int 2
+         %14 = OpConstantNull %v2int
+         %15 = OpTypeFunction %void %_ptr_UniformConstant_6
+       %main = OpFunction %void None %3
+         %22 = OpLabel
+         %23 = OpLoad %6 %u_read
+         %24 = OpImageRead %v4float %23 %14
+         %25 = OpLoad %6 %u_written
+               OpImageWrite %25 %14 %24
+         %26 = OpFunctionCall %void %read_write %u_read_written
+               OpReturn
+               OpFunctionEnd
+ %read_write = OpFunction %void None %15
+         %17 = OpFunctionParameter %_ptr_UniformConstant_6
+         %18 = OpLabel
+         %19 = OpLoad %6 %17
+         %20 = OpImageRead %v4float %19 %14
+         %21 = OpLoad %6 %17
+               OpImageWrite %21 %14 %20
+               OpReturn
+               OpFunctionEnd
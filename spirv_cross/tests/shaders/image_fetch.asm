@@ -0,0 +1,35 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 18
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Fragment %main "main" %target0
+               OpExecutionMode %main OriginUpperLeft
+               OpName %main "main"
+               OpName %target0 "target0"
+               OpName %u_texture "u_texture"
+               OpDecorate %target0 Location 0
+               OpDecorate %u_texture DescriptorSet 0
+               OpDecorate %u_texture Binding 0
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+%_ptr_Output_v4float = OpTypePointer Output %v4float
+    %target0 = OpVariable %_ptr_Output_v4float Output
+          %9 = OpTypeImage %float 2D 0 0 0 1 Unknown
+%_ptr_UniformConstant_9 = OpTypePointer UniformConstant %9
+  %u_texture = OpVariable %_ptr_UniformConstant_9 UniformConstant
+        %int = OpTypeInt 32 1
+      %int_0 = OpConstant %int 0
+      %v2int = OpTypeVector %int 2
+         %17 = OpConstantNull %v2int
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+         %14 = OpLoad %9 %u_texture
+         %15 = OpImageFetch %v4float %14 %17 Lod %int_0
+               OpStore %target0 %15
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,65 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 36
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Fragment %main "main" %out_color
+               OpExecutionMode %main OriginUpperLeft
+               OpName %main "main"
+               OpName %sample_helper "sample_helper"
+               OpName %tex_a "tex_a"
+               OpName %tex_b "tex_b"
+               OpName %samp_linear "samp_linear"
+               OpName %samp_point "samp_point"
+               OpName %out_color "out_color"
+               OpDecorate %tex_a DescriptorSet 0
+               OpDecorate %tex_a Binding 0
+               OpDecorate %tex_b DescriptorSet 0
+               OpDecorate %tex_b Binding 1
+               OpDecorate %samp_linear DescriptorSet 0
+               OpDecorate %samp_linear Binding 2
+               OpDecorate %samp_point DescriptorSet 0
+               OpDecorate %samp_point Binding 3
+               OpDecorate %out_color Location 0
+       %void = OpTypeVoid
+          %4 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+    %v2float = OpTypeVector %float 2
+          %8 = OpTypeImage %float 2D 0 0 0 1 Unknown
+          %9 = OpTypeSampler
+         %10 = OpTypeSampledImage %8
+%_ptr_UniformConstant_8 = OpTypePointer UniformConstant %8
+%_ptr_UniformConstant_9 = OpTypePointer UniformConstant %9
+%_ptr_Output_v4float = OpTypePointer Output %v4float
+      %tex_a = OpVariable %_ptr_UniformConstant_8 UniformConstant
+      %tex_b = OpVariable %_ptr_UniformConstant_8 UniformConstant
+%samp_linear = OpVariable %_ptr_UniformConstant_9 UniformConstant
+ %samp_point = OpVariable %_ptr_UniformConstant_9 UniformConstant
+  %out_color = OpVariable %_ptr_Output_v4float Output
+         %19 = OpTypeFunction %v4float %8 %9
+  %float_0_5 = OpConstant %float 0.5
+         %21 = OpConstantComposite %v2float %float_0_5 %float_0_5
+       %main = OpFunction %void None %4
+         %22 = OpLabel
+         %23 = OpLoad %8 %tex_a
+         %24 = OpLoad %9 %samp_linear
+         %25 = OpSampledImage %10 %23 %24
+         %26 = OpImageSampleImplicitLod %v4float %25 %21
+         %27 = OpLoad %8 %tex_b
+         %28 = OpLoad %9 %samp_point
+         %29 = OpFunctionCall %v4float %sample_helper %27 %28
+         %30 = OpFAdd %v4float %26 %29
+               OpStore %out_color %30
+               OpReturn
+               OpFunctionEnd
+%sample_helper = OpFunction %v4float None %19
+         %31 = OpFunctionParameter %8
+         %32 = OpFunctionParameter %9
+         %33 = OpLabel
+         %34 = OpSampledImage %10 %31 %32
+         %35 = OpImageSampleImplicitLod %v4float %34 %21
+               OpReturnValue %35
+               OpFunctionEnd
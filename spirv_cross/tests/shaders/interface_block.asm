@@ -0,0 +1,33 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 13
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Vertex %main "main" %vs_out
+               OpName %main "main"
+               OpName %VertexOut "VertexOut"
+               OpMemberName %VertexOut 0 "normal"
+               OpMemberName %VertexOut 1 "transform"
+               OpMemberName %VertexOut 2 "id"
+               OpMemberName %VertexOut 3 "uv"
+               OpName %vs_out "vs_out"
+               OpMemberDecorate %VertexOut 2 Location 8
+               OpMemberDecorate %VertexOut 2 Flat
+               OpDecorate %VertexOut Block
+               OpDecorate %vs_out Location 2
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v3float = OpTypeVector %float 3
+    %v2float = OpTypeVector %float 2
+%mat2v2float = OpTypeMatrix %v2float 2
+        %int = OpTypeInt 32 1
+  %VertexOut = OpTypeStruct %v3float %mat2v2float %int %v2float
+%_ptr_Output_VertexOut = OpTypePointer Output %VertexOut
+     %vs_out = OpVariable %_ptr_Output_VertexOut Output
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,34 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 16
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Vertex %main "main" %a_position %v_color %v_index
+               OpName %main "main"
+               OpName %a_position "a_position"
+               OpName %v_color "v_color"
+               OpName %v_index "v_index"
+               OpDecorate %a_position Location 0
+               OpDecorate %v_color Location 0
+               OpDecorate %v_index Location 1
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+       %uint = OpTypeInt 32 0
+%_ptr_Input_v4float = OpTypePointer Input %v4float
+%_ptr_Output_v4float = OpTypePointer Output %v4float
+%_ptr_Output_uint = OpTypePointer Output %uint
+ %a_position = OpVariable %_ptr_Input_v4float Input
+    %v_color = OpVariable %_ptr_Output_v4float Output
+    %v_index = OpVariable %_ptr_Output_uint Output
+     %uint_0 = OpConstant %uint 0
+       %main = OpFunction %void None %3
+         %14 = OpLabel
+         %15 = OpLoad %v4float %a_position
+               OpStore %v_color %15
+               OpStore %v_index %uint_0
+               OpReturn
+               OpFunctionEnd
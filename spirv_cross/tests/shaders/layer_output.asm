@@ -0,0 +1,64 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 29
+; Schema: 0
+               OpCapability Shader
+               OpCapability Geometry
+               OpCapability MultiViewport
+               OpCapability ShaderViewportIndexLayerEXT
+               OpExtension "SPV_EXT_shader_viewport_index_layer"
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Vertex %layered_vs "layered_vs" %out_layer
+               OpEntryPoint Geometry %viewport_gs "viewport_gs" %out_viewport
+               OpEntryPoint Fragment %layer_fs "layer_fs" %in_layer %color
+               OpExecutionMode %viewport_gs Triangles
+               OpExecutionMode %viewport_gs OutputTriangleStrip
+               OpExecutionMode %viewport_gs OutputVertices 3
+               OpExecutionMode %viewport_gs Invocations 1
+               OpExecutionMode %layer_fs OriginUpperLeft
+               OpName %layered_vs "layered_vs"
+               OpName %viewport_gs "viewport_gs"
+               OpName %layer_fs "layer_fs"
+               OpName %out_layer "out_layer"
+               OpName %out_viewport "out_viewport"
+               OpName %in_layer "in_layer"
+               OpName %color "color"
+               OpDecorate %out_layer BuiltIn Layer
+               OpDecorate %out_viewport BuiltIn ViewportIndex
+               OpDecorate %in_layer BuiltIn Layer
+               OpDecorate %in_layer Flat
+               OpDecorate %color Location 0
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+        %int = OpTypeInt 32 1
+%_ptr_Output_int = OpTypePointer Output %int
+  %out_layer = OpVariable %_ptr_Output_int Output
+%out_viewport = OpVariable %_ptr_Output_int Output
+%_ptr_Input_int = OpTypePointer Input %int
+   %in_layer = OpVariable %_ptr_Input_int Input
+      %int_1 = OpConstant %int 1
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+%_ptr_Output_v4float = OpTypePointer Output %v4float
+      %color = OpVariable %_ptr_Output_v4float Output
+ %layered_vs = OpFunction %void None %3
+         %21 = OpLabel
+               OpStore %out_layer %int_1
+               OpReturn
+               OpFunctionEnd
+%viewport_gs = OpFunction %void None %3
+         %23 = OpLabel
+               OpStore %out_viewport %int_1
+               OpEmitVertex
+               OpEndPrimitive
+               OpReturn
+               OpFunctionEnd
+   %layer_fs = OpFunction %void None %3
+         %25 = OpLabel
+         %26 = OpLoad %int %in_layer
+         %27 = OpConvertSToF %float %26
+         %28 = OpCompositeConstruct %v4float %27 %27 %27 %27
+               OpStore %color %28
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,22 @@
+; SPIR-V
+; Version: 1.2
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 9
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main"
+               OpExecutionModeId %main LocalSizeId %uint_8 %size_y %uint_1
+               OpName %main "main"
+               OpName %size_y "size_y"
+               OpDecorate %size_y SpecId 0
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+       %uint = OpTypeInt 32 0
+     %uint_8 = OpConstant %uint 8
+     %size_y = OpSpecConstant %uint 4
+     %uint_1 = OpConstant %uint 1
+       %main = OpFunction %void None %3
+          %8 = OpLabel
+               OpReturn
+               OpFunctionEnd
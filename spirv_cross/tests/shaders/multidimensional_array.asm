@@ -0,0 +1,47 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 24
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Fragment %main "main" %f_color
+               OpExecutionMode %main OriginUpperLeft
+               OpName %main "main"
+               OpName %Uniforms "Uniforms"
+               OpMemberName %Uniforms 0 "values"
+               OpName %ubo "ubo"
+               OpName %f_color "f_color"
+               OpDecorate %_arr_v4float_uint_3 ArrayStride 16
+               OpDecorate %_arr__arr_v4float_uint_3_uint_2 ArrayStride 48
+               OpMemberDecorate %Uniforms 0 Offset 0
+               OpDecorate %Uniforms Block
+               OpDecorate %ubo DescriptorSet 0
+               OpDecorate %ubo Binding 0
+               OpDecorate %f_color Location 0
+       %void = OpTypeVoid
+          %6 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+       %uint = OpTypeInt 32 0
+     %uint_3 = OpConstant %uint 3
+%_arr_v4float_uint_3 = OpTypeArray %v4float %uint_3
+     %uint_2 = OpConstant %uint 2
+%_arr__arr_v4float_uint_3_uint_2 = OpTypeArray %_arr_v4float_uint_3 %uint_2
+   %Uniforms = OpTypeStruct %_arr__arr_v4float_uint_3_uint_2
+%_ptr_Uniform_Uniforms = OpTypePointer Uniform %Uniforms
+        %ubo = OpVariable %_ptr_Uniform_Uniforms Uniform
+        %int = OpTypeInt 32 1
+      %int_0 = OpConstant %int 0
+      %int_1 = OpConstant %int 1
+      %int_2 = OpConstant %int 2
+%_ptr_Uniform_v4float = OpTypePointer Uniform %v4float
+%_ptr_Output_v4float = OpTypePointer Output %v4float
+    %f_color = OpVariable %_ptr_Output_v4float Output
+       %main = OpFunction %void None %6
+         %21 = OpLabel
+         %22 = OpAccessChain %_ptr_Uniform_v4float %ubo %int_0 %int_1 %int_2
+         %23 = OpLoad %v4float %22
+               OpStore %f_color %23
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,71 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 38
+; Schema: 0
+               OpCapability Shader
+               OpCapability ShaderNonUniformEXT
+               OpCapability RuntimeDescriptorArrayEXT
+               OpCapability SampledImageArrayNonUniformIndexingEXT
+               OpExtension "SPV_EXT_descriptor_indexing"
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Fragment %main "main" %in_index %out_color
+               OpExecutionMode %main OriginUpperLeft
+               OpName %main "main"
+               OpName %u_textures "u_textures"
+               OpName %u_materials "u_materials"
+               OpName %u_shadow "u_shadow"
+               OpName %in_index "in_index"
+               OpName %out_color "out_color"
+               OpDecorate %u_textures DescriptorSet 0
+               OpDecorate %u_textures Binding 0
+               OpDecorate %u_materials DescriptorSet 0
+               OpDecorate %u_materials Binding 1
+               OpDecorate %u_shadow DescriptorSet 0
+               OpDecorate %u_shadow Binding 2
+               OpDecorate %in_index Flat
+               OpDecorate %in_index Location 0
+               OpDecorate %out_color Location 0
+               OpDecorate %27 NonUniformEXT
+               OpDecorate %29 NonUniformEXT
+               OpDecorate %31 NonUniformEXT
+       %void = OpTypeVoid
+          %2 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+    %v2float = OpTypeVector %float 2
+          %6 = OpTypeImage %float 2D 0 0 0 1 Unknown
+          %7 = OpTypeSampledImage %6
+%_runtimearr_7 = OpTypeRuntimeArray %7
+%_ptr_UniformConstant__runtimearr_7 = OpTypePointer UniformConstant %_runtimearr_7
+ %u_textures = OpVariable %_ptr_UniformConstant__runtimearr_7 UniformConstant
+        %int = OpTypeInt 32 1
+      %int_4 = OpConstant %int 4
+%_arr_7_int_4 = OpTypeArray %7 %int_4
+%_ptr_UniformConstant__arr_7_int_4 = OpTypePointer UniformConstant %_arr_7_int_4
+%u_materials = OpVariable %_ptr_UniformConstant__arr_7_int_4 UniformConstant
+%_ptr_UniformConstant_7 = OpTypePointer UniformConstant %7
+   %u_shadow = OpVariable %_ptr_UniformConstant_7 UniformConstant
+%_ptr_Input_int = OpTypePointer Input %int
+   %in_index = OpVariable %_ptr_Input_int Input
+%_ptr_Output_v4float = OpTypePointer Output %v4float
+  %out_color = OpVariable %_ptr_Output_v4float Output
+    %float_0 = OpConstant %float 0
+         %23 = OpConstantComposite %v2float %float_0 %float_0
+       %main = OpFunction %void None %2
+         %25 = OpLabel
+         %26 = OpLoad %int %in_index
+         %27 = OpCopyObject %int %26
+         %28 = OpAccessChain %_ptr_UniformConstant_7 %u_textures %27
+         %29 = OpLoad %7 %28
+         %30 = OpImageSampleImplicitLod %v4float %29 %23
+         %31 = OpAccessChain %_ptr_UniformConstant_7 %u_materials %26
+         %32 = OpLoad %7 %31
+         %33 = OpImageSampleImplicitLod %v4float %32 %23
+         %34 = OpLoad %7 %u_shadow
+         %35 = OpImageSampleImplicitLod %v4float %34 %23
+         %36 = OpFAdd %v4float %30 %33
+         %37 = OpFAdd %v4float %36 %35
+               OpStore %out_color %37
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,53 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 25
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Vertex %main_vs "main_vs" %v_out
+               OpEntryPoint Fragment %main_fs "main_fs" %f_out
+               OpExecutionMode %main_fs OriginUpperLeft
+               OpName %main_vs "main_vs"
+               OpName %main_fs "main_fs"
+               OpName %PushConstants "PushConstants"
+               OpMemberName %PushConstants 0 "color"
+               OpMemberName %PushConstants 1 "scale"
+               OpName %pc "pc"
+               OpName %v_out "v_out"
+               OpName %f_out "f_out"
+               OpMemberDecorate %PushConstants 0 Offset 0
+               OpMemberDecorate %PushConstants 1 Offset 16
+               OpDecorate %PushConstants Block
+               OpDecorate %v_out Location 0
+               OpDecorate %f_out Location 0
+       %void = OpTypeVoid
+          %4 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+%PushConstants = OpTypeStruct %v4float %float
+%_ptr_PushConstant_PushConstants = OpTypePointer PushConstant %PushConstants
+         %pc = OpVariable %_ptr_PushConstant_PushConstants PushConstant
+        %int = OpTypeInt 32 1
+      %int_0 = OpConstant %int 0
+      %int_1 = OpConstant %int 1
+%_ptr_PushConstant_v4float = OpTypePointer PushConstant %v4float
+%_ptr_PushConstant_float = OpTypePointer PushConstant %float
+%_ptr_Output_v4float = OpTypePointer Output %v4float
+      %v_out = OpVariable %_ptr_Output_v4float Output
+%_ptr_Output_float = OpTypePointer Output %float
+      %f_out = OpVariable %_ptr_Output_float Output
+    %main_vs = OpFunction %void None %4
+         %19 = OpLabel
+         %20 = OpAccessChain %_ptr_PushConstant_v4float %pc %int_0
+         %21 = OpLoad %v4float %20
+               OpStore %v_out %21
+               OpReturn
+               OpFunctionEnd
+    %main_fs = OpFunction %void None %4
+         %22 = OpLabel
+         %23 = OpAccessChain %_ptr_PushConstant_float %pc %int_1
+         %24 = OpLoad %float %23
+               OpStore %f_out %24
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,36 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 13
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main"
+               OpExecutionMode %main LocalSize 1 1 1
+               OpName %main "main"
+               OpName %Particle "Particle"
+               OpName %Particles "Particles"
+               OpName %particles "particles"
+               OpMemberDecorate %Particle 0 Offset 0
+               OpMemberDecorate %Particle 1 Offset 16
+               OpDecorate %_runtimearr_Particle ArrayStride 32
+               OpMemberDecorate %Particles 0 Offset 0
+               OpMemberDecorate %Particles 1 Offset 16
+               OpMemberDecorate %Particles 2 Offset 32
+               OpDecorate %Particles BufferBlock
+               OpDecorate %particles DescriptorSet 0
+               OpDecorate %particles Binding 0
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+       %uint = OpTypeInt 32 0
+   %Particle = OpTypeStruct %v4float %v4float
+%_runtimearr_Particle = OpTypeRuntimeArray %Particle
+  %Particles = OpTypeStruct %uint %v4float %_runtimearr_Particle
+%_ptr_Uniform_Particles = OpTypePointer Uniform %Particles
+  %particles = OpVariable %_ptr_Uniform_Particles Uniform
+       %main = OpFunction %void None %3
+         %12 = OpLabel
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,58 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 24
+; Schema: 0
+               OpCapability Shader
+               OpCapability SampleRateShading
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Fragment %sample_input "sample_input" %v_color %out_color
+               OpEntryPoint Fragment %sample_id "sample_id" %gl_SampleID %out_color
+               OpEntryPoint Fragment %pixel "pixel" %v_plain %out_color
+               OpExecutionMode %sample_input OriginUpperLeft
+               OpExecutionMode %sample_id OriginUpperLeft
+               OpExecutionMode %pixel OriginUpperLeft
+               OpName %sample_input "sample_input"
+               OpName %sample_id "sample_id"
+               OpName %pixel "pixel"
+               OpName %v_color "v_color"
+               OpName %gl_SampleID "gl_SampleID"
+               OpName %out_color "out_color"
+               OpName %v_plain "v_plain"
+               OpDecorate %v_color Sample
+               OpDecorate %v_color Location 0
+               OpDecorate %gl_SampleID BuiltIn SampleId
+               OpDecorate %out_color Location 0
+               OpDecorate %v_plain Location 1
+       %void = OpTypeVoid
+          %5 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+        %int = OpTypeInt 32 1
+%_ptr_Input_v4float = OpTypePointer Input %v4float
+%_ptr_Output_v4float = OpTypePointer Output %v4float
+%_ptr_Input_int = OpTypePointer Input %int
+    %v_color = OpVariable %_ptr_Input_v4float Input
+%gl_SampleID = OpVariable %_ptr_Input_int Input
+  %out_color = OpVariable %_ptr_Output_v4float Output
+    %v_plain = OpVariable %_ptr_Input_v4float Input
+%sample_input = OpFunction %void None %5
+         %16 = OpLabel
+         %17 = OpLoad %v4float %v_color
+               OpStore %out_color %17
+               OpReturn
+               OpFunctionEnd
+  %sample_id = OpFunction %void None %5
+         %18 = OpLabel
+         %19 = OpLoad %int %gl_SampleID
+         %20 = OpConvertSToF %float %19
+         %21 = OpCompositeConstruct %v4float %20 %20 %20 %20
+               OpStore %out_color %21
+               OpReturn
+               OpFunctionEnd
+      %pixel = OpFunction %void None %5
+         %22 = OpLabel
+         %23 = OpLoad %v4float %v_plain
+               OpStore %out_color %23
+               OpReturn
+               OpFunctionEnd
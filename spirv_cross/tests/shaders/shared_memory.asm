@@ -0,0 +1,33 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 18
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main"
+               OpExecutionMode %main LocalSize 64 1 1
+               OpName %main "main"
+               OpName %data "data"
+               OpName %value "value"
+               OpName %counts "counts"
+               OpDecorate %count SpecId 0
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+       %uint = OpTypeInt 32 0
+    %uint_64 = OpConstant %uint 64
+%_arr_float_uint_64 = OpTypeArray %float %uint_64
+%_ptr_Workgroup__arr_float_uint_64 = OpTypePointer Workgroup %_arr_float_uint_64
+       %data = OpVariable %_ptr_Workgroup__arr_float_uint_64 Workgroup
+     %v3uint = OpTypeVector %uint 3
+%_ptr_Workgroup_v3uint = OpTypePointer Workgroup %v3uint
+      %value = OpVariable %_ptr_Workgroup_v3uint Workgroup
+      %count = OpSpecConstant %uint 16
+%_arr_uint_count = OpTypeArray %uint %count
+%_ptr_Workgroup__arr_uint_count = OpTypePointer Workgroup %_arr_uint_count
+     %counts = OpVariable %_ptr_Workgroup__arr_uint_count Workgroup
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,23 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 7
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Vertex %main "main"
+          %5 = OpString "shaders/main.hlsl"
+          %6 = OpString "shaders/common.hlsli"
+               OpSource HLSL 600 %5 "void main()
+"
+               OpSourceContinued "{
+}
+"
+               OpName %main "main"
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+               OpLine %6 3 1
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,39 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 19
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main"
+               OpExecutionMode %main LocalSize 1 1 1
+               OpName %main "main"
+               OpName %count "count"
+               OpName %size "size"
+               OpName %flag "flag"
+               OpName %chosen "chosen"
+               OpName %offset "offset"
+               OpName %half_offset "half_offset"
+               OpDecorate %count SpecId 3
+               OpDecorate %flag SpecId 5
+               OpDecorate %offset SpecId 7
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+       %uint = OpTypeInt 32 0
+        %int = OpTypeInt 32 1
+       %bool = OpTypeBool
+      %float = OpTypeFloat 32
+      %count = OpSpecConstant %uint 2
+     %uint_4 = OpConstant %uint 4
+       %size = OpSpecConstantOp %uint IMul %count %uint_4
+       %flag = OpSpecConstantTrue %bool
+     %uint_1 = OpConstant %uint 1
+     %chosen = OpSpecConstantOp %uint Select %flag %size %uint_1
+     %offset = OpSpecConstant %int -3
+      %int_2 = OpConstant %int 2
+%half_offset = OpSpecConstantOp %int SDiv %offset %int_2
+    %float_1 = OpConstant %float 1
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+               OpReturn
+               OpFunctionEnd
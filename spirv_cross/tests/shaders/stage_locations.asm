@@ -0,0 +1,36 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 20
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Vertex %main "main" %a_position %a_transform %a_uv %v_normal %v_color
+               OpName %main "main"
+               OpName %a_position "a_position"
+               OpName %a_transform "a_transform"
+               OpName %a_uv "a_uv"
+               OpName %v_normal "v_normal"
+               OpName %v_color "v_color"
+               OpDecorate %a_position Location 0
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v2float = OpTypeVector %float 2
+    %v3float = OpTypeVector %float 3
+    %v4float = OpTypeVector %float 4
+%mat4v4float = OpTypeMatrix %v4float 4
+%_ptr_Input_v4float = OpTypePointer Input %v4float
+%_ptr_Input_mat4v4float = OpTypePointer Input %mat4v4float
+%_ptr_Input_v2float = OpTypePointer Input %v2float
+%_ptr_Output_v3float = OpTypePointer Output %v3float
+%_ptr_Output_v4float = OpTypePointer Output %v4float
+ %a_position = OpVariable %_ptr_Input_v4float Input
+%a_transform = OpVariable %_ptr_Input_mat4v4float Input
+       %a_uv = OpVariable %_ptr_Input_v2float Input
+   %v_normal = OpVariable %_ptr_Output_v3float Output
+    %v_color = OpVariable %_ptr_Output_v4float Output
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,36 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 17
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main"
+               OpExecutionMode %main LocalSize 1 1 1
+               OpName %main "main"
+               OpName %u_output "u_output"
+               OpName %u_input "u_input"
+               OpDecorate %u_output DescriptorSet 0
+               OpDecorate %u_output Binding 0
+               OpDecorate %u_output NonReadable
+               OpDecorate %u_input DescriptorSet 0
+               OpDecorate %u_input Binding 1
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+          %7 = OpTypeImage %float 2D 0 0 0 2 Rgba8
+%_ptr_UniformConstant_7 = OpTypePointer UniformConstant %7
+   %u_output = OpVariable %_ptr_UniformConstant_7 UniformConstant
+    %u_input = OpVariable %_ptr_UniformConstant_7 UniformConstant
+        %int = OpTypeInt 32 1
+      %v2int = OpTypeVector %int 2
+         %13 = OpConstantNull %v2int
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+         %14 = OpLoad %7 %u_input
+         %15 = OpImageRead %v4float %14 %13
+         %16 = OpLoad %7 %u_output
+               OpImageWrite %16 %13 %15
+               OpReturn
+               OpFunctionEnd
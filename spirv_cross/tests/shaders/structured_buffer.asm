@@ -0,0 +1,42 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 16
+; Schema: 0
+               OpCapability Shader
+               OpExtension "SPV_GOOGLE_hlsl_functionality1"
+               OpExtension "SPV_GOOGLE_user_type"
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main"
+               OpExecutionMode %main LocalSize 1 1 1
+               OpName %main "main"
+               OpName %Foo "Foo"
+               OpMemberName %Foo 0 "a"
+               OpName %Buf "Buf"
+               OpMemberName %Buf 0 "data"
+               OpName %buf "buf"
+               OpMemberDecorate %Foo 0 Offset 0
+               OpDecorate %_runtimearr_Foo ArrayStride 4
+               OpMemberDecorate %Buf 0 Offset 0
+               OpDecorate %Buf BufferBlock
+               OpDecorate %buf DescriptorSet 0
+               OpDecorate %buf Binding 0
+               OpDecorateString %buf UserTypeGOOGLE "rwstructuredbuffer:<Foo>"
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+       %uint = OpTypeInt 32 0
+        %Foo = OpTypeStruct %uint
+%_runtimearr_Foo = OpTypeRuntimeArray %Foo
+        %Buf = OpTypeStruct %_runtimearr_Foo
+%_ptr_Uniform_Buf = OpTypePointer Uniform %Buf
+        %buf = OpVariable %_ptr_Uniform_Buf Uniform
+        %int = OpTypeInt 32 1
+      %int_0 = OpConstant %int 0
+%_ptr_Uniform_uint = OpTypePointer Uniform %uint
+     %uint_1 = OpConstant %uint 1
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+         %15 = OpAccessChain %_ptr_Uniform_uint %buf %int_0 %int_0 %int_0
+               OpStore %15 %uint_1
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,45 @@
+; SPIR-V
+; Version: 1.3
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 23
+; Schema: 0
+               OpCapability Shader
+               OpCapability GroupNonUniform
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main" %gl_SubgroupSize
+               OpExecutionMode %main LocalSize 64 1 1
+               OpName %main "main"
+               OpName %gl_SubgroupSize "gl_SubgroupSize"
+               OpName %Output "Output"
+               OpMemberName %Output 0 "value"
+               OpName %output "output"
+               OpDecorate %gl_SubgroupSize BuiltIn SubgroupSize
+               OpDecorate %Output Block
+               OpMemberDecorate %Output 0 Offset 0
+               OpDecorate %output DescriptorSet 0
+               OpDecorate %output Binding 0
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+       %uint = OpTypeInt 32 0
+%_ptr_Input_uint = OpTypePointer Input %uint
+%gl_SubgroupSize = OpVariable %_ptr_Input_uint Input
+     %Output = OpTypeStruct %uint
+%_ptr_StorageBuffer_Output = OpTypePointer StorageBuffer %Output
+     %output = OpVariable %_ptr_StorageBuffer_Output StorageBuffer
+        %int = OpTypeInt 32 1
+      %int_0 = OpConstant %int 0
+%_ptr_StorageBuffer_uint = OpTypePointer StorageBuffer %uint
+       %bool = OpTypeBool
+     %uint_3 = OpConstant %uint 3
+     %uint_1 = OpConstant %uint 1
+     %uint_0 = OpConstant %uint 0
+       %main = OpFunction %void None %3
+         %15 = OpLabel
+         %13 = OpLoad %uint %gl_SubgroupSize
+         %18 = OpGroupNonUniformElect %bool %uint_3
+         %21 = OpSelect %uint %18 %uint_1 %uint_0
+         %22 = OpIAdd %uint %13 %21
+         %14 = OpAccessChain %_ptr_StorageBuffer_uint %output %int_0
+               OpStore %14 %22
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,36 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 17
+; Schema: 0
+               OpCapability Shader
+               OpCapability InputAttachment
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Fragment %main "main" %target0
+               OpExecutionMode %main OriginUpperLeft
+               OpName %main "main"
+               OpName %target0 "target0"
+               OpName %u_input "u_input"
+               OpDecorate %target0 Location 0
+               OpDecorate %u_input DescriptorSet 0
+               OpDecorate %u_input Binding 0
+               OpDecorate %u_input InputAttachmentIndex 0
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+%_ptr_Output_v4float = OpTypePointer Output %v4float
+    %target0 = OpVariable %_ptr_Output_v4float Output
+          %9 = OpTypeImage %float SubpassData 0 0 0 2 Unknown
+%_ptr_UniformConstant_9 = OpTypePointer UniformConstant %9
+    %u_input = OpVariable %_ptr_UniformConstant_9 UniformConstant
+        %int = OpTypeInt 32 1
+      %v2int = OpTypeVector %int 2
+         %14 = OpConstantNull %v2int
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+         %15 = OpLoad %9 %u_input
+         %16 = OpImageRead %v4float %15 %14
+               OpStore %target0 %16
+               OpReturn
+               OpFunctionEnd
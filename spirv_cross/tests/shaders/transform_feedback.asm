@@ -0,0 +1,48 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 18
+; Schema: 0
+               OpCapability Shader
+               OpCapability TransformFeedback
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Vertex %main "main" %out_value %captured
+               OpExecutionMode %main Xfb
+               OpName %main "main"
+               OpName %out_value "out_value"
+               OpName %Captured "Captured"
+               OpMemberName %Captured 0 "a"
+               OpMemberName %Captured 1 "b"
+               OpMemberName %Captured 2 "c"
+               OpName %captured "captured"
+               OpDecorate %out_value Location 0
+               OpDecorate %out_value XfbBuffer 0
+               OpDecorate %out_value XfbStride 16
+               OpDecorate %out_value Offset 0
+               OpDecorate %Captured Block
+               OpMemberDecorate %Captured 0 Offset 16
+               OpMemberDecorate %Captured 2 Offset 0
+               OpDecorate %captured Location 1
+               OpDecorate %captured XfbBuffer 1
+               OpDecorate %captured XfbStride 20
+       %void = OpTypeVoid
+          %2 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+%_ptr_Output_v4float = OpTypePointer Output %v4float
+  %out_value = OpVariable %_ptr_Output_v4float Output
+   %Captured = OpTypeStruct %float %float %v4float
+%_ptr_Output_Captured = OpTypePointer Output %Captured
+   %captured = OpVariable %_ptr_Output_Captured Output
+    %float_1 = OpConstant %float 1
+         %13 = OpConstantComposite %v4float %float_1 %float_1 %float_1 %float_1
+        %int = OpTypeInt 32 1
+      %int_0 = OpConstant %int 0
+%_ptr_Output_float = OpTypePointer Output %float
+       %main = OpFunction %void None %2
+         %11 = OpLabel
+               OpStore %out_value %13
+         %17 = OpAccessChain %_ptr_Output_float %captured %int_0
+               OpStore %17 %float_1
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,41 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 22
+; Schema: 0
+               OpCapability Shader
+               OpCapability Float64
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Vertex %main "main" %a_transform %a_ids %a_double %a_weight
+               OpName %main "main"
+               OpName %a_transform "a_transform"
+               OpName %a_ids "a_ids"
+               OpName %a_double "a_double"
+               OpName %a_weight "a_weight"
+               OpDecorate %a_transform Location 0
+               OpDecorate %a_ids Location 3
+               OpDecorate %a_double Location 5
+               OpDecorate %a_weight Location 7
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v3float = OpTypeVector %float 3
+%mat3v3float = OpTypeMatrix %v3float 3
+%_ptr_Input_mat3v3float = OpTypePointer Input %mat3v3float
+%a_transform = OpVariable %_ptr_Input_mat3v3float Input
+       %uint = OpTypeInt 32 0
+     %v2uint = OpTypeVector %uint 2
+     %uint_2 = OpConstant %uint 2
+%_arr_v2uint_uint_2 = OpTypeArray %v2uint %uint_2
+%_ptr_Input__arr_v2uint_uint_2 = OpTypePointer Input %_arr_v2uint_uint_2
+      %a_ids = OpVariable %_ptr_Input__arr_v2uint_uint_2 Input
+     %double = OpTypeFloat 64
+   %v4double = OpTypeVector %double 4
+%_ptr_Input_v4double = OpTypePointer Input %v4double
+   %a_double = OpVariable %_ptr_Input_v4double Input
+%_ptr_Input_float = OpTypePointer Input %float
+   %a_weight = OpVariable %_ptr_Input_float Input
+       %main = OpFunction %void None %3
+          %4 = OpLabel
+               OpReturn
+               OpFunctionEnd
@@ -1,4 +1,6 @@
-use spirv_cross::{hlsl as lang, spirv};
+use spirv_cross::{glsl, hlsl as lang, spirv};
+
+use std::collections::HashMap;
 
 mod common;
 use crate::common::words_from_bytes;
@@ -18,6 +20,45 @@ fn ast_gets_multiple_entry_points() {
     assert!(entry_points.iter().any(|e| e.name == "entry_2"));
 }
 
+#[test]
+fn ast_visits_entry_points() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/multiple_entry_points.cl.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let mut entry_points = Vec::new();
+    ast.visit_entry_points(|entry_point| entry_points.push(entry_point.to_entry_point()))
+        .unwrap();
+    assert_eq!(entry_points, ast.get_entry_points().unwrap());
+}
+
+#[test]
+fn ast_gets_current_entry_point() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/multiple_entry_points.cl.spv"
+    )));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let entry_points = ast.get_entry_points().unwrap();
+    assert_eq!(
+        ast.get_current_entry_point().unwrap(),
+        (
+            entry_points[0].name.clone(),
+            entry_points[0].execution_model
+        )
+    );
+
+    let options = lang::CompilerOptions::builder()
+        .entry_point("entry_2", spirv::ExecutionModel::Kernel)
+        .build()
+        .unwrap();
+    ast.set_compiler_options(&options).unwrap();
+    assert_eq!(
+        ast.get_current_entry_point().unwrap(),
+        (String::from("entry_2"), spirv::ExecutionModel::Kernel)
+    );
+}
+
 #[test]
 fn ast_gets_shader_resources() {
     let module =
@@ -58,277 +99,2183 @@ fn ast_gets_shader_resources() {
 }
 
 #[test]
-fn ast_gets_decoration() {
+fn ast_gets_builtin_shader_resources() {
     let module =
-        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
-    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
-
-    let stage_inputs = ast.get_shader_resources().unwrap().stage_inputs;
-    let decoration = ast
-        .get_decoration(stage_inputs[0].id, spirv::Decoration::DescriptorSet)
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/array.vert.spv")));
+    let shader_resources = spirv::Ast::<lang::Target>::parse(&module)
+        .unwrap()
+        .get_shader_resources()
         .unwrap();
-    assert_eq!(decoration, 0);
+
+    assert_eq!(shader_resources.builtin_inputs.len(), 1);
+    assert_eq!(
+        shader_resources.builtin_inputs[0].builtin,
+        spirv::BuiltIn::VertexIndex
+    );
+    assert!(shader_resources
+        .builtin_outputs
+        .iter()
+        .any(|output| output.builtin == spirv::BuiltIn::Position));
+    assert!(shader_resources
+        .stage_outputs
+        .iter()
+        .all(|output| output.name != "gl_PerVertex"));
 }
 
 #[test]
-fn ast_sets_decoration() {
+fn diffs_shader_resources() {
     let module =
         spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let old_ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let old = old_ast.get_shader_resources().unwrap();
+    let a_normal = old
+        .stage_inputs
+        .iter()
+        .find(|stage_input| stage_input.name == "a_normal")
+        .unwrap();
+    assert!(spirv::diff_resources(&old_ast, &old, &old_ast, &old)
+        .unwrap()
+        .is_empty());
+
     let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    ast.set_decoration(old.uniform_buffers[0].id, spirv::Decoration::Binding, 3)
+        .unwrap();
+    ast.set_decoration(a_normal.id, spirv::Decoration::Location, 2)
+        .unwrap();
+    let new = ast.get_shader_resources().unwrap();
+    let diff = spirv::diff_resources(&old_ast, &old, &ast, &new).unwrap();
+    assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.retyped.is_empty());
+    assert_eq!(diff.rebound.len(), 2);
+    assert_eq!(
+        diff.rebound[0].new_kind,
+        spirv::ResourceKind::UniformBuffers
+    );
+    assert_eq!(diff.rebound[0].new.name, old.uniform_buffers[0].name);
+    assert_eq!(diff.rebound[1].new.name, "a_normal");
 
-    let stage_inputs = ast.get_shader_resources().unwrap().stage_inputs;
-    let updated_value = 3;
-    ast.set_decoration(
-        stage_inputs[0].id,
-        spirv::Decoration::DescriptorSet,
-        updated_value,
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let uniform_buffer = &old.uniform_buffers[0];
+    let offset = ast
+        .get_member_decoration(uniform_buffer.base_type_id, 0, spirv::Decoration::Offset)
+        .unwrap();
+    ast.set_member_decoration(
+        uniform_buffer.base_type_id,
+        0,
+        spirv::Decoration::Offset,
+        offset + 16,
     )
     .unwrap();
-    assert_eq!(
-        ast.get_decoration(stage_inputs[0].id, spirv::Decoration::DescriptorSet)
-            .unwrap(),
-        updated_value
-    );
-}
+    let diff = spirv::diff_resources(&old_ast, &old, &ast, &old).unwrap();
+    assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.rebound.is_empty());
+    assert_eq!(diff.retyped.len(), 1);
+    assert_eq!(diff.retyped[0].new.name, uniform_buffer.name);
 
-#[test]
-fn ast_gets_type_member_types_and_array() {
     let module =
-        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/two_ubo.vert.spv")));
     let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
-
-    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
-
-    let is_struct = match ast.get_type(uniform_buffers[0].base_type_id).unwrap() {
-        spirv::Type::Struct {
-            member_types,
-            array,
-            array_size_literal
-        } => {
-            assert_eq!(member_types.len(), 2);
-            assert_eq!(array.len(), 0);
-            assert_eq!(array_size_literal.len(), 0);
-            true
-        }
-        _ => false,
-    };
-
-    assert!(is_struct);
+    let new = ast.get_shader_resources().unwrap();
+    let diff = spirv::diff_resources(&old_ast, &old, &ast, &new).unwrap();
+    assert_eq!(diff.added.len(), 2);
+    assert!(diff
+        .added
+        .iter()
+        .all(|(kind, _)| *kind == spirv::ResourceKind::UniformBuffers));
+    assert_eq!(diff.removed.len(), 4);
 }
 
 #[test]
-fn ast_gets_array_dimensions() {
-    let module =
-        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/array.vert.spv")));
+fn ast_gets_shader_resources_for_entry_point() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/vs_and_fs.asm.spv"
+    )));
     let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
 
-    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
+    let vertex_resources = ast
+        .get_shader_resources_for_entry_point("main_vs", spirv::ExecutionModel::Vertex)
+        .unwrap();
+    assert_eq!(vertex_resources.stage_outputs.len(), 0);
+    assert!(vertex_resources
+        .builtin_outputs
+        .iter()
+        .any(|output| output.builtin == spirv::BuiltIn::Position));
 
-    let is_struct = match ast.get_type(uniform_buffers[0].base_type_id).unwrap() {
-        spirv::Type::Struct { member_types, .. } => {
-            assert_eq!(member_types.len(), 3);
-            let is_float = match ast.get_type(member_types[2]).unwrap() {
-                spirv::Type::Float {
-                    vecsize,
-                    columns,
-                    array,
-                    array_size_literal
-                } => {
-                    assert_eq!(vecsize, 3);
-                    assert_eq!(columns, 1);
-                    assert_eq!(array.len(), 1);
-                    assert_eq!(array_size_literal.len(), 1);
-                    assert_eq!(array[0], 3);
-                    assert_eq!(array_size_literal[0], true);
-                    true
-                }
-                _ => false,
-            };
-            assert!(is_float);
-            true
-        }
-        _ => false,
-    };
+    let fragment_resources = ast
+        .get_shader_resources_for_entry_point("main_fs", spirv::ExecutionModel::Fragment)
+        .unwrap();
+    assert_eq!(fragment_resources.stage_outputs.len(), 1);
+    assert_eq!(fragment_resources.stage_outputs[0].name, "color");
+    assert_eq!(fragment_resources.builtin_outputs.len(), 0);
 
-    assert!(is_struct);
+    assert!(matches!(
+        ast.get_shader_resources_for_entry_point("main_cs", spirv::ExecutionModel::GlCompute),
+        Err(spirv_cross::ErrorCode::InvalidOptions(_))
+    ));
 }
 
 #[test]
-fn ast_gets_declared_struct_size_and_struct_member_size() {
-    let module =
-        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+fn ast_gets_specialization_constants_for_entry_point() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/entry_point_spec_constants.asm.spv"
+    )));
     let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
-    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
-    let mat4_size = 4 * 16;
-    let float_size = 4;
-    assert_eq!(
-        ast.get_declared_struct_size(uniform_buffers[0].base_type_id)
-            .unwrap(),
-        mat4_size + float_size
-    );
-    assert_eq!(
-        ast.get_declared_struct_member_size(uniform_buffers[0].base_type_id, 0)
-            .unwrap(),
-        mat4_size
-    );
+    let constant_ids = |name, execution_model| {
+        ast.get_specialization_constants_for_entry_point(name, execution_model)
+            .unwrap()
+            .into_iter()
+            .map(|constant| constant.constant_id)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(ast.get_specialization_constants().unwrap().len(), 3);
+    // `scale` is read by a function `main_vs` calls
+    assert_eq!(constant_ids("main_vs", spirv::ExecutionModel::Vertex), [0]);
+    // `tint` is an operand of the `OpSpecConstantOp` `main_fs` writes
     assert_eq!(
-        ast.get_declared_struct_member_size(uniform_buffers[0].base_type_id, 1)
-            .unwrap(),
-        float_size
+        constant_ids("main_fs", spirv::ExecutionModel::Fragment),
+        [1]
     );
+    assert!(matches!(
+        ast.get_specialization_constants_for_entry_point("main_fs", spirv::ExecutionModel::Vertex),
+        Err(spirv_cross::ErrorCode::InvalidOptions(_))
+    ));
 }
 
 #[test]
-fn ast_gets_member_name() {
-    let module =
-        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
-    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
-
-    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
-
+fn ast_gets_work_group_size_specialization_constants_for_entry_point() {
+    let comp = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/workgroup.comp.spv"
+    )));
+    let comp_ast = spirv::Ast::<lang::Target>::parse(&comp).unwrap();
+    let work_group_size = comp_ast
+        .get_work_group_size_specialization_constants()
+        .unwrap();
+    let mut constants = comp_ast
+        .get_specialization_constants_for_entry_point("main", spirv::ExecutionModel::GlCompute)
+        .unwrap();
+    constants.sort_by_key(|constant| constant.id);
     assert_eq!(
-        ast.get_member_name(uniform_buffers[0].base_type_id, 0)
-            .unwrap(),
-        "u_model_view_projection"
+        constants,
+        [work_group_size.x, work_group_size.y, work_group_size.z]
     );
 }
 
 #[test]
-fn ast_gets_member_decoration() {
-    let module =
-        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+fn ast_gets_shader_resources_with_activity() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/vs_and_fs.asm.spv"
+    )));
     let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
 
-    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
-
-    assert_eq!(
-        ast.get_member_decoration(
-            uniform_buffers[0].base_type_id,
-            1,
-            spirv::Decoration::Offset
-        )
-        .unwrap(),
-        64
-    );
+    let color = |entry_point_name, execution_model| {
+        ast.get_shader_resources_with_activity(entry_point_name, execution_model)
+            .unwrap()
+            .into_iter()
+            .find(|activity| activity.resource.name == "color")
+            .unwrap()
+    };
+    let fragment = color("main_fs", spirv::ExecutionModel::Fragment);
+    assert_eq!(fragment.kind, spirv::ResourceKind::StageOutputs);
+    assert!(fragment.is_active_in_entry_point);
+    assert!(!color("main_vs", spirv::ExecutionModel::Vertex).is_active_in_entry_point);
 }
 
 #[test]
-fn ast_sets_member_decoration() {
+fn ast_gets_interface_variable() {
     let module =
         spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
     let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
 
-    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
+    let stage_inputs = ast.get_shader_resources().unwrap().stage_inputs;
+    let a_normal = stage_inputs
+        .iter()
+        .find(|stage_input| stage_input.name == "a_normal")
+        .unwrap();
 
-    let new_offset = 128;
+    let variable = ast.get_interface_variable(a_normal).unwrap();
+    assert_eq!(variable.id, a_normal.id);
+    assert_eq!(variable.location, Some(1));
+    assert_eq!(variable.component, None);
+    assert_eq!(variable.index, 0);
+    assert_eq!(variable.vecsize, 3);
+    assert!(matches!(variable.base_type, spirv::Type::Float { .. }));
+    assert!(!variable.flat);
+    assert!(!variable.no_perspective);
 
-    ast.set_member_decoration(
-        uniform_buffers[0].base_type_id,
-        1,
-        spirv::Decoration::Offset,
-        new_offset,
-    )
-    .unwrap();
+    ast.set_decoration(a_normal.id, spirv::Decoration::Flat, 1)
+        .unwrap();
+    assert!(ast.get_interface_variable(a_normal).unwrap().flat);
 
+    // Built-in blocks have no location
+    let per_vertex = &ast.get_shader_resources().unwrap().builtin_outputs[0].resource;
     assert_eq!(
-        ast.get_member_decoration(
-            uniform_buffers[0].base_type_id,
-            1,
-            spirv::Decoration::Offset
-        )
-        .unwrap(),
-        new_offset
+        ast.get_interface_variable(per_vertex).unwrap().location,
+        None
     );
 }
 
 #[test]
-fn ast_gets_specialization_constants() {
-    let comp = spirv::Module::from_words(words_from_bytes(include_bytes!(
-        "shaders/specialization.comp.spv"
+fn ast_gets_dual_source_blending_indices() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/dual_source.asm.spv"
     )));
-    let comp_ast = spirv::Ast::<lang::Target>::parse(&comp).unwrap();
-    let specialization_constants = comp_ast.get_specialization_constants().unwrap();
-    assert_eq!(specialization_constants[0].constant_id, 10);
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let mut outputs = ast
+        .get_shader_resources()
+        .unwrap()
+        .stage_outputs
+        .iter()
+        .map(|resource| ast.get_interface_variable(resource).unwrap())
+        .map(|variable| (variable.name, variable.location, variable.index))
+        .collect::<Vec<_>>();
+    outputs.sort();
+    assert_eq!(
+        outputs,
+        [
+            (String::from("color0"), Some(0), 0),
+            (String::from("color1"), Some(0), 1)
+        ]
+    );
 }
 
 #[test]
-fn ast_gets_work_group_size_specialization_constants() {
-    let comp = spirv::Module::from_words(words_from_bytes(include_bytes!(
-        "shaders/workgroup.comp.spv"
+fn ast_gets_interface_block_members() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/interface_block.asm.spv"
     )));
-    let comp_ast = spirv::Ast::<lang::Target>::parse(&comp).unwrap();
-    let work_group_size = comp_ast
-        .get_work_group_size_specialization_constants()
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let stage_outputs = ast.get_shader_resources().unwrap().stage_outputs;
+    let vs_out = stage_outputs
+        .iter()
+        .find(|stage_output| stage_output.id == 12)
         .unwrap();
+
+    let members = ast.get_interface_block_members(vs_out).unwrap();
     assert_eq!(
-        work_group_size,
-        spirv::WorkGroupSizeSpecializationConstants {
-            x: spirv::SpecializationConstant {
-                id: 7,
-                constant_id: 5,
-            },
-            y: spirv::SpecializationConstant {
-                id: 8,
-                constant_id: 10,
-            },
-            z: spirv::SpecializationConstant {
-                id: 9,
-                constant_id: 15,
-            },
-        }
+        members
+            .iter()
+            .map(|member| (member.name.as_str(), member.location, member.flat))
+            .collect::<Vec<_>>(),
+        [
+            ("normal", Some(2), false),
+            ("transform", Some(3), false),
+            ("id", Some(8), true),
+            ("uv", Some(9), false)
+        ]
     );
+    assert_eq!(members[0].vecsize, 3);
+    assert!(matches!(members[2].base_type, spirv::Type::Int { .. }));
+    assert!(members.iter().all(|member| !member.built_in));
+    assert!(members.iter().all(|member| member.component.is_none()));
 }
 
 #[test]
-fn ast_gets_active_buffer_ranges() {
+fn ast_gets_built_in_block_members_without_locations() {
     let module =
-        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/two_ubo.vert.spv")));
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
     let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
 
-    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
-    assert_eq!(uniform_buffers.len(), 2);
+    let builtin_outputs = ast.get_shader_resources().unwrap().builtin_outputs;
+    let per_vertex = builtin_outputs
+        .iter()
+        .find(|output| output.builtin == spirv::BuiltIn::Position)
+        .map(|output| &output.resource)
+        .unwrap();
+    assert_eq!(per_vertex.name, "gl_PerVertex");
 
-    let ubo1 = ast.get_active_buffer_ranges(uniform_buffers[0].id).unwrap();
+    let members = ast.get_interface_block_members(per_vertex).unwrap();
     assert_eq!(
-        ubo1,
+        members
+            .iter()
+            .map(|member| (member.name.as_str(), member.built_in, member.location))
+            .collect::<Vec<_>>(),
+        [("gl_Position", true, None), ("gl_PointSize", true, None)]
+    );
+    assert!(members.iter().all(|member| member.component.is_none()));
+}
+
+#[test]
+fn ast_assigns_stage_locations() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/stage_locations.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let (a_transform, a_uv, v_normal, v_color) = (16, 17, 18, 19);
+    assert!(!ast
+        .has_decoration(a_uv, spirv::Decoration::Location)
+        .unwrap());
+
+    let locations = ast.assign_stage_locations().unwrap();
+    // `a_position` keeps location 0 and `a_transform` takes one location per column
+    assert_eq!(
+        locations.inputs.into_iter().collect::<Vec<_>>(),
+        [(a_transform, 1), (a_uv, 5)]
+    );
+    assert_eq!(
+        locations.outputs.into_iter().collect::<Vec<_>>(),
+        [(v_normal, 1), (v_color, 0)]
+    );
+    assert!(ast
+        .has_decoration(a_uv, spirv::Decoration::Location)
+        .unwrap());
+    assert_eq!(
+        ast.get_decoration(a_uv, spirv::Decoration::Location)
+            .unwrap(),
+        5
+    );
+
+    assert_eq!(
+        ast.assign_stage_locations().unwrap(),
+        spirv::StageLocations::default()
+    );
+}
+
+#[test]
+fn ast_validates_buffer_layouts() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/buffer_layout.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let block = 12;
+
+    assert_eq!(
+        ast.validate_buffer_layout(block, spirv::BufferLayout::Std140)
+            .unwrap(),
+        None
+    );
+    // The float array keeps the 16 byte stride std140 requires
+    assert_eq!(
+        ast.validate_buffer_layout(block, spirv::BufferLayout::Std430)
+            .unwrap(),
+        Some(spirv::LayoutViolation {
+            struct_type_id: block,
+            member_index: 4,
+            member_name: String::from("arr"),
+            kind: spirv::LayoutViolationKind::ArrayStride {
+                stride: 16,
+                expected: 4,
+            },
+        })
+    );
+    assert_eq!(
+        ast.validate_buffer_layout(block, spirv::BufferLayout::Scalar)
+            .unwrap(),
+        Some(spirv::LayoutViolation {
+            struct_type_id: block,
+            member_index: 3,
+            member_name: String::from("m"),
+            kind: spirv::LayoutViolationKind::MatrixStride {
+                stride: 16,
+                expected: 12,
+            },
+        })
+    );
+}
+
+#[test]
+fn ast_gets_reflection_hash() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let hash = ast.get_reflection_hash().unwrap();
+    assert_eq!(
+        spirv::Ast::<lang::Target>::parse(&module)
+            .unwrap()
+            .get_reflection_hash()
+            .unwrap(),
+        hash
+    );
+
+    let two_ubo =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/two_ubo.vert.spv")));
+    assert_ne!(
+        spirv::Ast::<lang::Target>::parse(&two_ubo)
+            .unwrap()
+            .get_reflection_hash()
+            .unwrap(),
+        hash
+    );
+
+    let uniform_buffer = ast.get_shader_resources().unwrap().uniform_buffers[0].id;
+    ast.set_decoration(uniform_buffer, spirv::Decoration::Binding, 3)
+        .unwrap();
+    let rebound_hash = ast.get_reflection_hash().unwrap();
+    assert_ne!(rebound_hash, hash);
+
+    ast.set_compiler_options(
+        &lang::CompilerOptions::builder()
+            .shader_model(lang::ShaderModel::V6_0)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_ne!(ast.get_reflection_hash().unwrap(), rebound_hash);
+
+    // Changes the value of the first constant, which reflection does not report
+    let mut words = words_from_bytes(include_bytes!("shaders/simple.vert.spv")).to_vec();
+    let mut offset = 5;
+    while words[offset] & 0xffff != 43 {
+        offset += (words[offset] >> 16) as usize;
+    }
+    words[offset + 3] ^= 1;
+    assert_ne!(
+        spirv::Ast::<lang::Target>::parse(&spirv::Module::from_words(&words))
+            .unwrap()
+            .get_reflection_hash()
+            .unwrap(),
+        hash
+    );
+}
+
+#[test]
+fn ast_gets_reflection_hash_of_modules_with_decoration_groups() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/decoration_group.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    assert!(ast.to_spirv_words().is_err());
+    let hash = ast.get_reflection_hash().unwrap();
+    assert_ne!(
+        spirv::Ast::<glsl::Target>::parse(&module)
+            .unwrap()
+            .get_reflection_hash()
+            .unwrap(),
+        hash
+    );
+
+    // a and b take their descriptor set from the group
+    let b = ast
+        .get_shader_resources()
+        .unwrap()
+        .storage_buffers
+        .iter()
+        .find(|storage_buffer| storage_buffer.name == "b")
+        .unwrap()
+        .id;
+    assert_eq!(
+        ast.get_decoration(b, spirv::Decoration::DescriptorSet)
+            .unwrap(),
+        0
+    );
+    ast.set_decoration(b, spirv::Decoration::DescriptorSet, 1)
+        .unwrap();
+    assert_ne!(ast.get_reflection_hash().unwrap(), hash);
+}
+
+#[test]
+fn ast_serializes_edited_spirv_words() {
+    let words = words_from_bytes(include_bytes!("shaders/simple.vert.spv"));
+    let module = spirv::Module::from_words(words);
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    assert_eq!(ast.to_spirv_words().unwrap().len(), words.len());
+
+    let resources = ast.get_shader_resources().unwrap();
+    let uniform_buffer = resources.uniform_buffers[0].id;
+    let a_normal = resources
+        .stage_inputs
+        .iter()
+        .find(|stage_input| stage_input.name == "a_normal")
+        .unwrap()
+        .id;
+    ast.set_decoration(uniform_buffer, spirv::Decoration::Binding, 3)
+        .unwrap();
+    ast.set_decoration(uniform_buffer, spirv::Decoration::DescriptorSet, 1)
+        .unwrap();
+    ast.set_name(a_normal, "normal").unwrap();
+    ast.unset_decoration(a_normal, spirv::Decoration::Location)
+        .unwrap();
+
+    let words = ast.to_spirv_words().unwrap();
+    let exported = spirv::Module::from_words(&words);
+    let mut reparsed = spirv::Ast::<lang::Target>::parse(&exported).unwrap();
+    assert_eq!(
+        reparsed
+            .get_decoration(uniform_buffer, spirv::Decoration::Binding)
+            .unwrap(),
+        3
+    );
+    assert_eq!(reparsed.get_name(a_normal).unwrap(), "normal");
+    assert!(!reparsed
+        .has_decoration(a_normal, spirv::Decoration::Location)
+        .unwrap());
+    assert_eq!(
+        reparsed.get_reflection_hash().unwrap(),
+        ast.get_reflection_hash().unwrap()
+    );
+}
+
+#[test]
+fn ast_gets_basic_types() {
+    use spirv::{BasicType, ScalarKind};
+
+    let basic_type = |kind, width| Some(BasicType { kind, width });
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/vertex_attributes.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let inputs = ast.get_shader_resources().unwrap().stage_inputs;
+    let input_types = inputs
+        .iter()
+        .map(|input| {
+            let basic_type = ast.get_basic_type(input.base_type_id).unwrap();
+            (input.name.as_str(), basic_type)
+        })
+        .collect::<HashMap<_, _>>();
+    assert_eq!(
+        input_types["a_transform"],
+        basic_type(ScalarKind::Float, 32)
+    );
+    assert_eq!(input_types["a_ids"], basic_type(ScalarKind::UInt, 32));
+    assert_eq!(input_types["a_double"], basic_type(ScalarKind::Float, 64));
+
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/interface_block.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let vertex_out = ast.get_shader_resources().unwrap().stage_outputs[0].base_type_id;
+    assert_eq!(ast.get_basic_type(vertex_out).unwrap(), None);
+    assert_eq!(ast.get_common_basic_type(vertex_out).unwrap(), None);
+    let member_types = match ast.get_type(vertex_out).unwrap() {
+        spirv::Type::Struct { member_types, .. } => member_types,
+        ty => panic!("unexpected type {:?}", ty),
+    };
+    assert_eq!(
+        ast.get_common_basic_type(member_types[2]).unwrap(),
+        basic_type(ScalarKind::Int, 32)
+    );
+
+    // A struct holding a runtime array of structs
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/structured_buffer.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let buf = ast.get_shader_resources().unwrap().storage_buffers[0].base_type_id;
+    assert_eq!(
+        ast.get_common_basic_type(buf).unwrap(),
+        basic_type(ScalarKind::UInt, 32)
+    );
+}
+
+#[test]
+fn ast_gets_image_access() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/image_access.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let accesses = ast
+        .get_shader_resources()
+        .unwrap()
+        .storage_images
+        .into_iter()
+        .map(|image| (image.name, ast.get_image_access(image.id).unwrap()))
+        .collect::<HashMap<_, _>>();
+    assert_eq!(accesses.len(), 4);
+    assert_eq!(accesses["u_read"], spirv::ImageAccess::ReadOnly);
+    assert_eq!(accesses["u_written"], spirv::ImageAccess::WriteOnly);
+    // Accessed through a function parameter
+    assert_eq!(accesses["u_read_written"], spirv::ImageAccess::ReadWrite);
+    // Decorated, but never accessed
+    assert_eq!(accesses["u_non_writable"], spirv::ImageAccess::ReadOnly);
+
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/storage_image.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let resources = ast.get_shader_resources().unwrap();
+    for image in &resources.storage_images {
+        let expected = match image.name.as_str() {
+            // Decorated NonReadable
+            "u_output" => spirv::ImageAccess::WriteOnly,
+            _ => spirv::ImageAccess::ReadOnly,
+        };
+        assert_eq!(ast.get_image_access(image.id).unwrap(), expected);
+    }
+}
+
+#[test]
+fn ast_gets_image_sampler_pairs() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/image_sampler_pairs.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let resources = ast.get_shader_resources().unwrap();
+    let names = resources
+        .separate_images
+        .iter()
+        .chain(&resources.separate_samplers)
+        .map(|resource| (resource.id, resource.name.as_str()))
+        .collect::<HashMap<_, _>>();
+    let pairs = ast
+        .get_image_sampler_pairs()
+        .unwrap()
+        .into_iter()
+        .map(|pair| (names[&pair.image_id], names[&pair.sampler_id]))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pairs,
+        [
+            ("tex_a", "samp_linear"),
+            // Combined in a function taking the image and sampler as arguments
+            ("tex_b", "samp_point"),
+        ]
+    );
+}
+
+#[test]
+fn ast_gets_descriptor_sets() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/descriptor_arrays.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let sets = ast.get_descriptor_sets().unwrap();
+
+    assert_eq!(sets.len(), 2);
+    assert_eq!(sets[0].set, 0);
+    let bindings = sets[0]
+        .bindings
+        .iter()
+        .map(|binding| {
+            (
+                binding.binding,
+                binding.kind,
+                binding.resource.name.as_str(),
+                binding.count,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        bindings,
+        [
+            (
+                0,
+                spirv::ResourceKind::SeparateSamplers,
+                "u_sampler",
+                spirv::DescriptorCount::Fixed(1)
+            ),
+            (
+                1,
+                spirv::ResourceKind::SeparateImages,
+                "u_textures",
+                spirv::DescriptorCount::Fixed(4)
+            ),
+        ]
+    );
+    assert_eq!(sets[1].set, 1);
+    assert_eq!(sets[1].bindings.len(), 1);
+    assert_eq!(sets[1].bindings[0].resource.name, "u_bindless");
+    assert_eq!(
+        sets[1].bindings[0].count,
+        spirv::DescriptorCount::RuntimeSized
+    );
+    // None of the resources are used by the entry point
+    assert!(sets
+        .iter()
+        .flat_map(|set| set.bindings.iter())
+        .all(|binding| binding.stages.is_empty()));
+
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/sampler.frag.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let sets = ast.get_descriptor_sets().unwrap();
+    assert_eq!(sets.len(), 1);
+    assert_eq!(sets[0].bindings.len(), 2);
+    for binding in &sets[0].bindings {
+        assert_eq!(binding.stages, [spirv::ExecutionModel::Fragment]);
+    }
+}
+
+#[test]
+fn ast_merges_descriptor_sets() {
+    let parse = |bytes: &[u8]| {
+        let module = spirv::Module::from_words(words_from_bytes(bytes));
+        spirv::Ast::<lang::Target>::parse(&module).unwrap()
+    };
+    let sampler = parse(include_bytes!("shaders/sampler.frag.spv"));
+    let no_resources = parse(include_bytes!("shaders/struct.frag.spv"));
+    let sets = spirv::Ast::merge_descriptor_sets(&[&no_resources, &sampler]).unwrap();
+
+    assert_eq!(sets.len(), 1);
+    assert_eq!(sets[0].set, 0);
+    let bindings = sets[0]
+        .bindings
+        .iter()
+        .map(|binding| (binding.binding, binding.kind, binding.usage_mask.as_slice()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        bindings,
+        [
+            (0, spirv::ResourceKind::SeparateImages, &[false, true][..]),
+            (1, spirv::ResourceKind::SeparateSamplers, &[false, true][..]),
+        ]
+    );
+    for binding in &sets[0].bindings {
+        assert_eq!(binding.count, spirv::DescriptorCount::Fixed(1));
+        assert_eq!(binding.stages, [spirv::ExecutionModel::Fragment]);
+    }
+
+    // Binding 0 of set 0 is a sampler here but an image in the other module
+    let arrays = parse(include_bytes!("shaders/descriptor_arrays.asm.spv"));
+    assert!(spirv::Ast::merge_descriptor_sets(&[&sampler, &arrays]).is_err());
+}
+
+#[cfg(feature = "shaderc")]
+#[test]
+fn module_compiles_glsl_and_hlsl_source() {
+    let module = spirv::Module::from_glsl_source(
+        include_str!("shaders/sampler.frag"),
+        spirv::ExecutionModel::Fragment,
+    )
+    .unwrap();
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let resources = ast.get_shader_resources().unwrap();
+    assert_eq!(resources.separate_images[0].name, "u_texture");
+    assert_eq!(resources.separate_samplers[0].name, "u_sampler");
+
+    let module = spirv::Module::from_hlsl_source(
+        "float4 ps_main() : SV_Target { return float4(1.0, 0.0, 0.0, 1.0); }",
+        spirv::ExecutionModel::Fragment,
+        "ps_main",
+    )
+    .unwrap();
+    let entry_points = spirv::Ast::<lang::Target>::parse(&module)
+        .unwrap()
+        .get_entry_points()
+        .unwrap();
+    assert_eq!(entry_points[0].name, "ps_main");
+
+    match spirv::Module::from_glsl_source("void main() {", spirv::ExecutionModel::Vertex) {
+        Err(spirv_cross::ErrorCode::CompilationError(_)) => {}
+        _ => panic!("expected a compilation error"),
+    }
+}
+
+#[test]
+fn ast_visits_shader_resources() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/sampler.frag.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let resources = ast.get_shader_resources().unwrap();
+
+    let mut visited = Vec::new();
+    ast.visit_shader_resources(spirv::ResourceKinds::all(), |kind, resource| {
+        visited.push((kind, resource.to_resource()))
+    })
+    .unwrap();
+    let expected = resources
+        .iter()
+        .map(|(kind, resource)| (kind, resource.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(visited, expected);
+
+    let mut names = Vec::new();
+    ast.visit_shader_resources(spirv::ResourceKind::SeparateSamplers, |kind, resource| {
+        assert_eq!(kind, spirv::ResourceKind::SeparateSamplers);
+        names.push(resource.name.to_owned());
+    })
+    .unwrap();
+    assert_eq!(names, ["u_sampler"]);
+}
+
+#[test]
+fn ast_evaluates_specialization_constant_expressions() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/spec_constant_op.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let (count, size, flag, chosen, offset, half_offset, float_1) = (8, 10, 11, 13, 14, 16, 18);
+
+    let constant_ids = |id| {
+        ast.get_specialization_constant_dependencies(id)
+            .unwrap()
+            .into_iter()
+            .map(|constant| constant.constant_id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(constant_ids(count), [3]);
+    assert_eq!(constant_ids(size), [3]);
+    assert_eq!(constant_ids(chosen), [3, 5]);
+    assert!(constant_ids(9).is_empty());
+    assert!(ast
+        .get_specialization_constant_dependencies(float_1)
+        .is_err());
+
+    let evaluate =
+        |id, values: &HashMap<u32, u64>| ast.evaluate_specialization_constant(id, values).unwrap();
+    let defaults = HashMap::new();
+    assert_eq!(evaluate(size, &defaults), 8);
+    assert_eq!(evaluate(flag, &defaults), 1);
+    assert_eq!(evaluate(chosen, &defaults), 8);
+    assert_eq!(evaluate(half_offset, &defaults), u64::from(-1i32 as u32));
+
+    let values = [(3, 5), (5, 0), (7, 9)].iter().copied().collect();
+    assert_eq!(evaluate(size, &values), 20);
+    assert_eq!(evaluate(chosen, &values), 1);
+    assert_eq!(evaluate(offset, &values), 9);
+    assert_eq!(evaluate(half_offset, &values), 4);
+    assert!(ast
+        .evaluate_specialization_constant(float_1, &values)
+        .is_err());
+}
+
+#[cfg(feature = "codegen")]
+#[test]
+fn ast_generates_rust_structs() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/buffer_layout.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let block = ast.get_shader_resources().unwrap().uniform_buffers[0].base_type_id;
+
+    assert_eq!(
+        ast.generate_rust_structs(block).unwrap(),
+        "\
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct BlockArrElement {
+    pub value: f32,
+    pub _pad0: [u8; 12],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Block {
+    pub a: f32,
+    pub _pad0: [u8; 12],
+    pub b: [f32; 3],
+    pub c: f32,
+    pub m: [[f32; 4]; 3],
+    pub arr: [BlockArrElement; 2],
+    pub d: [f32; 2],
+}
+"
+    );
+}
+
+#[test]
+fn ast_gets_vertex_attributes() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/vertex_attributes.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let attributes = ast
+        .get_vertex_attributes()
+        .unwrap()
+        .into_iter()
+        .map(|a| format!("{} {}: {}", a.location, a.name, a.format))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        attributes,
+        [
+            "0 a_transform: 3 x f32",
+            "1 a_transform: 3 x f32",
+            "2 a_transform: 3 x f32",
+            "3 a_ids: 2 x u32",
+            "4 a_ids: 2 x u32",
+            "5 a_double: 4 x f64",
+            "7 a_weight: 1 x f32",
+        ]
+    );
+}
+
+#[test]
+fn ast_gets_descriptor_count() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/descriptor_arrays.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let resources = ast.get_shader_resources().unwrap();
+
+    let descriptor_count = |name: &str| {
+        let resource = resources
+            .separate_images
+            .iter()
+            .chain(resources.separate_samplers.iter())
+            .find(|resource| resource.name == name)
+            .unwrap();
+        ast.get_descriptor_count(resource.id).unwrap()
+    };
+
+    assert_eq!(
+        descriptor_count("u_sampler"),
+        spirv::DescriptorCount::Fixed(1)
+    );
+    assert_eq!(
+        descriptor_count("u_textures"),
+        spirv::DescriptorCount::Fixed(4)
+    );
+    assert_eq!(
+        descriptor_count("u_bindless"),
+        spirv::DescriptorCount::RuntimeSized
+    );
+}
+
+#[test]
+fn ast_gets_decoration() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let stage_inputs = ast.get_shader_resources().unwrap().stage_inputs;
+    let decoration = ast
+        .get_decoration(stage_inputs[0].id, spirv::Decoration::DescriptorSet)
+        .unwrap();
+    assert_eq!(decoration, 0);
+}
+
+#[test]
+fn ast_gets_decorations_in_bulk() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/sampler.frag.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let resources = ast.get_shader_resources().unwrap();
+    let ids = [
+        resources.separate_images[0].id,
+        resources.separate_samplers[0].id,
+    ];
+    let decorations = [
+        spirv::Decoration::DescriptorSet,
+        spirv::Decoration::Binding,
+        spirv::Decoration::Location,
+    ];
+    assert_eq!(
+        ast.get_decorations_bulk(&ids, &decorations).unwrap(),
+        [Some(0), Some(0), None, Some(0), Some(1), None]
+    );
+    let none = ast.get_decorations_bulk(&[], &decorations).unwrap();
+    assert!(none.is_empty());
+}
+
+#[test]
+fn ast_gets_missing_decoration_string() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let stage_inputs = ast.get_shader_resources().unwrap().stage_inputs;
+    let semantic = ast
+        .get_decoration_string(stage_inputs[0].id, spirv::Decoration::HlslSemanticGoogle)
+        .unwrap();
+    assert_eq!(semantic, "");
+}
+
+#[test]
+fn ast_gets_missing_hlsl_counter_buffer() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
+    assert!(!ast
+        .buffer_is_hlsl_counter_buffer(uniform_buffers[0].id)
+        .unwrap());
+    assert_eq!(
+        ast.buffer_get_hlsl_counter_buffer(uniform_buffers[0].id)
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn ast_gets_hlsl_counter_buffer() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/counter_buffer.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let (buf, buf_count) = (10, 18);
+
+    assert!(ast.buffer_is_hlsl_counter_buffer(buf_count).unwrap());
+    assert!(!ast.buffer_is_hlsl_counter_buffer(buf).unwrap());
+    assert_eq!(
+        ast.buffer_get_hlsl_counter_buffer(buf).unwrap(),
+        Some(buf_count)
+    );
+    assert_eq!(ast.buffer_get_hlsl_counter_buffer(buf_count).unwrap(), None);
+}
+
+#[test]
+fn ast_sets_decoration_string() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let stage_inputs = ast.get_shader_resources().unwrap().stage_inputs;
+    ast.set_decoration_string(
+        stage_inputs[0].id,
+        spirv::Decoration::HlslSemanticGoogle,
+        "POSITION",
+    )
+    .unwrap();
+    assert_eq!(
+        ast.get_decoration_string(stage_inputs[0].id, spirv::Decoration::HlslSemanticGoogle)
+            .unwrap(),
+        "POSITION"
+    );
+}
+
+#[test]
+fn ast_sets_decoration() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let stage_inputs = ast.get_shader_resources().unwrap().stage_inputs;
+    let updated_value = 3;
+    ast.set_decoration(
+        stage_inputs[0].id,
+        spirv::Decoration::DescriptorSet,
+        updated_value,
+    )
+    .unwrap();
+    assert_eq!(
+        ast.get_decoration(stage_inputs[0].id, spirv::Decoration::DescriptorSet)
+            .unwrap(),
+        updated_value
+    );
+}
+
+#[test]
+fn ast_gets_type_member_types_and_array() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
+
+    let is_struct = match ast.get_type(uniform_buffers[0].base_type_id).unwrap() {
+        spirv::Type::Struct {
+            member_types,
+            array,
+            array_size_literal
+        } => {
+            assert_eq!(member_types.len(), 2);
+            assert_eq!(array.len(), 0);
+            assert_eq!(array_size_literal.len(), 0);
+            true
+        }
+        _ => false,
+    };
+
+    assert!(is_struct);
+}
+
+#[test]
+fn ast_gets_array_dimensions() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/array.vert.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
+
+    let is_struct = match ast.get_type(uniform_buffers[0].base_type_id).unwrap() {
+        spirv::Type::Struct { member_types, .. } => {
+            assert_eq!(member_types.len(), 3);
+            let is_float = match ast.get_type(member_types[2]).unwrap() {
+                spirv::Type::Float {
+                    vecsize,
+                    columns,
+                    array,
+                    array_size_literal
+                } => {
+                    assert_eq!(vecsize, 3);
+                    assert_eq!(columns, 1);
+                    assert_eq!(array.len(), 1);
+                    assert_eq!(array_size_literal.len(), 1);
+                    assert_eq!(array[0], 3);
+                    assert_eq!(array_size_literal[0], true);
+                    true
+                }
+                _ => false,
+            };
+            assert!(is_float);
+            true
+        }
+        _ => false,
+    };
+
+    assert!(is_struct);
+}
+
+#[test]
+fn ast_gets_declared_struct_size_and_struct_member_size() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
+    let mat4_size = 4 * 16;
+    let float_size = 4;
+    assert_eq!(
+        ast.get_declared_struct_size(uniform_buffers[0].base_type_id)
+            .unwrap(),
+        mat4_size + float_size
+    );
+    assert_eq!(
+        ast.get_declared_struct_member_size(uniform_buffers[0].base_type_id, 0)
+            .unwrap(),
+        mat4_size
+    );
+    assert_eq!(
+        ast.get_declared_struct_member_size(uniform_buffers[0].base_type_id, 1)
+            .unwrap(),
+        float_size
+    );
+}
+
+#[test]
+fn ast_gets_declared_struct_size_with_runtime_array() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/runtime_array.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let block_type = ast.get_shader_resources().unwrap().storage_buffers[0].base_type_id;
+    assert_eq!(ast.get_declared_struct_size(block_type).unwrap(), 32);
+    assert_eq!(ast.get_runtime_array_stride(block_type).unwrap(), Some(32));
+    assert_eq!(
+        ast.get_declared_struct_size_runtime_array(block_type, 10)
+            .unwrap(),
+        32 + 10 * 32
+    );
+
+    // Without a runtime array, the array size is ignored
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let block_type = ast.get_shader_resources().unwrap().uniform_buffers[0].base_type_id;
+    assert_eq!(ast.get_runtime_array_stride(block_type).unwrap(), None);
+    assert_eq!(
+        ast.get_declared_struct_size_runtime_array(block_type, 10)
+            .unwrap(),
+        ast.get_declared_struct_size(block_type).unwrap()
+    );
+}
+
+#[test]
+fn ast_gets_member_name() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
+
+    assert_eq!(
+        ast.get_member_name(uniform_buffers[0].base_type_id, 0)
+            .unwrap(),
+        "u_model_view_projection"
+    );
+}
+
+#[test]
+fn ast_gets_member_decoration() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
+
+    assert_eq!(
+        ast.get_member_decoration(
+            uniform_buffers[0].base_type_id,
+            1,
+            spirv::Decoration::Offset
+        )
+        .unwrap(),
+        64
+    );
+}
+
+#[test]
+fn ast_sets_member_decoration() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
+
+    let new_offset = 128;
+
+    ast.set_member_decoration(
+        uniform_buffers[0].base_type_id,
+        1,
+        spirv::Decoration::Offset,
+        new_offset,
+    )
+    .unwrap();
+
+    assert_eq!(
+        ast.get_member_decoration(
+            uniform_buffers[0].base_type_id,
+            1,
+            spirv::Decoration::Offset
+        )
+        .unwrap(),
+        new_offset
+    );
+}
+
+#[test]
+fn ast_gets_specialization_constants() {
+    let comp = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/specialization.comp.spv"
+    )));
+    let comp_ast = spirv::Ast::<lang::Target>::parse(&comp).unwrap();
+    let specialization_constants = comp_ast.get_specialization_constants().unwrap();
+    assert_eq!(specialization_constants[0].constant_id, 10);
+}
+
+#[test]
+fn ast_gets_work_group_size_specialization_constants() {
+    let comp = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/workgroup.comp.spv"
+    )));
+    let comp_ast = spirv::Ast::<lang::Target>::parse(&comp).unwrap();
+    let work_group_size = comp_ast
+        .get_work_group_size_specialization_constants()
+        .unwrap();
+    assert_eq!(
+        work_group_size,
+        spirv::WorkGroupSizeSpecializationConstants {
+            x: spirv::SpecializationConstant {
+                id: 7,
+                constant_id: 5,
+            },
+            y: spirv::SpecializationConstant {
+                id: 8,
+                constant_id: 10,
+            },
+            z: spirv::SpecializationConstant {
+                id: 9,
+                constant_id: 15,
+            },
+        }
+    );
+}
+
+#[test]
+fn ast_gets_subgroup_size_and_features() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/subgroup.asm.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    assert_eq!(
+        ast.get_required_subgroup_size("main", spirv::ExecutionModel::GlCompute)
+            .unwrap(),
+        Some(32)
+    );
+    // The module declares the vote capability without using it
+    assert_eq!(
+        ast.get_subgroup_features().unwrap(),
+        spirv::SubgroupFeatures {
+            basic: true,
+            ballot: true,
+            ..Default::default()
+        }
+    );
+
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/workgroup.comp.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    assert_eq!(
+        ast.get_required_subgroup_size("main", spirv::ExecutionModel::GlCompute)
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        ast.get_subgroup_features().unwrap(),
+        spirv::SubgroupFeatures::default()
+    );
+}
+
+#[test]
+fn ast_checks_sample_rate_shading() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/sample_shading.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    assert!(ast.uses_sample_rate_shading("sample_input").unwrap());
+    assert!(ast.uses_sample_rate_shading("sample_id").unwrap());
+    assert!(!ast.uses_sample_rate_shading("pixel").unwrap());
+}
+
+#[test]
+fn ast_gets_active_builtins() {
+    use spirv::BuiltIn;
+
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let active_builtins = ast.get_active_builtins().unwrap();
+    assert!(active_builtins.inputs.is_empty());
+    assert_eq!(
+        active_builtins.outputs,
+        vec![BuiltIn::Position].into_iter().collect()
+    );
+    assert!(!active_builtins.outputs.contains(BuiltIn::PointSize));
+
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/sample_shading.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    ast.set_compiler_options(
+        &lang::CompilerOptions::builder()
+            .entry_point("sample_id", spirv::ExecutionModel::Fragment)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    let active_builtins = ast.get_active_builtins().unwrap();
+    assert!(active_builtins.inputs.contains(BuiltIn::SampleId));
+    assert_eq!(active_builtins.inputs.len(), 1);
+}
+
+#[test]
+fn ast_gets_layer_and_viewport_outputs() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/layer_output.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    assert_eq!(
+        ast.get_layer_and_viewport_outputs().unwrap(),
+        [
+            spirv::LayerAndViewportOutput {
+                entry_point: String::from("layered_vs"),
+                execution_model: spirv::ExecutionModel::Vertex,
+                layer: true,
+                viewport_index: false,
+            },
+            spirv::LayerAndViewportOutput {
+                entry_point: String::from("viewport_gs"),
+                execution_model: spirv::ExecutionModel::Geometry,
+                layer: false,
+                viewport_index: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn ast_gets_execution_mode_arguments() {
+    use spirv::ExecutionMode;
+
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/execution_modes.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let argument = |ast: &spirv::Ast<lang::Target>, mode, index| {
+        ast.get_execution_mode_argument(mode, index).unwrap()
+    };
+    assert_eq!(argument(&ast, ExecutionMode::Invocations, 0), Some(4));
+    assert_eq!(argument(&ast, ExecutionMode::OutputVertices, 0), Some(3));
+    assert_eq!(argument(&ast, ExecutionMode::OutputVertices, 1), None);
+    assert_eq!(argument(&ast, ExecutionMode::LocalSizeId, 0), None);
+
+    ast.set_compiler_options(
+        &lang::CompilerOptions::builder()
+            .entry_point("comp", spirv::ExecutionModel::GlCompute)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(argument(&ast, ExecutionMode::Invocations, 0), None);
+    assert_eq!(argument(&ast, ExecutionMode::LocalSizeId, 0), Some(8));
+    assert_eq!(argument(&ast, ExecutionMode::LocalSizeId, 2), Some(9));
+}
+
+#[test]
+fn ast_gets_workgroup_shared_memory_size() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/shared_memory.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    // float[64] + uvec3 + uint[count], with count defaulting to 16
+    assert_eq!(
+        ast.get_workgroup_shared_memory_size("main", spirv::ExecutionModel::GlCompute)
+            .unwrap(),
+        256 + 12 + 64
+    );
+
+    let count = ast.get_specialization_constants().unwrap()[0];
+    ast.set_scalar_constant(count.id, 32).unwrap();
+    assert_eq!(
+        ast.get_workgroup_shared_memory_size("main", spirv::ExecutionModel::GlCompute)
+            .unwrap(),
+        256 + 12 + 128
+    );
+}
+
+#[test]
+fn ast_gets_active_buffer_ranges() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/two_ubo.vert.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
+    assert_eq!(uniform_buffers.len(), 2);
+
+    let ubo1 = ast.get_active_buffer_ranges(uniform_buffers[0].id).unwrap();
+    assert_eq!(
+        ubo1,
+        [
+            spirv::BufferRange {
+                index: 0,
+                offset: 0,
+                range: 64,
+            },
+            spirv::BufferRange {
+                index: 1,
+                offset: 64,
+                range: 16,
+            },
+            spirv::BufferRange {
+                index: 2,
+                offset: 80,
+                range: 32,
+            }
+        ]
+    );
+
+    let ubo2 = ast.get_active_buffer_ranges(uniform_buffers[1].id).unwrap();
+    assert_eq!(
+        ubo2,
+        [
+            spirv::BufferRange {
+                index: 0,
+                offset: 0,
+                range: 16,
+            },
+            spirv::BufferRange {
+                index: 1,
+                offset: 16,
+                range: 16,
+            },
+            spirv::BufferRange {
+                index: 2,
+                offset: 32,
+                range: 12,
+            }
+        ]
+    );
+}
+
+#[test]
+fn ast_gets_push_constant_ranges() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/push_constants.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let pc = ast.get_shader_resources().unwrap().push_constant_buffers[0].id;
+
+    assert_eq!(
+        ast.get_push_constant_ranges().unwrap(),
+        [spirv::PushConstantRange {
+            id: pc,
+            offset: 0,
+            size: 16,
+        }]
+    );
+
+    ast.set_compiler_options(
+        &lang::CompilerOptions::builder()
+            .entry_point("main_fs", spirv::ExecutionModel::Fragment)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        ast.get_push_constant_ranges().unwrap(),
+        [spirv::PushConstantRange {
+            id: pc,
+            offset: 16,
+            size: 4,
+        }]
+    );
+}
+
+fn bytes_from_words(words: &[u32]) -> &[u8] {
+    unsafe {
+        std::slice::from_raw_parts(
+            words.as_ptr() as *const u8,
+            words.len() * std::mem::size_of::<u32>(),
+        )
+    }
+}
+
+#[test]
+fn module_from_bytes_matches_words() {
+    let words = words_from_bytes(include_bytes!("shaders/simple.vert.spv")).to_vec();
+    let module = spirv::Module::from_bytes(bytes_from_words(&words)).unwrap();
+    let entry_points = spirv::Ast::<lang::Target>::parse(&module)
+        .unwrap()
+        .get_entry_points()
+        .unwrap();
+
+    assert_eq!(entry_points.len(), 1);
+    assert_eq!(entry_points[0].name, "main");
+}
+
+#[test]
+fn module_from_bytes_converts_byte_swapped_modules() {
+    let words = words_from_bytes(include_bytes!("shaders/simple.vert.spv"))
+        .iter()
+        .map(|word| word.swap_bytes())
+        .collect::<Vec<_>>();
+    let module = spirv::Module::from_bytes(bytes_from_words(&words)).unwrap();
+    let entry_points = spirv::Ast::<lang::Target>::parse(&module)
+        .unwrap()
+        .get_entry_points()
+        .unwrap();
+
+    assert_eq!(entry_points.len(), 1);
+    assert_eq!(entry_points[0].name, "main");
+}
+
+#[test]
+fn module_from_bytes_rejects_invalid_input() {
+    let words = words_from_bytes(include_bytes!("shaders/simple.vert.spv")).to_vec();
+    let bytes = bytes_from_words(&words);
+
+    match spirv::Module::from_bytes(&bytes[..bytes.len() - 1]) {
+        Err(spirv_cross::ErrorCode::InvalidSpirv(_)) => {}
+        _ => panic!("expected a length error"),
+    }
+    match spirv::Module::from_bytes(&bytes[..8]) {
+        Err(spirv_cross::ErrorCode::InvalidSpirv(_)) => {}
+        _ => panic!("expected a header error"),
+    }
+
+    let mut corrupted = words.clone();
+    corrupted[0] = 0xdead_beef;
+    match spirv::Module::from_bytes(bytes_from_words(&corrupted)) {
+        Err(spirv_cross::ErrorCode::InvalidSpirv(message)) => {
+            assert!(message.contains("0xdeadbeef"))
+        }
+        _ => panic!("expected a magic number error"),
+    }
+}
+
+#[test]
+fn module_from_reader_and_file_match() {
+    let from_reader =
+        spirv::Module::from_reader(&include_bytes!("shaders/simple.vert.spv")[..]).unwrap();
+    let from_file = spirv::Module::from_file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/shaders/simple.vert.spv"
+    ))
+    .unwrap();
+
+    for module in &[from_reader, from_file] {
+        let entry_points = spirv::Ast::<lang::Target>::parse(module)
+            .unwrap()
+            .get_entry_points()
+            .unwrap();
+        assert_eq!(entry_points.len(), 1);
+        assert_eq!(entry_points[0].name, "main");
+    }
+
+    match spirv::Module::from_file("does/not/exist.spv") {
+        Err(spirv_cross::ErrorCode::Io(_)) => {}
+        _ => panic!("expected an IO error"),
+    }
+}
+
+#[test]
+fn module_from_bytes_accepts_unaligned_bytes() {
+    let words = words_from_bytes(include_bytes!("shaders/simple.vert.spv")).to_vec();
+    let mut storage = vec![0u32; words.len() + 1];
+    let unaligned = unsafe {
+        std::slice::from_raw_parts_mut(
+            (storage.as_mut_ptr() as *mut u8).add(1),
+            words.len() * std::mem::size_of::<u32>(),
+        )
+    };
+    unaligned.copy_from_slice(bytes_from_words(&words));
+
+    let module = spirv::Module::from_bytes(unaligned).unwrap();
+    let entry_points = spirv::Ast::<lang::Target>::parse(&module)
+        .unwrap()
+        .get_entry_points()
+        .unwrap();
+
+    assert_eq!(entry_points.len(), 1);
+    assert_eq!(entry_points[0].name, "main");
+}
+
+#[test]
+fn module_gets_source_files() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/source_files.asm.spv"
+    )));
+    assert_eq!(
+        module.get_source_files().unwrap(),
         [
-            spirv::BufferRange {
-                index: 0,
-                offset: 0,
-                range: 64,
+            spirv::SourceFile {
+                id: 5,
+                name: String::from("shaders/main.hlsl"),
+                source: Some(String::from("void main()\n{\n}\n")),
             },
-            spirv::BufferRange {
-                index: 1,
-                offset: 64,
-                range: 16,
+            spirv::SourceFile {
+                id: 6,
+                name: String::from("shaders/common.hlsli"),
+                source: None,
             },
-            spirv::BufferRange {
-                index: 2,
-                offset: 80,
-                range: 32,
-            }
         ]
     );
 
-    let ubo2 = ast.get_active_buffer_ranges(uniform_buffers[1].id).unwrap();
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    assert_eq!(module.get_source_files().unwrap(), []);
+}
+
+#[test]
+fn ast_builds_dummy_sampler_for_combined_images() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/sampler.frag.spv")));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    assert!(!ast.needs_dummy_sampler_for_combined_images().unwrap());
+    assert_eq!(ast.build_dummy_sampler_for_combined_images().unwrap(), None);
+
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/image_fetch.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    assert!(ast.needs_dummy_sampler_for_combined_images().unwrap());
+    assert!(ast
+        .build_dummy_sampler_for_combined_images()
+        .unwrap()
+        .is_some());
+}
+
+#[test]
+fn ast_finds_depth_comparison_variables() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/compare_sampler.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let resources = ast.get_shader_resources().unwrap();
+    let compare_names = resources
+        .separate_images
+        .iter()
+        .chain(resources.separate_samplers.iter())
+        .filter(|resource| ast.variable_is_depth_or_compare(resource.id).unwrap())
+        .map(|resource| resource.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(compare_names, ["u_shadow", "u_shadow_sampler"]);
+}
+
+#[test]
+fn ast_gets_entry_point_interface_variables() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/struct.vert.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let entry_point = &ast.get_entry_points().unwrap()[0];
+    let interface = ast
+        .get_entry_point_interface_variables(&entry_point.name, entry_point.execution_model)
+        .unwrap();
+
+    let resources = ast.get_shader_resources().unwrap();
+    let stage_variables = resources
+        .stage_inputs
+        .iter()
+        .chain(resources.stage_outputs.iter())
+        .map(|resource| resource.id)
+        .collect::<Vec<_>>();
+    assert!(!stage_variables.is_empty());
+    assert!(stage_variables.iter().all(|id| interface.contains(id)));
+
+    assert!(ast
+        .get_entry_point_interface_variables("missing", entry_point.execution_model)
+        .is_err());
+}
+
+/// Builds malformed variants of a module: every truncation, every instruction with a word count
+/// of zero or one running past the end, every operand replaced with the id bound, and out of range
+/// id bounds.
+fn malformed_modules(words: &[u32]) -> Vec<Vec<u32>> {
+    let mut corpus = (0..words.len())
+        .map(|length| words[..length].to_vec())
+        .collect::<Vec<_>>();
+
+    let mut offset = 5;
+    while offset < words.len() {
+        let word_count = words[offset] >> 16;
+        for &corrupted_count in &[0, (words.len() - offset + 1) as u32] {
+            let mut corrupted = words.to_vec();
+            corrupted[offset] = (corrupted_count << 16) | (words[offset] & 0xffff);
+            corpus.push(corrupted);
+        }
+        for operand in offset + 1..offset + word_count as usize {
+            let mut corrupted = words.to_vec();
+            corrupted[operand] = words[3];
+            corpus.push(corrupted);
+        }
+        offset += word_count as usize;
+    }
+
+    for &bound in &[0, 0x0040_0000, u32::MAX] {
+        let mut corrupted = words.to_vec();
+        corrupted[3] = bound;
+        corpus.push(corrupted);
+    }
+
+    corpus
+}
+
+#[test]
+fn ast_rejects_malformed_modules() {
+    let shaders: &[&[u8]] = &[
+        include_bytes!("shaders/simple.vert.spv"),
+        include_bytes!("shaders/sampler.frag.spv"),
+        include_bytes!("shaders/workgroup.comp.spv"),
+        include_bytes!("shaders/vs_and_fs.asm.spv"),
+    ];
+
+    for shader in shaders {
+        for words in malformed_modules(words_from_bytes(shader)) {
+            let module = spirv::Module::from_words(&words);
+            // Truncating at an instruction boundary can leave a module which still parses
+            match spirv::Ast::<lang::Target>::parse(&module) {
+                Ok(_) | Err(spirv_cross::ErrorCode::InvalidSpirv(_)) => {}
+                Err(error) => panic!("unexpected error {:?}", error),
+            }
+            match spirv::ParsedModule::parse(&module) {
+                Ok(_) | Err(spirv_cross::ErrorCode::InvalidSpirv(_)) => {}
+                Err(error) => panic!("unexpected error {:?}", error),
+            }
+        }
+    }
+
+    let mut words = words_from_bytes(include_bytes!("shaders/simple.vert.spv")).to_vec();
+    words[5] &= 0xffff;
+    match spirv::Ast::<lang::Target>::parse(&spirv::Module::from_words(&words)) {
+        Err(spirv_cross::ErrorCode::InvalidSpirv(message)) => assert!(message.contains("word 5")),
+        _ => panic!("expected an invalid word count error"),
+    }
+
+    let mut words = words_from_bytes(include_bytes!("shaders/simple.vert.spv")).to_vec();
+    let mut offset = 5;
+    while words[offset] & 0xffff != 15 {
+        offset += (words[offset] >> 16) as usize;
+    }
+    // The function of OpEntryPoint is its second operand
+    words[offset + 2] = words[3];
+    match spirv::Ast::<lang::Target>::parse(&spirv::Module::from_words(&words)) {
+        Err(spirv_cross::ErrorCode::InvalidSpirv(message)) => assert!(message.contains(&format!(
+            "instruction at word {} refers to id {}",
+            offset, words[3]
+        ))),
+        _ => panic!("expected an id outside of the bound"),
+    }
+}
+
+#[test]
+fn ast_round_trips_through_raw_handle() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let expected = ast.get_entry_points().unwrap();
+
+    let handle = unsafe { ast.as_raw() };
+    assert!(!handle.is_null());
+    assert_eq!(ast.into_raw(), handle);
+
+    let mut ast = unsafe { spirv::Ast::<lang::Target>::from_raw(handle) };
+    assert_eq!(ast.get_entry_points().unwrap(), expected);
+    assert!(ast.compile().is_ok());
+}
+
+#[test]
+fn ast_reflects_inside_bridge_arena() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    fn get_names(ast: &mut spirv::Ast<lang::Target>) -> Vec<(String, String)> {
+        let resources = ast.get_shader_resources().unwrap();
+        resources
+            .uniform_buffers
+            .iter()
+            .map(|resource| (resource.name.clone(), ast.get_name(resource.id).unwrap()))
+            .collect()
+    }
+    let expected_entry_points = ast.get_entry_points().unwrap();
+    let expected_names = get_names(&mut ast);
+    let expected_source = ast.compile().unwrap();
+
+    let (entry_points, names, source) = spirv::with_bridge_arena(|| {
+        let entry_points = ast.get_entry_points().unwrap();
+        let names = spirv::with_bridge_arena(|| get_names(&mut ast)).unwrap();
+        (entry_points, names, ast.compile().unwrap())
+    })
+    .unwrap();
+
+    assert_eq!(entry_points, expected_entry_points);
+    assert_eq!(names, expected_names);
+    assert_eq!(names[0].0, "uniform_buffer_object");
+    assert_eq!(source, expected_source);
+    assert_eq!(ast.get_entry_points().unwrap(), expected_entry_points);
+}
+
+#[test]
+fn ast_classifies_stages() {
+    use spirv::ExecutionModel;
+
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    assert!(ast.has_stage(ExecutionModel::Vertex).unwrap());
+    assert!(!ast.has_stage(ExecutionModel::Fragment).unwrap());
+    assert!(!ast.is_compute().unwrap());
+    assert!(!ast.is_tessellation_shader().unwrap());
+    assert_eq!(ast.primary_stage().unwrap(), Some(ExecutionModel::Vertex));
+
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/workgroup.comp.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    assert!(ast.is_compute().unwrap());
     assert_eq!(
-        ubo2,
-        [
-            spirv::BufferRange {
-                index: 0,
-                offset: 0,
-                range: 16,
-            },
-            spirv::BufferRange {
-                index: 1,
-                offset: 16,
-                range: 16,
-            },
-            spirv::BufferRange {
-                index: 2,
-                offset: 32,
-                range: 12,
+        ast.primary_stage().unwrap(),
+        Some(ExecutionModel::GlCompute)
+    );
+
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/tessellation.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    assert!(ast.is_tessellation_shader().unwrap());
+    assert!(!ast.is_compute().unwrap());
+
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/layer_output.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    assert!(ast.has_stage(ExecutionModel::Geometry).unwrap());
+    assert!(ast.has_stage(ExecutionModel::Fragment).unwrap());
+    assert_eq!(ast.primary_stage().unwrap(), None);
+}
+
+#[test]
+fn ast_resolves_local_size_id() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/local_size_id.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let work_group_size = ast.get_entry_points().unwrap()[0].work_group_size;
+    assert_eq!(
+        work_group_size,
+        spirv::WorkGroupSize {
+            x: 8,
+            y: 0,
+            z: 1,
+            constant_ids: Some([5, 6, 7]),
+        }
+    );
+
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/workgroup.comp.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    assert_eq!(
+        ast.get_entry_points().unwrap()[0]
+            .work_group_size
+            .constant_ids,
+        None
+    );
+}
+
+#[test]
+fn ast_forces_early_fragment_tests() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/sampler.frag.spv")));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    assert!(!ast.uses_early_fragment_tests().unwrap());
+    assert!(!ast.compile().unwrap().contains("[earlydepthstencil]"));
+
+    ast.set_early_fragment_tests(true).unwrap();
+    assert!(ast.uses_early_fragment_tests().unwrap());
+    assert!(ast.compile().unwrap().contains("[earlydepthstencil]"));
+
+    ast.set_early_fragment_tests(false).unwrap();
+    assert!(!ast.uses_early_fragment_tests().unwrap());
+    assert!(!ast.compile().unwrap().contains("[earlydepthstencil]"));
+
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    match ast.set_early_fragment_tests(true) {
+        Err(spirv_cross::ErrorCode::InvalidOptions(_)) => {}
+        result => panic!("unexpected result {:?}", result),
+    }
+}
+
+#[test]
+fn ast_applies_decorations_atomically() {
+    use spirv::Decoration;
+
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let uniform_buffer = ast.get_shader_resources().unwrap().uniform_buffers[0].id;
+
+    ast.apply_decorations(&[
+        (uniform_buffer, Decoration::Binding, 5),
+        (uniform_buffer, Decoration::DescriptorSet, 2),
+        (uniform_buffer, Decoration::Binding, 6),
+    ])
+    .unwrap();
+    assert_eq!(
+        ast.get_decoration(uniform_buffer, Decoration::Binding)
+            .unwrap(),
+        6
+    );
+    assert_eq!(
+        ast.get_decoration(uniform_buffer, Decoration::DescriptorSet)
+            .unwrap(),
+        2
+    );
+
+    for decorations in &[
+        vec![
+            (uniform_buffer, Decoration::Binding, 7),
+            (u32::MAX, Decoration::Binding, 1),
+        ],
+        vec![
+            (uniform_buffer, Decoration::Binding, 7),
+            (uniform_buffer, Decoration::HlslSemanticGoogle, 0),
+        ],
+    ] {
+        match ast.apply_decorations(decorations) {
+            Err(spirv_cross::ErrorCode::InvalidOptions(message)) => {
+                assert!(message.starts_with("entry 1"))
             }
-        ]
+            result => panic!("unexpected result {:?}", result),
+        }
+        assert_eq!(
+            ast.get_decoration(uniform_buffer, Decoration::Binding)
+                .unwrap(),
+            6
+        );
+    }
+}
+
+#[test]
+fn ast_resolves_type_chains() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let uniform_buffer = &ast.get_shader_resources().unwrap().uniform_buffers[0];
+    let pointee = ast.resolve_pointee_type(uniform_buffer.type_id).unwrap();
+    assert_eq!(pointee, uniform_buffer.base_type_id);
+    assert_eq!(ast.resolve_pointee_type(pointee).unwrap(), pointee);
+    assert_eq!(
+        ast.strip_arrays(uniform_buffer.type_id).unwrap(),
+        uniform_buffer.type_id
+    );
+    assert_eq!(
+        ast.underlying_struct(uniform_buffer.type_id).unwrap(),
+        Some(uniform_buffer.base_type_id)
     );
+
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/descriptor_arrays.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let resources = ast.get_shader_resources().unwrap();
+    let textures = resources
+        .separate_images
+        .iter()
+        .find(|resource| resource.name == "u_textures")
+        .unwrap();
+    let array = ast.resolve_pointee_type(textures.type_id).unwrap();
+    let element = ast.strip_arrays(array).unwrap();
+    assert_ne!(array, element);
+    match ast.get_type(element).unwrap() {
+        spirv::Type::Image { array, .. } => assert!(array.is_empty()),
+        _ => panic!("Unexpected element type"),
+    }
+    assert_eq!(ast.underlying_struct(textures.type_id).unwrap(), None);
+}
+
+#[test]
+fn ast_gets_sorted_shader_resources() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/stage_locations.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let sorted = ast.get_sorted_shader_resources().unwrap();
+    let names = |resources: &[spirv::Resource]| {
+        resources
+            .iter()
+            .map(|resource| resource.name.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        names(&sorted.stage_inputs),
+        vec!["a_position", "a_transform", "a_uv"]
+    );
+    assert_eq!(names(&sorted.stage_outputs), vec!["v_color", "v_normal"]);
+
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/image_sampler_pairs.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let mut resources = ast.get_shader_resources().unwrap();
+    resources.separate_images.reverse();
+    resources.separate_samplers.reverse();
+    ast.sort_shader_resources(&mut resources).unwrap();
+    let sorted = ast.get_sorted_shader_resources().unwrap();
+    assert_eq!(resources.separate_images, sorted.separate_images);
+    assert_eq!(resources.separate_samplers, sorted.separate_samplers);
+    assert_eq!(names(&resources.separate_images), vec!["tex_a", "tex_b"]);
+    assert_eq!(
+        names(&resources.separate_samplers),
+        vec!["samp_linear", "samp_point"]
+    );
+}
+
+#[test]
+fn reflection_types_work_as_keys() {
+    use std::collections::BTreeSet;
+
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/image_sampler_pairs.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let resources = ast.get_shader_resources().unwrap();
+    assert_eq!(
+        resources,
+        spirv::Ast::<lang::Target>::parse(&module)
+            .unwrap()
+            .get_shader_resources()
+            .unwrap()
+    );
+
+    let by_binding = resources
+        .iter()
+        .filter(|(kind, _)| *kind == spirv::ResourceKind::SeparateSamplers)
+        .map(|(_, resource)| {
+            let binding = spirv::DescriptorBinding {
+                desc_set: ast
+                    .get_decoration(resource.id, spirv::Decoration::DescriptorSet)
+                    .unwrap(),
+                binding: ast
+                    .get_decoration(resource.id, spirv::Decoration::Binding)
+                    .unwrap(),
+            };
+            (binding, resource.resource_id())
+        })
+        .collect::<HashMap<_, _>>();
+    let samp_point = &resources.separate_samplers[1];
+    assert_eq!(
+        by_binding[&spirv::DescriptorBinding {
+            desc_set: 0,
+            binding: 3,
+        }],
+        samp_point.resource_id()
+    );
+    assert_eq!(u32::from(samp_point.resource_id()), samp_point.id);
+
+    let sorted = resources
+        .separate_images
+        .iter()
+        .rev()
+        .collect::<BTreeSet<_>>();
+    assert_eq!(
+        sorted.into_iter().cloned().collect::<Vec<_>>(),
+        resources.separate_images
+    );
+
+    let entry_points = ast
+        .get_entry_points()
+        .unwrap()
+        .into_iter()
+        .collect::<BTreeSet<_>>();
+    assert_eq!(entry_points.len(), 1);
+}
+
+#[test]
+fn ast_gets_shader_statistics() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/control_flow.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    assert_eq!(
+        ast.get_shader_statistics().unwrap(),
+        spirv::ShaderStatistics {
+            instruction_count: 21,
+            function_count: 2,
+            max_control_flow_depth: 2,
+            max_loop_depth: 1,
+            texture_sample_count: 2,
+        }
+    );
+}
+
+#[test]
+fn ast_gets_descriptor_indexing() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/non_uniform.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let indexing = ast
+        .get_descriptor_indexing()
+        .unwrap()
+        .into_iter()
+        .map(|indexing| {
+            (
+                indexing.resource.name.clone(),
+                (
+                    indexing.runtime_sized,
+                    indexing.non_uniform_indexing,
+                    indexing.requires_update_after_bind(),
+                ),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+
+    assert_eq!(indexing.len(), 3);
+    assert_eq!(indexing["u_textures"], (true, true, true));
+    assert_eq!(indexing["u_materials"], (false, true, true));
+    assert_eq!(indexing["u_shadow"], (false, false, false));
+}
+
+#[test]
+fn ast_gets_resources_of_one_kind() {
+    for bytes in [
+        &include_bytes!("shaders/descriptor_arrays.asm.spv")[..],
+        &include_bytes!("shaders/push_constants.asm.spv")[..],
+        &include_bytes!("shaders/struct.vert.spv")[..],
+    ]
+    .iter()
+    {
+        let module = spirv::Module::from_words(words_from_bytes(bytes));
+        let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+        let resources = ast.get_shader_resources().unwrap();
+        for &kind in spirv::ResourceKind::ALL.iter() {
+            assert_eq!(ast.get_resources(kind).unwrap(), resources.get(kind));
+        }
+
+        assert_eq!(
+            ast.get_uniform_buffers().unwrap(),
+            resources.uniform_buffers
+        );
+        assert_eq!(ast.get_stage_inputs().unwrap(), resources.stage_inputs);
+        assert_eq!(
+            ast.get_push_constant_buffers().unwrap(),
+            resources.push_constant_buffers
+        );
+        assert_eq!(
+            ast.get_separate_images().unwrap(),
+            resources.separate_images
+        );
+        assert_eq!(
+            ast.get_separate_samplers().unwrap(),
+            resources.separate_samplers
+        );
+    }
 }
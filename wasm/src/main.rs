@@ -9,6 +9,7 @@ fn main() {
             "-DSPIRV_CROSS_EXCEPTIONS_TO_ASSERTIONS=1",
             "-DSPIRV_CROSS_WRAPPER_NO_EXCEPTIONS=1",
             "-DSPIRV_CROSS_WRAPPER_GLSL",
+            "-DSPIRV_CROSS_WRAPPER_REFLECT",
             "--closure",
             "1",
             "-o",
@@ -28,24 +29,51 @@ fn main() {
                 "_malloc",
                 "_free",
                 "_sc_internal_get_latest_exception_message",
+                "_sc_internal_parsed_ir_new",
+                "_sc_internal_parsed_ir_delete",
                 "_sc_internal_compiler_glsl_new",
+                "_sc_internal_compiler_glsl_new_from_ir",
+                "_sc_internal_compiler_reflect_new",
+                "_sc_internal_compiler_reflect_new_from_ir",
                 "_sc_internal_compiler_glsl_set_options",
                 "_sc_internal_compiler_glsl_build_combined_image_samplers",
                 "_sc_internal_compiler_glsl_get_combined_image_samplers",
                 "_sc_internal_compiler_glsl_add_header_line",
                 "_sc_internal_compiler_glsl_flatten_buffer_block",
+                "_sc_internal_compiler_glsl_remap_subpass_input_to_sampler",
+                "_sc_internal_compiler_glsl_set_image_format",
+                "_sc_internal_compiler_glsl_get_image_format",
+                "_sc_internal_compiler_glsl_get_required_extensions",
                 "_sc_internal_compiler_get_decoration",
+                "_sc_internal_compiler_has_decoration",
+                "_sc_internal_compiler_has_member_decoration",
+                "_sc_internal_compiler_get_decoration_string",
+                "_sc_internal_compiler_buffer_is_hlsl_counter_buffer",
+                "_sc_internal_compiler_buffer_get_hlsl_counter_buffer",
+                "_sc_internal_compiler_needs_dummy_sampler_for_combined_images",
+                "_sc_internal_compiler_variable_is_depth_or_compare",
+                "_sc_internal_compiler_build_dummy_sampler_for_combined_images",
                 "_sc_internal_compiler_set_decoration",
+                "_sc_internal_compiler_set_decorations",
+                "_sc_internal_compiler_set_decoration_string",
                 "_sc_internal_compiler_unset_decoration",
                 "_sc_internal_compiler_get_name",
+                "_sc_internal_compiler_get_remapped_declared_block_name",
                 "_sc_internal_compiler_set_name",
                 "_sc_internal_compiler_set_member_name",
+                "_sc_internal_compiler_strip_names",
                 "_sc_internal_compiler_get_entry_points",
+                "_sc_internal_compiler_get_current_entry_point",
+                "_sc_internal_compiler_get_active_buffer_ranges",
                 "_sc_internal_compiler_get_cleansed_entry_point_name",
                 "_sc_internal_compiler_get_shader_resources",
+                "_sc_internal_compiler_get_shader_resources_of_kinds",
+                "_sc_internal_compiler_get_shader_resource_list",
+                "_sc_internal_compiler_get_shader_resources_for_entry_point",
                 "_sc_internal_compiler_get_specialization_constants",
                 "_sc_internal_compiler_set_scalar_constant",
                 "_sc_internal_compiler_get_type",
+                "_sc_internal_compiler_get_type_parent",
                 "_sc_internal_compiler_get_member_name",
                 "_sc_internal_compiler_get_member_decoration",
                 "_sc_internal_compiler_set_member_decoration",
@@ -54,9 +82,30 @@ fn main() {
                 "_sc_internal_compiler_rename_interface_variable",
                 "_sc_internal_compiler_get_work_group_size_specialization_constants",
                 "_sc_internal_compiler_set_entry_point",
+                "_sc_internal_compiler_rename_entry_point",
+                "_sc_internal_compiler_get_active_interface_variables",
+                "_sc_internal_compiler_get_active_builtins",
+                "_sc_internal_compiler_set_remove_unused_variables",
+                "_sc_internal_compiler_keep_unused_resources",
+                "_sc_internal_compiler_alias_variable",
+                "_sc_internal_compiler_get_declared_capabilities",
+                "_sc_internal_compiler_get_used_opcodes",
+                "_sc_internal_compiler_get_spirv",
+                "_sc_internal_compiler_get_meta_ids",
+                "_sc_internal_compiler_get_entry_point_subgroup_size",
+                "_sc_internal_compiler_get_decorations",
+                "_sc_internal_compiler_get_execution_mode_argument",
+                "_sc_internal_compiler_has_execution_mode",
+                "_sc_internal_compiler_set_execution_mode",
+                "_sc_internal_compiler_get_workgroup_shared_memory_size",
+                "_sc_internal_compiler_get_entry_point_interface_variables",
+                "_sc_internal_compiler_get_descriptor_count",
+                "_sc_internal_compiler_get_outer_array",
                 "_sc_internal_compiler_compile",
                 "_sc_internal_compiler_delete",
-                "_sc_internal_free_pointer"
+                "_sc_internal_free_pointer",
+                "_sc_internal_arena_begin",
+                "_sc_internal_arena_end"
             ]"#,
             "-s",
             "../spirv_cross/src/wrapper.cpp",